@@ -0,0 +1,46 @@
+//! Settings selecting the assists which are offered, so that clients can turn
+//! off the assists they don't want in their menus.
+
+use crate::{AssistId, AssistKind};
+
+/// The config offering all the assists.
+pub(crate) static DEFAULT_CONFIG: AssistConfig =
+    AssistConfig { allowed: None, denied: Vec::new(), kinds: None };
+
+/// Which assists are offered.
+///
+/// The entries of the lists are assist ids, like `add_derive`, or prefixes of
+/// ids ending with `*`, like `add_*`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssistConfig {
+    /// The assists which are offered, all of them when `None`.
+    pub allowed: Option<Vec<String>>,
+    /// The assists which are never offered, even when allowed.
+    pub denied: Vec<String>,
+    /// The kinds of the assists which are offered, all of them when `None`.
+    pub kinds: Option<Vec<AssistKind>>,
+}
+
+impl AssistConfig {
+    /// Whether the assists with the given `id` are offered.
+    pub fn is_enabled(&self, id: AssistId) -> bool {
+        let allowed = self.allowed.as_ref().map_or(true, |it| it.iter().any(|it| matches(it, id)));
+        allowed && !self.denied.iter().any(|it| matches(it, id))
+    }
+
+    /// Whether the assists of the given `kind` are offered.
+    ///
+    /// The kind of an assist is only known once its handler found it
+    /// applicable, but the edit isn't computed for the other kinds.
+    pub fn is_kind_enabled(&self, kind: AssistKind) -> bool {
+        self.kinds.as_ref().map_or(true, |it| it.iter().any(|&filter| kind.is_within(filter)))
+    }
+}
+
+fn matches(entry: &str, id: AssistId) -> bool {
+    if entry.ends_with('*') {
+        id.0.starts_with(&entry[..entry.len() - 1])
+    } else {
+        entry == id.0
+    }
+}
@@ -1,19 +1,23 @@
 //! This module defines `AssistCtx` -- the API surface that is exposed to assists.
 use hir::Semantics;
-use ra_db::FileRange;
+use ra_db::{FileId, FileRange, RelativePathBuf, SourceDatabase, SourceRootId};
 use ra_fmt::{leading_indent, reindent};
 use ra_ide_db::RootDatabase;
 use ra_syntax::{
     algo::{self, find_covering_element, find_node_at_offset},
+    ast::NameOwner,
     AstNode, SourceFile, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextUnit,
     TokenAtOffset,
 };
 use ra_text_edit::TextEditBuilder;
 
-use crate::{AssistAction, AssistId, AssistLabel, GroupLabel, ResolvedAssist};
+use crate::{
+    assist_config::DEFAULT_CONFIG, AssistAction, AssistConfig, AssistId, AssistKind, AssistLabel,
+    FileCreation, GroupLabel, ResolvedAssist, Snippet,
+};
 
 #[derive(Clone, Debug)]
-pub(crate) struct Assist(pub(crate) Vec<AssistInfo>);
+pub struct Assist(pub(crate) Vec<AssistInfo>);
 
 #[derive(Clone, Debug)]
 pub(crate) struct AssistInfo {
@@ -42,7 +46,7 @@ impl AssistInfo {
     }
 }
 
-pub(crate) type AssistHandler = fn(AssistCtx) -> Option<Assist>;
+pub type AssistHandler = fn(AssistCtx) -> Option<Assist>;
 
 /// `AssistCtx` allows to apply an assist or check if it could be applied.
 ///
@@ -75,12 +79,13 @@ pub(crate) type AssistHandler = fn(AssistCtx) -> Option<Assist>;
 /// moment, because the LSP API is pretty awkward in this place, and it's much
 /// easier to just compute the edit eagerly :-)
 #[derive(Clone)]
-pub(crate) struct AssistCtx<'a> {
-    pub(crate) sema: &'a Semantics<'a, RootDatabase>,
-    pub(crate) db: &'a RootDatabase,
-    pub(crate) frange: FileRange,
+pub struct AssistCtx<'a> {
+    pub sema: &'a Semantics<'a, RootDatabase>,
+    pub db: &'a RootDatabase,
+    pub frange: FileRange,
     source_file: SourceFile,
     should_compute_edit: bool,
+    config: &'a AssistConfig,
 }
 
 impl<'a> AssistCtx<'a> {
@@ -90,21 +95,42 @@ impl<'a> AssistCtx<'a> {
         should_compute_edit: bool,
     ) -> AssistCtx<'a> {
         let source_file = sema.parse(frange.file_id);
-        AssistCtx { sema, db: sema.db, frange, source_file, should_compute_edit }
+        AssistCtx {
+            sema,
+            db: sema.db,
+            frange,
+            source_file,
+            should_compute_edit,
+            config: &DEFAULT_CONFIG,
+        }
+    }
+
+    /// Offers only the assists enabled by `config`, instead of all of them.
+    pub fn with_config(self, config: &'a AssistConfig) -> AssistCtx<'a> {
+        AssistCtx { config, ..self }
+    }
+
+    pub fn config(&self) -> &AssistConfig {
+        self.config
     }
 
-    pub(crate) fn add_assist(
+    pub fn add_assist(
         self,
         id: AssistId,
+        kind: AssistKind,
         label: impl Into<String>,
+        target: TextRange,
         f: impl FnOnce(&mut ActionBuilder),
     ) -> Option<Assist> {
-        let label = AssistLabel::new(label.into(), id);
+        if !self.config.is_enabled(id) || !self.config.is_kind_enabled(kind) {
+            return None;
+        }
+        let label = AssistLabel::new(label.into(), id, kind, target);
 
         let mut info = AssistInfo::new(label);
         if self.should_compute_edit {
             let action = {
-                let mut edit = ActionBuilder::default();
+                let mut edit = ActionBuilder::new(self.frange.file_id);
                 f(&mut edit);
                 edit.build()
             };
@@ -114,48 +140,110 @@ impl<'a> AssistCtx<'a> {
         Some(Assist(vec![info]))
     }
 
-    pub(crate) fn add_assist_group(self, group_name: impl Into<String>) -> AssistGroup<'a> {
+    pub fn add_assist_group(self, group_name: impl Into<String>) -> AssistGroup<'a> {
         AssistGroup { ctx: self, group_name: group_name.into(), assists: Vec::new() }
     }
 
-    pub(crate) fn token_at_offset(&self) -> TokenAtOffset<SyntaxToken> {
+    pub fn token_at_offset(&self) -> TokenAtOffset<SyntaxToken> {
         self.source_file.syntax().token_at_offset(self.frange.range.start())
     }
 
-    pub(crate) fn find_token_at_offset(&self, kind: SyntaxKind) -> Option<SyntaxToken> {
+    pub fn find_token_at_offset(&self, kind: SyntaxKind) -> Option<SyntaxToken> {
         self.token_at_offset().find(|it| it.kind() == kind)
     }
 
-    pub(crate) fn find_node_at_offset<N: AstNode>(&self) -> Option<N> {
+    pub fn find_node_at_offset<N: AstNode>(&self) -> Option<N> {
         find_node_at_offset(self.source_file.syntax(), self.frange.range.start())
     }
-    pub(crate) fn covering_element(&self) -> SyntaxElement {
+
+    /// Like `find_node_at_offset`, but an item spanning more than `max_lines`
+    /// lines is only found from the line of its name, so that its assists are
+    /// not offered from everywhere inside it.
+    pub fn find_enclosing_item_if_small<N: NameOwner>(&self, max_lines: usize) -> Option<N> {
+        let item = self.find_node_at_offset::<N>()?;
+        let offset = self.frange.range.start();
+        let text = item.syntax().text().to_string();
+        if text.lines().count() <= max_lines {
+            return Some(item);
+        }
+        let name_end = item.name()?.syntax().text_range().end();
+        if offset <= name_end {
+            return Some(item);
+        }
+        let start = item.syntax().text_range().start();
+        let after_name = &text[(name_end - start).to_usize()..(offset - start).to_usize()];
+        if after_name.contains('\n') {
+            return None;
+        }
+        Some(item)
+    }
+    pub fn covering_element(&self) -> SyntaxElement {
         find_covering_element(self.source_file.syntax(), self.frange.range)
     }
-    pub(crate) fn covering_node_for_range(&self, range: TextRange) -> SyntaxElement {
+    pub fn covering_node_for_range(&self, range: TextRange) -> SyntaxElement {
         find_covering_element(self.source_file.syntax(), range)
     }
+
+    /// Like `find_token_at_offset`, but a token of a macro call is replaced by
+    /// the corresponding token of the expansion.
+    ///
+    /// Ranges of the syntax found in an expansion must be mapped back with
+    /// `original_range` before being edited.
+    pub fn find_token_at_offset_with_macros(&self, kind: SyntaxKind) -> Option<SyntaxToken> {
+        self.token_at_offset()
+            .map(|it| self.sema.descend_into_macros(it))
+            .find(|it| it.kind() == kind)
+    }
+
+    /// Returns the range of the `node`, which may come from a macro expansion,
+    /// in the file of the assist.
+    ///
+    /// Returns `None` if the `node` doesn't map to the same code there, for
+    /// example because it was (partially) written in the macro definition.
+    pub fn original_range(&self, node: &SyntaxNode) -> Option<TextRange> {
+        let range = self.sema.original_range(node);
+        if range.file_id != self.frange.file_id {
+            return None;
+        }
+        // Expansions don't preserve whitespace.
+        let strip = |text: &str| text.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+        if strip(&self.source_text(range.range)) != strip(&node.text().to_string()) {
+            return None;
+        }
+        Some(range.range)
+    }
+
+    /// Returns the text of the `range` of the file of the assist.
+    pub fn source_text(&self, range: TextRange) -> String {
+        self.db.file_text(self.frange.file_id)[range.start().to_usize()..range.end().to_usize()]
+            .to_string()
+    }
 }
 
-pub(crate) struct AssistGroup<'a> {
+pub struct AssistGroup<'a> {
     ctx: AssistCtx<'a>,
     group_name: String,
     assists: Vec<AssistInfo>,
 }
 
 impl<'a> AssistGroup<'a> {
-    pub(crate) fn add_assist(
+    pub fn add_assist(
         &mut self,
         id: AssistId,
+        kind: AssistKind,
         label: impl Into<String>,
+        target: TextRange,
         f: impl FnOnce(&mut ActionBuilder),
     ) {
-        let label = AssistLabel::new(label.into(), id);
+        if !self.ctx.config.is_enabled(id) || !self.ctx.config.is_kind_enabled(kind) {
+            return;
+        }
+        let label = AssistLabel::new(label.into(), id, kind, target);
 
         let mut info = AssistInfo::new(label).with_group(GroupLabel(self.group_name.clone()));
         if self.ctx.should_compute_edit {
             let action = {
-                let mut edit = ActionBuilder::default();
+                let mut edit = ActionBuilder::new(self.ctx.frange.file_id);
                 f(&mut edit);
                 edit.build()
             };
@@ -165,7 +253,26 @@ impl<'a> AssistGroup<'a> {
         self.assists.push(info)
     }
 
-    pub(crate) fn finish(self) -> Option<Assist> {
+    /// Like `add_assist`, also highlighting the `secondary_target` range.
+    pub fn add_assist_with_secondary_target(
+        &mut self,
+        id: AssistId,
+        kind: AssistKind,
+        label: impl Into<String>,
+        target: TextRange,
+        secondary_target: TextRange,
+        f: impl FnOnce(&mut ActionBuilder),
+    ) {
+        let len = self.assists.len();
+        self.add_assist(id, kind, label, target, f);
+        if self.assists.len() > len {
+            if let Some(info) = self.assists.last_mut() {
+                info.label.secondary_target = Some(secondary_target);
+            }
+        }
+    }
+
+    pub fn finish(self) -> Option<Assist> {
         if self.assists.is_empty() {
             None
         } else {
@@ -174,27 +281,36 @@ impl<'a> AssistGroup<'a> {
     }
 }
 
-#[derive(Default)]
-pub(crate) struct ActionBuilder {
+pub struct ActionBuilder {
+    file_id: FileId,
     edit: TextEditBuilder,
+    other_edits: Vec<(FileId, TextEditBuilder)>,
+    file_creation: Option<FileCreation>,
     cursor_position: Option<TextUnit>,
-    target: Option<TextRange>,
+    tabstops: Vec<TextRange>,
 }
 
 impl ActionBuilder {
+    fn new(file_id: FileId) -> ActionBuilder {
+        ActionBuilder {
+            file_id,
+            edit: TextEditBuilder::default(),
+            other_edits: Vec::new(),
+            file_creation: None,
+            cursor_position: None,
+            tabstops: Vec::new(),
+        }
+    }
+
     /// Replaces specified `range` of text with a given string.
-    pub(crate) fn replace(&mut self, range: TextRange, replace_with: impl Into<String>) {
+    pub fn replace(&mut self, range: TextRange, replace_with: impl Into<String>) {
         self.edit.replace(range, replace_with.into())
     }
 
     /// Replaces specified `node` of text with a given string, reindenting the
     /// string to maintain `node`'s existing indent.
     // FIXME: remove in favor of ra_syntax::edit::IndentLevel::increase_indent
-    pub(crate) fn replace_node_and_indent(
-        &mut self,
-        node: &SyntaxNode,
-        replace_with: impl Into<String>,
-    ) {
+    pub fn replace_node_and_indent(&mut self, node: &SyntaxNode, replace_with: impl Into<String>) {
         let mut replace_with = replace_with.into();
         if let Some(indent) = leading_indent(node) {
             replace_with = reindent(&replace_with, &indent)
@@ -204,42 +320,72 @@ impl ActionBuilder {
 
     /// Remove specified `range` of text.
     #[allow(unused)]
-    pub(crate) fn delete(&mut self, range: TextRange) {
+    pub fn delete(&mut self, range: TextRange) {
         self.edit.delete(range)
     }
 
     /// Append specified `text` at the given `offset`
-    pub(crate) fn insert(&mut self, offset: TextUnit, text: impl Into<String>) {
+    pub fn insert(&mut self, offset: TextUnit, text: impl Into<String>) {
         self.edit.insert(offset, text.into())
     }
 
     /// Specify desired position of the cursor after the assist is applied.
-    pub(crate) fn set_cursor(&mut self, offset: TextUnit) {
+    pub fn set_cursor(&mut self, offset: TextUnit) {
         self.cursor_position = Some(offset)
     }
 
-    /// Specify that the assist should be active withing the `target` range.
-    ///
-    /// Target ranges are used to sort assists: the smaller the target range,
-    /// the more specific assist is, and so it should be sorted first.
-    pub(crate) fn target(&mut self, target: TextRange) {
-        self.target = Some(target)
+    /// Adds a tabstop selecting `range` of the edited text, after the ones
+    /// added before.
+    pub fn add_tabstop(&mut self, range: TextRange) {
+        self.tabstops.push(range)
     }
 
     /// Get access to the raw `TextEditBuilder`.
-    pub(crate) fn text_edit_builder(&mut self) -> &mut TextEditBuilder {
+    pub fn text_edit_builder(&mut self) -> &mut TextEditBuilder {
         &mut self.edit
     }
 
-    pub(crate) fn replace_ast<N: AstNode>(&mut self, old: N, new: N) {
+    pub fn replace_ast<N: AstNode>(&mut self, old: N, new: N) {
         algo::diff(old.syntax(), new.syntax()).into_text_edit(&mut self.edit)
     }
 
-    fn build(self) -> AssistAction {
-        AssistAction {
-            edit: self.edit.finish(),
-            cursor_position: self.cursor_position,
-            target: self.target,
+    /// Get access to the edit of another file than the one of the assist, like
+    /// the file of the module an import is added to.
+    ///
+    /// The other methods of the builder, including the cursor and the
+    /// tabstops, are about the file of the assist.
+    pub fn edit_file(&mut self, file_id: FileId) -> &mut TextEditBuilder {
+        if file_id == self.file_id {
+            return &mut self.edit;
         }
+        let idx = match self.other_edits.iter().position(|(it, _)| *it == file_id) {
+            Some(idx) => idx,
+            None => {
+                self.other_edits.push((file_id, TextEditBuilder::default()));
+                self.other_edits.len() - 1
+            }
+        };
+        &mut self.other_edits[idx].1
+    }
+
+    /// Creates an empty file at `path` in the `source_root`.
+    pub fn create_file(&mut self, source_root: SourceRootId, path: RelativePathBuf) {
+        self.file_creation = Some(FileCreation { source_root, path })
+    }
+
+    fn build(self) -> AssistAction {
+        let cursor_position =
+            self.cursor_position.or_else(|| self.tabstops.first().map(|it| it.start()));
+        let snippet = if self.tabstops.is_empty() {
+            None
+        } else {
+            Some(Snippet { tabstops: self.tabstops, final_cursor: self.cursor_position })
+        };
+        let mut action =
+            AssistAction::single_file(self.file_id, self.edit.finish(), cursor_position);
+        action
+            .source_file_edits
+            .extend(self.other_edits.into_iter().map(|(file_id, edit)| (file_id, edit.finish())));
+        AssistAction { file_creation: self.file_creation, snippet, ..action }
     }
 }
@@ -0,0 +1,95 @@
+//! Applies every assist at (nearly) every offset of some real code, checking
+//! that the results still parse.
+//!
+//! The quick test below runs on a single small file. To check the sources of
+//! the whole crate, run the ignored test:
+//!
+//! ```text
+//! cargo test -p ra_assists -- --ignored assists_keep_crate_sources_parsing
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ra_db::FileRange;
+use ra_syntax::{SourceFile, TextRange, TextUnit};
+
+use crate::{diff::render_diff, helpers, resolved_assists, unresolved_assists};
+
+#[test]
+fn assists_keep_corpus_parsing() {
+    check_corpus_file("handlers/remove_mut.rs", include_str!("handlers/remove_mut.rs"), 50);
+}
+
+#[test]
+#[ignore]
+fn assists_keep_crate_sources_parsing() {
+    let mut files = Vec::new();
+    collect_rust_files(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut files);
+    files.sort();
+    for file in files {
+        let text = fs::read_to_string(&file).unwrap();
+        check_corpus_file(&file.display().to_string(), &text, 1);
+    }
+}
+
+/// Applies all the assists available at every `step`-th offset of `text`.
+///
+/// Each result should parse, and computing the assists twice should give the
+/// same assists, with the same edits.
+fn check_corpus_file(path: &str, text: &str, step: usize) {
+    assert!(SourceFile::parse(text).errors().is_empty(), "corpus file {} has syntax errors", path);
+    let (db, file_id) = helpers::with_single_file(text);
+
+    let mut failures = Vec::new();
+    for (offset, _) in text.char_indices().step_by(step) {
+        let offset = TextUnit::from_usize(offset);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let assists = resolved_assists(&db, frange);
+
+        // Resolved assists are sorted by their target, so only compare the
+        // sets of ids.
+        let mut unresolved_ids =
+            unresolved_assists(&db, frange).into_iter().map(|it| it.label.id.0).collect::<Vec<_>>();
+        unresolved_ids.sort();
+        let mut resolved_ids = assists.iter().map(|it| it.label.id.0).collect::<Vec<_>>();
+        resolved_ids.sort();
+        if unresolved_ids != resolved_ids {
+            failures.push(format!(
+                "{}:{:?}: unresolved assists {:?} differ from resolved ones {:?}",
+                path, offset, unresolved_ids, resolved_ids
+            ));
+        }
+        let assists_again = resolved_assists(&db, frange);
+
+        for (idx, assist) in assists.iter().enumerate() {
+            let after = assist.action.edit().apply(text);
+            let context = || format!("{}:{:?}: {}", path, offset, render_diff(assist, text));
+            let parse = SourceFile::parse(&after);
+            if !parse.errors().is_empty() {
+                failures.push(format!("syntax errors {:?} after {}", parse.errors(), context()));
+            }
+            let again = assists_again.get(idx).map(|it| it.action.edit().apply(text));
+            if again.as_ref() != Some(&after) {
+                failures.push(format!("different edit on second run of {}", context()));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("{} failures:\n\n{}", failures.len(), failures.join("\n"));
+    }
+}
+
+fn collect_rust_files(dir: &Path, acc: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_rust_files(&path, acc);
+        } else if path.extension().map_or(false, |it| it == "rs") {
+            acc.push(path);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Renders the edit of a resolved assist as a unified diff, to preview it
+//! without applying it.
+
+use std::{fmt::Write, ops::Range};
+
+use ra_syntax::{TextRange, TextUnit};
+use ra_text_edit::{AtomTextEdit, TextEdit};
+
+use crate::ResolvedAssist;
+
+/// Number of unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+/// Renders a unified diff of what `assist` does to `text`, the text of the
+/// file it was computed for. The edits of other files are left out.
+pub fn render_diff(assist: &ResolvedAssist, text: &str) -> String {
+    let title = format!("assist: {} ({})", assist.label.label, assist.label.id.0);
+    unified_diff(&title, text, assist.action.edit())
+}
+
+pub(crate) fn unified_diff(title: &str, text: &str, edit: &TextEdit) -> String {
+    let mut buf = format!("{}\n--- before\n+++ after\n", title);
+
+    let old_lines = lines(text);
+    let changes = changes(text, &old_lines, edit.as_atoms());
+    let mut delta: isize = 0;
+    let mut idx = 0;
+    while idx < changes.len() {
+        // Changes whose context overlaps are rendered in a single hunk.
+        let mut last = idx;
+        while last + 1 < changes.len()
+            && changes[last + 1].old_lines.start <= changes[last].old_lines.end + 2 * CONTEXT
+        {
+            last += 1;
+        }
+        let start = changes[idx].old_lines.start.saturating_sub(CONTEXT);
+        let end = (changes[last].old_lines.end + CONTEXT).min(old_lines.len());
+
+        let mut body = String::new();
+        let mut old_len = 0;
+        let mut new_len = 0;
+        let mut line = start;
+        for change in &changes[idx..=last] {
+            for context in &old_lines[line..change.old_lines.start] {
+                push_line(&mut body, ' ', context);
+            }
+            for removed in &old_lines[change.old_lines.start..change.old_lines.end] {
+                push_line(&mut body, '-', removed);
+            }
+            for added in lines(&change.new_text) {
+                push_line(&mut body, '+', added);
+                new_len += 1;
+            }
+            old_len += change.old_lines.end - line;
+            new_len += change.old_lines.start - line;
+            line = change.old_lines.end;
+        }
+        for context in &old_lines[line..end] {
+            push_line(&mut body, ' ', context);
+        }
+        old_len += end - line;
+        new_len += end - line;
+
+        let new_start = start as isize + delta;
+        writeln!(buf, "@@ -{},{} +{},{} @@", start + 1, old_len, new_start + 1, new_len).unwrap();
+        buf.push_str(&body);
+        delta += new_len as isize - old_len as isize;
+        idx = last + 1;
+    }
+    buf
+}
+
+/// The edit of a range of whole lines.
+struct Change {
+    old_lines: Range<usize>,
+    new_text: String,
+}
+
+fn changes(text: &str, old_lines: &[&str], atoms: &[AtomTextEdit]) -> Vec<Change> {
+    let mut line_starts = old_lines
+        .iter()
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some(start)
+        })
+        .collect::<Vec<_>>();
+    if text.is_empty() || text.ends_with('\n') {
+        line_starts.push(text.len());
+    }
+    let line_of = |offset: TextUnit| match line_starts.binary_search(&offset.to_usize()) {
+        Ok(idx) => idx,
+        Err(idx) => idx - 1,
+    };
+    let is_line_start = |offset: TextUnit| line_starts.binary_search(&offset.to_usize()).is_ok();
+
+    let mut atoms = atoms.to_vec();
+    atoms.sort_by_key(|it| it.delete.start());
+    let mut groups: Vec<(Range<usize>, Vec<AtomTextEdit>)> = Vec::new();
+    for atom in atoms {
+        let range: TextRange = atom.delete;
+        // Edits of whole lines don't touch the surrounding lines.
+        let whole_lines = is_line_start(range.start())
+            && is_line_start(range.end())
+            && (atom.insert.is_empty() || atom.insert.ends_with('\n'));
+        let first = line_of(range.start());
+        let last = if whole_lines { line_of(range.end()) } else { line_of(range.end()) + 1 };
+        let lines = first..last.min(old_lines.len());
+        match groups.last_mut() {
+            Some((group_lines, group)) if lines.start < group_lines.end => {
+                group_lines.end = group_lines.end.max(lines.end);
+                group.push(atom);
+            }
+            _ => groups.push((lines, vec![atom])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(lines, atoms)| {
+            let start = line_starts.get(lines.start).copied().unwrap_or(text.len());
+            let end = line_starts.get(lines.end).copied().unwrap_or(text.len());
+            let mut new_text = String::new();
+            let mut offset = start;
+            for atom in atoms {
+                new_text.push_str(&text[offset..atom.delete.start().to_usize()]);
+                new_text.push_str(&atom.insert);
+                offset = atom.delete.end().to_usize();
+            }
+            new_text.push_str(&text[offset..end]);
+            Change { old_lines: lines, new_text }
+        })
+        .collect()
+}
+
+/// Splits `text` into lines, keeping the line terminators.
+fn lines(text: &str) -> Vec<&str> {
+    let mut res = Vec::new();
+    let mut start = 0;
+    for (idx, _) in text.match_indices('\n') {
+        res.push(&text[start..idx + 1]);
+        start = idx + 1;
+    }
+    if start < text.len() {
+        res.push(&text[start..]);
+    }
+    res
+}
+
+fn push_line(buf: &mut String, marker: char, line: &str) {
+    buf.push(marker);
+    buf.push_str(line);
+    if !line.ends_with('\n') {
+        buf.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileId;
+    use ra_text_edit::TextEditBuilder;
+    use test_utils::assert_eq_text;
+
+    use super::*;
+    use crate::{AssistAction, AssistId, AssistKind, AssistLabel};
+
+    fn check(text: &str, edit: TextEdit, expected: &str) {
+        let assist = ResolvedAssist {
+            label: AssistLabel::new(
+                "Do something".to_string(),
+                AssistId("do_something"),
+                AssistKind::Refactor,
+                TextRange::from_to(0.into(), 0.into()),
+            ),
+            group_label: None,
+            action: AssistAction::single_file(FileId(0), edit, None),
+        };
+        assert_eq_text!(expected, &render_diff(&assist, text));
+    }
+
+    #[test]
+    fn diff_of_insertion() {
+        check(
+            "fn main() {\n    foo();\n}\n",
+            TextEdit::insert(TextUnit::from(23), "    bar();\n".to_string()),
+            r#"assist: Do something (do_something)
+--- before
++++ after
+@@ -1,3 +1,4 @@
+ fn main() {
+     foo();
++    bar();
+ }
+"#,
+        );
+    }
+
+    #[test]
+    fn diff_of_deletion() {
+        check(
+            "a\nb\nc\nd\ne\nf\ng\nh",
+            TextEdit::delete(TextRange::from_to(10.into(), 14.into())),
+            r#"assist: Do something (do_something)
+--- before
++++ after
+@@ -3,6 +3,4 @@
+ c
+ d
+ e
+-f
+-g
+ h
+"#,
+        );
+    }
+
+    #[test]
+    fn diff_with_several_hunks() {
+        let text = (1..=20).map(|it| format!("line {}\n", it)).collect::<String>();
+        let line_start =
+            |line: usize| TextUnit::from_usize(text.find(&format!("line {}\n", line)).unwrap());
+        let mut builder = TextEditBuilder::default();
+        builder.replace(
+            TextRange::offset_len(line_start(2), TextUnit::of_str("line 2")),
+            "first\nsecond".to_string(),
+        );
+        builder.delete(TextRange::from_to(line_start(15), line_start(16)));
+        check(
+            &text,
+            builder.finish(),
+            r#"assist: Do something (do_something)
+--- before
++++ after
+@@ -1,5 +1,6 @@
+ line 1
+-line 2
++first
++second
+ line 3
+ line 4
+ line 5
+@@ -12,7 +13,6 @@
+ line 12
+ line 13
+ line 14
+-line 15
+ line 16
+ line 17
+ line 18
+"#,
+        );
+    }
+}
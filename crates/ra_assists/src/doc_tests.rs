@@ -5,24 +5,45 @@
 
 mod generated;
 
-use ra_db::FileRange;
-use test_utils::{assert_eq_text, extract_range_or_offset};
+use ra_db::{fixture::WithFixture, FileRange, SourceDatabase};
+use ra_ide_db::RootDatabase;
+use ra_syntax::TextRange;
+use test_utils::{assert_eq_text, extract_range_or_offset, parse_fixture, CURSOR_MARKER};
 
-use crate::resolved_assists;
+use crate::{duplicate_assists, resolved_assists, unresolved_assists};
 
 fn check(assist_id: &str, before: &str, after: &str) {
+    // Examples needing several crates are fixtures, only the file with the
+    // cursor is compared then.
+    if before.trim_start().starts_with("//-") {
+        let (db, position) = RootDatabase::with_position(before);
+        let meta = parse_fixture(before)
+            .into_iter()
+            .find(|it| it.text.contains(CURSOR_MARKER))
+            .map(|it| it.meta)
+            .unwrap();
+        let after = parse_fixture(after).into_iter().find(|it| it.meta == meta).unwrap().text;
+        let before = db.file_text(position.file_id);
+        let range = TextRange::offset_len(position.offset, 0.into());
+        let frange = FileRange { file_id: position.file_id, range };
+        return check_at(&db, frange, assist_id, &before, &after);
+    }
+
     let (selection, before) = extract_range_or_offset(before);
     let (db, file_id) = crate::helpers::with_single_file(&before);
     let frange = FileRange { file_id, range: selection.into() };
+    check_at(&db, frange, assist_id, &before, after)
+}
 
-    let assist = resolved_assists(&db, frange)
+fn check_at(db: &RootDatabase, frange: FileRange, assist_id: &str, before: &str, after: &str) {
+    let assist = resolved_assists(db, frange)
         .into_iter()
         .find(|assist| assist.label.id.0 == assist_id)
         .unwrap_or_else(|| {
             panic!(
                 "\n\nAssist is not applicable: {}\nAvailable assists: {}",
                 assist_id,
-                resolved_assists(&db, frange)
+                unresolved_assists(db, frange)
                     .into_iter()
                     .map(|assist| assist.label.id.0)
                     .collect::<Vec<_>>()
@@ -30,6 +51,14 @@ fn check(assist_id: &str, before: &str, after: &str) {
             )
         });
 
-    let actual = assist.action.edit.apply(&before);
+    // Which of the duplicates is kept depends on the targets only, an example
+    // shouldn't rely on it.
+    if let Some(duplicate) =
+        duplicate_assists(db, frange).into_iter().find(|it| it.id.0 == assist_id)
+    {
+        panic!("\n\nAssist is offered twice: {} ({})", assist_id, duplicate.label);
+    }
+
+    let actual = assist.action.edit().apply(before);
     assert_eq_text!(after, &actual);
 }
@@ -2,6 +2,38 @@
 
 use super::check;
 
+#[test]
+fn doctest_add_as_ref_impl() {
+    check(
+        "add_as_ref_impl",
+        r#####"
+struct Name<|>(String);
+"#####,
+        r#####"
+struct Name(String);
+
+impl AsRef<String> for Name {
+    fn as_ref(&self) -> &String {
+        &self.0
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_add_braces_to_use() {
+    check(
+        "add_braces_to_use",
+        r#####"
+use std::collections::Hash<|>Map;
+"#####,
+        r#####"
+use std::collections::{HashMap};
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_custom_impl() {
     check(
@@ -41,6 +73,60 @@ struct Point {
     )
 }
 
+#[test]
+fn doctest_add_else_branch() {
+    check(
+        "add_else_branch",
+        r#####"
+fn main() {
+    let x = <|>if cond { 1 };
+}
+"#####,
+        r#####"
+fn main() {
+    let x = if cond { 1 } else { todo!() };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_add_error_impl() {
+    check(
+        "add_error_impl",
+        r#####"
+enum ConfigError<|> {
+    Missing,
+    Io(std::io::Error),
+}
+"#####,
+        r#####"
+enum ConfigError {
+    Missing,
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Missing => write!(f, "Missing"),
+            ConfigError::Io(e) => write!(f, "Io: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_explicit_type() {
     check(
@@ -75,6 +161,38 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_add_hash_impl() {
+    check(
+        "add_hash_impl",
+        r#####"
+struct Point<|> { x: u32, y: u32, cache: u32 }
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+"#####,
+        r#####"
+struct Point { x: u32, y: u32, cache: u32 }
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl std::hash::Hash for Point {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_impl() {
     check(
@@ -161,122 +279,138 @@ impl Trait<u32> for () {
 }
 
 #[test]
-fn doctest_add_new() {
+fn doctest_add_index_impl() {
     check(
-        "add_new",
+        "add_index_impl",
         r#####"
-struct Ctx<T: Clone> {
-     data: T,<|>
+struct Stack<|> {
+    items: Vec<u32>,
 }
 "#####,
         r#####"
-struct Ctx<T: Clone> {
-     data: T,
+struct Stack {
+    items: Vec<u32>,
 }
 
-impl<T: Clone> Ctx<T> {
-    fn new(data: T) -> Self { Self { data } }
-}
+impl std::ops::Index<usize> for Stack {
+    type Output = u32;
 
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.items[index]
+    }
+}
 "#####,
     )
 }
 
 #[test]
-fn doctest_apply_demorgan() {
+fn doctest_add_map_err() {
     check(
-        "apply_demorgan",
+        "add_map_err",
         r#####"
-fn main() {
-    if x != 4 ||<|> !y {}
+enum Result<T, E> { Ok(T), Err(E) }
+fn read_config() -> Result<String, u32> { loop {} }
+fn load() -> Result<String, u32> {
+    let text = read_config()<|>?;
+    Ok(text)
 }
 "#####,
         r#####"
-fn main() {
-    if !(x == 4 && y) {}
+enum Result<T, E> { Ok(T), Err(E) }
+fn read_config() -> Result<String, u32> { loop {} }
+fn load() -> Result<String, u32> {
+    let text = read_config().map_err(|e| e)?;
+    Ok(text)
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_auto_import() {
+fn doctest_add_missing_derive() {
     check(
-        "auto_import",
+        "add_missing_derive",
         r#####"
-fn main() {
-    let map = HashMap<|>::new();
+struct Point { x: u32, y: u32 }
+
+fn is_origin(p: Point) -> bool {
+    p ==<|> Point { x: 0, y: 0 }
 }
-pub mod std { pub mod collections { pub struct HashMap { } } }
 "#####,
         r#####"
-use std::collections::HashMap;
+#[derive(PartialEq)]
+struct Point { x: u32, y: u32 }
 
-fn main() {
-    let map = HashMap::new();
+fn is_origin(p: Point) -> bool {
+    p == Point { x: 0, y: 0 }
 }
-pub mod std { pub mod collections { pub struct HashMap { } } }
 "#####,
     )
 }
 
 #[test]
-fn doctest_change_visibility() {
+fn doctest_add_move() {
     check(
-        "change_visibility",
+        "add_move",
         r#####"
-<|>fn frobnicate() {}
+fn main() {
+    let name = String::new();
+    std::thread::spawn(<|>|| println!("{}", name));
+}
 "#####,
         r#####"
-pub(crate) fn frobnicate() {}
+fn main() {
+    let name = String::new();
+    std::thread::spawn(move || println!("{}", name));
+}
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_to_guarded_return() {
+fn doctest_add_new() {
     check(
-        "convert_to_guarded_return",
+        "add_new",
         r#####"
-fn main() {
-    <|>if cond {
-        foo();
-        bar();
-    }
+struct Ctx<T: Clone> {
+     data: T,<|>
 }
 "#####,
         r#####"
-fn main() {
-    if !cond {
-        return;
-    }
-    foo();
-    bar();
+struct Ctx<T: Clone> {
+     data: T,
+}
+
+impl<T: Clone> Ctx<T> {
+    fn new(data: T) -> Self { Self { data } }
 }
+
 "#####,
     )
 }
 
 #[test]
-fn doctest_fill_match_arms() {
+fn doctest_add_non_exhaustive() {
     check(
-        "fill_match_arms",
+        "add_non_exhaustive",
         r#####"
-enum Action { Move { distance: u32 }, Stop }
+pub enum Mode<|> { Read, Write }
 
-fn handle(action: Action) {
-    match action {
-        <|>
+fn describe(mode: Mode) -> u32 {
+    match mode {
+        Mode::Read => 0,
+        Mode::Write => 1,
     }
 }
 "#####,
         r#####"
-enum Action { Move { distance: u32 }, Stop }
+#[non_exhaustive]
+pub enum Mode { Read, Write }
 
-fn handle(action: Action) {
-    match action {
-        Action::Move { distance } => (),
-        Action::Stop => (),
+fn describe(mode: Mode) -> u32 {
+    match mode {
+        Mode::Read => 0,
+        Mode::Write => 1,
     }
 }
 "#####,
@@ -284,159 +418,185 @@ fn handle(action: Action) {
 }
 
 #[test]
-fn doctest_flip_binexpr() {
+fn doctest_add_number_suffixes() {
     check(
-        "flip_binexpr",
+        "add_number_suffixes",
         r#####"
-fn main() {
-    let _ = 90 +<|> 2;
-}
+const MASKS: [u8; 3] = [<|>0x0F, 0xF0, 0xFF<|>];
 "#####,
         r#####"
-fn main() {
-    let _ = 2 + 90;
-}
+const MASKS: [u8; 3] = [0x0Fu8, 0xF0u8, 0xFFu8];
 "#####,
     )
 }
 
 #[test]
-fn doctest_flip_comma() {
+fn doctest_add_ref_trait_impl() {
     check(
-        "flip_comma",
+        "add_ref_trait_impl",
         r#####"
-fn main() {
-    ((1, 2),<|> (3, 4));
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for <|>Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
 }
 "#####,
         r#####"
-fn main() {
-    ((3, 4), (1, 2));
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+impl Shape for &Square {
+    fn area(&self) -> f64 {
+        (*self).area()
+    }
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_flip_trait_bound() {
+fn doctest_add_serde_derive() {
     check(
-        "flip_trait_bound",
+        "add_serde_derive",
         r#####"
-fn foo<T: Clone +<|> Copy>() { }
+//- /main.rs crate:main deps:serde
+struct Point<|> { x: i32, y: i32 }
+//- /serde.rs crate:serde
+pub trait Serialize {}
+pub trait Deserialize {}
 "#####,
         r#####"
-fn foo<T: Copy + Clone>() { }
+//- /main.rs crate:main deps:serde
+#[derive(serde::Serialize)]
+struct Point { x: i32, y: i32 }
+//- /serde.rs crate:serde
+pub trait Serialize {}
+pub trait Deserialize {}
 "#####,
     )
 }
 
 #[test]
-fn doctest_inline_local_variable() {
+fn doctest_add_use_alias() {
     check(
-        "inline_local_variable",
+        "add_use_alias",
         r#####"
-fn main() {
-    let x<|> = 1 + 2;
-    x * 4;
-}
+mod fmt { pub struct Formatter; }
+use fmt::Format<|>ter;
+
+fn format(f: &Formatter) {}
 "#####,
         r#####"
-fn main() {
-    (1 + 2) * 4;
-}
+mod fmt { pub struct Formatter; }
+use fmt::Formatter as <|>Formatter2;
+
+fn format(f: &Formatter2) {}
 "#####,
     )
 }
 
 #[test]
-fn doctest_introduce_variable() {
+fn doctest_apply_demorgan() {
     check(
-        "introduce_variable",
+        "apply_demorgan",
         r#####"
 fn main() {
-    <|>(1 + 2)<|> * 4;
+    if x != 4 ||<|> !y {}
 }
 "#####,
         r#####"
 fn main() {
-    let var_name = (1 + 2);
-    var_name * 4;
+    if !(x == 4 && y) {}
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_invert_if() {
+fn doctest_auto_import() {
     check(
-        "invert_if",
+        "auto_import",
         r#####"
 fn main() {
-    if<|> !y { A } else { B }
+    let map = HashMap<|>::new();
 }
+pub mod std { pub mod collections { pub struct HashMap { } } }
 "#####,
         r#####"
+use std::collections::HashMap;
+
 fn main() {
-    if y { B } else { A }
+    let map = HashMap::new();
 }
+pub mod std { pub mod collections { pub struct HashMap { } } }
 "#####,
     )
 }
 
 #[test]
-fn doctest_make_raw_string() {
+fn doctest_bind_to_underscore() {
     check(
-        "make_raw_string",
+        "bind_to_underscore",
         r#####"
 fn main() {
-    "Hello,<|> World!";
+    <|>compute();
 }
+fn compute() -> u32 { 92 }
 "#####,
         r#####"
 fn main() {
-    r#"Hello, World!"#;
+    let _ = compute();
 }
+fn compute() -> u32 { 92 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_make_usual_string() {
+fn doctest_change_visibility() {
     check(
-        "make_usual_string",
+        "change_visibility",
         r#####"
-fn main() {
-    r#"Hello,<|> "World!""#;
-}
+<|>fn frobnicate() {}
 "#####,
         r#####"
-fn main() {
-    "Hello, \"World!\"";
-}
+pub(crate) fn frobnicate() {}
 "#####,
     )
 }
 
 #[test]
-fn doctest_merge_match_arms() {
+fn doctest_collapse_to_range_pattern() {
     check(
-        "merge_match_arms",
+        "collapse_to_range_pattern",
         r#####"
-enum Action { Move { distance: u32 }, Stop }
-
-fn handle(action: Action) {
-    match action {
-        <|>Action::Move(..) => foo(),
-        Action::Stop => foo(),
+fn is_small(n: u32) -> bool {
+    match n {
+        3 | 1 <|>| 2 => true,
+        _ => false,
     }
 }
 "#####,
         r#####"
-enum Action { Move { distance: u32 }, Stop }
-
-fn handle(action: Action) {
-    match action {
-        Action::Move(..) | Action::Stop => foo(),
+fn is_small(n: u32) -> bool {
+    match n {
+        1..=3 => true,
+        _ => false,
     }
 }
 "#####,
@@ -444,123 +604,1438 @@ fn handle(action: Action) {
 }
 
 #[test]
-fn doctest_move_arm_cond_to_match_guard() {
+fn doctest_convert_bool_params_to_struct() {
     check(
-        "move_arm_cond_to_match_guard",
+        "convert_bool_params_to_struct",
         r#####"
-enum Action { Move { distance: u32 }, Stop }
+fn copy(src: &str, verbose<|>: bool, dry_run: bool) {
+    if verbose && !dry_run {}
+}
 
-fn handle(action: Action) {
-    match action {
-        Action::Move { distance } => <|>if distance > 10 { foo() },
-        _ => (),
-    }
+fn main() {
+    copy("a", true, false);
 }
 "#####,
         r#####"
-enum Action { Move { distance: u32 }, Stop }
+struct CopyOptions {
+    pub verbose: bool,
+    pub dry_run: bool,
+}
 
-fn handle(action: Action) {
-    match action {
-        Action::Move { distance } if distance > 10 => foo(),
-        _ => (),
-    }
+fn copy(src: &str, opts: CopyOptions) {
+    if opts.verbose && !opts.dry_run {}
+}
+
+fn main() {
+    copy("a", CopyOptions { verbose: true, dry_run: false });
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_move_bounds_to_where_clause() {
+fn doctest_convert_byte_literal_to_number() {
     check(
-        "move_bounds_to_where_clause",
+        "convert_byte_literal_to_number",
         r#####"
-fn apply<T, U, <|>F: FnOnce(T) -> U>(f: F, x: T) -> U {
-    f(x)
-}
+const STAR: u8 = b'*'<|>;
 "#####,
         r#####"
-fn apply<T, U, F>(f: F, x: T) -> U where F: FnOnce(T) -> U {
-    f(x)
+const STAR: u8 = 42u8;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_call_to_struct_literal() {
+    check(
+        "convert_call_to_struct_literal",
+        r#####"
+struct Point { x: f32, y: f32 }
+
+fn main() {
+    let p = Point<|>(1.0, 2.0);
+}
+"#####,
+        r#####"
+struct Point { x: f32, y: f32 }
+
+fn main() {
+    let p = Point { x: 1.0, y: 2.0 };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_const_to_static() {
+    check(
+        "convert_const_to_static",
+        r#####"
+const <|>LIMIT: usize = 92;
+"#####,
+        r#####"
+static LIMIT: usize = 92;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_doc_attr_to_comment() {
+    check(
+        "convert_doc_attr_to_comment",
+        r#####"
+#[doc = " Frobnicates<|> the widget."]
+#[doc = " Returns the number of \"frobs\"."]
+fn frobnicate() -> usize { 0 }
+"#####,
+        r#####"
+/// Frobnicates the widget.
+/// Returns the number of "frobs".
+fn frobnicate() -> usize { 0 }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_doc_comment_to_attr() {
+    check(
+        "convert_doc_comment_to_attr",
+        r#####"
+/// Frobnicates<|> the widget.
+/// Returns the number of "frobs".
+fn frobnicate() -> usize { 0 }
+"#####,
+        r#####"
+#[doc = " Frobnicates the widget."]
+#[doc = " Returns the number of \"frobs\"."]
+fn frobnicate() -> usize { 0 }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_float_to_scientific() {
+    check(
+        "convert_float_to_scientific",
+        r#####"
+const SPEED: f64 = 1_200_000.0<|>;
+"#####,
+        r#####"
+const SPEED: f64 = 1.2e6;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_loop_to_while() {
+    check(
+        "convert_loop_to_while",
+        r#####"
+fn main() {
+    <|>loop {
+        if stack.is_empty() {
+            break;
+        }
+        stack.pop();
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    while !stack.is_empty() {
+        stack.pop();
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_number_to_byte_literal() {
+    check(
+        "convert_number_to_byte_literal",
+        r#####"
+const STAR: u8 = 42<|>;
+"#####,
+        r#####"
+const STAR: u8 = b'*';
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_out_param_to_return() {
+    check(
+        "convert_out_param_to_return",
+        r#####"
+fn evens(n: u32, out<|>: &mut Vec<u32>) {
+    for i in 0..n {
+        out.push(i * 2);
+    }
+}
+
+fn main() {
+    let mut v = Vec::new();
+    evens(3, &mut v);
+}
+"#####,
+        r#####"
+fn evens(n: u32) -> Vec<u32> {
+    let mut out = Vec::new();
+    for i in 0..n {
+        out.push(i * 2);
+    }
+    out
+}
+
+fn main() {
+    let v = evens(3);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_return_type() {
+    check(
+        "convert_return_type",
+        r#####"
+fn numbers() -> impl Iterator<Item = u32><|> {
+    vec![1, 2].into_iter()
+}
+"#####,
+        r#####"
+fn numbers() -> Box<dyn Iterator<Item = u32>> {
+    Box::new(vec![1, 2].into_iter())
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_scientific_to_float() {
+    check(
+        "convert_scientific_to_float",
+        r#####"
+const SPEED: f64 = 1.2e6<|>;
+"#####,
+        r#####"
+const SPEED: f64 = 1200000.0;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_static_to_const() {
+    check(
+        "convert_static_to_const",
+        r#####"
+static <|>LIMIT: usize = 92;
+"#####,
+        r#####"
+const LIMIT: usize = 92;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_struct_literal_to_call() {
+    check(
+        "convert_struct_literal_to_call",
+        r#####"
+struct Point(f32, f32);
+
+fn main() {
+    let p = Point<|> { 0: 1.0, 1: 2.0 };
+}
+"#####,
+        r#####"
+struct Point(f32, f32);
+
+fn main() {
+    let p = Point(1.0, 2.0);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_to_guarded_return() {
+    check(
+        "convert_to_guarded_return",
+        r#####"
+fn main() {
+    <|>if cond {
+        foo();
+        bar();
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    if !cond {
+        return;
+    }
+    foo();
+    bar();
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_tuple_return_to_struct() {
+    check(
+        "convert_tuple_return_to_struct",
+        r#####"
+fn min_max(xs: &[u32]) -> (u32<|>, u32) {
+    (xs[0], xs[xs.len() - 1])
+}
+
+fn main() {
+    let (min, max) = min_max(&[1, 2]);
+}
+"#####,
+        r#####"
+struct MinMaxResult {
+    f0: u32,
+    f1: u32,
+}
+
+fn min_max(xs: &[u32]) -> MinMaxResult {
+    MinMaxResult { f0: xs[0], f1: xs[xs.len() - 1] }
+}
+
+fn main() {
+    let MinMaxResult { f0: min, f1: max } = min_max(&[1, 2]);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_copy_doc_example_to_test() {
+    check(
+        "copy_doc_example_to_test",
+        r#####"
+/// Doubles a number.
+///
+/// ~~~
+/// # use crate::double;
+/// assert_eq!(double(2<|>), 4);
+/// ~~~
+fn double(x: u32) -> u32 { x * 2 }
+"#####,
+        r#####"
+/// Doubles a number.
+///
+/// ~~~
+/// # use crate::double;
+/// assert_eq!(double(2), 4);
+/// ~~~
+fn double(x: u32) -> u32 { x * 2 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doc_example_double() {
+        use crate::double;
+        assert_eq!(double(2), 4);
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_create_module_file() {
+    check(
+        "create_module_file",
+        r#####"
+mod <|>foo;
+"#####,
+        r#####"
+mod foo;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_destructure_tuple_param() {
+    check(
+        "destructure_tuple_param",
+        r#####"
+fn main() {
+    let sum = |pair<|>: (u32, u32)| pair.0 + pair.1;
+}
+"#####,
+        r#####"
+fn main() {
+    let sum = |(a, b): (u32, u32)| a + b;
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_duplicate_item() {
+    check(
+        "duplicate_item",
+        r#####"
+fn frobnicate<|>() {}
+"#####,
+        r#####"
+fn frobnicate() {}
+
+fn frobnicate_copy() {}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_escape_non_ascii() {
+    check(
+        "escape_non_ascii",
+        r#####"
+fn main() {
+    "caf<|>é";
+}
+"#####,
+        r#####"
+fn main() {
+    "caf\u{00E9}";
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_expand_range_pattern() {
+    check(
+        "expand_range_pattern",
+        r#####"
+fn is_small(n: u32) -> bool {
+    match n {
+        1..=3<|> => true,
+        _ => false,
+    }
+}
+"#####,
+        r#####"
+fn is_small(n: u32) -> bool {
+    match n {
+        1 | 2 | 3 => true,
+        _ => false,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_extract_method() {
+    check(
+        "extract_method",
+        r#####"
+struct Counter { count: u32 }
+impl Counter {
+    fn tick(&mut self, step: u32) {
+        <|>self.count += step<|>;
+    }
+}
+"#####,
+        r#####"
+struct Counter { count: u32 }
+impl Counter {
+    fn tick(&mut self, step: u32) {
+        self.<|>extracted(step);
+    }
+
+    fn extracted(&mut self, step: u32) {
+        self.count += step
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_extract_option_helper() {
+    check(
+        "extract_option_helper",
+        r#####"
+enum Option<T> { Some(T), None }
+impl<T> Option<T> { fn unwrap(self) -> T { loop {} } }
+struct Section { value: Option<u32> }
+struct Config { section: Option<Section> }
+impl Config {
+    fn value(self) -> u32 {
+        <|>self.section.unwrap().value.unwrap()<|>
+    }
+}
+"#####,
+        r#####"
+enum Option<T> { Some(T), None }
+impl<T> Option<T> { fn unwrap(self) -> T { loop {} } }
+struct Section { value: Option<u32> }
+struct Config { section: Option<Section> }
+impl Config {
+    fn value(self) -> u32 {
+        self.<|>extracted().unwrap()
+    }
+
+    fn extracted(self) -> Option<u32> {
+        self.section?.value
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_extract_struct_from_enum_variant() {
+    check(
+        "extract_struct_from_enum_variant",
+        r#####"
+enum A { <|>One { foo: u32, bar: u32 } }
+"#####,
+        r#####"
+struct One { foo: u32, bar: u32 }
+
+enum A { One(One) }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_fill_match_arms() {
+    check(
+        "fill_match_arms",
+        r#####"
+enum Action { Move { distance: u32 }, Stop }
+
+fn handle(action: Action) {
+    match action {
+        <|>
+    }
+}
+"#####,
+        r#####"
+enum Action { Move { distance: u32 }, Stop }
+
+fn handle(action: Action) {
+    match action {
+        Action::Move { distance } => (),
+        Action::Stop => (),
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_fill_struct_fields() {
+    check(
+        "fill_struct_fields",
+        r#####"
+struct Point { x: i32, y: i32, z: i32 }
+
+fn main() {
+    let p = Point { x: 1<|> };
+}
+"#####,
+        r#####"
+struct Point { x: i32, y: i32, z: i32 }
+
+fn main() {
+    let p = Point { x: 1, y: todo!(), z: todo!() };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_flip_binexpr() {
+    check(
+        "flip_binexpr",
+        r#####"
+fn main() {
+    let _ = 90 +<|> 2;
+}
+"#####,
+        r#####"
+fn main() {
+    let _ = 2 + 90;
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_flip_comma() {
+    check(
+        "flip_comma",
+        r#####"
+fn main() {
+    ((1, 2),<|> (3, 4));
+}
+"#####,
+        r#####"
+fn main() {
+    ((3, 4), (1, 2));
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_flip_trait_bound() {
+    check(
+        "flip_trait_bound",
+        r#####"
+fn foo<T: Clone +<|> Copy>() { }
+"#####,
+        r#####"
+fn foo<T: Copy + Clone>() { }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_fold_guard_into_pattern() {
+    check(
+        "fold_guard_into_pattern",
+        r#####"
+fn describe(n: u32) -> &'static str {
+    match n {
+        x <|>if x == 5 => "five",
+        _ => "other",
+    }
+}
+"#####,
+        r#####"
+fn describe(n: u32) -> &'static str {
+    match n {
+        5 => "five",
+        _ => "other",
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_generate_delegate_methods() {
+    check(
+        "generate_delegate_methods",
+        r#####"
+struct Age(u8);
+impl Age {
+    pub fn age(&self) -> u8 { self.0 }
+}
+
+struct Person {
+    ag<|>e: Age,
+}
+"#####,
+        r#####"
+struct Age(u8);
+impl Age {
+    pub fn age(&self) -> u8 { self.0 }
+}
+
+struct Person {
+    age: Age,
+}
+
+impl Person {
+    <|>pub fn age(&self) -> u8 {
+        self.age.age()
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_inline_constant() {
+    check(
+        "inline_constant",
+        r#####"
+const SIZE: usize = 4 + 4;
+fn area() -> usize { SIZE<|> * 2 }
+"#####,
+        r#####"
+const SIZE: usize = 4 + 4;
+fn area() -> usize { (4 + 4) * 2 }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_inline_local_variable() {
+    check(
+        "inline_local_variable",
+        r#####"
+fn main() {
+    let x<|> = 1 + 2;
+    x * 4;
+}
+"#####,
+        r#####"
+fn main() {
+    (1 + 2) * 4;
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_inline_use_alias() {
+    check(
+        "inline_use_alias",
+        r#####"
+mod fmt { pub struct Formatter; }
+use fmt::Formatter as <|>F;
+
+fn format(f: &F) {}
+"#####,
+        r#####"
+mod fmt { pub struct Formatter; }
+use fmt::Formatter<|>;
+
+fn format(f: &Formatter) {}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_introduce_generic_parameter() {
+    check(
+        "introduce_generic_parameter",
+        r#####"
+fn greet(name: <|>String) {
+    println!("Hello, {}!", name);
+}
+"#####,
+        r#####"
+fn greet<S: Into<String>>(name: S) {
+    let name: String = name.into();
+    println!("Hello, {}!", name);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_introduce_variable() {
+    check(
+        "introduce_variable",
+        r#####"
+fn main() {
+    <|>(1 + 2)<|> * 4;
+}
+"#####,
+        r#####"
+fn main() {
+    let var_name = (1 + 2);
+    var_name * 4;
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_invert_if() {
+    check(
+        "invert_if",
+        r#####"
+fn main() {
+    if<|> !y { A } else { B }
+}
+"#####,
+        r#####"
+fn main() {
+    if y { B } else { A }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_invert_if_condition() {
+    check(
+        "invert_if_condition",
+        r#####"
+fn main() {
+    if !x.is_empty() &&<|> y < 3 { A } else { B }
+}
+"#####,
+        r#####"
+fn main() {
+    if x.is_empty() || y >= 3 { B } else { A }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_async() {
+    check(
+        "make_async",
+        r#####"
+fn fetch<|>() -> u32 { 92 }
+
+async fn run() -> u32 {
+    fetch()
+}
+"#####,
+        r#####"
+async fn fetch() -> u32 { 92 }
+
+async fn run() -> u32 {
+    fetch().await
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_byte_string() {
+    check(
+        "make_byte_string",
+        r#####"
+fn main() {
+    "Hello,<|> World!";
+}
+"#####,
+        r#####"
+fn main() {
+    b"Hello, World!";
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_const_fn() {
+    check(
+        "make_const_fn",
+        r#####"
+fn square<|>(x: u32) -> u32 { x * x }
+"#####,
+        r#####"
+const fn square(x: u32) -> u32 { x * x }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_default_variant() {
+    check(
+        "make_default_variant",
+        r#####"
+enum Mode {
+    Fast,
+    Sa<|>fe,
+}
+"#####,
+        r#####"
+#[derive(Default)]
+enum Mode {
+    Fast,
+    #[default]
+    Safe,
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_path_absolute() {
+    check(
+        "make_path_absolute",
+        r#####"
+mod a {
+    pub fn f() {}
+    mod b {
+        fn g() { super::f<|>(); }
+    }
+}
+"#####,
+        r#####"
+mod a {
+    pub fn f() {}
+    mod b {
+        fn g() { crate::a::f(); }
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_path_relative() {
+    check(
+        "make_path_relative",
+        r#####"
+mod a {
+    pub fn f() {}
+    mod b {
+        fn g() { crate::a::f<|>(); }
+    }
+}
+"#####,
+        r#####"
+mod a {
+    pub fn f() {}
+    mod b {
+        fn g() { super::f(); }
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_raw_identifier() {
+    check(
+        "make_raw_identifier",
+        r#####"
+fn gen<|>() {}
+fn main() { gen() }
+"#####,
+        r#####"
+fn r#gen() {}
+fn main() { r#gen() }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_raw_string() {
+    check(
+        "make_raw_string",
+        r#####"
+fn main() {
+    "Hello,<|> World!";
+}
+"#####,
+        r#####"
+fn main() {
+    r#"Hello, World!"#;
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_string_from_byte_string() {
+    check(
+        "make_string_from_byte_string",
+        r#####"
+fn main() {
+    b"Hello,<|> World!";
+}
+"#####,
+        r#####"
+fn main() {
+    "Hello, World!";
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_make_usual_string() {
+    check(
+        "make_usual_string",
+        r#####"
+fn main() {
+    r#"Hello,<|> "World!""#;
+}
+"#####,
+        r#####"
+fn main() {
+    "Hello, \"World!\"";
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_merge_impl_blocks() {
+    check(
+        "merge_impl_blocks",
+        r#####"
+struct Counter { count: u32 }
+
+impl Counter {
+    fn new() -> Counter { Counter { count: 0 } }
+}
+
+impl<|> Counter {
+    fn tick(&mut self) { self.count += 1 }
+}
+"#####,
+        r#####"
+struct Counter { count: u32 }
+
+impl Counter {
+    fn new() -> Counter { Counter { count: 0 } }
+
+    fn tick(&mut self) { self.count += 1 }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_merge_match_arms() {
+    check(
+        "merge_match_arms",
+        r#####"
+enum Action { Move { distance: u32 }, Stop }
+
+fn handle(action: Action) {
+    match action {
+        <|>Action::Move(..) => foo(),
+        Action::Stop => foo(),
+    }
+}
+"#####,
+        r#####"
+enum Action { Move { distance: u32 }, Stop }
+
+fn handle(action: Action) {
+    match action {
+        Action::Move(..) | Action::Stop => foo(),
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_merge_string_pushes() {
+    check(
+        "merge_string_pushes",
+        r#####"
+fn greet(name: &str) -> String {
+    let mut s = String::new();
+    s.push_str("Hello, ");
+    s.push_str(name)<|>;
+    s.push('!');
+    s
+}
+"#####,
+        r#####"
+fn greet(name: &str) -> String {
+    let s = format!("Hello, {}!", name);
+    s
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_merge_write_calls() {
+    check(
+        "merge_write_calls",
+        r#####"
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        write!(f, "{}, {}", self.x, self.y)<|>?;
+        write!(f, ")")
+    }
+}
+"#####,
+        r#####"
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_move_arm_cond_to_match_guard() {
+    check(
+        "move_arm_cond_to_match_guard",
+        r#####"
+enum Action { Move { distance: u32 }, Stop }
+
+fn handle(action: Action) {
+    match action {
+        Action::Move { distance } => <|>if distance > 10 { foo() },
+        _ => (),
+    }
+}
+"#####,
+        r#####"
+enum Action { Move { distance: u32 }, Stop }
+
+fn handle(action: Action) {
+    match action {
+        Action::Move { distance } if distance > 10 => foo(),
+        _ => (),
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_move_bounds_to_where_clause() {
+    check(
+        "move_bounds_to_where_clause",
+        r#####"
+fn apply<T, U, <|>F: FnOnce(T) -> U>(f: F, x: T) -> U {
+    f(x)
+}
+"#####,
+        r#####"
+fn apply<T, U, F>(f: F, x: T) -> U where F: FnOnce(T) -> U {
+    f(x)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_move_guard_to_arm_body() {
+    check(
+        "move_guard_to_arm_body",
+        r#####"
+enum Action { Move { distance: u32 }, Stop }
+
+fn handle(action: Action) {
+    match action {
+        Action::Move { distance } <|>if distance > 10 => foo(),
+        _ => (),
+    }
+}
+"#####,
+        r#####"
+enum Action { Move { distance: u32 }, Stop }
+
+fn handle(action: Action) {
+    match action {
+        Action::Move { distance } => if distance > 10 { foo() },
+        _ => (),
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_relax_sized_bound() {
+    check(
+        "relax_sized_bound",
+        r#####"
+fn print<T<|>: std::fmt::Debug>(x: &T) {
+    println!("{:?}", x);
+}
+"#####,
+        r#####"
+fn print<T: std::fmt::Debug + ?Sized>(x: &T) {
+    println!("{:?}", x);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_clone_on_copy() {
+    check(
+        "remove_clone_on_copy",
+        r#####"
+#[lang = "copy"]
+trait Copy {}
+impl Copy for u32 {}
+
+fn double(x: u32) -> u32 {
+    x.clo<|>ne() * 2
+}
+"#####,
+        r#####"
+#[lang = "copy"]
+trait Copy {}
+impl Copy for u32 {}
+
+fn double(x: u32) -> u32 {
+    x * 2
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_dbg() {
+    check(
+        "remove_dbg",
+        r#####"
+fn main() {
+    <|>dbg!(92);
+}
+"#####,
+        r#####"
+fn main() {
+    92;
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_debug_print() {
+    check(
+        "remove_debug_print",
+        r#####"
+fn main() {
+    let x = 92;
+    <|>eprintln!("DEBUG: x = {}", x);
+    run(x);
+}
+"#####,
+        r#####"
+fn main() {
+    let x = 92;
+    run(x);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_derive() {
+    check(
+        "remove_derive",
+        r#####"
+#[derive(Clone, Deb<|>ug, PartialEq)]
+struct S;
+"#####,
+        r#####"
+#[derive(Clone, PartialEq)]
+struct S;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_digit_separators() {
+    check(
+        "remove_digit_separators",
+        r#####"
+const MAX: u32 = 4_294_967_295<|>;
+"#####,
+        r#####"
+const MAX: u32 = 4294967295;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_hash() {
+    check(
+        "remove_hash",
+        r#####"
+fn main() {
+    r#"Hello,<|> World!"#;
+}
+"#####,
+        r#####"
+fn main() {
+    r"Hello, World!";
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_let_underscore() {
+    check(
+        "remove_let_underscore",
+        r#####"
+fn main() {
+    let _ = <|>compute();
+}
+fn compute() -> u32 { 92 }
+"#####,
+        r#####"
+fn main() {
+    compute();
+}
+fn compute() -> u32 { 92 }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_move() {
+    check(
+        "remove_move",
+        r#####"
+fn main() {
+    let v = vec![1, 2];
+    let len = <|>move || v.len();
+}
+"#####,
+        r#####"
+fn main() {
+    let v = vec![1, 2];
+    let len = || v.len();
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_mut() {
+    check(
+        "remove_mut",
+        r#####"
+impl Walrus {
+    fn feed(&mut<|> self, amount: u32) {}
+}
+"#####,
+        r#####"
+impl Walrus {
+    fn feed(&self, amount: u32) {}
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_number_suffixes() {
+    check(
+        "remove_number_suffixes",
+        r#####"
+const MASKS: [u8; 3] = [<|>0x0Fu8, 0xF0u8, 0xFF_u8<|>];
+"#####,
+        r#####"
+const MASKS: [u8; 3] = [0x0F, 0xF0, 0xFF];
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_raw_identifier_prefix() {
+    check(
+        "remove_raw_identifier_prefix",
+        r#####"
+fn r#foo<|>() {}
+fn main() { r#foo() }
+"#####,
+        r#####"
+fn foo() {}
+fn main() { foo() }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_remove_redundant_binding() {
+    check(
+        "remove_redundant_binding",
+        r#####"
+fn sum(a: u32, b: u32) -> u32 {
+    let s<|> = a + b;
+    s
+}
+"#####,
+        r#####"
+fn sum(a: u32, b: u32) -> u32 {
+    a + b
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_move_guard_to_arm_body() {
+fn doctest_remove_unnecessary_braces() {
     check(
-        "move_guard_to_arm_body",
+        "remove_unnecessary_braces",
         r#####"
-enum Action { Move { distance: u32 }, Stop }
-
-fn handle(action: Action) {
-    match action {
-        Action::Move { distance } <|>if distance > 10 => foo(),
-        _ => (),
-    }
-}
+use std::{collections::{Hash<|>Map}};
 "#####,
         r#####"
-enum Action { Move { distance: u32 }, Stop }
-
-fn handle(action: Action) {
-    match action {
-        Action::Move { distance } => if distance > 10 { foo() },
-        _ => (),
-    }
-}
+use std::collections::HashMap;
 "#####,
     )
 }
 
 #[test]
-fn doctest_remove_dbg() {
+fn doctest_reorder_fields() {
     check(
-        "remove_dbg",
+        "reorder_fields",
         r#####"
-fn main() {
-    <|>dbg!(92);
-}
+struct Foo { foo: i32, bar: i32 }
+const TEST: Foo = <|>Foo { bar: 0, foo: 1 };
 "#####,
         r#####"
-fn main() {
-    92;
-}
+struct Foo { foo: i32, bar: i32 }
+const TEST: Foo = <|>Foo { foo: 1, bar: 0 };
 "#####,
     )
 }
 
 #[test]
-fn doctest_remove_hash() {
+fn doctest_replace_for_with_if_let() {
     check(
-        "remove_hash",
+        "replace_for_with_if_let",
         r#####"
-fn main() {
-    r#"Hello,<|> World!"#;
+fn print(name: Option<&str>) {
+    f<|>or name in name {
+        println!("{}", name);
+    }
 }
 "#####,
         r#####"
-fn main() {
-    r"Hello, World!";
+fn print(name: Option<&str>) {
+    if let Some(name) = name {
+        println!("{}", name);
+    }
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_remove_mut() {
+fn doctest_replace_if_let_err_with_unwrap_or_else() {
     check(
-        "remove_mut",
+        "replace_if_let_err_with_unwrap_or_else",
         r#####"
-impl Walrus {
-    fn feed(&mut<|> self, amount: u32) {}
+enum Result<T, E> { Ok(T), Err(E) }
+
+fn main() {
+    <|>if let Err(e) = run() {
+        report(e);
+    }
 }
+
+fn run() -> Result<(), String> { Ok(()) }
+fn report(e: String) {}
 "#####,
         r#####"
-impl Walrus {
-    fn feed(&self, amount: u32) {}
+enum Result<T, E> { Ok(T), Err(E) }
+
+fn main() {
+    run().unwrap_or_else(|e| {
+        report(e);
+    });
 }
+
+fn run() -> Result<(), String> { Ok(()) }
+fn report(e: String) {}
 "#####,
     )
 }
@@ -593,6 +2068,47 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_replace_impl_with_derive() {
+    check(
+        "replace_impl_with_derive",
+        r#####"
+struct Config { verbose: bool }
+
+impl Default for Config<|> {
+    fn default() -> Self {
+        Self { verbose: Default::default() }
+    }
+}
+"#####,
+        r#####"
+#[derive(Default)]
+struct Config { verbose: bool }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_replace_literal_with_const() {
+    check(
+        "replace_literal_with_const",
+        r#####"
+const ANSWER: i32 = 42;
+
+fn main() {
+    let x = 4<|>2;
+}
+"#####,
+        r#####"
+const ANSWER: i32 = 42;
+
+fn main() {
+    let x = ANSWER;
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_qualified_name_with_use() {
     check(
@@ -608,6 +2124,196 @@ fn process(map: HashMap<String, String>) {}
     )
 }
 
+#[test]
+fn doctest_replace_self_with_type() {
+    check(
+        "replace_self_with_type",
+        r#####"
+struct Foo<T>(T);
+
+impl<T> Foo<T> {
+    fn new(t: T) -> <|>Self {
+        Foo(t)
+    }
+}
+"#####,
+        r#####"
+struct Foo<T>(T);
+
+impl<T> Foo<T> {
+    fn new(t: T) -> Foo<T> {
+        Foo(t)
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_replace_type_with_self() {
+    check(
+        "replace_type_with_self",
+        r#####"
+struct Foo;
+
+impl Foo {
+    fn new() -> Foo {
+        <|>Foo
+    }
+}
+"#####,
+        r#####"
+struct Foo;
+
+impl Foo {
+    fn new() -> Foo {
+        Self
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_replace_unwrap_or_else_with_if_let_err() {
+    check(
+        "replace_unwrap_or_else_with_if_let_err",
+        r#####"
+enum Result<T, E> { Ok(T), Err(E) }
+
+fn main() {
+    run().unwrap_or_<|>else(|e| report(e));
+}
+
+fn run() -> Result<(), String> { Ok(()) }
+fn report(e: String) {}
+"#####,
+        r#####"
+enum Result<T, E> { Ok(T), Err(E) }
+
+fn main() {
+    if let Err(e) = run() { report(e) }
+}
+
+fn run() -> Result<(), String> { Ok(()) }
+fn report(e: String) {}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_replace_with_bool_expr() {
+    check(
+        "replace_with_bool_expr",
+        r#####"
+fn is_empty(len: usize) -> bool {
+    <|>if len == 0 { true } else { false }
+}
+"#####,
+        r#####"
+fn is_empty(len: usize) -> bool {
+    len == 0
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_restrict_visibility() {
+    check(
+        "restrict_visibility",
+        r#####"
+mod frobnicator {
+    <|>pub(crate) fn frobnicate() {}
+
+    pub(crate) fn run() { frobnicate() }
+}
+fn main() { frobnicator::run() }
+"#####,
+        r#####"
+mod frobnicator {
+    fn frobnicate() {}
+
+    pub(crate) fn run() { frobnicate() }
+}
+fn main() { frobnicator::run() }
+"#####,
+    )
+}
+
+#[test]
+fn doctest_separate_number_literal() {
+    check(
+        "separate_number_literal",
+        r#####"
+const MAX: u32 = 4294967295<|>;
+"#####,
+        r#####"
+const MAX: u32 = 4_294_967_295;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_simplify_bool_comparison() {
+    check(
+        "simplify_bool_comparison",
+        r#####"
+fn is_hidden(visible: bool) -> bool {
+    visible =<|>= false
+}
+"#####,
+        r#####"
+fn is_hidden(visible: bool) -> bool {
+    !visible
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_sort_derives() {
+    check(
+        "sort_derives",
+        r#####"
+#[derive(PartialEq, Debug<|>)]
+#[derive(Clone, Debug)]
+struct S;
+"#####,
+        r#####"
+#[derive(Clone, Debug, PartialEq)]
+struct S;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_split_impl_block() {
+    check(
+        "split_impl_block",
+        r#####"
+struct Counter { count: u32 }
+
+impl Counter {
+    fn tick(&mut self) { self.count += 1 }
+
+    fn dump<|>(&self) { }
+}
+"#####,
+        r#####"
+struct Counter { count: u32 }
+
+impl Counter {
+    fn tick(&mut self) { self.count += 1 }
+}
+
+impl Counter {
+    fn dump(&self) { }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_split_import() {
     check(
@@ -620,3 +2326,92 @@ use std::{collections::HashMap};
 "#####,
     )
 }
+
+#[test]
+fn doctest_swap_match_arms() {
+    check(
+        "swap_match_arms",
+        r#####"
+enum Action { Move, Stop }
+
+fn handle(action: Action) {
+    match action {
+        <|>Action::Move => {}
+        Action::Stop => {}
+    }
+}
+"#####,
+        r#####"
+enum Action { Move, Stop }
+
+fn handle(action: Action) {
+    match action {
+        Action::Stop => {}
+        Action::Move => {}
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_toggle_ignore() {
+    check(
+        "toggle_ignore",
+        r#####"
+#[test]
+fn <|>slow_test() {}
+"#####,
+        r#####"
+#[test]
+#[ignore]
+fn slow_test() {}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_unescape_char_codes() {
+    check(
+        "unescape_char_codes",
+        r#####"
+fn main() {
+    "caf<|>\u{E9}";
+}
+"#####,
+        r#####"
+fn main() {
+    "café";
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_wrap_field_in_option() {
+    check(
+        "wrap_field_in_option",
+        r#####"
+struct Config { name<|>: String }
+
+fn make(name: String) -> Config {
+    Config { name }
+}
+
+fn name(config: Config) -> String {
+    config.name
+}
+"#####,
+        r#####"
+struct Config { name: Option<String> }
+
+fn make(name: String) -> Config {
+    Config { name: Some(name) }
+}
+
+fn name(config: Config) -> String {
+    config.name.unwrap()
+}
+"#####,
+    )
+}
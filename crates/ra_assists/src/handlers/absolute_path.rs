@@ -0,0 +1,283 @@
+use hir::{ModuleDef, PathResolution, SemanticsScope};
+use ra_ide_db::RootDatabase;
+use ra_syntax::{
+    ast::{self, make, AstNode, PathSegmentKind},
+    SyntaxKind::{PATH_EXPR, USE_TREE},
+};
+
+use crate::{
+    utils::{absolute_module_path, relative_module_path},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: make_path_absolute
+//
+// Rewrites a path relative to the current module as a `crate::` path.
+//
+// ```
+// mod a {
+//     pub fn f() {}
+//     mod b {
+//         fn g() { super::f<|>(); }
+//     }
+// }
+// ```
+// ->
+// ```
+// mod a {
+//     pub fn f() {}
+//     mod b {
+//         fn g() { crate::a::f(); }
+//     }
+// }
+// ```
+pub(crate) fn make_path_absolute(ctx: AssistCtx) -> Option<Assist> {
+    let path = outermost_path(ctx.find_node_at_offset::<ast::Path>()?)?;
+    match first_segment(&path).kind()? {
+        PathSegmentKind::CrateKw | PathSegmentKind::Type { .. } => return None,
+        _ => (),
+    }
+    let scope = ctx.sema.scope(path.syntax());
+    let (prefix, module) = module_prefix(&scope, &path)?;
+    let text = replace_prefix(&path, &prefix, &absolute_module_path(ctx.db, module));
+    if !resolves_the_same(&scope, &path, &text) {
+        return None;
+    }
+
+    let range = path.syntax().text_range();
+    ctx.add_assist(
+        AssistId("make_path_absolute"),
+        AssistKind::RefactorRewrite,
+        "Use absolute path",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+// Assist: make_path_relative
+//
+// Rewrites a `crate::` path as the shortest path relative to the current
+// module.
+//
+// ```
+// mod a {
+//     pub fn f() {}
+//     mod b {
+//         fn g() { crate::a::f<|>(); }
+//     }
+// }
+// ```
+// ->
+// ```
+// mod a {
+//     pub fn f() {}
+//     mod b {
+//         fn g() { super::f(); }
+//     }
+// }
+// ```
+pub(crate) fn make_path_relative(ctx: AssistCtx) -> Option<Assist> {
+    let path = outermost_path(ctx.find_node_at_offset::<ast::Path>()?)?;
+    match first_segment(&path).kind()? {
+        PathSegmentKind::CrateKw => (),
+        _ => return None,
+    }
+    let scope = ctx.sema.scope(path.syntax());
+    let (prefix, module) = module_prefix(&scope, &path)?;
+    let mut relative = relative_module_path(ctx.db, scope.module()?, module);
+    // `self::` is only needed to import from the current module.
+    let in_use_tree = path.syntax().parent().map_or(false, |it| it.kind() == USE_TREE);
+    if !in_use_tree && relative.starts_with("self") && prefix.syntax() != path.syntax() {
+        relative = relative.trim_start_matches("self").trim_start_matches("::").to_string();
+    }
+    let text = replace_prefix(&path, &prefix, &relative);
+    if !resolves_the_same(&scope, &path, &text) {
+        return None;
+    }
+
+    let range = path.syntax().text_range();
+    ctx.add_assist(
+        AssistId("make_path_relative"),
+        AssistKind::RefactorRewrite,
+        "Use relative path",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// The whole path containing `path`, if it's an expression or an import.
+fn outermost_path(path: ast::Path) -> Option<ast::Path> {
+    let path = path.syntax().ancestors().take_while(|it| ast::Path::can_cast(it.kind())).last()?;
+    match path.parent()?.kind() {
+        PATH_EXPR | USE_TREE => ast::Path::cast(path),
+        _ => None,
+    }
+}
+
+fn first_segment(path: &ast::Path) -> ast::PathSegment {
+    let mut path = path.clone();
+    while let Some(qualifier) = path.qualifier() {
+        path = qualifier;
+    }
+    // Parsed paths always have a segment.
+    path.segment().unwrap()
+}
+
+/// The longest prefix of `path` naming a module of the current crate.
+fn module_prefix(
+    scope: &SemanticsScope<RootDatabase>,
+    path: &ast::Path,
+) -> Option<(ast::Path, hir::Module)> {
+    let krate = scope.module()?.krate();
+    let mut prefix = Some(path.clone());
+    while let Some(it) = prefix {
+        let resolution = hir::Path::from_ast(it.clone()).and_then(|it| scope.resolve_hir_path(&it));
+        if let Some(PathResolution::Def(ModuleDef::Module(module))) = resolution {
+            return if module.krate() == krate { Some((it, module)) } else { None };
+        }
+        prefix = it.qualifier();
+    }
+    None
+}
+
+/// The text of `path` with `prefix` replaced by `text`.
+fn replace_prefix(path: &ast::Path, prefix: &ast::Path, text: &str) -> String {
+    let path_text = path.syntax().to_string();
+    let rest = &path_text[prefix.syntax().text_range().len().to_usize()..];
+    if text.is_empty() {
+        rest.trim_start_matches("::").to_string()
+    } else {
+        format!("{}{}", text, rest)
+    }
+}
+
+fn resolves_the_same(scope: &SemanticsScope<RootDatabase>, path: &ast::Path, text: &str) -> bool {
+    let resolve =
+        |path: ast::Path| hir::Path::from_ast(path).and_then(|it| scope.resolve_hir_path(&it));
+    match resolve(path.clone()) {
+        Some(it) => resolve(make::path_from_text(text)) == Some(it),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn make_super_super_path_absolute() {
+        check_assist(
+            make_path_absolute,
+            "
+mod a {
+    pub struct S;
+    mod b {
+        mod c {
+            fn f() { let _ = super::super::S<|>; }
+        }
+    }
+}",
+            "
+mod a {
+    pub struct S;
+    mod b {
+        mod c {
+            fn f() { let _ = <|>crate::a::S; }
+        }
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn make_import_absolute() {
+        check_assist(
+            make_path_absolute,
+            "
+mod a { pub struct S; }
+mod b { use super::a::S<|>; }",
+            "
+mod a { pub struct S; }
+mod b { use <|>crate::a::S; }",
+        );
+    }
+
+    #[test]
+    fn make_path_relative_to_sibling_module() {
+        check_assist(
+            make_path_relative,
+            "
+mod a { pub struct S; }
+mod b { fn f() { let _ = crate::a::S<|>; } }",
+            "
+mod a { pub struct S; }
+mod b { fn f() { let _ = <|>super::a::S; } }",
+        );
+    }
+
+    #[test]
+    fn make_path_relative_to_nested_module() {
+        check_assist(
+            make_path_relative,
+            "
+mod a {
+    mod b { pub struct S; }
+    fn f() { let _ = crate::a::b::S<|>; }
+}",
+            "
+mod a {
+    mod b { pub struct S; }
+    fn f() { let _ = <|>b::S; }
+}",
+        );
+    }
+
+    #[test]
+    fn paths_through_reexports_keep_the_reexport() {
+        check_assist(
+            make_path_absolute,
+            "
+mod a {
+    mod inner { pub struct S; }
+    pub use self::inner::S;
+}
+mod b { fn f() { let _ = super::a::S<|>; } }",
+            "
+mod a {
+    mod inner { pub struct S; }
+    pub use self::inner::S;
+}
+mod b { fn f() { let _ = <|>crate::a::S; } }",
+        );
+        check_assist(
+            make_path_relative,
+            "
+mod a {
+    mod inner { pub struct S; }
+    pub use self::inner::S;
+}
+mod b { fn f() { let _ = crate::a::S<|>; } }",
+            "
+mod a {
+    mod inner { pub struct S; }
+    pub use self::inner::S;
+}
+mod b { fn f() { let _ = <|>super::a::S; } }",
+        );
+    }
+
+    #[test]
+    fn make_path_relative_not_applicable_to_relative_path() {
+        check_assist_not_applicable(
+            make_path_relative,
+            "mod a { pub struct S; } fn f() { let _ = a::S<|>; }",
+        );
+    }
+}
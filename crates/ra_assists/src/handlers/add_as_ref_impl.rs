@@ -0,0 +1,235 @@
+use ra_fmt::{leading_indent, reindent};
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner, StructKind, TypeAscriptionOwner},
+    TextUnit,
+};
+
+use crate::{utils::impl_header, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: add_as_ref_impl
+//
+// Adds an `AsRef` or a `Borrow` impl to a newtype, delegating to its field.
+//
+// ```
+// struct Name<|>(String);
+// ```
+// ->
+// ```
+// struct Name(String);
+//
+// impl AsRef<String> for Name {
+//     fn as_ref(&self) -> &String {
+//         &self.0
+//     }
+// }
+// ```
+pub(crate) fn add_as_ref_impl(ctx: AssistCtx) -> Option<Assist> {
+    let strukt = ctx.find_node_at_offset::<ast::StructDef>()?;
+    let name = strukt.name()?;
+    let (field, ty) = single_field(&strukt)?;
+    let inner = ty.syntax().to_string();
+
+    let mut targets = vec![
+        Target { trait_: "AsRef", method: "as_ref", ty: inner.clone(), key: type_key(&ty) },
+        Target { trait_: "Borrow", method: "borrow", ty: inner, key: type_key(&ty) },
+    ];
+    if let Some((ty, key)) = borrowed_form(&ty) {
+        targets.push(Target { trait_: "AsRef", method: "as_ref", ty, key });
+    }
+    let existing = existing_impls(&strukt, &name);
+    targets.retain(|it| !existing.contains(&(it.trait_.to_string(), it.key.clone())));
+    if targets.is_empty() {
+        return None;
+    }
+
+    let nominal = ast::NominalDef::StructDef(strukt.clone());
+    let indent = leading_indent(strukt.syntax()).unwrap_or_default();
+    let insert_offset = strukt.syntax().text_range().end();
+    let mut group = ctx.add_assist_group("Implement AsRef or Borrow");
+    for target in targets {
+        let trait_path = match target.trait_ {
+            "Borrow" => format!("std::borrow::Borrow<{}>", target.ty),
+            _ => format!("AsRef<{}>", target.ty),
+        };
+        let text = format!(
+            "{} {{\n    fn {}(&self) -> &{} {{\n        &self.{}\n    }}\n}}",
+            impl_header(&nominal, &name, Some(&trait_path)),
+            target.method,
+            target.ty,
+            field,
+        );
+        group.add_assist(
+            AssistId("add_as_ref_impl"),
+            AssistKind::Refactor,
+            format!("Implement {}<{}>", target.trait_, target.ty),
+            strukt.syntax().text_range(),
+            |edit| {
+                let prefix = format!("\n\n{}", indent);
+                edit.insert(insert_offset, format!("{}{}", prefix, reindent(&text, &indent)));
+                edit.set_cursor(insert_offset + TextUnit::of_str(&prefix));
+            },
+        );
+    }
+    group.finish()
+}
+
+struct Target {
+    trait_: &'static str,
+    method: &'static str,
+    ty: String,
+    /// The type as written in an impl, without qualifiers and whitespace.
+    key: String,
+}
+
+/// The name of the only field of `strukt`, or its index for a tuple struct.
+fn single_field(strukt: &ast::StructDef) -> Option<(String, ast::TypeRef)> {
+    let mut fields: Vec<(String, ast::TypeRef)> = match strukt.kind() {
+        StructKind::Record(field_list) => field_list
+            .fields()
+            .map(|it| Some((it.name()?.text().to_string(), it.ascribed_type()?)))
+            .collect::<Option<_>>()?,
+        StructKind::Tuple(field_list) => field_list
+            .fields()
+            .enumerate()
+            .map(|(idx, it)| Some((idx.to_string(), it.type_ref()?)))
+            .collect::<Option<_>>()?,
+        StructKind::Unit => return None,
+    };
+    if fields.len() != 1 {
+        return None;
+    }
+    fields.pop()
+}
+
+/// The type string-like and collection types deref to, as `str` for `String`.
+fn borrowed_form(ty: &ast::TypeRef) -> Option<(String, String)> {
+    let segment = match ty {
+        ast::TypeRef::PathType(it) => it.path()?.segment()?,
+        _ => return None,
+    };
+    let (ty, key) = match segment.name_ref()?.text().as_str() {
+        "String" => ("str".to_string(), "str".to_string()),
+        "PathBuf" => ("std::path::Path".to_string(), "Path".to_string()),
+        "OsString" => ("std::ffi::OsStr".to_string(), "OsStr".to_string()),
+        "Vec" => {
+            let item = segment.type_arg_list()?.type_args().next()?.type_ref()?;
+            (format!("[{}]", item.syntax()), format!("[{}]", type_key(&item)))
+        }
+        _ => return None,
+    };
+    Some((ty, key))
+}
+
+fn type_key(ty: &ast::TypeRef) -> String {
+    let text = match ty {
+        ast::TypeRef::PathType(it) => match it.path().and_then(|it| it.segment()) {
+            Some(segment) => segment.syntax().to_string(),
+            None => ty.syntax().to_string(),
+        },
+        _ => ty.syntax().to_string(),
+    };
+    text.chars().filter(|it| !it.is_whitespace()).collect()
+}
+
+/// The traits and type arguments of the trait impls for `strukt` in the file.
+fn existing_impls(strukt: &ast::StructDef, name: &ast::Name) -> Vec<(String, String)> {
+    let file = match strukt.syntax().ancestors().last() {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+    file.descendants()
+        .filter_map(ast::ImplDef::cast)
+        .filter(|it| match it.target_type() {
+            Some(ast::TypeRef::PathType(it)) => it
+                .path()
+                .and_then(|it| it.segment())
+                .and_then(|it| it.name_ref())
+                .map_or(false, |it| it.text() == name.text()),
+            _ => false,
+        })
+        .filter_map(|it| {
+            let segment = match it.target_trait()? {
+                ast::TypeRef::PathType(it) => it.path()?.segment()?,
+                _ => return None,
+            };
+            let arg = segment.type_arg_list()?.type_args().next()?.type_ref()?;
+            Some((segment.name_ref()?.text().to_string(), type_key(&arg)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use ra_syntax::TextRange;
+    use test_utils::extract_offset;
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist},
+        resolved_assists,
+    };
+
+    fn labels(before: &str) -> Vec<String> {
+        let (offset, before) = extract_offset(before);
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "add_as_ref_impl")
+            .map(|it| it.label.label)
+            .collect()
+    }
+
+    #[test]
+    fn add_as_ref_impls_for_string_newtype() {
+        assert_eq!(
+            labels("struct Name<|>(String);"),
+            vec!["Implement AsRef<String>", "Implement Borrow<String>", "Implement AsRef<str>"]
+        );
+        check_assist(
+            add_as_ref_impl,
+            "struct Name<|>(String);",
+            "struct Name(String);
+
+<|>impl AsRef<String> for Name {
+    fn as_ref(&self) -> &String {
+        &self.0
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn add_as_ref_impls_for_vec_newtype() {
+        assert_eq!(
+            labels("struct Stack<T> { <|>items: Vec<T> }"),
+            vec!["Implement AsRef<Vec<T>>", "Implement Borrow<Vec<T>>", "Implement AsRef<[T]>"]
+        );
+        check_assist(
+            add_as_ref_impl,
+            "struct Stack<T> { <|>items: Vec<T> }",
+            "struct Stack<T> { items: Vec<T> }
+
+<|>impl<T> AsRef<Vec<T>> for Stack<T> {
+    fn as_ref(&self) -> &Vec<T> {
+        &self.items
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn add_as_ref_impl_skips_existing_impls() {
+        assert_eq!(
+            labels(
+                "
+struct Name<|>(String);
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str { &self.0 }
+}"
+            ),
+            vec!["Implement AsRef<String>", "Implement Borrow<String>"]
+        );
+    }
+}
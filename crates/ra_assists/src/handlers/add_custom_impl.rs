@@ -1,6 +1,6 @@
 //! FIXME: write short doc here
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 use join_to_string::join;
 use ra_syntax::{
@@ -52,50 +52,54 @@ pub(crate) fn add_custom_impl(ctx: AssistCtx) -> Option<Assist> {
     let label =
         format!("Add custom impl '{}' for '{}'", trait_token.text().as_str(), annotated_name);
 
-    ctx.add_assist(AssistId("add_custom_impl"), label, |edit| {
-        edit.target(attr.syntax().text_range());
-
-        let new_attr_input = input
-            .syntax()
-            .descendants_with_tokens()
-            .filter(|t| t.kind() == IDENT)
-            .filter_map(|t| t.into_token().map(|t| t.text().clone()))
-            .filter(|t| t != trait_token.text())
-            .collect::<Vec<SmolStr>>();
-        let has_more_derives = !new_attr_input.is_empty();
-        let new_attr_input =
-            join(new_attr_input.iter()).separator(", ").surround_with("(", ")").to_string();
-        let new_attr_input_len = new_attr_input.len();
-
-        let mut buf = String::new();
-        buf.push_str("\n\nimpl ");
-        buf.push_str(trait_token.text().as_str());
-        buf.push_str(" for ");
-        buf.push_str(annotated_name.as_str());
-        buf.push_str(" {\n");
-
-        let cursor_delta = if has_more_derives {
-            edit.replace(input.syntax().text_range(), new_attr_input);
-            input.syntax().text_range().len() - TextUnit::from_usize(new_attr_input_len)
-        } else {
-            let attr_range = attr.syntax().text_range();
-            edit.delete(attr_range);
-
-            let line_break_range = attr
+    ctx.add_assist(
+        AssistId("add_custom_impl"),
+        AssistKind::Refactor,
+        label,
+        attr.syntax().text_range(),
+        |edit| {
+            let new_attr_input = input
                 .syntax()
-                .next_sibling_or_token()
-                .filter(|t| t.kind() == WHITESPACE)
-                .map(|t| t.text_range())
-                .unwrap_or_else(|| TextRange::from_to(TextUnit::from(0), TextUnit::from(0)));
-            edit.delete(line_break_range);
-
-            attr_range.len() + line_break_range.len()
-        };
-
-        edit.set_cursor(start_offset + TextUnit::of_str(&buf) - cursor_delta);
-        buf.push_str("\n}");
-        edit.insert(start_offset, buf);
-    })
+                .descendants_with_tokens()
+                .filter(|t| t.kind() == IDENT)
+                .filter_map(|t| t.into_token().map(|t| t.text().clone()))
+                .filter(|t| t != trait_token.text())
+                .collect::<Vec<SmolStr>>();
+            let has_more_derives = !new_attr_input.is_empty();
+            let new_attr_input =
+                join(new_attr_input.iter()).separator(", ").surround_with("(", ")").to_string();
+            let new_attr_input_len = new_attr_input.len();
+
+            let mut buf = String::new();
+            buf.push_str("\n\nimpl ");
+            buf.push_str(trait_token.text().as_str());
+            buf.push_str(" for ");
+            buf.push_str(annotated_name.as_str());
+            buf.push_str(" {\n");
+
+            let cursor_delta = if has_more_derives {
+                edit.replace(input.syntax().text_range(), new_attr_input);
+                input.syntax().text_range().len() - TextUnit::from_usize(new_attr_input_len)
+            } else {
+                let attr_range = attr.syntax().text_range();
+                edit.delete(attr_range);
+
+                let line_break_range = attr
+                    .syntax()
+                    .next_sibling_or_token()
+                    .filter(|t| t.kind() == WHITESPACE)
+                    .map(|t| t.text_range())
+                    .unwrap_or_else(|| TextRange::from_to(TextUnit::from(0), TextUnit::from(0)));
+                edit.delete(line_break_range);
+
+                attr_range.len() + line_break_range.len()
+            };
+
+            edit.set_cursor(start_offset + TextUnit::of_str(&buf) - cursor_delta);
+            buf.push_str("\n}");
+            edit.insert(start_offset, buf);
+        },
+    )
 }
 
 #[cfg(test)]
@@ -4,7 +4,7 @@ use ra_syntax::{
     TextUnit,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{utils::SMALL_ITEM_LINES, Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: add_derive
 //
@@ -25,25 +25,30 @@ use crate::{Assist, AssistCtx, AssistId};
 // }
 // ```
 pub(crate) fn add_derive(ctx: AssistCtx) -> Option<Assist> {
-    let nominal = ctx.find_node_at_offset::<ast::NominalDef>()?;
+    let nominal = ctx.find_enclosing_item_if_small::<ast::NominalDef>(SMALL_ITEM_LINES)?;
     let node_start = derive_insertion_offset(&nominal)?;
-    ctx.add_assist(AssistId("add_derive"), "Add `#[derive]`", |edit| {
-        let derive_attr = nominal
-            .attrs()
-            .filter_map(|x| x.as_simple_call())
-            .filter(|(name, _arg)| name == "derive")
-            .map(|(_name, arg)| arg)
-            .next();
-        let offset = match derive_attr {
-            None => {
-                edit.insert(node_start, "#[derive()]\n");
-                node_start + TextUnit::of_str("#[derive(")
-            }
-            Some(tt) => tt.syntax().text_range().end() - TextUnit::of_char(')'),
-        };
-        edit.target(nominal.syntax().text_range());
-        edit.set_cursor(offset)
-    })
+    ctx.add_assist(
+        AssistId("add_derive"),
+        AssistKind::Refactor,
+        "Add `#[derive]`",
+        nominal.syntax().text_range(),
+        |edit| {
+            let derive_attr = nominal
+                .attrs()
+                .filter_map(|x| x.as_simple_call())
+                .filter(|(name, _arg)| name == "derive")
+                .map(|(_name, arg)| arg)
+                .next();
+            let offset = match derive_attr {
+                None => {
+                    edit.insert(node_start, "#[derive()]\n");
+                    node_start + TextUnit::of_str("#[derive(")
+                }
+                Some(tt) => tt.syntax().text_range().end() - TextUnit::of_char(')'),
+            };
+            edit.set_cursor(offset)
+        },
+    )
 }
 
 // Insert `derive` after doc comments.
@@ -58,7 +63,7 @@ fn derive_insertion_offset(nominal: &ast::NominalDef) -> Option<TextUnit> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::helpers::{check_assist, check_assist_target};
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
 
     #[test]
     fn add_derive_new() {
@@ -117,4 +122,18 @@ struct EvenMoreIrrelevant;
 struct Foo { a: i32, }",
         );
     }
+
+    #[test]
+    fn add_derive_on_name_line_of_big_struct() {
+        let fields = (0..30).map(|it| format!("    f{}: u32,\n", it)).collect::<String>();
+        check_assist(
+            add_derive,
+            &format!("struct Foo {{<|>\n{}}}", fields),
+            &format!("#[derive(<|>)]\nstruct Foo {{\n{}}}", fields),
+        );
+        check_assist_not_applicable(
+            add_derive,
+            &format!("struct Foo {{\n    a<|>: u32,\n{}}}", fields),
+        );
+    }
 }
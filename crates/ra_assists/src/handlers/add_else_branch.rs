@@ -0,0 +1,229 @@
+use ra_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind::{BLOCK, EXPR_STMT, IF_EXPR, WHITESPACE},
+    SyntaxNode, TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: add_else_branch
+//
+// Adds an `else` branch to an `if` expression which doesn't have one.
+//
+// ```
+// fn main() {
+//     let x = <|>if cond { 1 };
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let x = if cond { 1 } else { todo!() };
+// }
+// ```
+pub(crate) fn add_else_branch(ctx: AssistCtx) -> Option<Assist> {
+    let if_expr = ctx.find_node_at_offset::<ast::IfExpr>()?;
+    let then_range = if_expr.then_branch()?.syntax().text_range();
+    let offset = ctx.frange.range.start();
+    if then_range.start() < offset && offset < then_range.end() {
+        return None;
+    }
+
+    let mut last_if = if_expr.clone();
+    loop {
+        match last_if.else_branch() {
+            None => break,
+            Some(ast::ElseBranch::IfExpr(it)) => last_if = it,
+            Some(ast::ElseBranch::Block(_)) => return None,
+        }
+    }
+    let last_then = last_if.then_branch()?;
+    let top_if = if_expr.syntax().ancestors().take_while(|it| it.kind() == IF_EXPR).last()?;
+
+    let body = if is_in_value_position(&top_if, &last_then) { "todo!()" } else { "" };
+    let multiline_indent = if last_then.syntax().text().contains_char('\n') {
+        Some(closing_curly_indent(&last_then))
+    } else {
+        None
+    };
+    let (block, body_offset) = make_block(body, multiline_indent.as_deref());
+    let insert_offset = last_then.syntax().text_range().end();
+    let target = if_expr.syntax().text_range();
+
+    let mut group = ctx.add_assist_group("Add else branch");
+    group.add_assist(
+        AssistId("add_else_branch"),
+        AssistKind::Refactor,
+        "Add `else` branch",
+        target,
+        |edit| {
+            let prefix = " else ";
+            edit.insert(insert_offset, format!("{}{}", prefix, block));
+            edit.set_cursor(insert_offset + TextUnit::of_str(prefix) + body_offset);
+        },
+    );
+    group.add_assist(
+        AssistId("add_else_branch"),
+        AssistKind::Refactor,
+        "Add `else if` branch",
+        target,
+        |edit| {
+            let prefix = " else if ";
+            edit.insert(insert_offset, format!("{}{}", prefix, block));
+            edit.set_cursor(insert_offset + TextUnit::of_str(prefix));
+        },
+    );
+    group.finish()
+}
+
+/// An `if` without `else` as a statement or as a unit-valued tail
+/// expression doesn't need a meaningful else body.
+fn is_in_value_position(top_if: &SyntaxNode, then_branch: &ast::BlockExpr) -> bool {
+    match top_if.parent().map(|it| it.kind()) {
+        Some(EXPR_STMT) => false,
+        Some(BLOCK) => then_branch.block().and_then(|it| it.expr()).is_some(),
+        _ => true,
+    }
+}
+
+fn closing_curly_indent(block: &ast::BlockExpr) -> String {
+    block
+        .syntax()
+        .last_token()
+        .and_then(|it| it.prev_token())
+        .filter(|it| it.kind() == WHITESPACE)
+        .and_then(|ws| {
+            let text = ws.text();
+            text.rfind('\n').map(|pos| text[pos + 1..].to_string())
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the text of the new block and the offset of the cursor inside it.
+fn make_block(body: &str, multiline_indent: Option<&str>) -> (String, TextUnit) {
+    let block = match multiline_indent {
+        Some(indent) if body.is_empty() => format!("{{\n{}}}", indent),
+        Some(indent) => format!("{{\n{}    {}\n{}}}", indent, body, indent),
+        None if body.is_empty() => "{}".to_string(),
+        None => format!("{{ {} }}", body),
+    };
+    let body_offset = if body.is_empty() { 1 } else { block.find(body).unwrap() };
+    (block, TextUnit::from_usize(body_offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{
+        check_assist, check_assist_multi_file_entry, check_assist_not_applicable,
+        check_assist_target,
+    };
+
+    #[test]
+    fn add_else_branch_to_statement() {
+        check_assist(
+            add_else_branch,
+            r#"
+fn f() {
+    <|>if x {
+        foo();
+    }
+    bar();
+}"#,
+            r#"
+fn f() {
+    if x {
+        foo();
+    } else {<|>
+    }
+    bar();
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_else_branch_in_value_position() {
+        check_assist(
+            add_else_branch,
+            "fn f() { let x = <|>if c { 1 }; }",
+            "fn f() { let x = if c { 1 } else { <|>todo!() }; }",
+        );
+        check_assist(
+            add_else_branch,
+            r#"
+fn f() -> i32 {
+    <|>if c {
+        1
+    }
+}"#,
+            r#"
+fn f() -> i32 {
+    if c {
+        1
+    } else {
+        <|>todo!()
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_else_branch_to_if_let() {
+        check_assist(
+            add_else_branch,
+            "fn f(o: Option<i32>) { <|>if let Some(x) = o { foo(x); } }",
+            "fn f(o: Option<i32>) { if let Some(x) = o { foo(x); } else {<|>} }",
+        );
+    }
+
+    #[test]
+    fn add_else_branch_to_end_of_else_if_chain() {
+        check_assist(
+            add_else_branch,
+            r#"
+fn f() {
+    <|>if a {
+        foo();
+    } else if b {
+        bar();
+    }
+}"#,
+            r#"
+fn f() {
+    if a {
+        foo();
+    } else if b {
+        bar();
+    } else {<|>
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_else_if_branch() {
+        check_assist_multi_file_entry(
+            add_else_branch,
+            "Add `else if` branch",
+            r#"
+//- /main.rs
+fn f() { let x = <|>if a { 1 }; }
+"#,
+            r#"
+//- /main.rs
+fn f() { let x = if a { 1 } else if <|>{ todo!() }; }
+"#,
+        );
+    }
+
+    #[test]
+    fn add_else_branch_target() {
+        check_assist_target(add_else_branch, "fn f() { <|>if x { foo(); } }", "if x { foo(); }");
+    }
+
+    #[test]
+    fn add_else_branch_not_applicable() {
+        check_assist_not_applicable(add_else_branch, "fn f() { <|>if x { 1 } else { 2 } }");
+        check_assist_not_applicable(add_else_branch, "fn f() { if x { fo<|>o(); } }");
+    }
+}
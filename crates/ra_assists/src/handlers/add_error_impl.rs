@@ -0,0 +1,222 @@
+use ra_fmt::{leading_indent, reindent};
+use ra_syntax::ast::{self, AstNode, NameOwner, TypeParamsOwner};
+
+use crate::{utils::find_trait_impl, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: add_error_impl
+//
+// Implements `Display` and `std::error::Error` for an enum. Variants holding
+// a single error are displayed with it and returned as the source.
+//
+// ```
+// enum ConfigError<|> {
+//     Missing,
+//     Io(std::io::Error),
+// }
+// ```
+// ->
+// ```
+// enum ConfigError {
+//     Missing,
+//     Io(std::io::Error),
+// }
+//
+// impl std::fmt::Display for ConfigError {
+//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//         match self {
+//             ConfigError::Missing => write!(f, "Missing"),
+//             ConfigError::Io(e) => write!(f, "Io: {}", e),
+//         }
+//     }
+// }
+//
+// impl std::error::Error for ConfigError {
+//     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+//         match self {
+//             ConfigError::Io(e) => Some(e),
+//             _ => None,
+//         }
+//     }
+// }
+// ```
+pub(crate) fn add_error_impl(ctx: AssistCtx) -> Option<Assist> {
+    let enum_def = ctx.find_node_at_offset::<ast::EnumDef>()?;
+    let name = enum_def.name()?;
+    if ctx.frange.range.start() > name.syntax().text_range().end()
+        || enum_def.type_param_list().is_some()
+    {
+        return None;
+    }
+    let scope = enum_def.syntax().parent()?;
+    if find_trait_impl(&scope, name.text(), "Error").is_some() {
+        return None;
+    }
+    let has_display = find_trait_impl(&scope, name.text(), "Display").is_some();
+    let variants = enum_def.variant_list()?.variants().collect::<Vec<_>>();
+    if variants.is_empty() {
+        return None;
+    }
+
+    let mut display_arms = String::new();
+    let mut source_arms = String::new();
+    for variant in &variants {
+        let variant_name = variant.name()?;
+        let path = format!("{}::{}", name.text(), variant_name.text());
+        match variant.kind() {
+            ast::StructKind::Unit => {
+                display_arms +=
+                    &format!("            {} => write!(f, \"{}\"),\n", path, variant_name.text())
+            }
+            ast::StructKind::Tuple(fields) if fields.fields().count() == 1 => {
+                display_arms += &format!(
+                    "            {}(e) => write!(f, \"{}: {{}}\", e),\n",
+                    path,
+                    variant_name.text()
+                );
+                let field = fields.fields().next()?;
+                if field.type_ref().map_or(false, |it| is_error_type(&it)) {
+                    source_arms += &format!("            {}(e) => Some(e),\n", path);
+                }
+            }
+            ast::StructKind::Tuple(_) => {
+                display_arms += &format!(
+                    "            {}(..) => write!(f, \"{}\"),\n",
+                    path,
+                    variant_name.text()
+                )
+            }
+            ast::StructKind::Record(_) => {
+                display_arms += &format!(
+                    "            {} {{ .. }} => write!(f, \"{}\"),\n",
+                    path,
+                    variant_name.text()
+                )
+            }
+        }
+    }
+    ctx.add_assist(
+        AssistId("add_error_impl"),
+        AssistKind::Refactor,
+        "Implement std::error::Error",
+        name.syntax().text_range(),
+        |edit| {
+            let mut buf = String::new();
+            if !has_display {
+                buf.push_str(&format!("impl std::fmt::Display for {} {{\n", name.text()));
+                buf.push_str(
+                    "    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n",
+                );
+                buf.push_str(&format!(
+                    "        match self {{\n{}        }}\n    }}\n}}\n\n",
+                    display_arms
+                ));
+            }
+            buf.push_str(&format!("impl std::error::Error for {} {{", name.text()));
+            if source_arms.is_empty() {
+                buf.push_str("}");
+            } else {
+                buf.push_str(
+                    "\n    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {\n",
+                );
+                buf.push_str(&format!(
+                    "        match self {{\n{}            _ => None,\n        }}\n    }}\n}}",
+                    source_arms
+                ));
+            }
+
+            let indent = leading_indent(enum_def.syntax()).unwrap_or_default();
+            edit.insert(
+                enum_def.syntax().text_range().end(),
+                format!("\n\n{}{}", indent, reindent(&buf, &indent)),
+            );
+        },
+    )
+}
+
+/// Whether the type looks like an error, like `io::Error` or `ParseIntError`.
+fn is_error_type(type_ref: &ast::TypeRef) -> bool {
+    match type_ref {
+        ast::TypeRef::PathType(it) => it
+            .path()
+            .and_then(|it| it.segment())
+            .and_then(|it| it.name_ref())
+            .map_or(false, |it| it.text().ends_with("Error")),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn add_error_impl_for_unit_and_payload_variants() {
+        check_assist(
+            add_error_impl,
+            "
+enum Error<|> {
+    Empty,
+    Parse(ParseIntError),
+    Position { line: u32 },
+}",
+            "
+enum Error<|> {
+    Empty,
+    Parse(ParseIntError),
+    Position { line: u32 },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Empty => write!(f, \"Empty\"),
+            Error::Parse(e) => write!(f, \"Parse: {}\", e),
+            Error::Position { .. } => write!(f, \"Position\"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn add_only_error_impl_when_display_exists() {
+        check_assist(
+            add_error_impl,
+            "
+enum Error<|> { Empty, Code(u32) }
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Ok(()) }
+}",
+            "
+enum Error<|> { Empty, Code(u32) }
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Ok(()) }
+}",
+        );
+    }
+
+    #[test]
+    fn add_error_impl_not_applicable_with_error_impl() {
+        check_assist_not_applicable(
+            add_error_impl,
+            "
+enum Error<|> { Empty }
+
+impl std::error::Error for Error {}",
+        );
+    }
+}
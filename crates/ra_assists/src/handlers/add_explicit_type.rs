@@ -4,7 +4,7 @@ use ra_syntax::{
     TextRange,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: add_explicit_type
 //
@@ -60,9 +60,10 @@ pub(crate) fn add_explicit_type(ctx: AssistCtx) -> Option<Assist> {
     let db = ctx.db;
     ctx.add_assist(
         AssistId("add_explicit_type"),
+        AssistKind::Refactor,
         format!("Insert explicit type '{}'", ty.display(db)),
+        pat_range,
         |edit| {
-            edit.target(pat_range);
             if let Some(ascribed_ty) = ascribed_ty {
                 edit.replace(ascribed_ty.syntax().text_range(), format!("{}", ty.display(db)));
             } else {
@@ -0,0 +1,291 @@
+use ra_fmt::{leading_indent, reindent};
+use ra_syntax::ast::{self, AstNode, NameOwner, TypeParamsOwner};
+
+use crate::{
+    utils::{find_trait_impl, self_type_name, trait_name},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: add_hash_impl
+//
+// Adds a `Hash` impl which hashes exactly the fields compared by a manual
+// `PartialEq` impl.
+//
+// ```
+// struct Point<|> { x: u32, y: u32, cache: u32 }
+//
+// impl PartialEq for Point {
+//     fn eq(&self, other: &Self) -> bool {
+//         self.x == other.x && self.y == other.y
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Point { x: u32, y: u32, cache: u32 }
+//
+// impl PartialEq for Point {
+//     fn eq(&self, other: &Self) -> bool {
+//         self.x == other.x && self.y == other.y
+//     }
+// }
+//
+// impl std::hash::Hash for Point {
+//     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+//         self.x.hash(state);
+//         self.y.hash(state);
+//     }
+// }
+// ```
+pub(crate) fn add_hash_impl(ctx: AssistCtx) -> Option<Assist> {
+    let eq_impl = match ctx.find_node_at_offset::<ast::ImplDef>() {
+        Some(impl_def) => impl_def,
+        None => {
+            let nominal = ctx.find_node_at_offset::<ast::NominalDef>()?;
+            let name = nominal.name()?;
+            find_trait_impl(&nominal.syntax().parent()?, name.text(), "PartialEq")?
+        }
+    };
+    if trait_name(&eq_impl)? != "PartialEq" {
+        return None;
+    }
+    let type_name = self_type_name(&eq_impl)?;
+    if find_trait_impl(&eq_impl.syntax().parent()?, &type_name, "Hash").is_some() {
+        return None;
+    }
+    let fields = compared_fields(&eq_impl)?;
+
+    ctx.add_assist(
+        AssistId("add_hash_impl"),
+        AssistKind::Refactor,
+        "Implement Hash matching PartialEq",
+        eq_impl.syntax().text_range(),
+        |edit| {
+            let mut buf = String::from("impl");
+            if let Some(type_params) = eq_impl.type_param_list() {
+                // The bounds required by `PartialEq` aren't needed to hash.
+                let lifetime_params =
+                    type_params.lifetime_params().map(|it| it.syntax().to_string());
+                let type_params = type_params
+                    .type_params()
+                    .filter_map(|it| it.name())
+                    .map(|it| format!("{}: std::hash::Hash", it.text()));
+                let params = lifetime_params.chain(type_params).collect::<Vec<_>>();
+                buf.push_str(&format!("<{}>", params.join(", ")));
+            }
+            buf.push_str(" std::hash::Hash for ");
+            buf.push_str(&eq_impl.target_type().unwrap().syntax().to_string());
+            match eq_impl.where_clause() {
+                Some(where_clause) => buf.push_str(&format!("\n{}\n{{", where_clause.syntax())),
+                None => buf.push_str(" {"),
+            }
+            buf.push_str("\n    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {\n");
+            for field in fields {
+                buf.push_str(&format!("        self.{}.hash(state);\n", field));
+            }
+            buf.push_str("    }\n}");
+
+            let indent = leading_indent(eq_impl.syntax()).unwrap_or_default();
+            edit.insert(
+                eq_impl.syntax().text_range().end(),
+                format!("\n\n{}{}", indent, reindent(&buf, &indent)),
+            );
+        },
+    )
+}
+
+/// Returns fields compared by `eq`, if its body is a plain conjunction of
+/// `self.field == other.field` comparisons.
+fn compared_fields(eq_impl: &ast::ImplDef) -> Option<Vec<String>> {
+    let eq_fn = eq_impl.item_list()?.impl_items().find_map(|it| match it {
+        ast::ImplItem::FnDef(f) if f.name().map_or(false, |name| name.text() == "eq") => Some(f),
+        _ => None,
+    })?;
+    let params = eq_fn.param_list()?;
+    params.self_param()?;
+    let other = match params.params().next()?.pat()? {
+        ast::Pat::BindPat(it) => it.name()?.text().to_string(),
+        _ => return None,
+    };
+    let block = eq_fn.body()?.block()?;
+    if block.statements().next().is_some() {
+        return None;
+    }
+    let mut fields = Vec::new();
+    collect_compared_fields(block.expr()?, &other, &mut fields)?;
+    Some(fields)
+}
+
+fn collect_compared_fields(expr: ast::Expr, other: &str, acc: &mut Vec<String>) -> Option<()> {
+    let bin_expr = match expr {
+        ast::Expr::BinExpr(it) => it,
+        ast::Expr::ParenExpr(it) => return collect_compared_fields(it.expr()?, other, acc),
+        _ => return None,
+    };
+    match bin_expr.op_kind()? {
+        ast::BinOp::BooleanAnd => {
+            collect_compared_fields(bin_expr.lhs()?, other, acc)?;
+            collect_compared_fields(bin_expr.rhs()?, other, acc)
+        }
+        ast::BinOp::EqualityTest => {
+            let field = field_of(bin_expr.lhs()?, "self")?;
+            if field_of(bin_expr.rhs()?, other)? != field {
+                return None;
+            }
+            acc.push(field);
+            Some(())
+        }
+        _ => None,
+    }
+}
+
+fn field_of(expr: ast::Expr, receiver: &str) -> Option<String> {
+    let field_expr = match expr {
+        ast::Expr::FieldExpr(it) => it,
+        _ => return None,
+    };
+    match field_expr.expr()? {
+        ast::Expr::PathExpr(path) if path.syntax().text() == receiver => (),
+        _ => return None,
+    }
+    let field = match field_expr.field_access()? {
+        ast::FieldKind::Name(name_ref) => name_ref.text().to_string(),
+        ast::FieldKind::Index(idx) => idx.text().to_string(),
+    };
+    Some(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn add_hash_impl_for_subset_of_fields() {
+        check_assist(
+            add_hash_impl,
+            r#"
+struct Foo<|> { a: u32, b: u32, c: u32, d: u32 }
+
+impl PartialEq for Foo {
+    fn eq(&self, other: &Self) -> bool {
+        self.a == other.a && self.c == other.c
+    }
+}"#,
+            r#"
+struct Foo<|> { a: u32, b: u32, c: u32, d: u32 }
+
+impl PartialEq for Foo {
+    fn eq(&self, other: &Self) -> bool {
+        self.a == other.a && self.c == other.c
+    }
+}
+
+impl std::hash::Hash for Foo {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.a.hash(state);
+        self.c.hash(state);
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_hash_impl_from_eq_impl_with_single_field() {
+        check_assist(
+            add_hash_impl,
+            r#"
+struct Id(u32, String);
+
+impl Partial<|>Eq for Id {
+    fn eq(&self, rhs: &Id) -> bool {
+        self.0 == rhs.0
+    }
+}"#,
+            r#"
+struct Id(u32, String);
+
+impl Partial<|>Eq for Id {
+    fn eq(&self, rhs: &Id) -> bool {
+        self.0 == rhs.0
+    }
+}
+
+impl std::hash::Hash for Id {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_hash_impl_for_generic_type() {
+        check_assist(
+            add_hash_impl,
+            r#"
+struct Pair<'a, T> { a: &'a T, b: T }
+
+impl<'a, T: PartialEq> Partial<|>Eq for Pair<'a, T>
+where
+    T: Copy,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.b == other.b
+    }
+}"#,
+            r#"
+struct Pair<'a, T> { a: &'a T, b: T }
+
+impl<'a, T: PartialEq> Partial<|>Eq for Pair<'a, T>
+where
+    T: Copy,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.b == other.b
+    }
+}
+
+impl<'a, T: std::hash::Hash> std::hash::Hash for Pair<'a, T>
+where
+    T: Copy,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.b.hash(state);
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_hash_impl_not_applicable_for_complex_eq() {
+        check_assist_not_applicable(
+            add_hash_impl,
+            r#"
+struct Foo<|> { a: u32, b: u32 }
+
+impl PartialEq for Foo {
+    fn eq(&self, other: &Self) -> bool {
+        self.a == other.a || self.b == other.b
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_hash_impl_not_applicable_if_hash_exists() {
+        check_assist_not_applicable(
+            add_hash_impl,
+            r#"
+struct Foo<|> { a: u32 }
+
+impl PartialEq for Foo {
+    fn eq(&self, other: &Self) -> bool { self.a == other.a }
+}
+
+impl Hash for Foo {
+    fn hash<H: Hasher>(&self, state: &mut H) {}
+}"#,
+        );
+    }
+}
@@ -1,16 +1,22 @@
-use format_buf::format;
-
-use join_to_string::join;
+use hir::{HasSource, ModuleDef, PathResolution, Semantics};
+use ra_ide_db::RootDatabase;
 use ra_syntax::{
-    ast::{self, AstNode, NameOwner, TypeParamsOwner},
+    ast::{self, make, AstNode, NameOwner, TypeParamsOwner},
     TextUnit,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{
+    ast_transform::QualifyPaths,
+    utils::{
+        impl_header, items_for_mode, scaffold_items, AddMissingImplMembersMode, SMALL_ITEM_LINES,
+    },
+    Assist, AssistCtx, AssistId, AssistKind,
+};
 
 // Assist: add_impl
 //
-// Adds a new inherent impl for a type.
+// Adds a new inherent impl for a type. On a trait path, offers implementing
+// the trait for each type of the file instead.
 //
 // ```
 // struct Ctx<T: Clone> {
@@ -28,39 +34,131 @@ use crate::{Assist, AssistCtx, AssistId};
 // }
 // ```
 pub(crate) fn add_impl(ctx: AssistCtx) -> Option<Assist> {
-    let nominal = ctx.find_node_at_offset::<ast::NominalDef>()?;
-    let name = nominal.name()?;
-    ctx.add_assist(AssistId("add_impl"), format!("Implement {}", name.text().as_str()), |edit| {
-        edit.target(nominal.syntax().text_range());
-        let type_params = nominal.type_param_list();
-        let start_offset = nominal.syntax().text_range().end();
-        let mut buf = String::new();
-        buf.push_str("\n\nimpl");
-        if let Some(type_params) = &type_params {
-            format!(buf, "{}", type_params.syntax());
+    if let Some(path) = ctx.find_node_at_offset::<ast::Path>() {
+        if let Some(PathResolution::Def(ModuleDef::Trait(trait_))) = ctx.sema.resolve_path(&path) {
+            return add_trait_impl(ctx, trait_);
         }
-        buf.push_str(" ");
-        buf.push_str(name.text().as_str());
-        if let Some(type_params) = type_params {
-            let lifetime_params = type_params
-                .lifetime_params()
-                .filter_map(|it| it.lifetime_token())
-                .map(|it| it.text().clone());
-            let type_params =
-                type_params.type_params().filter_map(|it| it.name()).map(|it| it.text().clone());
-            join(lifetime_params.chain(type_params)).surround_with("<", ">").to_buf(&mut buf);
-        }
-        buf.push_str(" {\n");
-        edit.set_cursor(start_offset + TextUnit::of_str(&buf));
-        buf.push_str("\n}");
-        edit.insert(start_offset, buf);
-    })
+    }
+
+    let nominal = ctx.find_enclosing_item_if_small::<ast::NominalDef>(SMALL_ITEM_LINES)?;
+    let name = nominal.name()?;
+    ctx.add_assist(
+        AssistId("add_impl"),
+        AssistKind::Refactor,
+        format!("Implement {}", name.text().as_str()),
+        nominal.syntax().text_range(),
+        |edit| {
+            let start_offset = nominal.syntax().text_range().end();
+            let mut buf = String::new();
+            buf.push_str("\n\n");
+            buf.push_str(&impl_header(&nominal, &name, None));
+            buf.push_str(" {\n");
+            edit.set_cursor(start_offset + TextUnit::of_str(&buf));
+            buf.push_str("\n}");
+            edit.insert(start_offset, buf);
+        },
+    )
+}
+
+/// Offers implementing `trait_` for each type of the file which doesn't
+/// implement it yet, with the required members filled in.
+fn add_trait_impl(ctx: AssistCtx, trait_: hir::Trait) -> Option<Assist> {
+    let sema = ctx.sema;
+    let db = ctx.db;
+    if trait_.source(db).value.type_param_list().is_some() {
+        return None;
+    }
+    let krate = sema.to_module_def(ctx.frange.file_id)?.krate();
+    let implemented = hir::ImplDef::for_trait(db, krate, trait_)
+        .into_iter()
+        .filter_map(|it| it.target_ty(db).as_adt())
+        .collect::<Vec<_>>();
+    let candidates = sema
+        .parse(ctx.frange.file_id)
+        .syntax()
+        .descendants()
+        .filter_map(ast::NominalDef::cast)
+        .filter(|it| adt_of(sema, it).map_or(false, |adt| !implemented.contains(&adt)))
+        .filter_map(|it| {
+            let trait_path = sema.scope(it.syntax()).module()?.find_use_path(db, trait_.into())?;
+            Some((it.name()?, trait_path, it))
+        })
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return None;
+    }
+    let items = items_for_mode(db, trait_.items(db), AddMissingImplMembersMode::NoDefaultMethods);
+
+    let trait_name = trait_.name(db);
+    let mut group = ctx.add_assist_group(format!("Implement {}", trait_name));
+    for (name, trait_path, nominal) in candidates {
+        group.add_assist(
+            AssistId("add_impl"),
+            AssistKind::Refactor,
+            format!("Implement {} for {}", trait_name, name.text()),
+            nominal.syntax().text_range(),
+            |edit| {
+                let source_scope = sema.scope_for_def(trait_);
+                let target_scope = sema.scope(nominal.syntax());
+                let ast_transform = QualifyPaths::new(&target_scope, &source_scope, db);
+                let items = scaffold_items(&ast_transform, items.clone());
+                let item_list = make::impl_item_list().append_items(items.into_iter());
+
+                let start_offset = nominal.syntax().text_range().end();
+                let mut buf = String::new();
+                buf.push_str("\n\n");
+                buf.push_str(&impl_header(&nominal, &name, Some(&trait_path.to_string())));
+                buf.push_str(" ");
+                let cursor_in_list = match item_list.impl_items().next() {
+                    Some(first_item) => first_item.syntax().text_range().start(),
+                    None => TextUnit::of_str("{\n"),
+                };
+                edit.set_cursor(start_offset + TextUnit::of_str(&buf) + cursor_in_list);
+                if item_list.impl_items().next().is_some() {
+                    buf.push_str(&item_list.syntax().to_string());
+                } else {
+                    buf.push_str("{\n\n}");
+                }
+                edit.insert(start_offset, buf);
+            },
+        );
+    }
+    group.finish()
+}
+
+fn adt_of(sema: &Semantics<RootDatabase>, nominal: &ast::NominalDef) -> Option<hir::Adt> {
+    let adt = match nominal {
+        ast::NominalDef::StructDef(it) => sema.to_def(it)?.into(),
+        ast::NominalDef::EnumDef(it) => sema.to_def(it)?.into(),
+        ast::NominalDef::UnionDef(it) => sema.to_def(it)?.into(),
+    };
+    Some(adt)
 }
 
 #[cfg(test)]
 mod tests {
+    use ra_db::FileRange;
+    use ra_syntax::TextRange;
+    use test_utils::extract_offset;
+
     use super::*;
-    use crate::helpers::{check_assist, check_assist_target};
+    use crate::{
+        helpers::{self, check_assist, check_assist_target},
+        resolved_assists,
+    };
+
+    fn trait_impl_labels(before: &str) -> Vec<(String, Option<String>)> {
+        let (offset, before) = extract_offset(before);
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let mut labels = resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "add_impl")
+            .map(|it| (it.label.label, it.group_label.map(|it| it.0)))
+            .collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
 
     #[test]
     fn test_add_impl() {
@@ -91,4 +189,53 @@ struct EvenMoreIrrelevant;
 struct Foo<'a, T: Foo<'a>> {}",
         );
     }
+
+    #[test]
+    fn add_trait_impl_for_each_type() {
+        assert_eq!(
+            trait_impl_labels("trait Foo {} struct A; enum B { X } fn f<T: F<|>oo>(t: T) {}",),
+            vec![
+                ("Implement Foo for A".to_string(), Some("Implement Foo".to_string())),
+                ("Implement Foo for B".to_string(), Some("Implement Foo".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn add_trait_impl_skips_implementing_types() {
+        assert_eq!(
+            trait_impl_labels(
+                "trait Foo {} struct A; struct B; impl Foo for A {} fn f(x: &dyn F<|>oo) {}"
+            ),
+            vec![("Implement Foo for B".to_string(), Some("Implement Foo".to_string()))]
+        );
+    }
+
+    #[test]
+    fn add_trait_impl_with_missing_members() {
+        check_assist(
+            add_impl,
+            "
+trait Foo {
+    type Output;
+    fn foo(&self) -> Self::Output;
+    fn bar(&self) {}
+}
+struct S;
+fn f<T: Fo<|>o>() {}",
+            "
+trait Foo {
+    type Output;
+    fn foo(&self) -> Self::Output;
+    fn bar(&self) {}
+}
+struct S;
+
+impl Foo for S {
+    <|>type Output;
+    fn foo(&self) -> Self::Output { unimplemented!() }
+}
+fn f<T: Foo>() {}",
+        );
+    }
 }
@@ -0,0 +1,287 @@
+use hir::Adt;
+use join_to_string::join;
+use ra_fmt::{leading_indent, reindent};
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner, StructKind, TypeAscriptionOwner, TypeParamsOwner},
+    T,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: add_index_impl
+//
+// Adds an `Index` impl delegating to a collection field of a struct.
+//
+// ```
+// struct Stack<|> {
+//     items: Vec<u32>,
+// }
+// ```
+// ->
+// ```
+// struct Stack {
+//     items: Vec<u32>,
+// }
+//
+// impl std::ops::Index<usize> for Stack {
+//     type Output = u32;
+//
+//     fn index(&self, index: usize) -> &Self::Output {
+//         &self.items[index]
+//     }
+// }
+// ```
+pub(crate) fn add_index_impl(ctx: AssistCtx) -> Option<Assist> {
+    let strukt = ctx.find_node_at_offset::<ast::StructDef>()?;
+    let name = strukt.name()?;
+    let fields = collection_fields(&strukt);
+    if fields.is_empty() || has_index_impl(&ctx, &strukt)? {
+        return None;
+    }
+
+    let indent = leading_indent(strukt.syntax()).unwrap_or_default();
+    let insert_offset = strukt.syntax().text_range().end();
+
+    let mut group = ctx.add_assist_group("Implement Index");
+    for field in fields {
+        let index_impl = format!(
+            "{} {{\n    type Output = {};\n\n{}\n}}",
+            impl_header(&strukt, &name, "Index", &field.index_ty),
+            field.output_ty,
+            index_fn(&field, false),
+        );
+        group.add_assist(
+            AssistId("add_index_impl"),
+            AssistKind::Refactor,
+            format!("Implement Index for `{}`", field.name),
+            strukt.syntax().text_range(),
+            |edit| {
+                edit.insert(
+                    insert_offset,
+                    format!("\n\n{}{}", indent, reindent(&index_impl, &indent)),
+                );
+            },
+        );
+        if !field.mutable {
+            continue;
+        }
+        let index_mut_impl = format!(
+            "{} {{\n{}\n}}",
+            impl_header(&strukt, &name, "IndexMut", &field.index_ty),
+            index_fn(&field, true),
+        );
+        group.add_assist(
+            AssistId("add_index_impl"),
+            AssistKind::Refactor,
+            format!("Implement Index and IndexMut for `{}`", field.name),
+            strukt.syntax().text_range(),
+            |edit| {
+                let text = format!("{}\n\n{}", index_impl, index_mut_impl);
+                edit.insert(insert_offset, format!("\n\n{}{}", indent, reindent(&text, &indent)));
+            },
+        );
+    }
+    group.finish()
+}
+
+fn impl_header(strukt: &ast::StructDef, name: &ast::Name, trait_: &str, index_ty: &str) -> String {
+    let mut buf = String::from("impl");
+    if let Some(type_params) = strukt.type_param_list() {
+        buf.push_str(&type_params.syntax().to_string());
+    }
+    buf.push_str(&format!(" std::ops::{}<{}> for {}", trait_, index_ty, name.text()));
+    if let Some(type_params) = strukt.type_param_list() {
+        let lifetime_params = type_params
+            .lifetime_params()
+            .filter_map(|it| it.lifetime_token())
+            .map(|it| it.text().clone());
+        let type_params =
+            type_params.type_params().filter_map(|it| it.name()).map(|it| it.text().clone());
+        join(lifetime_params.chain(type_params)).surround_with("<", ">").to_buf(&mut buf);
+    }
+    buf
+}
+
+fn index_fn(field: &CollectionField, mutable: bool) -> String {
+    let (fn_name, self_ref, ret_ref) =
+        if mutable { ("index_mut", "&mut self", "&mut ") } else { ("index", "&self", "&") };
+    let mut buf = format!(
+        "    fn {}({}, index: {}) -> {}Self::Output {{\n",
+        fn_name, self_ref, field.index_ty, ret_ref
+    );
+    buf.push_str(&format!("        {}self.{}[index]\n    }}", ret_ref, field.name));
+    buf
+}
+
+struct CollectionField {
+    /// Field name, or the index of a tuple field.
+    name: String,
+    index_ty: String,
+    output_ty: String,
+    /// Whether the collection supports `IndexMut`.
+    mutable: bool,
+}
+
+fn collection_fields(strukt: &ast::StructDef) -> Vec<CollectionField> {
+    let fields: Vec<(String, ast::TypeRef)> = match strukt.kind() {
+        StructKind::Record(field_list) => field_list
+            .fields()
+            .filter_map(|it| Some((it.name()?.text().to_string(), it.ascribed_type()?)))
+            .collect(),
+        StructKind::Tuple(field_list) => field_list
+            .fields()
+            .enumerate()
+            .filter_map(|(idx, it)| Some((idx.to_string(), it.type_ref()?)))
+            .collect(),
+        StructKind::Unit => Vec::new(),
+    };
+    fields
+        .into_iter()
+        .filter_map(|(name, ty)| {
+            let (index_ty, output_ty, mutable) = index_and_output(&ty)?;
+            Some(CollectionField { name, index_ty, output_ty, mutable })
+        })
+        .collect()
+}
+
+/// `HashMap` and `BTreeMap` are indexed by a reference to the key type, and
+/// don't implement `IndexMut`, the other collections are indexed by `usize`.
+fn index_and_output(ty: &ast::TypeRef) -> Option<(String, String, bool)> {
+    match ty {
+        ast::TypeRef::PathType(path_type) => {
+            let segment = path_type.path()?.segment()?;
+            let args = segment
+                .type_arg_list()?
+                .type_args()
+                .filter_map(|it| it.type_ref())
+                .map(|it| it.syntax().to_string())
+                .collect::<Vec<_>>();
+            match (segment.name_ref()?.text().as_str(), args.as_slice()) {
+                ("Vec", [item]) | ("VecDeque", [item]) => {
+                    Some(("usize".to_string(), item.clone(), true))
+                }
+                ("HashMap", [key, value])
+                | ("HashMap", [key, value, _])
+                | ("BTreeMap", [key, value]) => Some((format!("&{}", key), value.clone(), false)),
+                _ => None,
+            }
+        }
+        ast::TypeRef::ReferenceType(reference) => match reference.type_ref()? {
+            ast::TypeRef::SliceType(slice) => {
+                let mutable =
+                    reference.syntax().children_with_tokens().any(|it| it.kind() == T![mut]);
+                Some(("usize".to_string(), slice.type_ref()?.syntax().to_string(), mutable))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn has_index_impl(ctx: &AssistCtx, strukt: &ast::StructDef) -> Option<bool> {
+    let struct_def = ctx.sema.to_def(strukt)?;
+    let scope = strukt.syntax().parent()?;
+    let res = scope.children().filter_map(ast::ImplDef::cast).any(|impl_def| {
+        let is_index = match impl_def.target_trait() {
+            Some(ast::TypeRef::PathType(it)) => it
+                .path()
+                .and_then(|it| it.segment())
+                .and_then(|it| it.name_ref())
+                .map_or(false, |it| it.text() == "Index"),
+            _ => false,
+        };
+        is_index
+            && ctx.sema.to_def(&impl_def).and_then(|it| it.target_ty(ctx.db).as_adt())
+                == Some(Adt::Struct(struct_def))
+    });
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use ra_syntax::TextRange;
+    use test_utils::extract_offset;
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    #[test]
+    fn add_index_impl_for_vec_field() {
+        check_assist(
+            add_index_impl,
+            r#"
+struct Items<T> {
+    <|>items: Vec<T>,
+}"#,
+            r#"
+struct Items<T> {
+    <|>items: Vec<T>,
+}
+
+impl<T> std::ops::Index<usize> for Items<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.items[index]
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_index_impl_for_hash_map_field() {
+        check_assist(
+            add_index_impl,
+            "struct Env<|>(HashMap<String, u32>);",
+            r#"struct Env<|>(HashMap<String, u32>);
+
+impl std::ops::Index<&String> for Env {
+    type Output = u32;
+
+    fn index(&self, index: &String) -> &Self::Output {
+        &self.0[index]
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_index_impl_offers_every_candidate_field() {
+        let (offset, before) =
+            extract_offset("struct S<|> { names: Vec<String>, ids: &'static [u32], len: usize }");
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let labels = resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "add_index_impl")
+            .map(|it| it.label.label)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![
+                "Implement Index for `names`",
+                "Implement Index and IndexMut for `names`",
+                "Implement Index for `ids`",
+            ]
+        );
+    }
+
+    #[test]
+    fn add_index_impl_not_applicable() {
+        check_assist_not_applicable(add_index_impl, "struct S<|> { len: usize }");
+        check_assist_not_applicable(
+            add_index_impl,
+            r#"
+struct S<|>(Vec<u32>);
+
+impl std::ops::Index<usize> for S {
+    type Output = u32;
+    fn index(&self, index: usize) -> &u32 { &self.0[index] }
+}"#,
+        );
+    }
+}
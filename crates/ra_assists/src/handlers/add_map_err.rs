@@ -0,0 +1,179 @@
+use hir::Adt;
+use ra_syntax::{
+    ast::{self, make, AstNode},
+    SyntaxKind::{FN_DEF, LAMBDA_EXPR},
+    TextUnit,
+};
+
+use crate::{
+    utils::{depends_on, insert_use_statement},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: add_map_err
+//
+// Maps the error of a `Result` before propagating it with `?`.
+//
+// ```
+// # enum Result<T, E> { Ok(T), Err(E) }
+// # fn read_config() -> Result<String, u32> { loop {} }
+// fn load() -> Result<String, u32> {
+//     let text = read_config()<|>?;
+//     Ok(text)
+// }
+// ```
+// ->
+// ```
+// # enum Result<T, E> { Ok(T), Err(E) }
+// # fn read_config() -> Result<String, u32> { loop {} }
+// fn load() -> Result<String, u32> {
+//     let text = read_config().map_err(|e| e)?;
+//     Ok(text)
+// }
+// ```
+pub(crate) fn add_map_err(ctx: AssistCtx) -> Option<Assist> {
+    let try_expr = ctx.find_node_at_offset::<ast::TryExpr>()?;
+    let expr = try_expr.expr()?;
+    let enclosing_fn = try_expr
+        .syntax()
+        .ancestors()
+        .find(|it| it.kind() == FN_DEF || it.kind() == LAMBDA_EXPR)
+        .and_then(ast::FnDef::cast)?;
+    if !returns_result(&enclosing_fn) || !is_result(&ctx, &expr) {
+        return None;
+    }
+
+    let expr_end = expr.syntax().text_range().end();
+    let uses_anyhow = depends_on(ctx.sema, ctx.frange.file_id, "anyhow");
+    let mut group = ctx.add_assist_group("Add error context");
+    group.add_assist(
+        AssistId("add_map_err"),
+        AssistKind::QuickFix,
+        "Add map_err",
+        try_expr.syntax().text_range(),
+        |edit| {
+            edit.insert(expr_end, ".map_err(|e| e)");
+            edit.set_cursor(expr_end + TextUnit::of_str(".map_err(|e| "));
+        },
+    );
+    if uses_anyhow {
+        group.add_assist(
+            AssistId("add_map_err"),
+            AssistKind::QuickFix,
+            "Add context",
+            try_expr.syntax().text_range(),
+            |edit| {
+                let context = make::path_from_text("anyhow::Context");
+                if let Some(path) = hir::Path::from_ast(context) {
+                    insert_use_statement(
+                        try_expr.syntax(),
+                        path.mod_path(),
+                        edit.text_edit_builder(),
+                    );
+                }
+                edit.insert(expr_end, ".context(\"\")");
+                edit.set_cursor(expr_end + TextUnit::of_str(".context(\""));
+            },
+        );
+    }
+    group.finish()
+}
+
+fn returns_result(fn_def: &ast::FnDef) -> bool {
+    let path = match fn_def.ret_type().and_then(|it| it.type_ref()) {
+        Some(ast::TypeRef::PathType(it)) => it.path(),
+        _ => None,
+    };
+    path.and_then(|it| it.segment())
+        .and_then(|it| it.name_ref())
+        .map_or(false, |it| it.text() == "Result")
+}
+
+fn is_result(ctx: &AssistCtx, expr: &ast::Expr) -> bool {
+    match ctx.sema.type_of_expr(expr).and_then(|it| it.as_adt()) {
+        Some(Adt::Enum(it)) => it.name(ctx.db).to_string() == "Result",
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::{fixture::WithFixture, FileRange, SourceDatabase};
+    use ra_ide_db::RootDatabase;
+    use ra_syntax::TextRange;
+
+    use super::*;
+    use crate::{
+        helpers::{check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    const RESULT: &str = "enum Result<T, E> { Ok(T), Err(E) }
+enum Option<T> { Some(T), None }
+fn read() -> Result<String, u32> { loop {} }
+fn find() -> Option<String> { loop {} }
+";
+
+    /// The labels of the entries offered in `main`, and its text after
+    /// applying the last one.
+    fn entries(main: &str, depends_on_anyhow: bool) -> (Vec<String>, Option<String>) {
+        let deps = if depends_on_anyhow { " deps:anyhow" } else { "" };
+        let fixture = format!(
+            "//- /main.rs crate:main{}\n{}{}\n//- /anyhow.rs crate:anyhow\npub trait Context {{}}",
+            deps, RESULT, main
+        );
+        let (db, position) = RootDatabase::with_position(&fixture);
+        let frange = FileRange {
+            file_id: position.file_id,
+            range: TextRange::offset_len(position.offset, 0.into()),
+        };
+        let entries = resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "add_map_err")
+            .collect::<Vec<_>>();
+        let text = db.file_text(position.file_id);
+        let after = entries.last().map(|it| it.action.edit().apply(&text));
+        (entries.into_iter().map(|it| it.label.label).collect(), after)
+    }
+
+    #[test]
+    fn add_map_err_to_question_mark() {
+        check_assist(
+            add_map_err,
+            &format!("{}fn f() -> Result<(), u32> {{ let s = read()<|>?; }}", RESULT),
+            &format!(
+                "{}fn f() -> Result<(), u32> {{ let s = read().map_err(|e| <|>e)?; }}",
+                RESULT
+            ),
+        );
+    }
+
+    #[test]
+    fn add_context_only_with_anyhow_dependency() {
+        let main = "fn f() -> Result<(), u32> { let s = read()<|>?; }";
+        let (labels, _) = entries(main, false);
+        assert_eq!(labels, vec!["Add map_err"]);
+
+        let (labels, after) = entries(main, true);
+        assert_eq!(labels, vec!["Add map_err", "Add context"]);
+        assert_eq!(
+            after.unwrap(),
+            format!(
+                "use anyhow::Context;\n\n{}fn f() -> Result<(), u32> {{ let s = read().context(\"\")?; }}\n",
+                RESULT
+            )
+        );
+    }
+
+    #[test]
+    fn add_map_err_not_applicable_to_option() {
+        check_assist_not_applicable(
+            add_map_err,
+            &format!("{}fn f() -> Option<()> {{ let s = find()<|>?; }}", RESULT),
+        );
+        check_assist_not_applicable(
+            add_map_err,
+            &format!("{}fn f() -> Result<(), u32> {{ let s = find()<|>?; }}", RESULT),
+        );
+    }
+}
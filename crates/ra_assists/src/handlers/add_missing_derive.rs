@@ -0,0 +1,311 @@
+use hir::{Adt, HasSource, ScopeDef, Type};
+use ra_syntax::{
+    ast::{self, AstNode, HasStringValue, NameOwner},
+    SyntaxElement,
+    SyntaxKind::{COMMENT, IDENT, STRING, WHITESPACE},
+    SyntaxNode, T,
+};
+
+use crate::{
+    utils::{derive_args, derives, find_trait_impl, insert_derives},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+/// The macros taking a format string and its arguments.
+const FORMAT_MACROS: &[&str] =
+    &["format", "print", "println", "eprint", "eprintln", "write", "writeln", "panic"];
+
+// Assist: add_missing_derive
+//
+// Derives the trait required by the expression under the cursor for a type of
+// the file, like `PartialEq` for `==` or `Clone` for `.clone()`.
+//
+// ```
+// struct Point { x: u32, y: u32 }
+//
+// fn is_origin(p: Point) -> bool {
+//     p ==<|> Point { x: 0, y: 0 }
+// }
+// ```
+// ->
+// ```
+// #[derive(PartialEq)]
+// struct Point { x: u32, y: u32 }
+//
+// fn is_origin(p: Point) -> bool {
+//     p == Point { x: 0, y: 0 }
+// }
+// ```
+pub(crate) fn add_missing_derive(ctx: AssistCtx) -> Option<Assist> {
+    let (ty, trait_) = required_trait(&ctx)?;
+    let adt = ty.autoderef(ctx.db).find_map(|it| it.as_adt())?;
+    let (item, name) = match adt {
+        Adt::Struct(it) => {
+            let source = it.source(ctx.db);
+            if source.file_id != ctx.frange.file_id.into() {
+                return None;
+            }
+            (source.value.syntax().clone(), source.value.name()?)
+        }
+        Adt::Enum(it) => {
+            let source = it.source(ctx.db);
+            if source.file_id != ctx.frange.file_id.into() {
+                return None;
+            }
+            (source.value.syntax().clone(), source.value.name()?)
+        }
+        Adt::Union(_) => return None,
+    };
+    let args = ast::NominalDef::cast(item.clone()).and_then(|it| derive_args(&it));
+    if args.as_ref().map_or(false, |it| derives(it, trait_))
+        || find_trait_impl(&item.parent()?, name.text(), trait_).is_some()
+    {
+        return None;
+    }
+
+    let (offset, text) = insert_derives(&item, args.as_ref(), trait_);
+    ctx.add_assist(
+        AssistId("add_missing_derive"),
+        AssistKind::QuickFix,
+        format!("Derive {} for {}", trait_, name.text()),
+        name.syntax().text_range(),
+        |edit| {
+            edit.insert(offset, text);
+        },
+    )
+}
+
+/// The type of the expression at the cursor and the trait it must implement.
+fn required_trait(ctx: &AssistCtx) -> Option<(Type, &'static str)> {
+    if let Some(macro_call) = ctx.find_node_at_offset::<ast::MacroCall>() {
+        return Some((debug_formatted_arg(ctx, &macro_call)?, "Debug"));
+    }
+    let expr = ctx.find_node_at_offset::<ast::Expr>()?;
+    for expr in expr.syntax().ancestors().filter_map(ast::Expr::cast) {
+        match &expr {
+            ast::Expr::BinExpr(bin) => match bin.op_kind() {
+                Some(ast::BinOp::EqualityTest) | Some(ast::BinOp::NegatedEqualityTest) => {
+                    return Some((ctx.sema.type_of_expr(&bin.lhs()?)?, "PartialEq"));
+                }
+                _ => (),
+            },
+            ast::Expr::MethodCallExpr(call) => {
+                let is_clone = call.name_ref().map_or(false, |it| it.text() == "clone");
+                if is_clone && ctx.sema.resolve_method_call(call).is_none() {
+                    return Some((ctx.sema.type_of_expr(&call.expr()?)?, "Clone"));
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+/// The type of the format argument at the cursor, if it's a local formatted
+/// with `{:?}`.
+fn debug_formatted_arg(ctx: &AssistCtx, macro_call: &ast::MacroCall) -> Option<Type> {
+    let macro_name = macro_call.path()?.segment()?.name_ref()?;
+    if !FORMAT_MACROS.contains(&macro_name.text().as_str()) {
+        return None;
+    }
+    let args = macro_args(macro_call.token_tree()?.syntax());
+    let fmt_idx = args.iter().position(|it| match it.as_slice() {
+        [it] => it.kind() == STRING,
+        _ => false,
+    })?;
+    let offset = ctx.frange.range.start();
+    let arg_idx = args.iter().position(|arg| {
+        arg.first().map_or(false, |it| it.text_range().start() <= offset)
+            && arg.last().map_or(false, |it| offset <= it.text_range().end())
+    })?;
+    if arg_idx <= fmt_idx {
+        return None;
+    }
+    let ident = match args[arg_idx].as_slice() {
+        [SyntaxElement::Token(it)] if it.kind() == IDENT => it.clone(),
+        _ => return None,
+    };
+    let fmt = ast::String::cast(args[fmt_idx][0].as_token()?.clone())?.value()?;
+    if !debug_placeholders(&fmt).contains(&(arg_idx - fmt_idx - 1)) {
+        return None;
+    }
+
+    let mut local = None;
+    ctx.sema.scope(macro_call.syntax()).process_all_names(&mut |name, def| {
+        if let ScopeDef::Local(it) = def {
+            if local.is_none() && name.to_string() == ident.text().as_str() {
+                local = Some(it);
+            }
+        }
+    });
+    Some(local?.ty(ctx.db))
+}
+
+/// The top-level arguments of a macro call, separated by commas.
+fn macro_args(token_tree: &SyntaxNode) -> Vec<Vec<SyntaxElement>> {
+    let mut args = vec![Vec::new()];
+    let elements = token_tree.children_with_tokens().collect::<Vec<_>>();
+    let inner = elements.get(1..elements.len().saturating_sub(1)).unwrap_or_default();
+    for element in inner {
+        match element.kind() {
+            T![,] => args.push(Vec::new()),
+            WHITESPACE | COMMENT => (),
+            _ => args.last_mut().unwrap().push(element.clone()),
+        }
+    }
+    args
+}
+
+/// The indices of the positional arguments formatted with `Debug` by the
+/// format string `fmt`.
+fn debug_placeholders(fmt: &str) -> Vec<usize> {
+    let mut res = Vec::new();
+    let mut next_idx = 0;
+    let mut chars = fmt.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
+        }
+        let placeholder = chars.by_ref().take_while(|&c| c != '}').collect::<String>();
+        let mut parts = placeholder.splitn(2, ':');
+        let arg = parts.next().unwrap_or_default();
+        let spec = parts.next().unwrap_or_default();
+        let idx = if arg.is_empty() {
+            next_idx += 1;
+            next_idx - 1
+        } else {
+            match arg.parse::<usize>() {
+                Ok(it) => it,
+                Err(_) => continue,
+            }
+        };
+        if spec.ends_with('?') {
+            res.push(idx);
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use hir::Semantics;
+    use ra_db::{fixture::WithFixture, FileRange};
+    use ra_ide_db::RootDatabase;
+    use ra_syntax::TextRange;
+
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn derive_partial_eq_for_comparison() {
+        check_assist(
+            add_missing_derive,
+            "
+#[derive(Debug)]
+struct Point { x: u32 }
+
+fn f(a: Point, b: Point) -> bool {
+    a <|>== b
+}",
+            "
+#[derive(Debug, PartialEq)]
+struct Point { x: u32 }
+
+fn f(a: Point, b: Point) -> bool {
+    a <|>== b
+}",
+        );
+    }
+
+    #[test]
+    fn derive_clone_for_clone_call() {
+        check_assist(
+            add_missing_derive,
+            "
+enum Mode { Read, Write }
+
+fn f(mode: &Mode) -> Mode {
+    mode.clo<|>ne()
+}",
+            "
+#[derive(Clone)]
+enum Mode { Read, Write }
+
+fn f(mode: &Mode) -> Mode {
+    mode.clo<|>ne()
+}",
+        );
+    }
+
+    #[test]
+    fn derive_debug_for_format_arg() {
+        check_assist(
+            add_missing_derive,
+            r#"
+struct Point { x: u32 }
+
+fn f(p: Point, n: u32) {
+    println!("{} {{}} {:?}", n, p<|>);
+}"#,
+            r#"
+#[derive(Debug)]
+struct Point { x: u32 }
+
+fn f(p: Point, n: u32) {
+    println!("{} {{}} {:?}", n, p<|>);
+}"#,
+        );
+        check_assist_not_applicable(
+            add_missing_derive,
+            r#"
+struct Point { x: u32 }
+
+fn f(p: Point, n: u32) {
+    println!("{:?} {}", n, p<|>);
+}"#,
+        );
+    }
+
+    #[test]
+    fn derive_not_applicable_to_type_of_other_crate() {
+        let (db, position) = RootDatabase::with_position(
+            "
+//- /main.rs crate:main deps:geometry
+use geometry::Point;
+
+fn f(a: Point, b: Point) -> bool {
+    a <|>== b
+}
+
+//- /lib.rs crate:geometry
+pub struct Point { pub x: u32 }",
+        );
+        let frange = FileRange {
+            file_id: position.file_id,
+            range: TextRange::offset_len(position.offset, 0.into()),
+        };
+        let sema = Semantics::new(&db);
+        assert!(add_missing_derive(AssistCtx::new(&sema, frange, true)).is_none());
+    }
+
+    #[test]
+    fn derive_not_applicable_with_manual_impl() {
+        check_assist_not_applicable(
+            add_missing_derive,
+            "
+struct Point { x: u32 }
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Point) -> bool { true }
+}
+
+fn f(a: Point, b: Point) -> bool {
+    a <|>== b
+}",
+        );
+    }
+}
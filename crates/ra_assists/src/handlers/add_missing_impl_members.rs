@@ -1,21 +1,14 @@
-use hir::HasSource;
-use ra_syntax::{
-    ast::{self, edit, make, AstNode, NameOwner},
-    SmolStr,
-};
+use ra_syntax::ast::{self, AstNode};
 
 use crate::{
-    ast_transform::{self, AstTransform, QualifyPaths, SubstituteTypeParams},
-    utils::{get_missing_impl_items, resolve_target_trait},
-    Assist, AssistCtx, AssistId,
+    ast_transform::{AstTransform, QualifyPaths, SubstituteTypeParams},
+    utils::{
+        get_missing_impl_items, items_for_mode, resolve_target_trait, scaffold_items,
+        AddMissingImplMembersMode,
+    },
+    Assist, AssistCtx, AssistId, AssistKind,
 };
 
-#[derive(PartialEq)]
-enum AddMissingImplMembersMode {
-    DefaultMethodsOnly,
-    NoDefaultMethods,
-}
-
 // Assist: add_impl_missing_members
 //
 // Adds scaffold for required impl members.
@@ -106,31 +99,7 @@ fn add_missing_impl_members_inner(
 
     let trait_ = resolve_target_trait(&ctx.sema, &impl_node)?;
 
-    let def_name = |item: &ast::ImplItem| -> Option<SmolStr> {
-        match item {
-            ast::ImplItem::FnDef(def) => def.name(),
-            ast::ImplItem::TypeAliasDef(def) => def.name(),
-            ast::ImplItem::ConstDef(def) => def.name(),
-        }
-        .map(|it| it.text().clone())
-    };
-
-    let missing_items = get_missing_impl_items(&ctx.sema, &impl_node)
-        .iter()
-        .map(|i| match i {
-            hir::AssocItem::Function(i) => ast::ImplItem::FnDef(i.source(ctx.db).value),
-            hir::AssocItem::TypeAlias(i) => ast::ImplItem::TypeAliasDef(i.source(ctx.db).value),
-            hir::AssocItem::Const(i) => ast::ImplItem::ConstDef(i.source(ctx.db).value),
-        })
-        .filter(|t| def_name(&t).is_some())
-        .filter(|t| match t {
-            ast::ImplItem::FnDef(def) => match mode {
-                AddMissingImplMembersMode::DefaultMethodsOnly => def.body().is_some(),
-                AddMissingImplMembersMode::NoDefaultMethods => def.body().is_none(),
-            },
-            _ => mode == AddMissingImplMembersMode::NoDefaultMethods,
-        })
-        .collect::<Vec<_>>();
+    let missing_items = items_for_mode(ctx.db, get_missing_impl_items(&ctx.sema, &impl_node), mode);
 
     if missing_items.is_empty() {
         return None;
@@ -138,42 +107,48 @@ fn add_missing_impl_members_inner(
 
     let sema = ctx.sema;
 
-    ctx.add_assist(AssistId(assist_id), label, |edit| {
-        let n_existing_items = impl_item_list.impl_items().count();
-        let source_scope = sema.scope_for_def(trait_);
-        let target_scope = sema.scope(impl_item_list.syntax());
-        let ast_transform = QualifyPaths::new(&target_scope, &source_scope, sema.db)
-            .or(SubstituteTypeParams::for_trait_impl(&source_scope, sema.db, trait_, impl_node));
-        let items = missing_items
-            .into_iter()
-            .map(|it| ast_transform::apply(&*ast_transform, it))
-            .map(|it| match it {
-                ast::ImplItem::FnDef(def) => ast::ImplItem::FnDef(add_body(def)),
-                _ => it,
-            })
-            .map(|it| edit::strip_attrs_and_docs(&it));
-        let new_impl_item_list = impl_item_list.append_items(items);
-        let cursor_position = {
-            let first_new_item = new_impl_item_list.impl_items().nth(n_existing_items).unwrap();
-            first_new_item.syntax().text_range().start()
-        };
-
-        edit.replace_ast(impl_item_list, new_impl_item_list);
-        edit.set_cursor(cursor_position);
-    })
-}
-
-fn add_body(fn_def: ast::FnDef) -> ast::FnDef {
-    if fn_def.body().is_none() {
-        fn_def.with_body(make::block_from_expr(make::expr_unimplemented()))
-    } else {
-        fn_def
-    }
+    ctx.add_assist(
+        AssistId(assist_id),
+        AssistKind::Refactor,
+        label,
+        impl_node.syntax().text_range(),
+        |edit| {
+            let n_existing_items = impl_item_list.impl_items().count();
+            let source_scope = sema.scope_for_def(trait_);
+            let target_scope = sema.scope(impl_item_list.syntax());
+            let ast_transform = QualifyPaths::new(&target_scope, &source_scope, sema.db).or(
+                SubstituteTypeParams::for_trait_impl(&source_scope, sema.db, trait_, impl_node),
+            );
+            let items = scaffold_items(&*ast_transform, missing_items);
+            let new_impl_item_list = impl_item_list.append_items(items.into_iter());
+            let cursor_position = {
+                let first_new_item = new_impl_item_list.impl_items().nth(n_existing_items).unwrap();
+                first_new_item.syntax().text_range().start()
+            };
+            // The placeholder bodies are tabstops, to be filled in turn.
+            let placeholders = new_impl_item_list
+                .impl_items()
+                .skip(n_existing_items)
+                .filter_map(|it| match it {
+                    ast::ImplItem::FnDef(it) => it.body()?.block()?.expr(),
+                    _ => None,
+                })
+                .filter(|it| it.syntax().text() == "unimplemented!()")
+                .map(|it| it.syntax().text_range())
+                .collect::<Vec<_>>();
+
+            edit.replace_ast(impl_item_list, new_impl_item_list);
+            for range in placeholders {
+                edit.add_tabstop(range);
+            }
+            edit.set_cursor(cursor_position);
+        },
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::helpers::{check_assist, check_assist_not_applicable};
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_snippet};
 
     use super::*;
 
@@ -579,4 +554,35 @@ impl Foo for S {
 }",
         )
     }
+
+    #[test]
+    fn placeholder_bodies_are_tabstops() {
+        check_assist_snippet(
+            add_missing_impl_members,
+            "
+trait Foo {
+    type Output;
+    fn foo(&self);
+    fn bar(&self) -> u32;
+}
+
+struct S;
+
+impl Foo for S { <|> }",
+            "
+trait Foo {
+    type Output;
+    fn foo(&self);
+    fn bar(&self) -> u32;
+}
+
+struct S;
+
+impl Foo for S {
+    $0type Output;
+    fn foo(&self) { ${1:unimplemented!()} }
+    fn bar(&self) -> u32 { ${2:unimplemented!()} }
+}",
+        );
+    }
 }
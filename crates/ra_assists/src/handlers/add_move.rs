@@ -0,0 +1,240 @@
+use hir::PathResolution;
+use ra_syntax::{
+    ast::{self, AstNode},
+    SyntaxElement,
+    SyntaxKind::WHITESPACE,
+    SyntaxNode, SyntaxToken, TextRange, T,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: add_move
+//
+// Adds the `move` keyword to a closure or an async block.
+//
+// ```
+// fn main() {
+//     let name = String::new();
+//     std::thread::spawn(<|>|| println!("{}", name));
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let name = String::new();
+//     std::thread::spawn(move || println!("{}", name));
+// }
+// ```
+pub(crate) fn add_move(ctx: AssistCtx) -> Option<Assist> {
+    let offset = ctx.frange.range.start();
+    if let Some(lambda) = ctx.find_node_at_offset::<ast::LambdaExpr>() {
+        let param_list = lambda.param_list()?;
+        let header = TextRange::from_to(
+            lambda.syntax().text_range().start(),
+            param_list.syntax().text_range().end(),
+        );
+        if header.contains_inclusive(offset) && move_token(lambda.syntax()).is_none() {
+            return ctx.add_assist(
+                AssistId("add_move"),
+                AssistKind::RefactorRewrite,
+                "Add move to closure",
+                header,
+                |edit| {
+                    edit.insert(param_list.syntax().text_range().start(), "move ");
+                },
+            );
+        }
+    }
+
+    let block_expr = ctx.find_node_at_offset::<ast::BlockExpr>()?;
+    let async_token = block_expr.syntax().first_token().filter(|it| it.kind() == T![async])?;
+    let block = block_expr.block()?;
+    let header =
+        TextRange::from_to(async_token.text_range().start(), block.syntax().text_range().start());
+    if !header.contains_inclusive(offset) || move_token(block_expr.syntax()).is_some() {
+        return None;
+    }
+    ctx.add_assist(
+        AssistId("add_move"),
+        AssistKind::RefactorRewrite,
+        "Add move to async block",
+        header,
+        |edit| {
+            edit.insert(block.syntax().text_range().start(), "move ");
+        },
+    )
+}
+
+// Assist: remove_move
+//
+// Removes the `move` keyword from a closure.
+//
+// ```
+// fn main() {
+//     let v = vec![1, 2];
+//     let len = <|>move || v.len();
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let v = vec![1, 2];
+//     let len = || v.len();
+// }
+// ```
+pub(crate) fn remove_move(ctx: AssistCtx) -> Option<Assist> {
+    let lambda = ctx.find_node_at_offset::<ast::LambdaExpr>()?;
+    let move_token = move_token(lambda.syntax())?;
+    let header = TextRange::from_to(
+        lambda.syntax().text_range().start(),
+        lambda.param_list()?.syntax().text_range().end(),
+    );
+    if !header.contains_inclusive(ctx.frange.range.start()) {
+        return None;
+    }
+    if captures_used_after(&ctx, &lambda) {
+        return None;
+    }
+
+    ctx.add_assist(
+        AssistId("remove_move"),
+        AssistKind::RefactorRewrite,
+        "Remove move from closure",
+        header,
+        |edit| {
+            let range = match move_token.next_sibling_or_token() {
+                Some(ws) if ws.kind() == WHITESPACE => {
+                    move_token.text_range().extend_to(&ws.text_range())
+                }
+                _ => move_token.text_range(),
+            };
+            edit.delete(range);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+fn move_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|it| it.kind() == T![move])
+}
+
+/// A `move` closure owns copies of captured variables which are still used
+/// after it, removing `move` would make it borrow them instead.
+fn captures_used_after(ctx: &AssistCtx, lambda: &ast::LambdaExpr) -> bool {
+    let captures = lambda
+        .syntax()
+        .descendants()
+        .filter_map(ast::PathExpr::cast)
+        .filter_map(|it| resolve_local(ctx, &it))
+        .collect::<Vec<_>>();
+    if captures.is_empty() {
+        return false;
+    }
+    let scope = match lambda.syntax().ancestors().find_map(ast::FnDef::cast) {
+        Some(it) => it,
+        None => return false,
+    };
+    let lambda_end = lambda.syntax().text_range().end();
+    scope
+        .syntax()
+        .descendants()
+        .filter(|it| it.text_range().start() >= lambda_end)
+        .filter_map(ast::PathExpr::cast)
+        .filter_map(|it| resolve_local(ctx, &it))
+        .any(|it| captures.contains(&it))
+}
+
+fn resolve_local(ctx: &AssistCtx, path_expr: &ast::PathExpr) -> Option<hir::Local> {
+    match ctx.sema.resolve_path(&path_expr.path()?)? {
+        PathResolution::Local(local) => Some(local),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn add_move_to_closure_with_capture_used_afterwards() {
+        check_assist(
+            add_move,
+            r#"
+fn f() {
+    let v = vec![1];
+    let c = <|>|| v.len();
+    v.len();
+}"#,
+            r#"
+fn f() {
+    let v = vec![1];
+    let c = <|>move || v.len();
+    v.len();
+}"#,
+        );
+    }
+
+    #[test]
+    fn add_move_to_async_closure() {
+        check_assist(
+            add_move,
+            "fn f() { let c = async <|>|x| x; }",
+            "fn f() { let c = async <|>move |x| x; }",
+        );
+    }
+
+    #[test]
+    fn add_move_to_async_block() {
+        check_assist(
+            add_move,
+            "fn f() { let fut = <|>async { 92 }; }",
+            "fn f() { let fut = <|>async move { 92 }; }",
+        );
+    }
+
+    #[test]
+    fn add_move_target() {
+        check_assist_target(add_move, "fn f() { let c = |<|>x: u32| x; }", "|x: u32|");
+    }
+
+    #[test]
+    fn add_move_not_applicable() {
+        check_assist_not_applicable(add_move, "fn f() { let c = <|>move || 92; }");
+        check_assist_not_applicable(add_move, "fn f() { let c = || { 9<|>2 }; }");
+        check_assist_not_applicable(add_move, "fn f() { <|>{ 92 }; }");
+    }
+
+    #[test]
+    fn remove_move_from_closure() {
+        check_assist(
+            remove_move,
+            r#"
+fn f() {
+    let v = vec![1];
+    let c = mo<|>ve || v.len();
+}"#,
+            r#"
+fn f() {
+    let v = vec![1];
+    let c = <|>|| v.len();
+}"#,
+        );
+    }
+
+    #[test]
+    fn remove_move_not_applicable_if_capture_is_used_afterwards() {
+        check_assist_not_applicable(
+            remove_move,
+            r#"
+fn f() {
+    let v = vec![1];
+    let c = <|>move || v.len();
+    v.len();
+}"#,
+        );
+        check_assist_not_applicable(remove_move, "fn f() { let c = <|>|| 92; }");
+    }
+}
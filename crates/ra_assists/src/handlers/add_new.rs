@@ -5,11 +5,11 @@ use ra_syntax::{
     ast::{
         self, AstNode, NameOwner, StructKind, TypeAscriptionOwner, TypeParamsOwner, VisibilityOwner,
     },
-    TextUnit, T,
+    TextRange, TextUnit, T,
 };
 use std::fmt::Write;
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{utils::SMALL_ITEM_LINES, Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: add_new
 //
@@ -32,7 +32,7 @@ use crate::{Assist, AssistCtx, AssistId};
 //
 // ```
 pub(crate) fn add_new(ctx: AssistCtx) -> Option<Assist> {
-    let strukt = ctx.find_node_at_offset::<ast::StructDef>()?;
+    let strukt = ctx.find_enclosing_item_if_small::<ast::StructDef>(SMALL_ITEM_LINES)?;
 
     // We want to only apply this to non-union structs with named fields
     let field_list = match strukt.kind() {
@@ -43,56 +43,67 @@ pub(crate) fn add_new(ctx: AssistCtx) -> Option<Assist> {
     // Return early if we've found an existing new fn
     let impl_def = find_struct_impl(&ctx, &strukt)?;
 
-    ctx.add_assist(AssistId("add_new"), "Add default constructor", |edit| {
-        edit.target(strukt.syntax().text_range());
+    ctx.add_assist(
+        AssistId("add_new"),
+        AssistKind::Refactor,
+        "Add default constructor",
+        strukt.syntax().text_range(),
+        |edit| {
+            let mut buf = String::with_capacity(512);
 
-        let mut buf = String::with_capacity(512);
-
-        if impl_def.is_some() {
-            buf.push('\n');
-        }
-
-        let vis = strukt.visibility().map(|v| format!("{} ", v.syntax()));
-        let vis = vis.as_deref().unwrap_or("");
-        write!(&mut buf, "    {}fn new(", vis).unwrap();
-
-        join(field_list.fields().filter_map(|f| {
-            Some(format!("{}: {}", f.name()?.syntax().text(), f.ascribed_type()?.syntax().text()))
-        }))
-        .separator(", ")
-        .to_buf(&mut buf);
-
-        buf.push_str(") -> Self { Self {");
+            if impl_def.is_some() {
+                buf.push('\n');
+            }
 
-        join(field_list.fields().filter_map(|f| Some(f.name()?.syntax().text())))
+            let vis = strukt.visibility().map(|v| format!("{} ", v.syntax()));
+            let vis = vis.as_deref().unwrap_or("");
+            write!(&mut buf, "    {}fn new(", vis).unwrap();
+
+            join(field_list.fields().filter_map(|f| {
+                Some(format!(
+                    "{}: {}",
+                    f.name()?.syntax().text(),
+                    f.ascribed_type()?.syntax().text()
+                ))
+            }))
             .separator(", ")
-            .surround_with(" ", " ")
             .to_buf(&mut buf);
 
-        buf.push_str("} }");
-
-        let (start_offset, end_offset) = impl_def
-            .and_then(|impl_def| {
-                buf.push('\n');
-                let start = impl_def
-                    .syntax()
-                    .descendants_with_tokens()
-                    .find(|t| t.kind() == T!['{'])?
-                    .text_range()
-                    .end();
-
-                Some((start, TextUnit::from_usize(1)))
-            })
-            .unwrap_or_else(|| {
-                buf = generate_impl_text(&strukt, &buf);
-                let start = strukt.syntax().text_range().end();
-
-                (start, TextUnit::from_usize(3))
-            });
-
-        edit.set_cursor(start_offset + TextUnit::of_str(&buf) - end_offset);
-        edit.insert(start_offset, buf);
-    })
+            buf.push_str(") -> Self { Self {");
+
+            join(field_list.fields().filter_map(|f| Some(f.name()?.syntax().text())))
+                .separator(", ")
+                .surround_with(" ", " ")
+                .to_buf(&mut buf);
+
+            buf.push_str("} }");
+
+            let (start_offset, end_offset) = impl_def
+                .and_then(|impl_def| {
+                    buf.push('\n');
+                    let start = impl_def
+                        .syntax()
+                        .descendants_with_tokens()
+                        .find(|t| t.kind() == T!['{'])?
+                        .text_range()
+                        .end();
+
+                    Some((start, TextUnit::from_usize(1)))
+                })
+                .unwrap_or_else(|| {
+                    buf = generate_impl_text(&strukt, &buf);
+                    let start = strukt.syntax().text_range().end();
+
+                    (start, TextUnit::from_usize(3))
+                });
+
+            // The name is a tabstop, for constructors named otherwise.
+            let name = TextUnit::from_usize(buf.find("fn new(").unwrap() + "fn ".len());
+            edit.add_tabstop(TextRange::offset_len(start_offset + name, TextUnit::of_str("new")));
+            edit.set_cursor(start_offset + TextUnit::of_str(&buf) - end_offset);
+            edit.insert(start_offset, buf);
+        },
+    )
 }
 
 // Generates the surrounding `impl Type { <code> }` including type and lifetime
@@ -182,7 +193,9 @@ fn has_new_fn(imp: &ast::ImplDef) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+    use crate::helpers::{
+        check_assist, check_assist_not_applicable, check_assist_snippet, check_assist_target,
+    };
 
     use super::*;
 
@@ -428,4 +441,18 @@ impl<T> Source<T> {
 "##,
         );
     }
+
+    #[test]
+    fn add_new_name_is_tabstop() {
+        check_assist_snippet(
+            add_new,
+            "struct Foo { baz: String <|>}",
+            "struct Foo { baz: String }
+
+impl Foo {
+    fn ${1:new}(baz: String) -> Self { Self { baz } }$0
+}
+",
+        );
+    }
 }
@@ -0,0 +1,261 @@
+use hir::Adt;
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, AttrsOwner, NameOwner},
+    Direction, SyntaxKind, TextUnit, T,
+};
+
+use crate::{utils::insert_attribute, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: add_non_exhaustive
+//
+// Adds `#[non_exhaustive]` to a public struct or enum. For an enum, a second
+// entry also adds a wildcard arm to the exhaustive matches over it in the
+// file.
+//
+// ```
+// pub enum Mode<|> { Read, Write }
+//
+// fn describe(mode: Mode) -> u32 {
+//     match mode {
+//         Mode::Read => 0,
+//         Mode::Write => 1,
+//     }
+// }
+// ```
+// ->
+// ```
+// #[non_exhaustive]
+// pub enum Mode { Read, Write }
+//
+// fn describe(mode: Mode) -> u32 {
+//     match mode {
+//         Mode::Read => 0,
+//         Mode::Write => 1,
+//     }
+// }
+// ```
+pub(crate) fn add_non_exhaustive(ctx: AssistCtx) -> Option<Assist> {
+    let item = ctx.find_node_at_offset::<ast::NominalDef>()?;
+    if let ast::NominalDef::UnionDef(_) = item {
+        return None;
+    }
+    let name = item.name()?;
+    let is_pub = item.syntax().children().any(|it| ast::Visibility::can_cast(it.kind()));
+    if ctx.frange.range.start() > name.syntax().text_range().end() || !is_pub {
+        return None;
+    }
+    let attrs = item.attrs().collect::<Vec<_>>();
+    if attrs.iter().any(|it| it.simple_name().map_or(false, |it| it == "non_exhaustive")) {
+        return None;
+    }
+    let (offset, text) = insert_attribute(item.syntax(), attrs.last(), "#[non_exhaustive]");
+    let arm_insertions = match &item {
+        ast::NominalDef::EnumDef(enum_def) => wildcard_arm_insertions(&ctx, enum_def)?,
+        _ => Vec::new(),
+    };
+
+    let mut group = ctx.add_assist_group("Add #[non_exhaustive]");
+    group.add_assist(
+        AssistId("add_non_exhaustive"),
+        AssistKind::Refactor,
+        "Add #[non_exhaustive]",
+        name.syntax().text_range(),
+        |edit| {
+            edit.insert(offset, text.clone());
+        },
+    );
+    if !arm_insertions.is_empty() {
+        group.add_assist(
+            AssistId("add_non_exhaustive"),
+            AssistKind::Refactor,
+            "Add #[non_exhaustive] and also update matches",
+            name.syntax().text_range(),
+            |edit| {
+                edit.insert(offset, text);
+                for (offset, text) in arm_insertions {
+                    edit.insert(offset, text);
+                }
+            },
+        );
+    }
+    group.finish()
+}
+
+/// The wildcard arms to add to the exhaustive matches over `enum_def` in the
+/// file.
+fn wildcard_arm_insertions(
+    ctx: &AssistCtx,
+    enum_def: &ast::EnumDef,
+) -> Option<Vec<(TextUnit, String)>> {
+    let enum_ = ctx.sema.to_def(enum_def)?;
+    let root = enum_def.syntax().ancestors().last()?;
+    let res = root
+        .descendants()
+        .filter_map(ast::MatchExpr::cast)
+        .filter(|match_expr| {
+            let ty = match_expr.expr().and_then(|it| ctx.sema.type_of_expr(&it));
+            ty.and_then(|it| it.as_adt()) == Some(Adt::Enum(enum_))
+        })
+        .filter_map(|it| it.match_arm_list())
+        .filter_map(|arm_list| {
+            let arms = arm_list.arms().collect::<Vec<_>>();
+            if arms.iter().any(|it| is_catch_all(ctx, it)) {
+                return None;
+            }
+            wildcard_arm_insertion(arms.last()?)
+        })
+        .collect();
+    Some(res)
+}
+
+fn is_catch_all(ctx: &AssistCtx, arm: &ast::MatchArm) -> bool {
+    if arm.guard().is_some() {
+        return false;
+    }
+    match arm.pat() {
+        Some(ast::Pat::PlaceholderPat(_)) => true,
+        Some(ast::Pat::BindPat(it)) => {
+            it.pat().is_none() && ctx.sema.resolve_bind_pat_to_const(&it).is_none()
+        }
+        _ => false,
+    }
+}
+
+/// Inserts `_ => todo!()` after `last_arm`, on its own line unless the match
+/// is written on one line.
+fn wildcard_arm_insertion(last_arm: &ast::MatchArm) -> Option<(TextUnit, String)> {
+    let indent = leading_indent(last_arm.syntax());
+    let separator = match &indent {
+        Some(indent) => format!("\n{}", indent),
+        None => " ".to_string(),
+    };
+    let comma = last_arm
+        .syntax()
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .find(|it| it.kind() != SyntaxKind::WHITESPACE && it.kind() != SyntaxKind::COMMENT)
+        .filter(|it| it.kind() == T![,]);
+    if let Some(comma) = comma {
+        return Some((comma.text_range().end(), format!("{}_ => todo!(),", separator)));
+    }
+    let needs_comma = match last_arm.expr()? {
+        ast::Expr::BlockExpr(_)
+        | ast::Expr::IfExpr(_)
+        | ast::Expr::MatchExpr(_)
+        | ast::Expr::LoopExpr(_)
+        | ast::Expr::WhileExpr(_)
+        | ast::Expr::ForExpr(_) => false,
+        _ => true,
+    };
+    let text = format!(
+        "{}{}_ => todo!(){}",
+        if needs_comma { "," } else { "" },
+        separator,
+        if indent.is_some() { "," } else { "" }
+    );
+    Some((last_arm.syntax().text_range().end(), text))
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use ra_syntax::TextRange;
+    use test_utils::extract_offset;
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    #[test]
+    fn add_non_exhaustive_to_struct() {
+        check_assist(
+            add_non_exhaustive,
+            "
+#[derive(Debug)]
+pub struct Config<|> { pub verbose: bool }",
+            "
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Config<|> { pub verbose: bool }",
+        );
+    }
+
+    #[test]
+    fn add_non_exhaustive_and_update_matches() {
+        let (offset, before) = extract_offset(
+            "
+pub enum Mode<|> { Read, Write, Append }
+
+fn describe(mode: Mode) -> u32 {
+    match mode {
+        Mode::Read => 0,
+        Mode::Write | Mode::Append => {
+            1
+        }
+    }
+}
+
+fn is_read(mode: &Mode) -> bool {
+    match *mode { Mode::Read => true, Mode::Write => false, Mode::Append => false }
+}
+
+fn is_write(mode: Mode) -> bool {
+    match mode {
+        Mode::Write => true,
+        _ => false,
+    }
+}",
+        );
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let entries = resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "add_non_exhaustive")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries.iter().map(|it| it.label.label.as_str()).collect::<Vec<_>>(),
+            vec!["Add #[non_exhaustive]", "Add #[non_exhaustive] and also update matches"]
+        );
+        assert_eq!(
+            entries[1].action.edit().apply(&before),
+            "
+#[non_exhaustive]
+pub enum Mode { Read, Write, Append }
+
+fn describe(mode: Mode) -> u32 {
+    match mode {
+        Mode::Read => 0,
+        Mode::Write | Mode::Append => {
+            1
+        }
+        _ => todo!(),
+    }
+}
+
+fn is_read(mode: &Mode) -> bool {
+    match *mode { Mode::Read => true, Mode::Write => false, Mode::Append => false, _ => todo!() }
+}
+
+fn is_write(mode: Mode) -> bool {
+    match mode {
+        Mode::Write => true,
+        _ => false,
+    }
+}"
+        );
+    }
+
+    #[test]
+    fn add_non_exhaustive_not_applicable() {
+        check_assist_not_applicable(
+            add_non_exhaustive,
+            "
+#[non_exhaustive]
+pub enum Mode<|> { Read, Write }",
+        );
+        check_assist_not_applicable(add_non_exhaustive, "enum Mode<|> { Read, Write }");
+    }
+}
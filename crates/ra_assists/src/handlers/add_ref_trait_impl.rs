@@ -0,0 +1,319 @@
+use hir::{AssocItem, HasSource};
+use ra_fmt::{leading_indent, reindent};
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner, TypeParamsOwner},
+    SyntaxKind::{ATTR, COMMENT, IDENT, LIFETIME, VISIBILITY, WHITESPACE},
+    SyntaxNode,
+};
+
+use crate::{
+    utils::{resolve_target_trait, trait_name},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: add_ref_trait_impl
+//
+// Implements the trait of an impl for references to the type too, when all the
+// methods of the trait take `&self`.
+//
+// ```
+// trait Shape {
+//     fn area(&self) -> f64;
+// }
+//
+// struct Square(f64);
+//
+// impl Shape for <|>Square {
+//     fn area(&self) -> f64 {
+//         self.0 * self.0
+//     }
+// }
+// ```
+// ->
+// ```
+// trait Shape {
+//     fn area(&self) -> f64;
+// }
+//
+// struct Square(f64);
+//
+// impl Shape for Square {
+//     fn area(&self) -> f64 {
+//         self.0 * self.0
+//     }
+// }
+//
+// impl Shape for &Square {
+//     fn area(&self) -> f64 {
+//         (*self).area()
+//     }
+// }
+// ```
+pub(crate) fn add_ref_trait_impl(ctx: AssistCtx) -> Option<Assist> {
+    let impl_def = ctx.find_node_at_offset::<ast::ImplDef>()?;
+    let item_list = impl_def.item_list()?;
+    if ctx.frange.range.start() >= item_list.syntax().text_range().start() {
+        return None;
+    }
+    let target_type = impl_def.target_type()?;
+    if let ast::TypeRef::ReferenceType(_) = target_type {
+        return None;
+    }
+    let trait_ = resolve_target_trait(&ctx.sema, &impl_def)?;
+    let trait_source = trait_.source(ctx.db).value;
+    let mut has_methods = false;
+    for item in trait_.items(ctx.db) {
+        let method = match item {
+            AssocItem::Function(it) => it.source(ctx.db).value,
+            // Associated types and constants can't be forwarded by a call.
+            _ => return None,
+        };
+        match method.param_list()?.self_param()?.kind() {
+            ast::SelfParamKind::Ref => has_methods = true,
+            _ => return None,
+        }
+    }
+    if !has_methods || has_ref_impl(&impl_def) {
+        return None;
+    }
+
+    let methods = item_list
+        .impl_items()
+        .filter_map(|it| match it {
+            ast::ImplItem::FnDef(it) => Some(delegate(&it)),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let lifetimes = |node: &SyntaxNode| {
+        node.descendants_with_tokens()
+            .filter(|it| it.kind() == LIFETIME)
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>()
+    };
+    let mut used = lifetimes(impl_def.syntax());
+    used.extend(lifetimes(trait_source.syntax()));
+    let lifetime = if used.is_empty() {
+        None
+    } else {
+        (b'a'..=b'z').map(|it| format!("'{}", it as char)).find(|it| !used.contains(it))
+    };
+
+    let mut generics = lifetime.iter().cloned().collect::<Vec<_>>();
+    if let Some(params) = impl_def.type_param_list() {
+        let text = params.syntax().text().to_string();
+        generics.push(text[1..text.len() - 1].trim().to_string());
+    }
+    let mut buf = String::from("impl");
+    if !generics.is_empty() {
+        buf.push_str(&format!("<{}>", generics.join(", ")));
+    }
+    buf.push_str(&format!(
+        " {} for &{}{}",
+        impl_def.target_trait()?.syntax(),
+        lifetime.as_ref().map_or(String::new(), |it| format!("{} ", it)),
+        target_type.syntax()
+    ));
+    if let Some(where_clause) = impl_def.where_clause() {
+        buf.push_str(&format!(" {}", where_clause.syntax()));
+    }
+    buf.push_str(" {");
+    buf.push_str(&methods.iter().map(|it| format!("\n    {}", it)).collect::<Vec<_>>().join("\n"));
+    buf.push_str("\n}");
+
+    let indent = leading_indent(impl_def.syntax()).unwrap_or_default();
+    ctx.add_assist(
+        AssistId("add_ref_trait_impl"),
+        AssistKind::Refactor,
+        format!("Also implement for &{}", target_type.syntax()),
+        impl_def.syntax().text_range(),
+        |edit| {
+            edit.insert(
+                impl_def.syntax().text_range().end(),
+                format!("\n\n{}{}", indent, reindent(&buf, &indent)),
+            );
+        },
+    )
+}
+
+/// Whether the trait of `impl_def` is already implemented for references to
+/// its type, in the same module.
+fn has_ref_impl(impl_def: &ast::ImplDef) -> bool {
+    let (trait_, ty) = match (trait_name(impl_def), impl_def.target_type()) {
+        (Some(trait_), Some(ty)) => (trait_, ty.syntax().to_string()),
+        _ => return false,
+    };
+    let parent = match impl_def.syntax().parent() {
+        Some(it) => it,
+        None => return false,
+    };
+    parent.children().filter_map(ast::ImplDef::cast).any(|it| {
+        let referenced = match it.target_type() {
+            Some(ast::TypeRef::ReferenceType(it)) if !it.is_mut() => it.type_ref(),
+            _ => None,
+        };
+        trait_name(&it).as_ref() == Some(&trait_)
+            && referenced.map_or(false, |it| it.syntax().to_string() == ty)
+    })
+}
+
+/// The method forwarding `method` to the referenced value, unless its
+/// signature mentions `Self`, which is another type in the new impl.
+fn delegate(method: &ast::FnDef) -> Option<String> {
+    let param_list = method.param_list()?;
+    let args = param_list
+        .params()
+        .map(|it| match it.pat()? {
+            ast::Pat::BindPat(it) => Some(it.name()?.text().to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let start = method
+        .syntax()
+        .children_with_tokens()
+        .find(|it| match it.kind() {
+            ATTR | COMMENT | VISIBILITY | WHITESPACE => false,
+            _ => true,
+        })?
+        .text_range()
+        .start();
+    let end = method.body()?.syntax().text_range().start();
+    let offset = method.syntax().text_range().start();
+    let signature = method.syntax().text().slice(start - offset..end - offset).to_string();
+    let mentions_self = method
+        .syntax()
+        .descendants_with_tokens()
+        .take_while(|it| it.text_range().start() < end)
+        .any(|it| it.kind() == IDENT && it.to_string() == "Self");
+    if mentions_self {
+        return None;
+    }
+    Some(format!(
+        "{} {{\n        (*self).{}({})\n    }}",
+        signature.trim_end(),
+        method.name()?.text(),
+        args.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn implement_two_method_trait_for_reference() {
+        check_assist(
+            add_ref_trait_impl,
+            "
+trait Named {
+    fn name(&self) -> String;
+    fn greet(&self, other: &str) -> String;
+}
+
+struct Person<T>(T);
+
+impl<T: Clone> Named for <|>Person<T> where T: Copy {
+    fn name(&self) -> String { String::new() }
+
+    fn greet(&self, other: &str) -> String { other.to_string() }
+}",
+            "
+trait Named {
+    fn name(&self) -> String;
+    fn greet(&self, other: &str) -> String;
+}
+
+struct Person<T>(T);
+
+impl<T: Clone> Named for <|>Person<T> where T: Copy {
+    fn name(&self) -> String { String::new() }
+
+    fn greet(&self, other: &str) -> String { other.to_string() }
+}
+
+impl<T: Clone> Named for &Person<T> where T: Copy {
+    fn name(&self) -> String {
+        (*self).name()
+    }
+
+    fn greet(&self, other: &str) -> String {
+        (*self).greet(other)
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn implement_for_reference_with_lifetime() {
+        check_assist(
+            add_ref_trait_impl,
+            "
+trait Parse {
+    fn parse<'s>(&self, text: &'s str) -> &'s str;
+}
+
+struct Parser;
+
+impl Parse for Parser<|> {
+    fn parse<'s>(&self, text: &'s str) -> &'s str { text }
+}",
+            "
+trait Parse {
+    fn parse<'s>(&self, text: &'s str) -> &'s str;
+}
+
+struct Parser;
+
+impl Parse for Parser<|> {
+    fn parse<'s>(&self, text: &'s str) -> &'s str { text }
+}
+
+impl<'a> Parse for &'a Parser {
+    fn parse<'s>(&self, text: &'s str) -> &'s str {
+        (*self).parse(text)
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_mut_self_method() {
+        check_assist_not_applicable(
+            add_ref_trait_impl,
+            "
+trait Counter {
+    fn get(&self) -> u32;
+    fn incr(&mut self);
+}
+
+struct C(u32);
+
+impl Counter for <|>C {
+    fn get(&self) -> u32 { self.0 }
+    fn incr(&mut self) { self.0 += 1 }
+}",
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_reference_impl_exists() {
+        check_assist_not_applicable(
+            add_ref_trait_impl,
+            "
+trait Named {
+    fn name(&self) -> String;
+}
+
+struct Person;
+
+impl Named for <|>Person {
+    fn name(&self) -> String { String::new() }
+}
+
+impl<'a> Named for &'a Person {
+    fn name(&self) -> String { (*self).name() }
+}",
+        );
+    }
+}
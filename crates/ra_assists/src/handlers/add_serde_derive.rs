@@ -0,0 +1,122 @@
+use ra_syntax::ast::{self, AstNode, NameOwner};
+
+use crate::{
+    utils::{depends_on, derive_args, derives, insert_derives},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: add_serde_derive
+//
+// Derives `Serialize` and/or `Deserialize` for a struct or an enum, in crates
+// depending on `serde`.
+//
+// ```
+// # //- /main.rs crate:main deps:serde
+// struct Point<|> { x: i32, y: i32 }
+// # //- /serde.rs crate:serde
+// # pub trait Serialize {}
+// # pub trait Deserialize {}
+// ```
+// ->
+// ```
+// # //- /main.rs crate:main deps:serde
+// #[derive(serde::Serialize)]
+// struct Point { x: i32, y: i32 }
+// # //- /serde.rs crate:serde
+// # pub trait Serialize {}
+// # pub trait Deserialize {}
+// ```
+pub(crate) fn add_serde_derive(ctx: AssistCtx) -> Option<Assist> {
+    let item = ctx.find_node_at_offset::<ast::NominalDef>()?;
+    if let ast::NominalDef::UnionDef(_) = item {
+        return None;
+    }
+    let name = item.name()?;
+    if ctx.frange.range.start() > name.syntax().text_range().end()
+        || !depends_on(ctx.sema, ctx.frange.file_id, "serde")
+    {
+        return None;
+    }
+    let derive_args = derive_args(&item);
+    let derived = |trait_| derive_args.as_ref().map_or(false, |args| derives(args, trait_));
+
+    let target = item.syntax().text_range();
+    let mut group = ctx.add_assist_group("Derive serde traits");
+    for traits in &[&["Serialize"][..], &["Deserialize"], &["Serialize", "Deserialize"]] {
+        if traits.iter().any(|it| derived(it)) {
+            continue;
+        }
+        let label = format!("Derive {}", traits.join(" + "));
+        let paths = traits.iter().map(|it| format!("serde::{}", it)).collect::<Vec<_>>();
+        group.add_assist(
+            AssistId("add_serde_derive"),
+            AssistKind::Refactor,
+            label,
+            target,
+            |edit| {
+                let (offset, text) =
+                    insert_derives(item.syntax(), derive_args.as_ref(), &paths.join(", "));
+                edit.insert(offset, text);
+                edit.set_cursor(name.syntax().text_range().start());
+            },
+        );
+    }
+    group.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::{fixture::WithFixture, FileRange};
+    use ra_ide_db::RootDatabase;
+    use ra_syntax::TextRange;
+
+    use crate::resolved_assists;
+
+    const SERDE: &str = "
+//- /serde.rs crate:serde
+pub trait Serialize {}
+pub trait Deserialize {}
+";
+
+    /// The labels of the entries offered for `main` and its text after
+    /// applying the first one.
+    fn entries(main: &str, depends_on_serde: bool) -> (Vec<String>, Option<String>) {
+        let deps = if depends_on_serde { " deps:serde" } else { "" };
+        let fixture = format!("//- /main.rs crate:main{}\n{}{}", deps, main, SERDE);
+        let (db, position) = RootDatabase::with_position(&fixture);
+        let frange = FileRange {
+            file_id: position.file_id,
+            range: TextRange::offset_len(position.offset, 0.into()),
+        };
+        let entries = resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "add_serde_derive")
+            .collect::<Vec<_>>();
+        let text = ra_db::SourceDatabase::file_text(&db, position.file_id);
+        let after = entries.first().map(|it| it.action.edit().apply(&text));
+        (entries.into_iter().map(|it| it.label.label).collect(), after)
+    }
+
+    #[test]
+    fn add_serde_derive_merges_into_existing_derive() {
+        let (labels, after) = entries("#[derive(Debug)]\nenum E<|> { A }", true);
+        assert_eq!(
+            labels,
+            vec!["Derive Serialize", "Derive Deserialize", "Derive Serialize + Deserialize"]
+        );
+        assert_eq!(after.unwrap(), "#[derive(Debug, serde::Serialize)]\nenum E { A }\n");
+    }
+
+    #[test]
+    fn add_serde_derive_not_applicable_without_dependency() {
+        let (labels, _) = entries("struct S<|>;", false);
+        assert!(labels.is_empty());
+    }
+
+    #[test]
+    fn add_serde_derive_skips_derived_traits() {
+        let (labels, after) = entries("#[derive(serde::Serialize)]\nstruct S<|>;", true);
+        assert_eq!(labels, vec!["Derive Deserialize"]);
+        assert_eq!(after.unwrap(), "#[derive(serde::Serialize, serde::Deserialize)]\nstruct S;\n");
+    }
+}
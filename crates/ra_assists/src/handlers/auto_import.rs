@@ -1,7 +1,7 @@
 use crate::{
-    assist_ctx::{Assist, AssistCtx},
-    utils::insert_use_statement,
-    AssistId,
+    assist_ctx::{ActionBuilder, Assist, AssistCtx},
+    utils::{import_location, insert_use_statement, ImportLocation},
+    AssistId, AssistKind,
 };
 use hir::{
     AsAssocItem, AssocItemContainer, ModPath, Module, ModuleDef, PathResolution, Semantics, Trait,
@@ -11,14 +11,16 @@ use ra_ide_db::{imports_locator::ImportsLocator, RootDatabase};
 use ra_prof::profile;
 use ra_syntax::{
     ast::{self, AstNode},
-    SyntaxNode,
+    SyntaxNode, TextRange, TextUnit,
 };
 use rustc_hash::FxHashSet;
 use std::collections::BTreeSet;
+use test_utils::tested_by;
 
 // Assist: auto_import
 //
-// If the name is unresolved, provides all possible imports for it.
+// If the name is unresolved, provides all possible imports for it. Apart from
+// trait methods, the name can also be qualified with the found path instead.
 //
 // ```
 // fn main() {
@@ -39,19 +41,58 @@ pub(crate) fn auto_import(ctx: AssistCtx) -> Option<Assist> {
     let auto_import_assets = AutoImportAssets::new(&ctx)?;
     let proposed_imports = auto_import_assets.search_for_imports(ctx.db);
     if proposed_imports.is_empty() {
+        tested_by!(auto_import_without_candidates);
         return None;
     }
 
     let mut group = ctx.add_assist_group(auto_import_assets.get_import_group_message());
     for import in proposed_imports {
-        group.add_assist(AssistId("auto_import"), format!("Import `{}`", &import), |edit| {
-            edit.target(auto_import_assets.syntax_under_caret.text_range());
+        let label = auto_import_assets.get_import_label(&import);
+        let insert_use = |edit: &mut ActionBuilder| {
             insert_use_statement(
                 &auto_import_assets.syntax_under_caret,
                 &import,
                 edit.text_edit_builder(),
             );
-        });
+        };
+        let target = auto_import_assets.syntax_under_caret.text_range();
+        match import_location(&auto_import_assets.syntax_under_caret, &import) {
+            Some(ImportLocation::MergedUse(range)) => group.add_assist_with_secondary_target(
+                AssistId("auto_import"),
+                AssistKind::QuickFix,
+                format!("{} (merge into existing use)", label),
+                target,
+                range,
+                insert_use,
+            ),
+            Some(ImportLocation::NewUse(range)) => group.add_assist_with_secondary_target(
+                AssistId("auto_import"),
+                AssistKind::QuickFix,
+                format!("{} (new use at top)", label),
+                target,
+                range,
+                insert_use,
+            ),
+            None => group.add_assist(
+                AssistId("auto_import"),
+                AssistKind::QuickFix,
+                label,
+                target,
+                insert_use,
+            ),
+        }
+        if let Some((range, text)) = auto_import_assets.qualified(&import) {
+            group.add_assist(
+                AssistId("qualify_path"),
+                AssistKind::QuickFix,
+                format!("Qualify as `{}`", &import),
+                auto_import_assets.syntax_under_caret.text_range(),
+                |edit| {
+                    edit.set_cursor(range.start() + TextUnit::of_str(&text));
+                    edit.replace(range, text);
+                },
+            );
+        }
     }
     group.finish()
 }
@@ -84,6 +125,7 @@ impl AutoImportAssets {
     fn for_regular_path(path_under_caret: ast::Path, ctx: &AssistCtx) -> Option<Self> {
         let syntax_under_caret = path_under_caret.syntax().to_owned();
         if syntax_under_caret.ancestors().find_map(ast::UseItem::cast).is_some() {
+            tested_by!(auto_import_in_use_item);
             return None;
         }
 
@@ -119,6 +161,42 @@ impl AutoImportAssets {
         }
     }
 
+    fn get_import_label(&self, import: &ModPath) -> String {
+        match &self.import_candidate {
+            ImportCandidate::TraitAssocItem(..) | ImportCandidate::TraitMethod(..) => {
+                format!("Import trait `{}`", import)
+            }
+            _ => format!("Import `{}`", import),
+        }
+    }
+
+    /// Returns the range to replace and the text qualifying the name under the
+    /// caret with `import`, `None` for a method, which can only be imported.
+    fn qualified(&self, import: &ModPath) -> Option<(TextRange, String)> {
+        let path = ast::Path::cast(self.syntax_under_caret.clone())?;
+        match &self.import_candidate {
+            ImportCandidate::UnqualifiedName(_) => {
+                let name_ref = path.segment()?.name_ref()?;
+                Some((name_ref.syntax().text_range(), import.to_string()))
+            }
+            ImportCandidate::QualifierStart(_) => {
+                let qualifier_start =
+                    path.qualifier()?.syntax().descendants().find_map(ast::NameRef::cast)?;
+                Some((qualifier_start.syntax().text_range(), import.to_string()))
+            }
+            ImportCandidate::TraitAssocItem(_, _) => {
+                let text = format!(
+                    "<{} as {}>::{}",
+                    path.qualifier()?.syntax(),
+                    import,
+                    path.segment()?.syntax()
+                );
+                Some((path.syntax().text_range(), text))
+            }
+            ImportCandidate::TraitMethod(_, _) => None,
+        }
+    }
+
     fn search_for_imports(&self, db: &RootDatabase) -> BTreeSet<ModPath> {
         let _p = profile("auto_import::search_for_imports");
         let current_crate = self.module_with_name_to_import.krate();
@@ -266,8 +344,28 @@ impl ImportCandidate {
 
 #[cfg(test)]
 mod tests {
+    use ra_db::FileRange;
+    use test_utils::{add_cursor, assert_eq_text, extract_offset};
+
     use super::*;
-    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+    use crate::{
+        helpers::{
+            self, check_assist, check_assist_not_applicable, check_assist_not_applicable_because,
+            check_assist_target,
+        },
+        marks, resolved_assists, ResolvedAssist,
+    };
+
+    fn auto_import_entries(before: &str) -> (String, Vec<ResolvedAssist>) {
+        let (offset, before) = extract_offset(before);
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let entries = resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "auto_import" || it.label.id.0 == "qualify_path")
+            .collect();
+        (before, entries)
+    }
 
     #[test]
     fn applicable_when_found_an_import() {
@@ -394,16 +492,17 @@ mod tests {
 
     #[test]
     fn not_applicable_when_no_imports_found() {
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             auto_import,
             "
             PubStruct<|>",
+            &marks::auto_import_without_candidates,
         );
     }
 
     #[test]
     fn not_applicable_in_import_statements() {
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             auto_import,
             r"
             use PubStruct<|>;
@@ -411,6 +510,7 @@ mod tests {
             pub mod PubMod {
                 pub struct PubStruct;
             }",
+            &marks::auto_import_in_use_item,
         );
     }
 
@@ -771,4 +871,193 @@ mod tests {
             ",
         )
     }
+
+    #[test]
+    fn qualify_entries_for_type_name() {
+        let (_, entries) = auto_import_entries(
+            r"
+            fn main() {
+                let map = Hash<|>Map::new();
+            }
+
+            pub mod std { pub mod collections { pub struct HashMap; } }
+            ",
+        );
+        let labels = entries.into_iter().map(|it| it.label.label).collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![
+                "Import `std::collections::HashMap` (new use at top)",
+                "Qualify as `std::collections::HashMap`"
+            ]
+        );
+    }
+
+    #[test]
+    fn import_label_and_secondary_target_for_new_use() {
+        let (before, entries) = auto_import_entries(
+            r"
+            fn main() {
+                let map = Hash<|>Map::new();
+            }
+
+            pub mod std { pub mod collections { pub struct HashMap; } }
+            ",
+        );
+        let entry = entries.into_iter().find(|it| it.label.id.0 == "auto_import").unwrap();
+        assert_eq!(entry.label.label, "Import `std::collections::HashMap` (new use at top)");
+        let offset = TextUnit::from_usize(before.find("fn main").unwrap());
+        assert_eq!(entry.label.secondary_target, Some(TextRange::offset_len(offset, 0.into())));
+    }
+
+    #[test]
+    fn import_label_and_secondary_target_for_merged_use() {
+        let (before, entries) = auto_import_entries(
+            r"
+            use std::collections::BTreeMap;
+
+            fn main() {
+                let map = Hash<|>Map::new();
+            }
+
+            pub mod std { pub mod collections { pub struct HashMap; pub struct BTreeMap; } }
+            ",
+        );
+        let entry = entries.into_iter().find(|it| it.label.id.0 == "auto_import").unwrap();
+        assert_eq!(
+            entry.label.label,
+            "Import `std::collections::HashMap` (merge into existing use)"
+        );
+        let use_item = "use std::collections::BTreeMap;";
+        let start = TextUnit::from_usize(before.find(use_item).unwrap());
+        assert_eq!(
+            entry.label.secondary_target,
+            Some(TextRange::offset_len(start, TextUnit::of_str(use_item)))
+        );
+        assert_eq_text!(
+            &entry.action.edit().apply(&before),
+            r"
+            use std::collections::{BTreeMap, HashMap};
+
+            fn main() {
+                let map = HashMap::new();
+            }
+
+            pub mod std { pub mod collections { pub struct HashMap; pub struct BTreeMap; } }
+            "
+        );
+    }
+
+    #[test]
+    fn qualify_path_instead_of_import() {
+        let (before, entries) = auto_import_entries(
+            r"
+            fn main() {
+                let map = collections<|>::HashMap::new();
+            }
+
+            pub mod std { pub mod collections { pub struct HashMap; } }
+            ",
+        );
+        let action = entries.into_iter().find(|it| it.label.id.0 == "qualify_path").unwrap().action;
+        let actual = add_cursor(&action.edit().apply(&before), action.cursor_position.unwrap());
+        assert_eq_text!(
+            r"
+            fn main() {
+                let map = std::collections<|>::HashMap::new();
+            }
+
+            pub mod std { pub mod collections { pub struct HashMap; } }
+            ",
+            &actual
+        );
+    }
+
+    #[test]
+    fn no_qualify_entry_for_trait_method() {
+        let (_, entries) = auto_import_entries(
+            r"
+            mod test_mod {
+                pub trait TestTrait {
+                    fn test_method(&self);
+                }
+                pub struct TestStruct {}
+                impl TestTrait for TestStruct {
+                    fn test_method(&self) {}
+                }
+            }
+
+            fn main() {
+                let test_struct = test_mod::TestStruct {};
+                test_struct.test_meth<|>od()
+            }
+            ",
+        );
+        let labels = entries.into_iter().map(|it| it.label.label).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["Import trait `test_mod::TestTrait` (new use at top)"]);
+    }
+
+    #[test]
+    fn trait_method_of_other_type_is_not_offered() {
+        check_assist_not_applicable_because(
+            auto_import,
+            r"
+            mod test_mod {
+                pub trait TestTrait {
+                    fn test_method(&self);
+                }
+                pub struct TestStruct {}
+                pub struct OtherStruct {}
+                impl TestTrait for OtherStruct {
+                    fn test_method(&self) {}
+                }
+            }
+
+            fn main() {
+                let test_struct = test_mod::TestStruct {};
+                test_struct.test_meth<|>od()
+            }
+            ",
+            &marks::auto_import_without_candidates,
+        );
+    }
+
+    #[test]
+    fn trait_method_of_several_traits() {
+        let (_, entries) = auto_import_entries(
+            r"
+            mod test_mod {
+                pub trait TestTrait {
+                    fn test_method(&self);
+                }
+                pub trait TestTrait2 {
+                    fn test_method(&self);
+                }
+                pub trait UnrelatedTrait {
+                    fn test_method(&self);
+                }
+                pub struct TestStruct {}
+                impl TestTrait for TestStruct {
+                    fn test_method(&self) {}
+                }
+                impl<T> TestTrait2 for T {
+                    fn test_method(&self) {}
+                }
+            }
+
+            fn main() {
+                let test_struct = test_mod::TestStruct {};
+                test_struct.test_meth<|>od()
+            }
+            ",
+        );
+        let labels = entries.into_iter().map(|it| it.label.label).collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![
+                "Import trait `test_mod::TestTrait` (new use at top)",
+                "Import trait `test_mod::TestTrait2` (new use at top)"
+            ]
+        );
+    }
 }
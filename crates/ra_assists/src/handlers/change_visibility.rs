@@ -2,13 +2,14 @@ use ra_syntax::{
     ast::{self, NameOwner, VisibilityOwner},
     AstNode,
     SyntaxKind::{
-        ATTR, COMMENT, CONST_DEF, ENUM_DEF, FN_DEF, IDENT, MODULE, STRUCT_DEF, TRAIT_DEF,
-        VISIBILITY, WHITESPACE,
+        ATTR, COMMENT, CONST_DEF, ENUM_DEF, ENUM_VARIANT, FN_DEF, IDENT, MODULE, STRUCT_DEF,
+        TRAIT_DEF, VISIBILITY, WHITESPACE,
     },
     SyntaxNode, TextUnit, T,
 };
+use test_utils::tested_by;
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: change_visibility
 //
@@ -45,6 +46,9 @@ fn add_vis(ctx: AssistCtx) -> Option<Assist> {
         if parent.children().any(|child| child.kind() == VISIBILITY) {
             return None;
         }
+        if !can_have_visibility(&parent) {
+            return None;
+        }
         (vis_offset(&parent), keyword.text_range())
     } else {
         let ident = ctx.token_at_offset().find(|leaf| leaf.kind() == IDENT)?;
@@ -53,14 +57,44 @@ fn add_vis(ctx: AssistCtx) -> Option<Assist> {
         {
             return None;
         }
+        if !can_have_visibility(field.syntax()) {
+            return None;
+        }
         (vis_offset(field.syntax()), ident.text_range())
     };
 
-    ctx.add_assist(AssistId("change_visibility"), "Change visibility to pub(crate)", |edit| {
-        edit.target(target);
-        edit.insert(offset, "pub(crate) ");
-        edit.set_cursor(offset);
-    })
+    ctx.add_assist(
+        AssistId("change_visibility"),
+        AssistKind::RefactorRewrite,
+        "Change visibility to pub(crate)",
+        target,
+        |edit| {
+            edit.insert(offset, "pub(crate) ");
+            edit.set_cursor(offset);
+        },
+    )
+}
+
+/// Members of traits and of trait impls take the visibility of the trait, and
+/// enum variants and their fields that of the enum.
+fn can_have_visibility(node: &SyntaxNode) -> bool {
+    if node.ancestors().any(|it| it.kind() == ENUM_VARIANT) {
+        tested_by!(change_visibility_in_enum_variant);
+        return false;
+    }
+    let container =
+        node.parent().filter(|it| ast::ItemList::can_cast(it.kind())).and_then(|it| it.parent());
+    if let Some(container) = container {
+        if ast::TraitDef::can_cast(container.kind()) {
+            tested_by!(change_visibility_in_trait);
+            return false;
+        }
+        if ast::ImplDef::cast(container).map_or(false, |it| it.target_trait().is_some()) {
+            tested_by!(change_visibility_in_trait_impl);
+            return false;
+        }
+    }
+    true
 }
 
 fn vis_offset(node: &SyntaxNode) -> TextUnit {
@@ -75,23 +109,32 @@ fn vis_offset(node: &SyntaxNode) -> TextUnit {
 }
 
 fn change_vis(ctx: AssistCtx, vis: ast::Visibility) -> Option<Assist> {
+    if !can_have_visibility(&vis.syntax().parent()?) {
+        return None;
+    }
     if vis.syntax().text() == "pub" {
         return ctx.add_assist(
             AssistId("change_visibility"),
+            AssistKind::RefactorRewrite,
             "Change Visibility to pub(crate)",
+            vis.syntax().text_range(),
             |edit| {
-                edit.target(vis.syntax().text_range());
                 edit.replace(vis.syntax().text_range(), "pub(crate)");
                 edit.set_cursor(vis.syntax().text_range().start())
             },
         );
     }
     if vis.syntax().text() == "pub(crate)" {
-        return ctx.add_assist(AssistId("change_visibility"), "Change visibility to pub", |edit| {
-            edit.target(vis.syntax().text_range());
-            edit.replace(vis.syntax().text_range(), "pub");
-            edit.set_cursor(vis.syntax().text_range().start());
-        });
+        return ctx.add_assist(
+            AssistId("change_visibility"),
+            AssistKind::RefactorRewrite,
+            "Change visibility to pub",
+            vis.syntax().text_range(),
+            |edit| {
+                edit.replace(vis.syntax().text_range(), "pub");
+                edit.set_cursor(vis.syntax().text_range().start());
+            },
+        );
     }
     None
 }
@@ -99,7 +142,10 @@ fn change_vis(ctx: AssistCtx, vis: ast::Visibility) -> Option<Assist> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::helpers::{check_assist, check_assist_target};
+    use crate::{
+        helpers::{check_assist, check_assist_not_applicable_because, check_assist_target},
+        marks,
+    };
 
     #[test]
     fn change_visibility_adds_pub_crate_to_items() {
@@ -169,4 +215,50 @@ mod tests {
         check_assist_target(change_visibility, "pub(crate)<|> fn foo() {}", "pub(crate)");
         check_assist_target(change_visibility, "struct S { <|>field: u32 }", "field");
     }
+
+    #[test]
+    fn change_visibility_works_in_inherent_impls() {
+        check_assist(
+            change_visibility,
+            "struct S; impl S { <|>fn foo() {} }",
+            "struct S; impl S { <|>pub(crate) fn foo() {} }",
+        );
+    }
+
+    #[test]
+    fn change_visibility_not_applicable_in_traits() {
+        check_assist_not_applicable_because(
+            change_visibility,
+            "trait Foo { <|>fn foo(); }",
+            &marks::change_visibility_in_trait,
+        );
+        check_assist_not_applicable_because(
+            change_visibility,
+            "trait Foo { <|>const FOO: u8; }",
+            &marks::change_visibility_in_trait,
+        );
+    }
+
+    #[test]
+    fn change_visibility_not_applicable_in_trait_impls() {
+        check_assist_not_applicable_because(
+            change_visibility,
+            "struct S; impl Foo for S { <|>fn foo() {} }",
+            &marks::change_visibility_in_trait_impl,
+        );
+        check_assist_not_applicable_because(
+            change_visibility,
+            "struct S; impl Foo for S { <|>pub fn foo() {} }",
+            &marks::change_visibility_in_trait_impl,
+        );
+    }
+
+    #[test]
+    fn change_visibility_not_applicable_to_enum_variant_fields() {
+        check_assist_not_applicable_because(
+            change_visibility,
+            "enum E { V { <|>field: u32 } }",
+            &marks::change_visibility_in_enum_variant,
+        );
+    }
 }
@@ -0,0 +1,294 @@
+use hir::ModuleDef;
+use ra_fmt::{leading_indent, reindent};
+use ra_ide_db::{
+    defs::{classify_name, Definition},
+    search::SearchScope,
+};
+use ra_syntax::{
+    algo::find_node_at_offset,
+    ast::{self, ArgListOwner, AstNode, NameOwner, TypeAscriptionOwner, VisibilityOwner},
+    TextRange, TextUnit,
+};
+
+use crate::{
+    utils::{call_of, is_assoc_fn, to_camel_case},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: convert_bool_params_to_struct
+//
+// Replaces the `bool` parameters of a function with a struct of options,
+// updating the callers.
+//
+// ```
+// fn copy(src: &str, verbose<|>: bool, dry_run: bool) {
+//     if verbose && !dry_run {}
+// }
+//
+// fn main() {
+//     copy("a", true, false);
+// }
+// ```
+// ->
+// ```
+// struct CopyOptions {
+//     pub verbose: bool,
+//     pub dry_run: bool,
+// }
+//
+// fn copy(src: &str, opts: CopyOptions) {
+//     if opts.verbose && !opts.dry_run {}
+// }
+//
+// fn main() {
+//     copy("a", CopyOptions { verbose: true, dry_run: false });
+// }
+// ```
+pub(crate) fn convert_bool_params_to_struct(ctx: AssistCtx) -> Option<Assist> {
+    let fn_def = ctx.find_node_at_offset::<ast::FnDef>()?;
+    let param_list = fn_def.param_list()?;
+    // Methods would need their call sites through method calls updated too.
+    if !param_list.syntax().text_range().contains_inclusive(ctx.frange.range.start())
+        || is_assoc_fn(&fn_def)
+    {
+        return None;
+    }
+    let params = param_list.params().collect::<Vec<_>>();
+    if params.iter().any(|it| it.pat().map_or(false, |it| it.syntax().text() == "opts")) {
+        return None;
+    }
+    let bool_params = params.iter().map(bool_param).collect::<Vec<_>>();
+    let names = bool_params.iter().flatten().collect::<Vec<_>>();
+    if names.len() < 2 || names.iter().any(|it| it.is_mutable()) {
+        return None;
+    }
+    let fn_name = fn_def.name()?;
+    let struct_name = format!("{}Options", to_camel_case(fn_name.text()));
+    let scope = fn_def.syntax().parent()?;
+    if scope
+        .children()
+        .filter_map(ast::StructDef::cast)
+        .any(|it| it.name().map_or(false, |it| it.text().as_str() == struct_name.as_str()))
+    {
+        return None;
+    }
+
+    let root = fn_def.syntax().ancestors().last()?;
+    let file_scope = SearchScope::single_file(ctx.frange.file_id);
+    let mut usage_edits = Vec::new();
+    for bind_pat in &names {
+        let name = bind_pat.name()?;
+        let def = classify_name(&ctx.sema, &name)?.definition();
+        for usage in def.find_usages(ctx.db, Some(file_scope.clone())) {
+            let name_ref =
+                find_node_at_offset::<ast::NameRef>(&root, usage.file_range.range.start())?;
+            usage_edits.push(usage_edit(&name_ref)?);
+        }
+    }
+
+    let function = ctx.sema.to_def(&fn_def)?;
+    let def = Definition::ModuleDef(ModuleDef::Function(function));
+    let mut call_site_edits = Vec::new();
+    for usage in def.find_usages(ctx.db, Some(file_scope)) {
+        let name_ref = find_node_at_offset::<ast::NameRef>(&root, usage.file_range.range.start())?;
+        let call = match call_of(&name_ref)? {
+            ast::Expr::CallExpr(it) => it,
+            _ => return None,
+        };
+        if call.syntax().ancestors().any(|it| it == *fn_def.syntax()) {
+            return None;
+        }
+        call_site_edits.push(call_site_edit(&call, &bool_params, &struct_name)?);
+    }
+    // Calls nested in the arguments of another call would be edited twice.
+    let overlaps = call_site_edits.iter().any(|(range, _)| {
+        call_site_edits.iter().any(|(other, _)| other != range && other.is_subrange(range))
+    });
+    if overlaps {
+        return None;
+    }
+
+    let visibility = fn_def.visibility().map(|it| format!("{} ", it.syntax())).unwrap_or_default();
+    let fields =
+        names.iter().map(|it| format!("    pub {}: bool,\n", it.syntax())).collect::<String>();
+    let struct_def = format!("{}struct {} {{\n{}}}", visibility, struct_name, fields);
+    let params = params.iter().map(|it| it.syntax().to_string()).collect::<Vec<_>>();
+    let new_params = replace_bools(&params, &bool_params, &format!("opts: {}", struct_name));
+
+    ctx.add_assist(
+        AssistId("convert_bool_params_to_struct"),
+        AssistKind::RefactorRewrite,
+        "Convert bool parameters to a struct",
+        param_list.syntax().text_range(),
+        |edit| {
+            let indent = leading_indent(fn_def.syntax()).unwrap_or_default();
+            let fn_start = fn_def.syntax().text_range().start();
+            let name_offset = struct_def.find("struct ").unwrap() + "struct ".len();
+            edit.set_cursor(fn_start + TextUnit::from_usize(name_offset));
+            edit.insert(fn_start, format!("{}\n\n{}", reindent(&struct_def, &indent), indent));
+            edit.replace(param_list.syntax().text_range(), new_params);
+            for (range, text) in usage_edits {
+                edit.replace(range, text);
+            }
+            for (range, text) in call_site_edits {
+                edit.replace(range, text);
+            }
+        },
+    )
+}
+
+/// The binding of `param`, if it's a plain `bool` parameter.
+fn bool_param(param: &ast::Param) -> Option<ast::BindPat> {
+    if param.ascribed_type()?.syntax().text() != "bool" {
+        return None;
+    }
+    match param.pat()? {
+        ast::Pat::BindPat(it) if !it.is_ref() && it.pat().is_none() => Some(it),
+        _ => None,
+    }
+}
+
+/// Replaces a use of a `bool` parameter with the field of the struct.
+fn usage_edit(name_ref: &ast::NameRef) -> Option<(TextRange, String)> {
+    let field = format!("opts.{}", name_ref.text());
+    if let Some(record_field) = name_ref.syntax().parent().and_then(ast::RecordField::cast) {
+        // `S { verbose }` is shorthand for `S { verbose: verbose }`.
+        if record_field.expr().is_none() {
+            let text = format!("{}: {}", name_ref.text(), field);
+            return Some((record_field.syntax().text_range(), text));
+        }
+    }
+    let path_expr = name_ref.syntax().ancestors().find_map(ast::PathExpr::cast)?;
+    Some((path_expr.syntax().text_range(), field))
+}
+
+/// Moves the `bool` arguments of a call into a struct literal.
+fn call_site_edit(
+    call: &ast::CallExpr,
+    bool_params: &[Option<ast::BindPat>],
+    struct_name: &str,
+) -> Option<(TextRange, String)> {
+    let arg_list = call.arg_list()?;
+    let args = arg_list.args().collect::<Vec<_>>();
+    if args.len() != bool_params.len() {
+        return None;
+    }
+    let fields = bool_params
+        .iter()
+        .zip(args.iter())
+        .filter_map(|(param, arg)| {
+            let name = param.as_ref()?.syntax().to_string();
+            let text = arg.syntax().to_string();
+            Some(if text == name { name } else { format!("{}: {}", name, text) })
+        })
+        .collect::<Vec<_>>();
+    let literal = format!("{} {{ {} }}", struct_name, fields.join(", "));
+    let args = args.iter().map(|it| it.syntax().to_string()).collect::<Vec<_>>();
+    Some((arg_list.syntax().text_range(), replace_bools(&args, bool_params, &literal)))
+}
+
+/// Joins the parameters or the arguments `items` of the function, with the
+/// `bool` ones replaced by `replacement`, placed where the first of them was.
+fn replace_bools(
+    items: &[String],
+    bool_params: &[Option<ast::BindPat>],
+    replacement: &str,
+) -> String {
+    let mut res = Vec::new();
+    let mut replaced = false;
+    for (item, bool_param) in items.iter().zip(bool_params.iter()) {
+        match bool_param {
+            None => res.push(item.as_str()),
+            Some(_) if !replaced => {
+                res.push(replacement);
+                replaced = true;
+            }
+            Some(_) => (),
+        }
+    }
+    format!("({})", res.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn convert_bool_params_with_literal_call_sites() {
+        check_assist(
+            convert_bool_params_to_struct,
+            r#"
+struct S { verbose: bool, n: u32 }
+
+fn run(<|>verbose: bool, dry_run: bool, n: u32) {
+    if verbose {
+        report(dry_run);
+    }
+    let s = S { verbose, n };
+}
+
+fn main() {
+    run(true, false, 1);
+    run(false, false, 2);
+}
+"#,
+            r#"
+struct S { verbose: bool, n: u32 }
+
+struct <|>RunOptions {
+    pub verbose: bool,
+    pub dry_run: bool,
+}
+
+fn run(opts: RunOptions, n: u32) {
+    if opts.verbose {
+        report(opts.dry_run);
+    }
+    let s = S { verbose: opts.verbose, n };
+}
+
+fn main() {
+    run(RunOptions { verbose: true, dry_run: false }, 1);
+    run(RunOptions { verbose: false, dry_run: false }, 2);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_bool_params_forwarding_expressions() {
+        check_assist(
+            convert_bool_params_to_struct,
+            r#"
+pub fn log(msg: &str, <|>verbose: bool, color: bool) {}
+
+fn main() {
+    let verbose = is_verbose();
+    log("hi", verbose, !is_tty());
+}
+"#,
+            r#"
+pub struct <|>LogOptions {
+    pub verbose: bool,
+    pub color: bool,
+}
+
+pub fn log(msg: &str, opts: LogOptions) {}
+
+fn main() {
+    let verbose = is_verbose();
+    log("hi", LogOptions { verbose, color: !is_tty() });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_bool_params_not_applicable_to_single_bool() {
+        check_assist_not_applicable(
+            convert_bool_params_to_struct,
+            "fn run(<|>verbose: bool, n: u32) {}",
+        );
+    }
+}
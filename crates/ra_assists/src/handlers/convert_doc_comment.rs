@@ -0,0 +1,334 @@
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, AstToken},
+    Direction, SyntaxElement,
+    SyntaxKind::{COMMENT, RAW_STRING, STRING, WHITESPACE},
+    TextRange,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: convert_doc_comment_to_attr
+//
+// Converts `///` doc comments into `#[doc]` attributes.
+//
+// ```
+// /// Frobnicates<|> the widget.
+// /// Returns the number of "frobs".
+// fn frobnicate() -> usize { 0 }
+// ```
+// ->
+// ```
+// #[doc = " Frobnicates the widget."]
+// #[doc = " Returns the number of \"frobs\"."]
+// fn frobnicate() -> usize { 0 }
+// ```
+pub(crate) fn convert_doc_comment_to_attr(ctx: AssistCtx) -> Option<Assist> {
+    let comment = ctx.find_token_at_offset(COMMENT)?;
+    let as_doc_comment = |element: SyntaxElement| {
+        element.into_token().and_then(ast::Comment::cast).filter(|it| {
+            let kind = it.kind();
+            kind.shape.is_line()
+                && kind.doc == Some(ast::CommentPlacement::Outer)
+                && !it.text().starts_with("////")
+        })
+    };
+    as_doc_comment(comment.clone().into())?;
+    let comments = contiguous(
+        comment.siblings_with_tokens(Direction::Prev).skip(1),
+        comment.siblings_with_tokens(Direction::Next),
+        as_doc_comment,
+    );
+    let target = TextRange::from_to(
+        comments.first()?.syntax().text_range().start(),
+        comments.last()?.syntax().text_range().end(),
+    );
+
+    ctx.add_assist(
+        AssistId("convert_doc_comment_to_attr"),
+        AssistKind::RefactorRewrite,
+        "Convert to #[doc] attributes",
+        target,
+        |edit| {
+            for comment in comments {
+                let text = &comment.text()[comment.prefix().len()..];
+                let attr = format!("#[doc = \"{}\"]", escape(text));
+                edit.replace(comment.syntax().text_range(), attr);
+            }
+        },
+    )
+}
+
+// Assist: convert_doc_attr_to_comment
+//
+// Converts `#[doc]` attributes into `///` doc comments.
+//
+// ```
+// #[doc = " Frobnicates<|> the widget."]
+// #[doc = " Returns the number of \"frobs\"."]
+// fn frobnicate() -> usize { 0 }
+// ```
+// ->
+// ```
+// /// Frobnicates the widget.
+// /// Returns the number of "frobs".
+// fn frobnicate() -> usize { 0 }
+// ```
+pub(crate) fn convert_doc_attr_to_comment(ctx: AssistCtx) -> Option<Assist> {
+    let attr = ctx.find_node_at_offset::<ast::Attr>()?;
+    let as_doc_attr = |element: SyntaxElement| {
+        let attr = element.into_node().and_then(ast::Attr::cast)?;
+        let lines = doc_lines(&attr)?;
+        Some((attr, lines))
+    };
+    as_doc_attr(attr.syntax().clone().into())?;
+    let attrs = contiguous(
+        attr.syntax().siblings_with_tokens(Direction::Prev).skip(1),
+        attr.syntax().siblings_with_tokens(Direction::Next),
+        as_doc_attr,
+    );
+    let target = TextRange::from_to(
+        attrs.first()?.0.syntax().text_range().start(),
+        attrs.last()?.0.syntax().text_range().end(),
+    );
+    let indent = leading_indent(attrs.first()?.0.syntax()).unwrap_or_default();
+
+    ctx.add_assist(
+        AssistId("convert_doc_attr_to_comment"),
+        AssistKind::RefactorRewrite,
+        "Convert to doc comments",
+        target,
+        |edit| {
+            for (attr, lines) in attrs {
+                let comments = lines
+                    .iter()
+                    .map(|line| format!("///{}", line))
+                    .collect::<Vec<_>>()
+                    .join(&format!("\n{}", indent));
+                edit.replace(attr.syntax().text_range(), comments);
+            }
+        },
+    )
+}
+
+/// Collects the elements accepted by `f` among `before` (in reverse order)
+/// and `after`, until one is rejected. A blank line ends the collection.
+fn contiguous<T>(
+    before: impl Iterator<Item = SyntaxElement>,
+    after: impl Iterator<Item = SyntaxElement>,
+    f: impl Fn(SyntaxElement) -> Option<T>,
+) -> Vec<T> {
+    let take = |elements: &mut dyn Iterator<Item = SyntaxElement>| {
+        let mut res = Vec::new();
+        for element in elements {
+            if element.kind() == WHITESPACE {
+                let is_blank_line =
+                    element.as_token().map_or(false, |it| it.text().matches('\n').count() > 1);
+                if is_blank_line {
+                    break;
+                }
+                continue;
+            }
+            match f(element) {
+                Some(it) => res.push(it),
+                None => break,
+            }
+        }
+        res
+    };
+    let mut res = take(&mut before.into_iter());
+    res.reverse();
+    res.extend(take(&mut after.into_iter()));
+    res
+}
+
+/// Returns the lines of the documentation of a `#[doc = "..."]` attribute.
+fn doc_lines(attr: &ast::Attr) -> Option<Vec<String>> {
+    if attr.kind() != ast::AttrKind::Outer || attr.simple_name()? != "doc" {
+        return None;
+    }
+    let literal = match attr.input()? {
+        ast::AttrInput::Literal(it) => it.token(),
+        ast::AttrInput::TokenTree(_) => return None,
+    };
+    let text = literal.text().as_str();
+    let value = match literal.kind() {
+        STRING => unescape(text.get(1..text.len() - 1)?)?,
+        RAW_STRING => {
+            let hashes = text[1..].chars().take_while(|&it| it == '#').count();
+            text.get(hashes + 2..text.len() - hashes - 1)?.to_string()
+        }
+        _ => return None,
+    };
+    let lines = value.split('\n').map(|it| it.to_string()).collect::<Vec<_>>();
+    // `////` starts a regular comment.
+    if lines.iter().any(|it| it.starts_with('/')) {
+        return None;
+    }
+    Some(lines)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Unescapes the simple escapes of a string literal, `None` for the others.
+fn unescape(text: &str) -> Option<String> {
+    let mut res = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        let unescaped = match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' => '\\',
+            '"' => '"',
+            '\'' => '\'',
+            _ => return None,
+        };
+        res.push(unescaped);
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn convert_doc_comment_block_to_attrs() {
+        check_assist(
+            convert_doc_comment_to_attr,
+            r#"
+/// First line.
+/// Second<|> line, with a \ in it.
+///   Third line.
+fn f() {}
+"#,
+            r#"
+<|>#[doc = " First line."]
+#[doc = " Second line, with a \\ in it."]
+#[doc = "   Third line."]
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_doc_attrs_to_comment_block() {
+        check_assist(
+            convert_doc_attr_to_comment,
+            r#"
+#[doc = " First line."]
+#[doc = " Second<|> line, with a \\ in it."]
+#[doc = "   Third line."]
+fn f() {}
+"#,
+            r#"
+<|>/// First line.
+/// Second line, with a \ in it.
+///   Third line.
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_doc_with_quotes() {
+        check_assist(
+            convert_doc_comment_to_attr,
+            r#"/// Returns <|>"quoted" text.
+fn f() {}"#,
+            r#"<|>#[doc = " Returns \"quoted\" text."]
+fn f() {}"#,
+        );
+        check_assist(
+            convert_doc_attr_to_comment,
+            r#"#[doc = " Returns <|>\"quoted\" text."]
+fn f() {}"#,
+            r#"<|>/// Returns "quoted" text.
+fn f() {}"#,
+        );
+    }
+
+    #[test]
+    fn convert_doc_keeps_other_attrs_in_place() {
+        check_assist(
+            convert_doc_comment_to_attr,
+            "
+/// Doc.
+#[inline]
+/// More<|> doc.
+#[cfg(test)]
+fn f() {}
+",
+            r#"
+/// Doc.
+#[inline]
+<|>#[doc = " More doc."]
+#[cfg(test)]
+fn f() {}
+"#,
+        );
+        check_assist(
+            convert_doc_attr_to_comment,
+            r#"
+#[doc = " Doc."]
+#[inline]
+#[doc = " More<|> doc."]
+#[cfg(test)]
+fn f() {}
+"#,
+            r#"
+#[doc = " Doc."]
+#[inline]
+<|>/// More doc.
+#[cfg(test)]
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_multiline_raw_doc_attr_to_comments() {
+        check_assist(
+            convert_doc_attr_to_comment,
+            r###"
+mod m {
+    #[doc = r#" First<|> line.
+ "Second" line."#]
+    fn f() {}
+}
+"###,
+            r#"
+mod m {
+    <|>/// First line.
+    /// "Second" line.
+    fn f() {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_doc_comment_not_applicable_to_regular_comments() {
+        check_assist_not_applicable(convert_doc_comment_to_attr, "// Not<|> a doc.\nfn f() {}");
+        check_assist_not_applicable(convert_doc_comment_to_attr, "//// Not<|> a doc.\nfn f() {}");
+        check_assist_not_applicable(convert_doc_attr_to_comment, "#[inl<|>ine]\nfn f() {}");
+    }
+
+    #[test]
+    fn convert_doc_comment_target() {
+        check_assist_target(
+            convert_doc_comment_to_attr,
+            "fn g() {}\n\n/// A<|>.\n/// B.\nfn f() {}",
+            "/// A.\n/// B.",
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use ra_syntax::{
+    ast::{self, make, AstNode, LoopBodyOwner, NameOwner},
+    SyntaxKind::{LIFETIME, WHITESPACE},
+    SyntaxNode, TextRange, T,
+};
+
+use crate::{utils::invert_boolean_expression, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: convert_loop_to_while
+//
+// Converts a `loop` which starts by breaking out on a condition into a `while` loop.
+//
+// ```
+// fn main() {
+//     <|>loop {
+//         if stack.is_empty() {
+//             break;
+//         }
+//         stack.pop();
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     while !stack.is_empty() {
+//         stack.pop();
+//     }
+// }
+// ```
+pub(crate) fn convert_loop_to_while(ctx: AssistCtx) -> Option<Assist> {
+    let loop_kw = ctx.find_token_at_offset(T![loop])?;
+    let loop_expr = loop_kw.parent().and_then(ast::LoopExpr::cast)?;
+    let body = loop_expr.loop_body()?;
+    let block = body.block()?;
+    let first = match block.statements().next() {
+        Some(stmt) => stmt.syntax().clone(),
+        None => block.expr()?.syntax().clone(),
+    };
+    let (header, label) = if let Some(cond) = break_condition(&first) {
+        (format!("while {} ", inverted(cond).syntax()), "Convert to `while` loop")
+    } else {
+        let (pat, expr) = break_match(&first)?;
+        (format!("while let {} = {} ", pat.syntax(), expr.syntax()), "Convert to `while let` loop")
+    };
+
+    let mut group = ctx.add_assist_group("Convert loop to while");
+    group.add_assist(
+        AssistId("convert_loop_to_while"),
+        AssistKind::RefactorRewrite,
+        label,
+        loop_expr.syntax().text_range(),
+        |edit| {
+            let kw_start = loop_kw.text_range().start();
+            edit.replace(TextRange::from_to(kw_start, body.syntax().text_range().start()), header);
+            edit.delete(range_with_trailing_whitespace(&first));
+            edit.set_cursor(kw_start);
+        },
+    );
+    group.finish()
+}
+
+/// Matches `if cond { break; }`, without an `else` branch.
+fn break_condition(node: &SyntaxNode) -> Option<ast::Expr> {
+    let if_expr = match ast::ExprStmt::cast(node.clone()) {
+        Some(stmt) => match stmt.expr()? {
+            ast::Expr::IfExpr(it) => it,
+            _ => return None,
+        },
+        None => ast::IfExpr::cast(node.clone())?,
+    };
+    if if_expr.else_branch().is_some() {
+        return None;
+    }
+    let cond = if_expr.condition()?;
+    if cond.pat().is_some() {
+        return None;
+    }
+    let then_block = if_expr.then_branch()?.block()?;
+    let statements = then_block.statements().collect::<Vec<_>>();
+    let break_expr = match (statements.as_slice(), then_block.expr()) {
+        ([], Some(ast::Expr::BreakExpr(it))) => it,
+        ([ast::Stmt::ExprStmt(stmt)], None) => match stmt.expr()? {
+            ast::Expr::BreakExpr(it) => it,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if !is_plain_break(&break_expr) {
+        return None;
+    }
+    cond.expr()
+}
+
+/// Matches `let x = match e { Some(x) => x, None => break };`, returning the
+/// pattern of the non-breaking arm and the matched expression.
+fn break_match(node: &SyntaxNode) -> Option<(ast::Pat, ast::Expr)> {
+    let let_stmt = ast::LetStmt::cast(node.clone())?;
+    let name = match let_stmt.pat()? {
+        ast::Pat::BindPat(it) if !it.is_mutable() && !it.is_ref() && it.pat().is_none() => {
+            it.name()?
+        }
+        _ => return None,
+    };
+    let match_expr = match let_stmt.initializer()? {
+        ast::Expr::MatchExpr(it) => it,
+        _ => return None,
+    };
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    if arms.len() != 2 || arms.iter().any(|it| it.guard().is_some()) {
+        return None;
+    }
+    let is_break_arm = |arm: &ast::MatchArm| match arm.expr() {
+        Some(ast::Expr::BreakExpr(it)) => is_plain_break(&it),
+        _ => false,
+    };
+    let value_arm = match (is_break_arm(&arms[0]), is_break_arm(&arms[1])) {
+        (false, true) => &arms[0],
+        (true, false) => &arms[1],
+        _ => return None,
+    };
+    match value_arm.expr()? {
+        ast::Expr::PathExpr(it) if it.syntax().text() == name.text().as_str() => (),
+        _ => return None,
+    }
+    Some((value_arm.pat()?, match_expr.expr()?))
+}
+
+/// A `while` loop can't produce a value, nor break out of an outer loop.
+fn is_plain_break(break_expr: &ast::BreakExpr) -> bool {
+    break_expr.expr().is_none()
+        && !break_expr.syntax().children_with_tokens().any(|it| it.kind() == LIFETIME)
+}
+
+fn inverted(cond: ast::Expr) -> ast::Expr {
+    let cond = match &cond {
+        ast::Expr::BinExpr(bin) => match bin.op_kind() {
+            Some(ast::BinOp::EqualityTest) | Some(ast::BinOp::NegatedEqualityTest) => cond,
+            _ => make::expr_from_text(&format!("({})", cond.syntax())),
+        },
+        _ => cond,
+    };
+    invert_boolean_expression(cond)
+}
+
+fn range_with_trailing_whitespace(node: &SyntaxNode) -> TextRange {
+    match node.next_sibling_or_token() {
+        Some(ws) if ws.kind() == WHITESPACE => node.text_range().extend_to(&ws.text_range()),
+        _ => node.text_range(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn convert_loop_with_plain_condition() {
+        check_assist(
+            convert_loop_to_while,
+            r#"
+fn f() {
+    <|>loop {
+        if a && b { break }
+        foo();
+    }
+}"#,
+            r#"
+fn f() {
+    <|>while !(a && b) {
+        foo();
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn convert_loop_inverts_is_none() {
+        check_assist(
+            convert_loop_to_while,
+            r#"
+fn f() {
+    'outer: lo<|>op {
+        if x.is_none() {
+            break;
+        }
+        x = next(x);
+    }
+}"#,
+            r#"
+fn f() {
+    'outer: <|>while x.is_some() {
+        x = next(x);
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn convert_loop_to_while_let() {
+        check_assist(
+            convert_loop_to_while,
+            r#"
+fn f() {
+    <|>loop {
+        let item = match iter.next() {
+            Some(item) => item,
+            None => break,
+        };
+        foo(item);
+    }
+}"#,
+            r#"
+fn f() {
+    <|>while let Some(item) = iter.next() {
+        foo(item);
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn convert_loop_to_while_target() {
+        check_assist_target(
+            convert_loop_to_while,
+            "fn f() { <|>loop { if c { break } } }",
+            "loop { if c { break } }",
+        );
+    }
+
+    #[test]
+    fn convert_loop_not_applicable_to_break_with_value() {
+        check_assist_not_applicable(
+            convert_loop_to_while,
+            "fn f() { let x = <|>loop { if c { break 92; } foo(); }; }",
+        );
+        check_assist_not_applicable(
+            convert_loop_to_while,
+            "fn f() { 'a: loop { <|>loop { if c { break 'a; } foo(); } } }",
+        );
+        check_assist_not_applicable(
+            convert_loop_to_while,
+            "fn f() { <|>loop { foo(); if c { break; } } }",
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use hir::ModuleDef;
+use ra_fmt::leading_indent;
+use ra_ide_db::{
+    defs::{classify_name, Definition},
+    search::SearchScope,
+};
+use ra_syntax::{
+    algo::find_node_at_offset,
+    ast::{self, ArgListOwner, AstNode, NameOwner, TypeAscriptionOwner},
+    Direction,
+    SyntaxKind::IDENT,
+    SyntaxNode, TextRange, TextUnit,
+};
+
+use crate::{utils::mutates, Assist, AssistCtx, AssistId, AssistKind};
+
+/// The collections which can be returned instead of filled.
+const COLLECTIONS: &[&str] = &["Vec", "String", "HashMap"];
+
+/// The methods adding to a collection without reading it.
+const ADDING_METHODS: &[&str] = &["push", "push_str", "insert", "extend"];
+
+// Assist: convert_out_param_to_return
+//
+// Converts a `&mut` collection parameter which is only added to into the
+// return value of the function, updating its calls.
+//
+// ```
+// fn evens(n: u32, out<|>: &mut Vec<u32>) {
+//     for i in 0..n {
+//         out.push(i * 2);
+//     }
+// }
+//
+// fn main() {
+//     let mut v = Vec::new();
+//     evens(3, &mut v);
+// }
+// ```
+// ->
+// ```
+// fn evens(n: u32) -> Vec<u32> {
+//     let mut out = Vec::new();
+//     for i in 0..n {
+//         out.push(i * 2);
+//     }
+//     out
+// }
+//
+// fn main() {
+//     let v = evens(3);
+// }
+// ```
+pub(crate) fn convert_out_param_to_return(ctx: AssistCtx) -> Option<Assist> {
+    let param = ctx.find_node_at_offset::<ast::Param>()?;
+    let param_list = ast::ParamList::cast(param.syntax().parent()?)?;
+    let fn_def = ast::FnDef::cast(param_list.syntax().parent()?)?;
+    if param_list.self_param().is_some() {
+        return None;
+    }
+    let bind_pat = match param.pat()? {
+        ast::Pat::BindPat(it) => it,
+        _ => return None,
+    };
+    if bind_pat.is_mutable() || bind_pat.is_ref() || bind_pat.pat().is_some() {
+        return None;
+    }
+    let name = bind_pat.name()?;
+    let collection = match param.ascribed_type()? {
+        ast::TypeRef::ReferenceType(it) if it.is_mut() => it.type_ref()?,
+        _ => return None,
+    };
+    let init = empty_collection(&collection)?;
+    let ret_type = match fn_def.ret_type() {
+        Some(it) if it.type_ref()?.syntax().text() != "()" => return None,
+        it => it,
+    };
+    let block = fn_def.body()?.block()?;
+
+    // The parameter must only be added to, and the body must not return
+    // before the collection is complete.
+    let def = classify_name(&ctx.sema, &name)?.definition();
+    let file_scope = SearchScope::single_file(ctx.frange.file_id);
+    let usages = def.find_usages(ctx.db, Some(file_scope.clone()));
+    let root = fn_def.syntax().ancestors().last()?;
+    for usage in &usages {
+        let path_expr =
+            find_node_at_offset::<ast::PathExpr>(&root, usage.file_range.range.start())?;
+        let call = ast::MethodCallExpr::cast(path_expr.syntax().parent()?)?;
+        let is_receiver = call.expr()?.syntax().text_range() == path_expr.syntax().text_range();
+        if !is_receiver || !ADDING_METHODS.contains(&call.name_ref()?.text().as_str()) {
+            return None;
+        }
+    }
+    // The uses in macro calls and the shadowing bindings are not usages.
+    let mentions = block
+        .syntax()
+        .descendants_with_tokens()
+        .filter(|it| it.kind() == IDENT && it.to_string() == name.text().as_str())
+        .count();
+    let returns = block.syntax().descendants().any(|it| ast::ReturnExpr::can_cast(it.kind()));
+    if usages.is_empty() || mentions != usages.len() || returns {
+        return None;
+    }
+
+    let params = param_list.params().collect::<Vec<_>>();
+    let idx = params.iter().position(|it| it.syntax() == param.syntax())?;
+    let func = ctx.sema.to_def(&fn_def)?;
+    let fn_usages =
+        Definition::ModuleDef(ModuleDef::Function(func)).find_usages(ctx.db, Some(file_scope));
+    let mut edits = fn_usages
+        .iter()
+        .map(|usage| call_site_edit(&root, usage.file_range.range.start(), idx))
+        .collect::<Option<Vec<_>>>()?;
+
+    let param_range = match (idx.checked_sub(1), params.get(idx + 1)) {
+        (Some(prev), _) => TextRange::from_to(
+            params[prev].syntax().text_range().end(),
+            param.syntax().text_range().end(),
+        ),
+        (None, Some(next)) => TextRange::from_to(
+            param.syntax().text_range().start(),
+            next.syntax().text_range().start(),
+        ),
+        (None, None) => param.syntax().text_range(),
+    };
+    edits.push((param_range, String::new()));
+    let ret_text = format!("-> {}", collection.syntax());
+    edits.push(match ret_type {
+        Some(it) => (it.syntax().text_range(), ret_text),
+        None => {
+            let offset = param_list.syntax().text_range().end();
+            (TextRange::offset_len(offset, 0.into()), format!(" {}", ret_text))
+        }
+    });
+    let indent = format!("{}    ", leading_indent(fn_def.syntax()).unwrap_or_default());
+    let l_curly = block.syntax().first_token()?.text_range().end();
+    edits.push((
+        TextRange::offset_len(l_curly, 0.into()),
+        format!("\n{}let mut {} = {};", indent, name.text(), init),
+    ));
+    let (end, semi) = match (block.expr(), block.statements().last()) {
+        (Some(tail), _) => (tail.syntax().text_range().end(), !tail.is_block_like()),
+        (None, Some(stmt)) => (stmt.syntax().text_range().end(), false),
+        (None, None) => return None,
+    };
+    edits.push((
+        TextRange::offset_len(end, 0.into()),
+        format!("{}\n{}{}", if semi { ";" } else { "" }, indent, name.text()),
+    ));
+
+    let anchor = param_list.syntax().text_range().start();
+    let cursor = edits
+        .iter()
+        .filter(|(range, _)| range.end() <= anchor)
+        .fold(anchor, |acc, (range, text)| acc + TextUnit::of_str(text) - range.len());
+    ctx.add_assist(
+        AssistId("convert_out_param_to_return"),
+        AssistKind::RefactorRewrite,
+        "Return instead of out-parameter",
+        param.syntax().text_range(),
+        |edit| {
+            for (range, text) in edits {
+                edit.replace(range, text);
+            }
+            edit.set_cursor(cursor);
+        },
+    )
+}
+
+/// The empty value of the collection type `ty`, like `Vec::new()`.
+fn empty_collection(ty: &ast::TypeRef) -> Option<String> {
+    let path = match ty {
+        ast::TypeRef::PathType(it) => it.path()?,
+        _ => return None,
+    };
+    let segment = path.segment()?;
+    let name = segment.name_ref()?;
+    if !COLLECTIONS.contains(&name.text().as_str()) {
+        return None;
+    }
+    match path.qualifier() {
+        Some(qualifier) => Some(format!("{}::{}::new()", qualifier.syntax(), name.text())),
+        None => Some(format!("{}::new()", name.text())),
+    }
+}
+
+/// The edit of the call statement at `offset`, `f(&mut acc)`, into
+/// `acc.extend(f())`, or into `let acc = f();` with a declaration of an empty
+/// `acc` right before it.
+fn call_site_edit(root: &SyntaxNode, offset: TextUnit, idx: usize) -> Option<(TextRange, String)> {
+    let path_expr = find_node_at_offset::<ast::PathExpr>(root, offset)?;
+    let call = ast::CallExpr::cast(path_expr.syntax().parent()?)?;
+    if call.expr()?.syntax().text_range() != path_expr.syntax().text_range() {
+        return None;
+    }
+    let stmt = ast::ExprStmt::cast(call.syntax().parent()?)?;
+    let mut args = call.arg_list()?.args().collect::<Vec<_>>();
+    if idx >= args.len() {
+        return None;
+    }
+    let acc = match args.remove(idx) {
+        ast::Expr::RefExpr(it) if it.is_mut() => match it.expr()? {
+            ast::Expr::PathExpr(it) if it.path()?.qualifier().is_none() => {
+                it.path()?.segment()?.name_ref()?.text().to_string()
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let new_call = format!(
+        "{}({})",
+        path_expr.syntax(),
+        args.iter().map(|it| it.syntax().to_string()).collect::<Vec<_>>().join(", ")
+    );
+
+    let declaration = stmt
+        .syntax()
+        .prev_sibling()
+        .and_then(ast::LetStmt::cast)
+        .filter(|it| declares_empty(it, &acc));
+    match declaration {
+        Some(declaration) => {
+            let is_mutated =
+                stmt.syntax().siblings(Direction::Next).skip(1).any(|it| mutates(&it, &acc));
+            let ascription = declaration
+                .ascribed_type()
+                .map(|it| format!(": {}", it.syntax()))
+                .unwrap_or_default();
+            let range = TextRange::from_to(
+                declaration.syntax().text_range().start(),
+                stmt.syntax().text_range().end(),
+            );
+            let text = format!(
+                "let {}{}{} = {};",
+                if is_mutated { "mut " } else { "" },
+                acc,
+                ascription,
+                new_call
+            );
+            Some((range, text))
+        }
+        None => Some((stmt.syntax().text_range(), format!("{}.extend({});", acc, new_call))),
+    }
+}
+
+/// Whether `let_stmt` is `let mut name = X::new();` or `let mut name = vec![];`.
+fn declares_empty(let_stmt: &ast::LetStmt, name: &str) -> bool {
+    let is_name = match let_stmt.pat() {
+        Some(ast::Pat::BindPat(it)) => {
+            it.is_mutable() && !it.is_ref() && it.name().map_or(false, |it| it.text() == name)
+        }
+        _ => false,
+    };
+    let is_empty = match let_stmt.initializer() {
+        Some(ast::Expr::CallExpr(it)) => {
+            it.arg_list().map_or(false, |it| it.args().next().is_none())
+                && it.expr().map_or(false, |it| it.syntax().text().to_string().ends_with("::new"))
+        }
+        Some(ast::Expr::MacroCall(it)) => it.syntax().text() == "vec![]",
+        _ => false,
+    };
+    is_name && is_empty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn convert_push_only_param() {
+        check_assist(
+            convert_out_param_to_return,
+            "
+fn collect_evens(n: u32, out<|>: &mut Vec<u32>) {
+    for i in 0..n {
+        if i % 2 == 0 {
+            out.push(i);
+        }
+    }
+}
+
+fn main() {
+    let mut evens = Vec::new();
+    collect_evens(10, &mut evens);
+    print_all(&evens);
+}",
+            "
+fn collect_evens<|>(n: u32) -> Vec<u32> {
+    let mut out = Vec::new();
+    for i in 0..n {
+        if i % 2 == 0 {
+            out.push(i);
+        }
+    }
+    out
+}
+
+fn main() {
+    let evens = collect_evens(10);
+    print_all(&evens);
+}",
+        );
+    }
+
+    #[test]
+    fn convert_param_extending_other_collection() {
+        check_assist(
+            convert_out_param_to_return,
+            "
+fn greet(<|>buf: &mut String, name: &str) {
+    buf.push_str(name)
+}
+
+fn main() {
+    let mut s = String::new();
+    s.push('>');
+    greet(&mut s, \"b\");
+}",
+            "
+fn greet<|>(name: &str) -> String {
+    let mut buf = String::new();
+    buf.push_str(name);
+    buf
+}
+
+fn main() {
+    let mut s = String::new();
+    s.push('>');
+    s.extend(greet(\"b\"));
+}",
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_body_reads_param() {
+        check_assist_not_applicable(
+            convert_out_param_to_return,
+            "
+fn collect(out<|>: &mut Vec<u32>) {
+    if out.is_empty() {
+        out.push(1);
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_return_type() {
+        check_assist_not_applicable(
+            convert_out_param_to_return,
+            "
+fn collect(out<|>: &mut Vec<u32>) -> bool {
+    out.push(1);
+    true
+}",
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use ra_syntax::{
+    ast::{self, ArgListOwner, AstNode, TypeBoundsOwner},
+    SyntaxKind::{FN_DEF, LAMBDA_EXPR},
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: convert_return_type
+//
+// Converts an `impl Trait` return type to `Box<dyn Trait>`, boxing the
+// returned values, or the other way around.
+//
+// ```
+// fn numbers() -> impl Iterator<Item = u32><|> {
+//     vec![1, 2].into_iter()
+// }
+// ```
+// ->
+// ```
+// fn numbers() -> Box<dyn Iterator<Item = u32>> {
+//     Box::new(vec![1, 2].into_iter())
+// }
+// ```
+pub(crate) fn convert_return_type(ctx: AssistCtx) -> Option<Assist> {
+    let ret_type = ctx.find_node_at_offset::<ast::RetType>()?;
+    let fn_def = ret_type.syntax().parent().and_then(ast::FnDef::cast)?;
+    let body = fn_def.body()?;
+    let type_ref = ret_type.type_ref()?;
+
+    let boxed_bounds = match &type_ref {
+        ast::TypeRef::PathType(it) => it
+            .path()
+            .and_then(|it| it.segment())
+            .filter(|it| it.name_ref().map_or(false, |it| it.text() == "Box"))
+            .and_then(|it| it.type_arg_list())
+            .and_then(|it| single(it.type_args()))
+            .and_then(|it| it.type_ref())
+            .and_then(|it| match it {
+                ast::TypeRef::DynTraitType(it) => it.type_bound_list(),
+                _ => None,
+            }),
+        _ => None,
+    };
+    let (label, new_type, sites) = match (&type_ref, boxed_bounds) {
+        (ast::TypeRef::ImplTraitType(it), _) => {
+            let new_type = format!("Box<dyn {}>", it.type_bound_list()?.syntax());
+            let sites = return_sites(&body)
+                .into_iter()
+                .filter(|it| box_new_arg(it).is_none())
+                .map(|it| (it.syntax().text_range(), format!("Box::new({})", it.syntax())))
+                .collect::<Vec<_>>();
+            (format!("Return `{}`", new_type), new_type, sites)
+        }
+        (_, Some(bounds)) => {
+            let new_type = format!("impl {}", bounds.syntax());
+            let sites = return_sites(&body)
+                .into_iter()
+                .filter_map(|it| {
+                    let arg = box_new_arg(&it)?;
+                    Some((it.syntax().text_range(), arg.syntax().to_string()))
+                })
+                .collect::<Vec<_>>();
+            (format!("Return `{}`", new_type), new_type, sites)
+        }
+        _ => return None,
+    };
+
+    let mut group = ctx.add_assist_group("Convert return type");
+    group.add_assist(
+        AssistId("convert_return_type"),
+        AssistKind::RefactorRewrite,
+        label,
+        type_ref.syntax().text_range(),
+        |edit| {
+            edit.replace(type_ref.syntax().text_range(), new_type);
+            edit.set_cursor(type_ref.syntax().text_range().start());
+            for (range, text) in sites {
+                edit.replace(range, text);
+            }
+        },
+    );
+    group.finish()
+}
+
+fn single<T>(mut iter: impl Iterator<Item = T>) -> Option<T> {
+    let first = iter.next()?;
+    if iter.next().is_some() {
+        return None;
+    }
+    Some(first)
+}
+
+/// The argument of a `Box::new(..)` call.
+fn box_new_arg(expr: &ast::Expr) -> Option<ast::Expr> {
+    let call = match expr {
+        ast::Expr::CallExpr(it) => it,
+        _ => return None,
+    };
+    match call.expr()? {
+        ast::Expr::PathExpr(it) if it.syntax().text() == "Box::new" => (),
+        _ => return None,
+    }
+    single(call.arg_list()?.args())
+}
+
+/// The expressions whose value is returned from `body`: its tail expressions
+/// and the operands of its `return`s.
+fn return_sites(body: &ast::BlockExpr) -> Vec<ast::Expr> {
+    let mut res = Vec::new();
+    tail_exprs(ast::Expr::from(body.clone()), &mut res);
+    let returns = body
+        .syntax()
+        .descendants()
+        .filter_map(ast::ReturnExpr::cast)
+        .filter(|ret| {
+            ret.syntax()
+                .ancestors()
+                .take_while(|it| it != body.syntax())
+                .all(|it| it.kind() != LAMBDA_EXPR && it.kind() != FN_DEF)
+        })
+        .filter_map(|it| it.expr());
+    res.extend(returns);
+    res
+}
+
+fn tail_exprs(expr: ast::Expr, acc: &mut Vec<ast::Expr>) {
+    match expr {
+        ast::Expr::BlockExpr(it) => {
+            if let Some(tail) = it.block().and_then(|it| it.expr()) {
+                tail_exprs(tail, acc)
+            }
+        }
+        ast::Expr::IfExpr(it) => {
+            if let Some(then_branch) = it.then_branch() {
+                tail_exprs(then_branch.into(), acc);
+            }
+            match it.else_branch() {
+                Some(ast::ElseBranch::Block(it)) => tail_exprs(it.into(), acc),
+                Some(ast::ElseBranch::IfExpr(it)) => tail_exprs(it.into(), acc),
+                None => (),
+            }
+        }
+        ast::Expr::MatchExpr(it) => {
+            for arm in it.match_arm_list().into_iter().flat_map(|it| it.arms()) {
+                if let Some(expr) = arm.expr() {
+                    tail_exprs(expr, acc);
+                }
+            }
+        }
+        ast::Expr::ReturnExpr(_) => (),
+        it => acc.push(it),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn convert_impl_trait_to_box_dyn() {
+        check_assist(
+            convert_return_type,
+            "
+trait Shape {}
+struct Circle;
+impl Shape for Circle {}
+fn shape() -> impl <|>Shape {
+    Circle
+}",
+            "
+trait Shape {}
+struct Circle;
+impl Shape for Circle {}
+fn shape() -> <|>Box<dyn Shape> {
+    Box::new(Circle)
+}",
+        );
+    }
+
+    #[test]
+    fn convert_box_dyn_to_impl_trait() {
+        check_assist(
+            convert_return_type,
+            "
+trait Shape {}
+struct Circle;
+impl Shape for Circle {}
+fn shape() -> Box<dyn <|>Shape> {
+    Box::new(Circle)
+}",
+            "
+trait Shape {}
+struct Circle;
+impl Shape for Circle {}
+fn shape() -> <|>impl Shape {
+    Circle
+}",
+        );
+    }
+
+    #[test]
+    fn convert_impl_trait_boxes_every_return_site() {
+        check_assist(
+            convert_return_type,
+            "
+trait Shape {}
+struct Circle;
+struct Square;
+fn shape(n: u32) -> impl Shape<|> {
+    if n == 0 {
+        return Box::new(Circle);
+    }
+    if n == 1 {
+        return Square;
+    }
+    match n {
+        2 => Circle,
+        _ => { Square }
+    }
+}",
+            "
+trait Shape {}
+struct Circle;
+struct Square;
+fn shape(n: u32) -> <|>Box<dyn Shape> {
+    if n == 0 {
+        return Box::new(Circle);
+    }
+    if n == 1 {
+        return Box::new(Square);
+    }
+    match n {
+        2 => Box::new(Circle),
+        _ => { Box::new(Square) }
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn convert_return_type_not_applicable_to_other_types() {
+        check_assist_not_applicable(convert_return_type, "fn f() -> Box<u32><|> { Box::new(0) }");
+    }
+}
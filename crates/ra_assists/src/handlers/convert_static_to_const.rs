@@ -0,0 +1,152 @@
+use hir::ModuleDef;
+use ra_ide_db::{defs::Definition, search::SearchScope};
+use ra_syntax::{
+    algo::find_node_at_offset,
+    ast::{self, AstNode, TypeAscriptionOwner},
+    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, T,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: convert_static_to_const
+//
+// Converts an immutable `static` item into a `const`.
+//
+// ```
+// static <|>LIMIT: usize = 92;
+// ```
+// ->
+// ```
+// const LIMIT: usize = 92;
+// ```
+pub(crate) fn convert_static_to_const(ctx: AssistCtx) -> Option<Assist> {
+    let static_def = ctx.find_node_at_offset::<ast::StaticDef>()?;
+    if in_body(&ctx, static_def.body()) || keyword(static_def.syntax(), T![mut]).is_some() {
+        return None;
+    }
+    if has_interior_mutability(&static_def.ascribed_type()?) {
+        return None;
+    }
+    let static_kw = keyword(static_def.syntax(), T![static])?;
+
+    let def = Definition::ModuleDef(ModuleDef::Static(ctx.sema.to_def(&static_def)?));
+    let usages = def.find_usages(ctx.db, Some(SearchScope::single_file(ctx.frange.file_id)));
+    let root = static_def.syntax().ancestors().last()?;
+    let address_taken = usages.iter().any(|usage| {
+        find_node_at_offset::<ast::PathExpr>(&root, usage.file_range.range.start())
+            .and_then(|it| it.syntax().parent())
+            .map_or(false, |it| ast::RefExpr::can_cast(it.kind()))
+    });
+    if address_taken {
+        return None;
+    }
+
+    ctx.add_assist(
+        AssistId("convert_static_to_const"),
+        AssistKind::RefactorRewrite,
+        "Convert to const",
+        static_def.syntax().text_range(),
+        |edit| {
+            edit.replace(static_kw.text_range(), "const");
+        },
+    )
+}
+
+// Assist: convert_const_to_static
+//
+// Converts a `const` item into a `static`.
+//
+// ```
+// const <|>LIMIT: usize = 92;
+// ```
+// ->
+// ```
+// static LIMIT: usize = 92;
+// ```
+pub(crate) fn convert_const_to_static(ctx: AssistCtx) -> Option<Assist> {
+    let const_def = ctx.find_node_at_offset::<ast::ConstDef>()?;
+    if in_body(&ctx, const_def.body()) {
+        return None;
+    }
+    let const_kw = keyword(const_def.syntax(), T![const])?;
+    ctx.add_assist(
+        AssistId("convert_const_to_static"),
+        AssistKind::RefactorRewrite,
+        "Convert to static",
+        const_def.syntax().text_range(),
+        |edit| {
+            edit.replace(const_kw.text_range(), "static");
+        },
+    )
+}
+
+fn in_body(ctx: &AssistCtx, body: Option<ast::Expr>) -> bool {
+    let offset = ctx.frange.range.start();
+    body.map_or(false, |body| {
+        let range = body.syntax().text_range();
+        range.start() < offset && offset < range.end()
+    })
+}
+
+fn keyword(node: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
+    node.children_with_tokens().filter_map(SyntaxElement::into_token).find(|it| it.kind() == kind)
+}
+
+/// A syntactic check for the common std types with interior mutability, a
+/// `const` of such a type would create a fresh copy everywhere it is used.
+fn has_interior_mutability(ty: &ast::TypeRef) -> bool {
+    ty.syntax().descendants().filter_map(ast::NameRef::cast).any(|name_ref| {
+        let name = name_ref.text().as_str();
+        name.starts_with("Atomic")
+            || ["Cell", "RefCell", "UnsafeCell", "Mutex", "RwLock", "Once"].contains(&name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn convert_static_to_const_works() {
+        check_assist(
+            convert_static_to_const,
+            r#"
+#[allow(unused)]
+pub(crate) static <|>NAMES: &[&str] = &["a", "b"];
+fn f() -> usize { NAMES.len() }"#,
+            r#"
+#[allow(unused)]
+pub(crate) const <|>NAMES: &[&str] = &["a", "b"];
+fn f() -> usize { NAMES.len() }"#,
+        );
+    }
+
+    #[test]
+    fn convert_const_to_static_works() {
+        check_assist(
+            convert_const_to_static,
+            "pub const <|>LIMIT: u32 = 1 << 10;",
+            "pub static <|>LIMIT: u32 = 1 << 10;",
+        );
+    }
+
+    #[test]
+    fn convert_static_to_const_not_applicable_if_address_is_taken() {
+        check_assist_not_applicable(
+            convert_static_to_const,
+            r#"
+static <|>COUNT: u32 = 0;
+fn f() -> &'static u32 { &COUNT }"#,
+        );
+    }
+
+    #[test]
+    fn convert_static_to_const_not_applicable_for_interior_mutability() {
+        check_assist_not_applicable(
+            convert_static_to_const,
+            "static <|>COUNT: std::sync::atomic::AtomicU32 = AtomicU32::new(0);",
+        );
+        check_assist_not_applicable(convert_static_to_const, "static mut <|>COUNT: u32 = 0;");
+    }
+}
@@ -0,0 +1,206 @@
+use hir::{Adt, ModuleDef, PathResolution, StructKind, VariantDef};
+use ra_syntax::ast::{self, ArgListOwner, AstNode};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: convert_call_to_struct_literal
+//
+// Converts a constructor call of a struct with named fields to a struct
+// literal, following the order of the fields in the declaration.
+//
+// ```
+// struct Point { x: f32, y: f32 }
+//
+// fn main() {
+//     let p = Point<|>(1.0, 2.0);
+// }
+// ```
+// ->
+// ```
+// struct Point { x: f32, y: f32 }
+//
+// fn main() {
+//     let p = Point { x: 1.0, y: 2.0 };
+// }
+// ```
+pub(crate) fn convert_call_to_struct_literal(ctx: AssistCtx) -> Option<Assist> {
+    let call = ctx.find_node_at_offset::<ast::CallExpr>()?;
+    let path = match call.expr()? {
+        ast::Expr::PathExpr(it) => it.path()?,
+        _ => return None,
+    };
+    let variant = match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(ModuleDef::Adt(Adt::Struct(it))) => VariantDef::Struct(it),
+        PathResolution::Def(ModuleDef::EnumVariant(it)) => VariantDef::EnumVariant(it),
+        _ => return None,
+    };
+    if variant.kind(ctx.db) != StructKind::Record {
+        return None;
+    }
+    let fields = variant.fields(ctx.db);
+    let args = call.arg_list()?.args().collect::<Vec<_>>();
+    if fields.is_empty() || fields.len() != args.len() {
+        return None;
+    }
+    let fields = fields
+        .iter()
+        .zip(args.iter())
+        .map(|(field, arg)| {
+            let name = field.name(ctx.db).to_string();
+            let arg = arg.syntax().to_string();
+            if arg == name {
+                name
+            } else {
+                format!("{}: {}", name, arg)
+            }
+        })
+        .collect::<Vec<_>>();
+    let text = format!("{} {{ {} }}", path.syntax(), fields.join(", "));
+
+    let range = call.syntax().text_range();
+    ctx.add_assist(
+        AssistId("convert_call_to_struct_literal"),
+        AssistKind::RefactorRewrite,
+        "Convert to struct literal",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+// Assist: convert_struct_literal_to_call
+//
+// Converts a struct literal of a tuple struct, giving all the fields in the
+// order of the declaration, to a constructor call.
+//
+// ```
+// struct Point(f32, f32);
+//
+// fn main() {
+//     let p = Point<|> { 0: 1.0, 1: 2.0 };
+// }
+// ```
+// ->
+// ```
+// struct Point(f32, f32);
+//
+// fn main() {
+//     let p = Point(1.0, 2.0);
+// }
+// ```
+pub(crate) fn convert_struct_literal_to_call(ctx: AssistCtx) -> Option<Assist> {
+    let record_lit = ctx.find_node_at_offset::<ast::RecordLit>()?;
+    let path = record_lit.path()?;
+    let field_list = record_lit.record_field_list()?;
+    if field_list.spread().is_some() {
+        return None;
+    }
+    let variant = ctx.sema.resolve_record_literal(&record_lit)?;
+    if variant.kind(ctx.db) != StructKind::Tuple {
+        return None;
+    }
+    let fields = variant.fields(ctx.db);
+    let record_fields = field_list.fields().collect::<Vec<_>>();
+    if fields.len() != record_fields.len() {
+        return None;
+    }
+    let mut args = Vec::new();
+    for (field, record_field) in fields.iter().zip(record_fields.iter()) {
+        if record_field.name_ref()?.text().as_str() != field.name(ctx.db).to_string().as_str() {
+            return None;
+        }
+        args.push(record_field.expr()?.syntax().to_string());
+    }
+    let text = format!("{}({})", path.syntax(), args.join(", "));
+
+    let range = record_lit.syntax().text_range();
+    ctx.add_assist(
+        AssistId("convert_struct_literal_to_call"),
+        AssistKind::RefactorRewrite,
+        "Convert to call",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn convert_call_of_named_struct() {
+        check_assist(
+            convert_call_to_struct_literal,
+            "
+struct Point { x: f32, y: f32 }
+fn f(y: f32) { let p = Point(<|>1.0, y); }",
+            "
+struct Point { x: f32, y: f32 }
+fn f(y: f32) { let p = <|>Point { x: 1.0, y }; }",
+        );
+    }
+
+    #[test]
+    fn convert_call_of_named_enum_variant() {
+        check_assist(
+            convert_call_to_struct_literal,
+            "
+enum Shape { Circle { radius: f32 } }
+fn f() { let s = Shape::Circle<|>(1.0); }",
+            "
+enum Shape { Circle { radius: f32 } }
+fn f() { let s = <|>Shape::Circle { radius: 1.0 }; }",
+        );
+    }
+
+    #[test]
+    fn convert_call_not_applicable_with_mismatched_arity() {
+        check_assist_not_applicable(
+            convert_call_to_struct_literal,
+            "
+struct Point { x: f32, y: f32 }
+fn f() { let p = Point<|>(1.0); }",
+        );
+        check_assist_not_applicable(
+            convert_call_to_struct_literal,
+            "
+struct Point(f32, f32);
+fn f() { let p = Point<|>(1.0, 2.0); }",
+        );
+    }
+
+    #[test]
+    fn convert_struct_literal_of_tuple_struct() {
+        check_assist(
+            convert_struct_literal_to_call,
+            "
+struct Point(f32, f32);
+fn f() { let p = Point<|> { 0: 1.0, 1: 2.0 }; }",
+            "
+struct Point(f32, f32);
+fn f() { let p = <|>Point(1.0, 2.0); }",
+        );
+    }
+
+    #[test]
+    fn convert_struct_literal_not_applicable_out_of_order() {
+        check_assist_not_applicable(
+            convert_struct_literal_to_call,
+            "
+struct Point(f32, f32);
+fn f() { let p = Point<|> { 1: 2.0, 0: 1.0 }; }",
+        );
+        check_assist_not_applicable(
+            convert_struct_literal_to_call,
+            "
+struct Point { x: f32, y: f32 }
+fn f() { let p = Point<|> { x: 1.0, y: 2.0 }; }",
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use hir::ModuleDef;
+use ra_fmt::{leading_indent, reindent};
+use ra_ide_db::{defs::Definition, search::SearchScope};
+use ra_syntax::{
+    algo::find_node_at_offset,
+    ast::{self, AstNode, NameOwner, TypeParamsOwner, VisibilityOwner},
+    SyntaxNode, TextUnit,
+};
+
+use crate::{
+    utils::{call_of, is_assoc_fn, to_camel_case},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: convert_tuple_return_to_struct
+//
+// Replaces the tuple returned by a function with a new struct, updating the
+// callers which destructure the result.
+//
+// ```
+// fn min_max(xs: &[u32]) -> (u32<|>, u32) {
+//     (xs[0], xs[xs.len() - 1])
+// }
+//
+// fn main() {
+//     let (min, max) = min_max(&[1, 2]);
+// }
+// ```
+// ->
+// ```
+// struct MinMaxResult {
+//     f0: u32,
+//     f1: u32,
+// }
+//
+// fn min_max(xs: &[u32]) -> MinMaxResult {
+//     MinMaxResult { f0: xs[0], f1: xs[xs.len() - 1] }
+// }
+//
+// fn main() {
+//     let MinMaxResult { f0: min, f1: max } = min_max(&[1, 2]);
+// }
+// ```
+pub(crate) fn convert_tuple_return_to_struct(ctx: AssistCtx) -> Option<Assist> {
+    let fn_def = ctx.find_node_at_offset::<ast::FnDef>()?;
+    let ret_type = fn_def.ret_type()?;
+    if !ret_type.syntax().text_range().contains_inclusive(ctx.frange.range.start())
+        || fn_def.type_param_list().is_some()
+        || is_assoc_fn(&fn_def)
+    {
+        return None;
+    }
+    let field_types = match ret_type.type_ref()? {
+        ast::TypeRef::TupleType(it) => it.fields().collect::<Vec<_>>(),
+        _ => return None,
+    };
+    // The struct would need lifetime parameters for the references.
+    let has_references = field_types
+        .iter()
+        .any(|ty| ty.syntax().descendants().any(|it| ast::ReferenceType::can_cast(it.kind())));
+    if field_types.len() < 2 || has_references {
+        return None;
+    }
+    let fn_name = fn_def.name()?;
+    let struct_name = format!("{}Result", to_camel_case(fn_name.text()));
+    let scope = fn_def.syntax().parent()?;
+    if scope
+        .children()
+        .filter_map(ast::StructDef::cast)
+        .any(|it| it.name().map_or(false, |it| it.text().as_str() == struct_name.as_str()))
+    {
+        return None;
+    }
+
+    let body = fn_def.body()?;
+    let returned = returned_tuples(&body, field_types.len())?;
+
+    let function = ctx.sema.to_def(&fn_def)?;
+    let def = Definition::ModuleDef(ModuleDef::Function(function));
+    let usages = def.find_usages(ctx.db, Some(SearchScope::single_file(ctx.frange.file_id)));
+    let root = fn_def.syntax().ancestors().last()?;
+    let mut call_site_edits = Vec::new();
+    for usage in usages {
+        let name_ref = find_node_at_offset::<ast::NameRef>(&root, usage.file_range.range.start())?;
+        let call = match call_of(&name_ref)? {
+            ast::Expr::CallExpr(it) => it,
+            _ => return None,
+        };
+        call_site_edits.push(call_site_edit(&call, field_types.len())?);
+    }
+
+    let visibility = fn_def.visibility().map(|it| format!("{} ", it.syntax())).unwrap_or_default();
+    let fields = field_types
+        .iter()
+        .enumerate()
+        .map(|(idx, ty)| format!("    {}f{}: {},\n", visibility, idx, ty.syntax()))
+        .collect::<String>();
+    let struct_def = format!("{}struct {} {{\n{}}}", visibility, struct_name, fields);
+
+    ctx.add_assist(
+        AssistId("convert_tuple_return_to_struct"),
+        AssistKind::RefactorRewrite,
+        "Convert tuple return type to a struct",
+        ret_type.syntax().text_range(),
+        |edit| {
+            let indent = leading_indent(fn_def.syntax()).unwrap_or_default();
+            let fn_start = fn_def.syntax().text_range().start();
+            let name_offset = struct_def.find("struct ").unwrap() + "struct ".len();
+            edit.set_cursor(fn_start + TextUnit::from_usize(name_offset));
+            edit.insert(fn_start, format!("{}\n\n{}", reindent(&struct_def, &indent), indent));
+            if let Some(ty) = ret_type.type_ref() {
+                edit.replace(ty.syntax().text_range(), struct_name.clone());
+            }
+            for tuple in returned {
+                let fields = tuple
+                    .exprs()
+                    .enumerate()
+                    .map(|(idx, expr)| format!("f{}: {}", idx, expr.syntax()))
+                    .collect::<Vec<_>>();
+                let text = format!("{} {{ {} }}", struct_name, fields.join(", "));
+                edit.replace(tuple.syntax().text_range(), text);
+            }
+            for edit_at_call_site in call_site_edits {
+                match edit_at_call_site {
+                    CallSiteEdit::Destructure(tuple_pat) => {
+                        let fields = tuple_pat
+                            .args()
+                            .enumerate()
+                            .map(|(idx, pat)| format!("f{}: {}", idx, pat.syntax()))
+                            .collect::<Vec<_>>();
+                        let text = format!("{} {{ {} }}", struct_name, fields.join(", "));
+                        edit.replace(tuple_pat.syntax().text_range(), text);
+                    }
+                    CallSiteEdit::Field(name_ref) => edit
+                        .replace(name_ref.syntax().text_range(), format!("f{}", name_ref.text())),
+                }
+            }
+        },
+    )
+}
+
+/// How a call site consuming the returned tuple is updated.
+enum CallSiteEdit {
+    /// `let (a, b) = f();`
+    Destructure(ast::TuplePat),
+    /// `f().0`
+    Field(ast::NameRef),
+}
+
+/// Call sites which use the tuple as a whole can't be updated, so return
+/// `None` for them.
+fn call_site_edit(call: &ast::CallExpr, arity: usize) -> Option<CallSiteEdit> {
+    let parent = call.syntax().parent()?;
+    if let Some(let_stmt) = ast::LetStmt::cast(parent.clone()) {
+        let tuple_pat = match let_stmt.pat()? {
+            ast::Pat::TuplePat(it) => it,
+            _ => return None,
+        };
+        let args = tuple_pat.args().collect::<Vec<_>>();
+        if args.len() != arity || args.iter().any(|it| ast::DotDotPat::can_cast(it.syntax().kind()))
+        {
+            return None;
+        }
+        return Some(CallSiteEdit::Destructure(tuple_pat));
+    }
+    let name_ref = ast::FieldExpr::cast(parent)?.name_ref()?;
+    let idx = name_ref.text().parse::<usize>().ok()?;
+    if idx < arity {
+        Some(CallSiteEdit::Field(name_ref))
+    } else {
+        None
+    }
+}
+
+/// Collects the tuple expressions returned by the tail expression and the
+/// `return`s of the `body`, or `None` if some other expression is returned.
+fn returned_tuples(body: &ast::BlockExpr, arity: usize) -> Option<Vec<ast::TupleExpr>> {
+    let mut res = Vec::new();
+    let returns = body
+        .syntax()
+        .descendants()
+        .filter_map(ast::ReturnExpr::cast)
+        .filter(|it| !in_nested_fn(it.syntax(), body.syntax()));
+    for ret in returns {
+        res.push(tuple_expr(ret.expr()?, arity)?);
+    }
+    match body.block()?.expr() {
+        Some(ast::Expr::ReturnExpr(_)) => (),
+        Some(tail) => res.push(tuple_expr(tail, arity)?),
+        None if res.is_empty() => return None,
+        None => (),
+    }
+    Some(res)
+}
+
+fn tuple_expr(expr: ast::Expr, arity: usize) -> Option<ast::TupleExpr> {
+    match expr {
+        ast::Expr::TupleExpr(it) if it.exprs().count() == arity => Some(it),
+        _ => None,
+    }
+}
+
+fn in_nested_fn(node: &SyntaxNode, body: &SyntaxNode) -> bool {
+    node.ancestors()
+        .take_while(|it| it != body)
+        .any(|it| ast::LambdaExpr::can_cast(it.kind()) || ast::FnDef::can_cast(it.kind()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn convert_tuple_return_with_destructuring_call_site() {
+        check_assist(
+            convert_tuple_return_to_struct,
+            r#"
+mod parse {
+    pub fn split_once(s: &str) -> (String, <|>Option<String>) {
+        if s.is_empty() {
+            return (String::new(), None);
+        }
+        (s[..1].to_string(), Some(s[1..].to_string()))
+    }
+
+    fn f() {
+        let (head, _) = split_once("ab");
+    }
+}"#,
+            r#"
+mod parse {
+    pub struct <|>SplitOnceResult {
+        pub f0: String,
+        pub f1: Option<String>,
+    }
+
+    pub fn split_once(s: &str) -> SplitOnceResult {
+        if s.is_empty() {
+            return SplitOnceResult { f0: String::new(), f1: None };
+        }
+        SplitOnceResult { f0: s[..1].to_string(), f1: Some(s[1..].to_string()) }
+    }
+
+    fn f() {
+        let SplitOnceResult { f0: head, f1: _ } = split_once("ab");
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn convert_tuple_return_with_field_access() {
+        check_assist(
+            convert_tuple_return_to_struct,
+            r#"
+fn pair() -> <|>(u32, bool) { (1, true) }
+fn f() -> bool { pair().1 }"#,
+            r#"
+struct <|>PairResult {
+    f0: u32,
+    f1: bool,
+}
+
+fn pair() -> PairResult { PairResult { f0: 1, f1: true } }
+fn f() -> bool { pair().f1 }"#,
+        );
+    }
+
+    #[test]
+    fn convert_tuple_return_not_applicable_to_opaque_use() {
+        check_assist_not_applicable(
+            convert_tuple_return_to_struct,
+            r#"
+fn pair() -> <|>(u32, bool) { (1, true) }
+fn f() { let p = pair(); consume(p); }"#,
+        );
+        check_assist_not_applicable(
+            convert_tuple_return_to_struct,
+            "fn pair(p: (u32, bool)) -> <|>(u32, bool) { p }",
+        );
+        check_assist_not_applicable(
+            convert_tuple_return_to_struct,
+            "fn split(s: &str) -> <|>(&str, &str) { (s, s) }",
+        );
+    }
+}
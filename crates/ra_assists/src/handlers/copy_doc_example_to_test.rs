@@ -0,0 +1,374 @@
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, AstToken, AttrsOwner, ModuleItemOwner, NameOwner},
+    SyntaxKind::COMMENT,
+    SyntaxNode,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+/// The attributes of a fenced code block which rustdoc tests as Rust code.
+const RUST_CODE_BLOCK_ATTRS: &[&str] =
+    &["", "rust", "ignore", "should_panic", "no_run", "compile_fail", "allow_fail"];
+
+// Assist: copy_doc_example_to_test
+//
+// Copies the example of a doc comment to a test of the `tests` module, creating
+// it if needed.
+//
+// ```
+// /// Doubles a number.
+// ///
+// /// ~~~
+// /// # use crate::double;
+// /// assert_eq!(double(2<|>), 4);
+// /// ~~~
+// fn double(x: u32) -> u32 { x * 2 }
+// ```
+// ->
+// ```
+// /// Doubles a number.
+// ///
+// /// ~~~
+// /// # use crate::double;
+// /// assert_eq!(double(2), 4);
+// /// ~~~
+// fn double(x: u32) -> u32 { x * 2 }
+//
+// #[cfg(test)]
+// mod tests {
+//     use super::*;
+//
+//     #[test]
+//     fn doc_example_double() {
+//         use crate::double;
+//         assert_eq!(double(2), 4);
+//     }
+// }
+// ```
+pub(crate) fn copy_doc_example_to_test(ctx: AssistCtx) -> Option<Assist> {
+    let token = ctx.find_token_at_offset(COMMENT)?;
+    let item = token.parent();
+    let name = item_name(&item)?;
+    let comments = item
+        .children_with_tokens()
+        .filter_map(|it| it.into_token().and_then(ast::Comment::cast))
+        .filter(|it| {
+            let kind = it.kind();
+            kind.shape.is_line() && kind.doc == Some(ast::CommentPlacement::Outer)
+        })
+        .collect::<Vec<_>>();
+    let cursor_line = comments.iter().position(|it| it.syntax() == &token)?;
+    let lines = comments
+        .iter()
+        .map(|it| {
+            let text = &it.text()[it.prefix().len()..];
+            if text.starts_with(' ') {
+                text[1..].to_string()
+            } else {
+                text.to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    let example = example_at(&lines, cursor_line)?;
+
+    let parent = item.parent()?;
+    let items = if let Some(file) = ast::SourceFile::cast(parent.clone()) {
+        file.items().map(|it| it.syntax().clone()).collect::<Vec<_>>()
+    } else {
+        ast::ItemList::cast(parent.clone())?.items().map(|it| it.syntax().clone()).collect()
+    };
+    let tests_module = items.iter().filter_map(|it| ast::Module::cast(it.clone())).find(|it| {
+        it.name().map_or(false, |name| name.text() == "tests")
+            && it.attrs().any(|attr| attr.syntax().text() == "#[cfg(test)]")
+    });
+    let fn_name = format!("doc_example_{}", to_snake_case(name.text()));
+
+    ctx.add_assist(
+        AssistId("copy_doc_example_to_test"),
+        AssistKind::Refactor,
+        "Copy example to test",
+        token.text_range(),
+        |edit| match tests_module.as_ref().and_then(|it| it.item_list()) {
+            Some(item_list) => {
+                let module_indent =
+                    leading_indent(tests_module.as_ref().unwrap().syntax()).unwrap_or_default();
+                let indent = format!("{}    ", module_indent);
+                let test = test_fn(&fn_name, &example, &indent);
+                let l_curly = item_list.syntax().first_token().unwrap().text_range().end();
+                let has_use_super = item_list.items().any(|it| match it {
+                    ast::ModuleItem::UseItem(it) => it.syntax().text() == "use super::*;",
+                    _ => false,
+                });
+                if !has_use_super {
+                    edit.insert(l_curly, format!("\n{}use super::*;\n", indent));
+                }
+                match item_list.items().last() {
+                    Some(last) => edit.insert(
+                        last.syntax().text_range().end(),
+                        format!("\n\n{}{}", indent, test),
+                    ),
+                    None => {
+                        let r_curly = item_list.syntax().last_token().unwrap().text_range().start();
+                        let prefix = if has_use_super { "\n" } else { "" };
+                        edit.insert(
+                            r_curly,
+                            format!("{}\n{}{}\n{}", prefix, indent, test, module_indent),
+                        );
+                    }
+                }
+            }
+            None => {
+                let indent = leading_indent(&item).unwrap_or_default();
+                let end = items.last().map_or(item.text_range().end(), |it| it.text_range().end());
+                let test = test_fn(&fn_name, &example, &format!("{}    ", indent));
+                let mut buf = format!("\n\n{i}#[cfg(test)]\n{i}mod tests {{\n", i = indent);
+                buf.push_str(&format!(
+                    "{i}    use super::*;\n\n{i}    {}\n{i}}}",
+                    test,
+                    i = indent
+                ));
+                edit.insert(end, buf);
+            }
+        },
+    )
+}
+
+/// The name of a function or a type, whose doc examples can be copied.
+fn item_name(item: &SyntaxNode) -> Option<ast::Name> {
+    if let Some(it) = ast::FnDef::cast(item.clone()) {
+        return it.name();
+    }
+    ast::NominalDef::cast(item.clone())?.name()
+}
+
+/// A Rust example of a doc comment, made of its lines.
+struct Example {
+    lines: Vec<String>,
+    should_panic: bool,
+}
+
+/// The Rust code block of the doc comment `lines` which contains the line
+/// `cursor_line`, without its hidden line markers.
+fn example_at(lines: &[String], cursor_line: usize) -> Option<Example> {
+    // The line index, the fence character and the info string of the open
+    // code block.
+    let mut open: Option<(usize, char, &str)> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        let fence = if line.starts_with("```") {
+            '`'
+        } else if line.starts_with("~~~") {
+            '~'
+        } else {
+            continue;
+        };
+        match open {
+            None => open = Some((idx, fence, line.trim_start_matches(fence))),
+            Some((_, open_fence, _)) if fence != open_fence => (),
+            Some((start, _, info)) => {
+                open = None;
+                if cursor_line < start || idx < cursor_line {
+                    continue;
+                }
+                let attrs = info.split(',').map(|it| it.trim()).collect::<Vec<_>>();
+                let is_rust = attrs
+                    .iter()
+                    .all(|it| RUST_CODE_BLOCK_ATTRS.contains(it) || it.starts_with("edition"));
+                if !is_rust {
+                    return None;
+                }
+                let lines = lines[start + 1..idx].iter().map(|it| unhide(it)).collect();
+                return Some(Example { lines, should_panic: attrs.contains(&"should_panic") });
+            }
+        }
+    }
+    None
+}
+
+/// A line of a code block as it's compiled: `# ` hides a line of the rendered
+/// example, and `##` escapes a `#`.
+fn unhide(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if trimmed == "#" {
+        String::new()
+    } else if trimmed.starts_with("# ") {
+        trimmed[2..].to_string()
+    } else if trimmed.starts_with("##") {
+        format!("{}{}", &line[..line.len() - trimmed.len()], &trimmed[1..])
+    } else {
+        line.to_string()
+    }
+}
+
+fn test_fn(name: &str, example: &Example, indent: &str) -> String {
+    let mut buf = String::from("#[test]\n");
+    if example.should_panic {
+        buf.push_str(&format!("{}#[should_panic]\n", indent));
+    }
+    buf.push_str(&format!("{}fn {}() {{\n", indent, name));
+    for line in &example.lines {
+        if line.is_empty() {
+            buf.push('\n');
+        } else {
+            buf.push_str(&format!("{}    {}\n", indent, line));
+        }
+    }
+    buf.push_str(&format!("{}}}", indent));
+    buf
+}
+
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut res = String::new();
+    for (idx, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && idx > 0 {
+            let prev = chars[idx - 1];
+            let next_is_lowercase = chars.get(idx + 1).map_or(false, |it| it.is_lowercase());
+            // `HTTPServer` is `http_server`.
+            if prev.is_lowercase() || prev.is_ascii_digit() || next_is_lowercase {
+                res.push('_');
+            }
+        }
+        res.extend(c.to_lowercase());
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn copy_simple_example() {
+        check_assist(
+            copy_doc_example_to_test,
+            "
+/// ```
+/// let p = PointXY<|>::new();
+/// ```
+struct PointXY;
+
+fn f() {}",
+            "
+/// ```
+/// let p = PointXY<|>::new();
+/// ```
+struct PointXY;
+
+fn f() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doc_example_point_xy() {
+        let p = PointXY::new();
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn copy_example_without_hidden_markers() {
+        check_assist(
+            copy_doc_example_to_test,
+            "
+/// ```should_panic
+/// # fn setup() {}
+/// #
+/// setup();<|>
+/// let s = \"##x\";
+/// ## attr
+/// panic!();
+/// ```
+fn run() {}",
+            "
+/// ```should_panic
+/// # fn setup() {}
+/// #
+/// setup();<|>
+/// let s = \"##x\";
+/// ## attr
+/// panic!();
+/// ```
+fn run() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn doc_example_run() {
+        fn setup() {}
+
+        setup();
+        let s = \"##x\";
+        # attr
+        panic!();
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn copy_example_into_existing_tests_module() {
+        check_assist(
+            copy_doc_example_to_test,
+            "
+/// ```ignore
+/// assert!(run()<|>);
+/// ```
+fn run() -> bool { true }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn other() {}
+}",
+            "
+/// ```ignore
+/// assert!(run()<|>);
+/// ```
+fn run() -> bool { true }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn other() {}
+
+    #[test]
+    fn doc_example_run() {
+        assert!(run());
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn copy_not_applicable_to_text_block() {
+        check_assist_not_applicable(
+            copy_doc_example_to_test,
+            "
+/// ```text
+/// run()<|>
+/// ```
+fn run() {}",
+        );
+        check_assist_not_applicable(
+            copy_doc_example_to_test,
+            "
+/// Runs<|>.
+///
+/// ```
+/// run()
+/// ```
+fn run() {}",
+        );
+    }
+}
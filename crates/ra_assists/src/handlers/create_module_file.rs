@@ -0,0 +1,143 @@
+use ra_db::{RelativePathBuf, SourceDatabaseExt};
+use ra_syntax::ast::{self, AstNode, AttrsOwner, NameOwner};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: create_module_file
+//
+// Creates the file of an unresolved `mod foo;` declaration, either as
+// `foo.rs` or as `foo/mod.rs`.
+//
+// ```
+// mod <|>foo;
+// ```
+// ->
+// ```
+// mod foo;
+// ```
+pub(crate) fn create_module_file(ctx: AssistCtx) -> Option<Assist> {
+    let module = ctx.find_node_at_offset::<ast::Module>()?;
+    let has_path_attr = module.attrs().any(|it| it.simple_name().map_or(false, |it| it == "path"));
+    if module.item_list().is_some() || has_path_attr {
+        return None;
+    }
+    let name = module.name()?;
+    // A declaration whose file exists resolves to a module.
+    if ctx.sema.to_def(&module).is_some() {
+        return None;
+    }
+
+    let file_id = ctx.frange.file_id;
+    let path = ctx.db.file_relative_path(file_id);
+    let parent = path.parent()?;
+    // The files of the submodules of a crate root or of a `mod.rs` are in its
+    // directory, the ones of other modules in a directory named after them.
+    let is_crate_root = ctx.sema.to_module_def(file_id)?.parent(ctx.db).is_none();
+    let dir = match path.file_stem()? {
+        "mod" => parent.to_relative_path_buf(),
+        _ if is_crate_root => parent.to_relative_path_buf(),
+        stem => parent.join(stem),
+    };
+    let source_root = ctx.db.file_source_root(file_id);
+    let target = module.syntax().text_range();
+    let name = name.text().to_string();
+    let candidates: Vec<(String, RelativePathBuf)> = vec![
+        (format!("{}.rs", name), dir.join(format!("{}.rs", name))),
+        (format!("{}/mod.rs", name), dir.join(name.as_str()).join("mod.rs")),
+    ];
+
+    let mut group = ctx.add_assist_group("Create module file");
+    for (label, path) in candidates {
+        group.add_assist(
+            AssistId("create_module_file"),
+            AssistKind::QuickFix,
+            format!("Create `{}`", label),
+            target,
+            |edit| edit.create_file(source_root, path),
+        );
+    }
+    group.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{
+        check_assist_multi_file, check_assist_multi_file_entry,
+        check_assist_multi_file_not_applicable, check_assist_not_applicable,
+    };
+
+    #[test]
+    fn create_file_next_to_crate_root() {
+        check_assist_multi_file(
+            create_module_file,
+            r#"
+//- /main.rs
+mod <|>foo;
+fn main() {}
+"#,
+            r#"
+//- /main.rs
+mod foo;
+fn main() {}
+//- /foo.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn create_mod_rs_in_directory_of_module() {
+        check_assist_multi_file_entry(
+            create_module_file,
+            "Create `baz/mod.rs`",
+            r#"
+//- /main.rs
+mod bar;
+//- /bar.rs
+mod <|>baz;
+"#,
+            r#"
+//- /bar.rs
+mod baz;
+//- /bar/baz/mod.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn create_file_next_to_crate_root_not_named_main() {
+        check_assist_multi_file(
+            create_module_file,
+            r#"
+//- /bin/tool.rs crate:tool
+mod <|>cli;
+fn main() {}
+"#,
+            r#"
+//- /bin/tool.rs
+mod cli;
+fn main() {}
+//- /bin/cli.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_resolved_module() {
+        check_assist_multi_file_not_applicable(
+            create_module_file,
+            r#"
+//- /main.rs
+mod foo<|>;
+//- /foo.rs
+struct S;
+"#,
+        );
+        check_assist_not_applicable(
+            create_module_file,
+            r#"
+mod <|>foo {}
+"#,
+        );
+    }
+}
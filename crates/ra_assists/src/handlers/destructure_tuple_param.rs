@@ -0,0 +1,232 @@
+use ra_ide_db::{
+    defs::{classify_name, Definition},
+    search::SearchScope,
+};
+use ra_syntax::{
+    algo::find_node_at_offset,
+    ast::{self, AstNode, NameOwner},
+    SyntaxKind::{NAME, NAME_REF},
+    TextRange,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: destructure_tuple_param
+//
+// Destructures a tuple parameter of a closure or a function which is only
+// used through its fields.
+//
+// ```
+// fn main() {
+//     let sum = |pair<|>: (u32, u32)| pair.0 + pair.1;
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let sum = |(a, b): (u32, u32)| a + b;
+// }
+// ```
+pub(crate) fn destructure_tuple_param(ctx: AssistCtx) -> Option<Assist> {
+    let bind_pat = ctx.find_node_at_offset::<ast::BindPat>()?;
+    let param = ast::Param::cast(bind_pat.syntax().parent()?)?;
+    if bind_pat.is_mutable() || bind_pat.is_ref() || bind_pat.pat().is_some() {
+        return None;
+    }
+    let name = bind_pat.name()?;
+    let def = classify_name(&ctx.sema, &name)?.definition();
+    let local = match &def {
+        Definition::Local(it) => *it,
+        _ => return None,
+    };
+    let ty = local.ty(ctx.db);
+    let reference = ty.remove_ref();
+    let fields = reference.as_ref().unwrap_or(&ty).tuple_fields(ctx.db);
+    if fields.is_empty() {
+        return None;
+    }
+    // Binding the copied elements keeps the uses as they are, while the
+    // other elements are bound by reference and dereferenced where needed.
+    let copies = reference.is_some()
+        && !ty.is_mutable_reference()
+        && fields.iter().all(|it| it.impls_copy(ctx.db));
+    let by_ref = reference.is_some() && !copies;
+
+    let owner = param.syntax().parent()?.parent()?;
+    let taken = owner
+        .descendants()
+        .filter(|it| it.kind() == NAME || it.kind() == NAME_REF)
+        .map(|it| it.text().to_string())
+        .collect::<Vec<_>>();
+    let names = binding_names(fields.len(), &taken);
+
+    let root = param.syntax().ancestors().last()?;
+    let mut edits = Vec::new();
+    for usage in def.find_usages(ctx.db, Some(SearchScope::single_file(ctx.frange.file_id))) {
+        let name_ref = find_node_at_offset::<ast::NameRef>(&root, usage.file_range.range.start())?;
+        let path_expr = ast::PathExpr::cast(name_ref.syntax().parent()?.parent()?)?;
+        // Any use of the whole tuple needs the parameter.
+        let field_expr = ast::FieldExpr::cast(path_expr.syntax().parent()?)?;
+        let idx = match field_expr.field_access()? {
+            ast::FieldKind::Name(it) => it.text().parse::<usize>().ok()?,
+            ast::FieldKind::Index(it) => it.text().parse::<usize>().ok()?,
+        };
+        let name = names.get(idx)?;
+        edits.push(if by_ref {
+            use_by_ref(&field_expr, name, ty.is_mutable_reference())
+        } else {
+            (field_expr.syntax().text_range(), name.clone())
+        });
+    }
+
+    let pat_range = bind_pat.syntax().text_range();
+    let pat = format!("{}({})", if copies { "&" } else { "" }, names.join(", "));
+    ctx.add_assist(
+        AssistId("destructure_tuple_param"),
+        AssistKind::RefactorRewrite,
+        "Destructure parameter",
+        pat_range,
+        |edit| {
+            edit.replace(pat_range, pat);
+            for (range, text) in edits {
+                edit.replace(range, text);
+            }
+            edit.set_cursor(pat_range.start());
+        },
+    )
+}
+
+/// `a`, `b`, `c`... skipping the names the closure or function already uses.
+fn binding_names(count: usize, taken: &[String]) -> Vec<String> {
+    (b'a'..=b'z')
+        .map(|it| (it as char).to_string())
+        .chain((0..).map(|it| format!("e{}", it)))
+        .filter(|it| !taken.contains(it))
+        .take(count)
+        .collect()
+}
+
+/// Rewrites a field access to the element bound by reference as `name`.
+fn use_by_ref(field_expr: &ast::FieldExpr, name: &str, is_mut: bool) -> (TextRange, String) {
+    let range = field_expr.syntax().text_range();
+    let parent = match field_expr.syntax().parent() {
+        Some(it) => it,
+        None => return (range, format!("*{}", name)),
+    };
+    if let Some(ref_expr) = ast::RefExpr::cast(parent.clone()) {
+        if ref_expr.is_mut() == is_mut {
+            return (ref_expr.syntax().text_range(), name.to_string());
+        }
+    }
+    // Method calls, field accesses and indexing dereference their receiver.
+    let is_receiver = match ast::Expr::cast(parent) {
+        Some(ast::Expr::MethodCallExpr(it)) => it.expr().map(|it| it.syntax().text_range()),
+        Some(ast::Expr::FieldExpr(it)) => it.expr().map(|it| it.syntax().text_range()),
+        Some(ast::Expr::IndexExpr(it)) => it.base().map(|it| it.syntax().text_range()),
+        _ => None,
+    } == Some(range);
+    (range, if is_receiver { name.to_string() } else { format!("*{}", name) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn destructure_closure_param_in_map() {
+        check_assist(
+            destructure_tuple_param,
+            "
+fn f(pairs: Vec<(u32, String)>) {
+    pairs.into_iter().map(|pa<|>ir: (u32, String)| pair.1.len() as u32 + pair.0);
+}",
+            "
+fn f(pairs: Vec<(u32, String)>) {
+    pairs.into_iter().map(|<|>(a, b): (u32, String)| b.len() as u32 + a);
+}",
+        );
+    }
+
+    #[test]
+    fn destructure_fn_param_skips_taken_names() {
+        check_assist(
+            destructure_tuple_param,
+            "
+fn f(<|>pair: (u32, u32, u32)) -> u32 {
+    let a = pair.2;
+    a + pair.0
+}",
+            "
+fn f(<|>(b, c, d): (u32, u32, u32)) -> u32 {
+    let a = d;
+    a + b
+}",
+        );
+    }
+
+    #[test]
+    fn destructure_not_applicable_to_bare_use() {
+        check_assist_not_applicable(
+            destructure_tuple_param,
+            "
+fn g(pair: (u32, u32)) {}
+
+fn f() {
+    let h = |pair<|>: (u32, u32)| { g(pair); pair.0 };
+}",
+        );
+    }
+
+    #[test]
+    fn destructure_reference_to_copy_elements() {
+        check_assist(
+            destructure_tuple_param,
+            r#"
+#[lang = "copy"]
+trait Copy {}
+impl Copy for u32 {}
+
+fn f() {
+    let h = |pair<|>: &(u32, u32)| pair.0 + pair.1;
+}"#,
+            r#"
+#[lang = "copy"]
+trait Copy {}
+impl Copy for u32 {}
+
+fn f() {
+    let h = |<|>&(a, b): &(u32, u32)| a + b;
+}"#,
+        );
+    }
+
+    #[test]
+    fn destructure_reference_to_other_elements() {
+        check_assist(
+            destructure_tuple_param,
+            r#"
+#[lang = "copy"]
+trait Copy {}
+impl Copy for u32 {}
+
+fn f() {
+    let h = |pair<|>: &(String, u32)| {
+        g(&pair.0);
+        pair.0.len() as u32 + pair.1
+    };
+}"#,
+            r#"
+#[lang = "copy"]
+trait Copy {}
+impl Copy for u32 {}
+
+fn f() {
+    let h = |<|>(a, b): &(String, u32)| {
+        g(a);
+        a.len() as u32 + *b
+    };
+}"#,
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner},
+    Direction, SyntaxElement,
+    SyntaxKind::{
+        CONST_DEF, FN_DEF, MATCH_ARM, RECORD_FIELD_DEF, STRUCT_DEF, TYPE_ALIAS_DEF, WHITESPACE,
+    },
+    SyntaxNode, SyntaxToken, TextUnit, T,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: duplicate_item
+//
+// Inserts a copy of the item, match arm or field under the cursor right after
+// it. A copied item gets a new name.
+//
+// ```
+// fn frobnicate<|>() {}
+// ```
+// ->
+// ```
+// fn frobnicate() {}
+//
+// fn frobnicate_copy() {}
+// ```
+pub(crate) fn duplicate_item(ctx: AssistCtx) -> Option<Assist> {
+    let node =
+        ctx.token_at_offset().right_biased()?.parent().ancestors().find(|it| match it.kind() {
+            FN_DEF | CONST_DEF | TYPE_ALIAS_DEF | STRUCT_DEF | MATCH_ARM | RECORD_FIELD_DEF => true,
+            _ => false,
+        })?;
+    // Only the header of an item with a body selects it.
+    if let Some(body) = ast::FnDef::cast(node.clone()).and_then(|it| it.body()) {
+        if body.syntax().text_range().is_subrange(&ctx.frange.range)
+            || ctx.frange.range.is_subrange(&body.syntax().text_range())
+        {
+            return None;
+        }
+    }
+
+    let indent = leading_indent(&node);
+    let on_own_line = indent.is_some() || node.first_token()?.prev_token().is_none();
+    let indent = indent.unwrap_or_default();
+    let node_start = node.text_range().start();
+    let mut copy = node.to_string();
+
+    let (offset, mut prefix, suffix, cursor_in_copy) = match node.kind() {
+        MATCH_ARM | RECORD_FIELD_DEF => {
+            let separator = if on_own_line { format!("\n{}", indent) } else { " ".to_string() };
+            // The pattern of the arm or the name of the field is to be edited.
+            let focus = if node.kind() == MATCH_ARM {
+                ast::MatchArm::cast(node.clone())?.pat()?.syntax().text_range().start()
+            } else {
+                ast::RecordFieldDef::cast(node.clone())?.name()?.syntax().text_range().start()
+            };
+            let cursor_in_copy = focus - node_start;
+            match trailing_comma(&node) {
+                Some(comma) => (comma.text_range().end(), separator, ",", cursor_in_copy),
+                None if needs_comma(&node) => {
+                    (node.text_range().end(), format!(",{}", separator), "", cursor_in_copy)
+                }
+                None => (node.text_range().end(), separator, "", cursor_in_copy),
+            }
+        }
+        _ => {
+            let name = node.children().find_map(ast::Name::cast)?;
+            let new_name = unused_name(&node, &name);
+            let name_range = name.syntax().text_range().checked_sub(node_start)?;
+            copy.replace_range(
+                name_range.start().to_usize()..name_range.end().to_usize(),
+                &new_name,
+            );
+            let separator = if on_own_line { format!("\n\n{}", indent) } else { " ".to_string() };
+            (node.text_range().end(), separator, "", name_range.start())
+        }
+    };
+
+    ctx.add_assist(
+        AssistId("duplicate_item"),
+        AssistKind::Refactor,
+        "Duplicate",
+        node.text_range(),
+        |edit| {
+            edit.set_cursor(offset + TextUnit::of_str(&prefix) + cursor_in_copy);
+            prefix.push_str(&copy);
+            prefix.push_str(suffix);
+            edit.insert(offset, prefix);
+        },
+    )
+}
+
+fn trailing_comma(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .find(|it| it.kind() != WHITESPACE)
+        .and_then(SyntaxElement::into_token)
+        .filter(|it| it.kind() == T![,])
+}
+
+/// A match arm with a block body can do without a comma.
+fn needs_comma(node: &SyntaxNode) -> bool {
+    match ast::MatchArm::cast(node.clone()) {
+        Some(arm) => arm.expr().map_or(true, |it| !it.is_block_like()),
+        None => true,
+    }
+}
+
+/// `foo_copy`, or `foo_copy2` and so on if an item next to `node` already has
+/// that name.
+fn unused_name(node: &SyntaxNode, name: &ast::Name) -> String {
+    let taken = node
+        .parent()
+        .into_iter()
+        .flat_map(|it| it.children())
+        .filter_map(|it| it.children().find_map(ast::Name::cast))
+        .map(|it| it.text().to_string())
+        .collect::<Vec<_>>();
+    let base = format!("{}_copy", name.text());
+    let mut res = base.clone();
+    let mut idx = 2;
+    while taken.contains(&res) {
+        res = format!("{}{}", base, idx);
+        idx += 1;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn duplicate_fn_with_attrs_and_docs() {
+        check_assist(
+            duplicate_item,
+            "
+/// Does things.
+#[inline]
+fn fo<|>o(x: u32) -> u32 {
+    x
+}
+",
+            "
+/// Does things.
+#[inline]
+fn foo(x: u32) -> u32 {
+    x
+}
+
+/// Does things.
+#[inline]
+fn <|>foo_copy(x: u32) -> u32 {
+    x
+}
+",
+        );
+    }
+
+    #[test]
+    fn duplicate_impl_item_with_a_free_name() {
+        check_assist(
+            duplicate_item,
+            "
+impl S {
+    fn foo_copy() {}
+
+    fn <|>foo() {}
+}",
+            "
+impl S {
+    fn foo_copy() {}
+
+    fn foo() {}
+
+    fn <|>foo_copy2() {}
+}",
+        );
+        check_assist(
+            duplicate_item,
+            "trait T { type It<|>em; }",
+            "trait T { type Item; type <|>Item_copy; }",
+        );
+    }
+
+    #[test]
+    fn duplicate_struct() {
+        check_assist(
+            duplicate_item,
+            "struct P<|>oint { x: u32 }",
+            "struct Point { x: u32 }\n\nstruct <|>Point_copy { x: u32 }",
+        );
+    }
+
+    #[test]
+    fn duplicate_match_arm() {
+        check_assist(
+            duplicate_item,
+            "
+fn f(x: u32) {
+    match x {
+        0 => fo<|>o(),
+        _ => {}
+    }
+}",
+            "
+fn f(x: u32) {
+    match x {
+        0 => foo(),
+        <|>0 => foo(),
+        _ => {}
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn duplicate_last_match_arm() {
+        check_assist(
+            duplicate_item,
+            "
+fn f(x: u32) {
+    match x {
+        0 => {}
+        <|>_ => foo()
+    }
+}",
+            "
+fn f(x: u32) {
+    match x {
+        0 => {}
+        _ => foo(),
+        <|>_ => foo()
+    }
+}",
+        );
+        check_assist(
+            duplicate_item,
+            "
+fn f(x: u32) {
+    match x {
+        <|>0 => {}
+    }
+}",
+            "
+fn f(x: u32) {
+    match x {
+        0 => {}
+        <|>0 => {}
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn duplicate_struct_field() {
+        check_assist(
+            duplicate_item,
+            "struct S { #[allow(dead_code)] x<|>: u32, y: u32 }",
+            "struct S { #[allow(dead_code)] x: u32, #[allow(dead_code)] <|>x: u32, y: u32 }",
+        );
+        check_assist(
+            duplicate_item,
+            "
+struct S {
+    x: u32,
+    <|>y: u32
+}",
+            "
+struct S {
+    x: u32,
+    y: u32,
+    <|>y: u32
+}",
+        );
+    }
+
+    #[test]
+    fn duplicate_not_applicable_in_fn_body() {
+        check_assist_not_applicable(duplicate_item, "fn foo() { let x<|> = 1; }");
+    }
+
+    #[test]
+    fn duplicate_item_target() {
+        check_assist_target(
+            duplicate_item,
+            "fn f() { match 1 { <|>1 => (), _ => () } }",
+            "1 => ()",
+        );
+    }
+}
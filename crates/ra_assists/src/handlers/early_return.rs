@@ -7,11 +7,12 @@ use ra_syntax::{
     SyntaxKind::{FN_DEF, LOOP_EXPR, L_CURLY, R_CURLY, WHILE_EXPR, WHITESPACE},
     SyntaxNode,
 };
+use test_utils::tested_by;
 
 use crate::{
     assist_ctx::{Assist, AssistCtx},
     utils::invert_boolean_expression,
-    AssistId,
+    AssistId, AssistKind,
 };
 
 // Assist: convert_to_guarded_return
@@ -39,6 +40,7 @@ use crate::{
 pub(crate) fn convert_to_guarded_return(ctx: AssistCtx) -> Option<Assist> {
     let if_expr: ast::IfExpr = ctx.find_node_at_offset()?;
     if if_expr.else_branch().is_some() {
+        tested_by!(guarded_return_with_else_branch);
         return None;
     }
 
@@ -57,7 +59,11 @@ pub(crate) fn convert_to_guarded_return(ctx: AssistCtx) -> Option<Assist> {
                 Some(_) => return None,
             }
         }
-        Some(_) => return None, // Unsupported IfLet.
+        Some(_) => {
+            // Unsupported IfLet.
+            tested_by!(guarded_return_for_unsupported_pattern);
+            return None;
+        }
     };
 
     let cond_expr = cond.expr()?;
@@ -65,7 +71,8 @@ pub(crate) fn convert_to_guarded_return(ctx: AssistCtx) -> Option<Assist> {
 
     let parent_block = if_expr.syntax().parent()?.ancestors().find_map(ast::Block::cast)?;
 
-    if parent_block.expr()? != if_expr.clone().into() {
+    if parent_block.expr() != Some(if_expr.clone().into()) {
+        tested_by!(guarded_return_for_non_tail_if);
         return None;
     }
 
@@ -77,6 +84,7 @@ pub(crate) fn convert_to_guarded_return(ctx: AssistCtx) -> Option<Assist> {
             .children()
             .any(|x| ast::ReturnExpr::can_cast(x.kind()) || ast::ContinueExpr::can_cast(x.kind()))
     {
+        tested_by!(guarded_return_for_existing_early_exit);
         return None;
     }
 
@@ -85,7 +93,10 @@ pub(crate) fn convert_to_guarded_return(ctx: AssistCtx) -> Option<Assist> {
     let early_expression: ast::Expr = match parent_container.kind() {
         WHILE_EXPR | LOOP_EXPR => make::expr_continue(),
         FN_DEF => make::expr_return(),
-        _ => return None,
+        _ => {
+            tested_by!(guarded_return_outside_of_fn_or_loop);
+            return None;
+        }
     };
 
     if then_block.syntax().first_child_or_token().map(|t| t.kind() == L_CURLY).is_none() {
@@ -95,94 +106,102 @@ pub(crate) fn convert_to_guarded_return(ctx: AssistCtx) -> Option<Assist> {
     then_block.syntax().last_child_or_token().filter(|t| t.kind() == R_CURLY)?;
     let cursor_position = ctx.frange.range.start();
 
-    ctx.add_assist(AssistId("convert_to_guarded_return"), "Convert to guarded return", |edit| {
-        let if_indent_level = IndentLevel::from_node(&if_expr.syntax());
-        let new_block = match if_let_pat {
-            None => {
-                // If.
-                let new_expr = {
-                    let then_branch =
-                        make::block_expr(once(make::expr_stmt(early_expression).into()), None);
-                    let cond = invert_boolean_expression(cond_expr);
-                    let e = make::expr_if(cond, then_branch);
-                    if_indent_level.increase_indent(e)
-                };
-                replace(new_expr.syntax(), &then_block, &parent_block, &if_expr)
-            }
-            Some((path, bound_ident)) => {
-                // If-let.
-                let match_expr = {
-                    let happy_arm = {
-                        let pat = make::tuple_struct_pat(
-                            path,
-                            once(make::bind_pat(make::name("it")).into()),
-                        );
-                        let expr = {
-                            let name_ref = make::name_ref("it");
-                            let segment = make::path_segment(name_ref);
-                            let path = make::path_unqualified(segment);
-                            make::expr_path(path)
-                        };
-                        make::match_arm(once(pat.into()), expr)
+    ctx.add_assist(
+        AssistId("convert_to_guarded_return"),
+        AssistKind::RefactorRewrite,
+        "Convert to guarded return",
+        if_expr.syntax().text_range(),
+        |edit| {
+            let if_indent_level = IndentLevel::from_node(&if_expr.syntax());
+            let new_block = match if_let_pat {
+                None => {
+                    // If.
+                    let new_expr = {
+                        let then_branch =
+                            make::block_expr(once(make::expr_stmt(early_expression).into()), None);
+                        let cond = invert_boolean_expression(cond_expr);
+                        let e = make::expr_if(cond, then_branch);
+                        if_indent_level.increase_indent(e)
                     };
+                    replace(new_expr.syntax(), &then_block, &parent_block, &if_expr)
+                }
+                Some((path, bound_ident)) => {
+                    // If-let.
+                    let match_expr = {
+                        let happy_arm = {
+                            let pat = make::tuple_struct_pat(
+                                path,
+                                once(make::bind_pat(make::name("it")).into()),
+                            );
+                            let expr = {
+                                let name_ref = make::name_ref("it");
+                                let segment = make::path_segment(name_ref);
+                                let path = make::path_unqualified(segment);
+                                make::expr_path(path)
+                            };
+                            make::match_arm(once(pat.into()), expr)
+                        };
 
-                    let sad_arm = make::match_arm(
-                        // FIXME: would be cool to use `None` or `Err(_)` if appropriate
-                        once(make::placeholder_pat().into()),
-                        early_expression,
-                    );
+                        let sad_arm = make::match_arm(
+                            // FIXME: would be cool to use `None` or `Err(_)` if appropriate
+                            once(make::placeholder_pat().into()),
+                            early_expression,
+                        );
 
-                    make::expr_match(cond_expr, make::match_arm_list(vec![happy_arm, sad_arm]))
-                };
+                        make::expr_match(cond_expr, make::match_arm_list(vec![happy_arm, sad_arm]))
+                    };
 
-                let let_stmt = make::let_stmt(
-                    make::bind_pat(make::name(&bound_ident.syntax().to_string())).into(),
-                    Some(match_expr),
+                    let let_stmt = make::let_stmt(
+                        make::bind_pat(make::name(&bound_ident.syntax().to_string())).into(),
+                        Some(match_expr),
+                    );
+                    let let_stmt = if_indent_level.increase_indent(let_stmt);
+                    replace(let_stmt.syntax(), &then_block, &parent_block, &if_expr)
+                }
+            };
+            edit.replace_ast(parent_block, ast::Block::cast(new_block).unwrap());
+            edit.set_cursor(cursor_position);
+
+            fn replace(
+                new_expr: &SyntaxNode,
+                then_block: &Block,
+                parent_block: &Block,
+                if_expr: &ast::IfExpr,
+            ) -> SyntaxNode {
+                let then_block_items = IndentLevel::from(1).decrease_indent(then_block.clone());
+                let end_of_then = then_block_items.syntax().last_child_or_token().unwrap();
+                let end_of_then =
+                    if end_of_then.prev_sibling_or_token().map(|n| n.kind()) == Some(WHITESPACE) {
+                        end_of_then.prev_sibling_or_token().unwrap()
+                    } else {
+                        end_of_then
+                    };
+                let mut then_statements = new_expr.children_with_tokens().chain(
+                    then_block_items
+                        .syntax()
+                        .children_with_tokens()
+                        .skip(1)
+                        .take_while(|i| *i != end_of_then),
                 );
-                let let_stmt = if_indent_level.increase_indent(let_stmt);
-                replace(let_stmt.syntax(), &then_block, &parent_block, &if_expr)
+                replace_children(
+                    &parent_block.syntax(),
+                    RangeInclusive::new(
+                        if_expr.clone().syntax().clone().into(),
+                        if_expr.syntax().clone().into(),
+                    ),
+                    &mut then_statements,
+                )
             }
-        };
-        edit.target(if_expr.syntax().text_range());
-        edit.replace_ast(parent_block, ast::Block::cast(new_block).unwrap());
-        edit.set_cursor(cursor_position);
-
-        fn replace(
-            new_expr: &SyntaxNode,
-            then_block: &Block,
-            parent_block: &Block,
-            if_expr: &ast::IfExpr,
-        ) -> SyntaxNode {
-            let then_block_items = IndentLevel::from(1).decrease_indent(then_block.clone());
-            let end_of_then = then_block_items.syntax().last_child_or_token().unwrap();
-            let end_of_then =
-                if end_of_then.prev_sibling_or_token().map(|n| n.kind()) == Some(WHITESPACE) {
-                    end_of_then.prev_sibling_or_token().unwrap()
-                } else {
-                    end_of_then
-                };
-            let mut then_statements = new_expr.children_with_tokens().chain(
-                then_block_items
-                    .syntax()
-                    .children_with_tokens()
-                    .skip(1)
-                    .take_while(|i| *i != end_of_then),
-            );
-            replace_children(
-                &parent_block.syntax(),
-                RangeInclusive::new(
-                    if_expr.clone().syntax().clone().into(),
-                    if_expr.syntax().clone().into(),
-                ),
-                &mut then_statements,
-            )
-        }
-    })
+        },
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::helpers::{check_assist, check_assist_not_applicable};
+    use crate::{
+        helpers::{check_assist, check_assist_not_applicable_because},
+        marks,
+    };
 
     use super::*;
 
@@ -417,7 +436,7 @@ mod tests {
 
     #[test]
     fn ignore_already_converted_if() {
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             convert_to_guarded_return,
             r#"
             fn main() {
@@ -426,12 +445,13 @@ mod tests {
                 }
             }
             "#,
+            &marks::guarded_return_for_existing_early_exit,
         );
     }
 
     #[test]
     fn ignore_already_converted_loop() {
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             convert_to_guarded_return,
             r#"
             fn main() {
@@ -442,12 +462,13 @@ mod tests {
                 }
             }
             "#,
+            &marks::guarded_return_for_existing_early_exit,
         );
     }
 
     #[test]
     fn ignore_return() {
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             convert_to_guarded_return,
             r#"
             fn main() {
@@ -456,12 +477,13 @@ mod tests {
                 }
             }
             "#,
+            &marks::guarded_return_for_existing_early_exit,
         );
     }
 
     #[test]
     fn ignore_else_branch() {
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             convert_to_guarded_return,
             r#"
             fn main() {
@@ -472,12 +494,13 @@ mod tests {
                 }
             }
             "#,
+            &marks::guarded_return_with_else_branch,
         );
     }
 
     #[test]
     fn ignore_statements_aftert_if() {
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             convert_to_guarded_return,
             r#"
             fn main() {
@@ -487,12 +510,13 @@ mod tests {
                 bar();
             }
             "#,
+            &marks::guarded_return_for_non_tail_if,
         );
     }
 
     #[test]
     fn ignore_statements_inside_if() {
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             convert_to_guarded_return,
             r#"
             fn main() {
@@ -503,6 +527,22 @@ mod tests {
                 }
             }
             "#,
+            &marks::guarded_return_outside_of_fn_or_loop,
+        );
+    }
+
+    #[test]
+    fn ignore_unsupported_if_let_pattern() {
+        check_assist_not_applicable_because(
+            convert_to_guarded_return,
+            r#"
+            fn main(p: Pair) {
+                if<|> let Pair(a, b) = p {
+                    foo(a, b);
+                }
+            }
+            "#,
+            &marks::guarded_return_for_unsupported_pattern,
         );
     }
 }
@@ -0,0 +1,278 @@
+use hir::{HasSource, Local, PathResolution};
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner, SelfParamKind},
+    SyntaxKind::{BREAK_EXPR, CONTINUE_EXPR, RETURN_EXPR, TRY_EXPR},
+    TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: extract_method
+//
+// Extracts an expression of a method into a new method of the same impl.
+//
+// ```
+// struct Counter { count: u32 }
+// impl Counter {
+//     fn tick(&mut self, step: u32) {
+//         <|>self.count += step<|>;
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Counter { count: u32 }
+// impl Counter {
+//     fn tick(&mut self, step: u32) {
+//         self.<|>extracted(step);
+//     }
+//
+//     fn extracted(&mut self, step: u32) {
+//         self.count += step
+//     }
+// }
+// ```
+pub(crate) fn extract_method(ctx: AssistCtx) -> Option<Assist> {
+    if ctx.frange.range.is_empty() {
+        return None;
+    }
+    let expr = ctx.covering_element().ancestors().find_map(ast::Expr::cast)?;
+    if expr.syntax().text_range() != ctx.frange.range {
+        return None;
+    }
+    if expr.syntax().descendants().any(|it| match it.kind() {
+        RETURN_EXPR | BREAK_EXPR | CONTINUE_EXPR | TRY_EXPR => true,
+        _ => false,
+    }) {
+        return None;
+    }
+    let method = expr.syntax().ancestors().find_map(ast::FnDef::cast)?;
+    method.param_list()?.self_param()?;
+    let impl_def =
+        method.syntax().parent().and_then(|it| it.parent()).and_then(ast::ImplDef::cast)?;
+    if impl_def.target_trait().is_some() {
+        return None;
+    }
+
+    let db = ctx.db;
+    let range = expr.syntax().text_range();
+    let mut mutates_self = false;
+    let mut params: Vec<Local> = Vec::new();
+    for path_expr in expr.syntax().descendants().filter_map(ast::PathExpr::cast) {
+        let local = match ctx.sema.resolve_path(&path_expr.path()?) {
+            Some(PathResolution::Local(it)) => it,
+            _ => continue,
+        };
+        let place = place_of(ast::Expr::from(path_expr));
+        if local.is_self(db) {
+            mutates_self |= is_mutated(&ctx, &place);
+            continue;
+        }
+        let declared_inside = local
+            .source(db)
+            .value
+            .left()
+            .map_or(false, |pat| pat.syntax().text_range().is_subrange(&range));
+        if declared_inside {
+            continue;
+        }
+        // Locals are passed by value, changes to them would be lost.
+        if is_mutated(&ctx, &place) {
+            return None;
+        }
+        if !params.contains(&local) {
+            params.push(local);
+        }
+    }
+
+    let mut param_list = vec![if mutates_self { "&mut self" } else { "&self" }.to_string()];
+    let mut args = Vec::new();
+    for param in &params {
+        let ty = param.ty(db);
+        if ty.contains_unknown() {
+            return None;
+        }
+        let name = param.name(db)?.to_string();
+        param_list.push(format!("{}: {}", name, ty.display(db)));
+        args.push(name);
+    }
+    let ret_ty = ctx.sema.type_of_expr(&expr)?;
+    if ret_ty.contains_unknown() {
+        return None;
+    }
+    let ret_ty = ret_ty.display(db).to_string();
+
+    let names = impl_def
+        .item_list()?
+        .impl_items()
+        .filter_map(|it| match it {
+            ast::ImplItem::FnDef(it) => it.name(),
+            _ => None,
+        })
+        .map(|it| it.text().to_string())
+        .collect::<Vec<_>>();
+    let name = (0..)
+        .map(|idx| if idx == 0 { "extracted".to_string() } else { format!("extracted{}", idx) })
+        .find(|it| !names.contains(it))?;
+
+    ctx.add_assist(
+        AssistId("extract_method"),
+        AssistKind::RefactorExtract,
+        "Extract into method",
+        range,
+        |edit| {
+            let indent = leading_indent(method.syntax()).unwrap_or_default();
+            let ret = if ret_ty == "()" { String::new() } else { format!(" -> {}", ret_ty) };
+            let new_method = format!(
+                "\n\n{indent}fn {}({}){} {{\n{indent}    {}\n{indent}}}",
+                name,
+                param_list.join(", "),
+                ret,
+                expr.syntax(),
+                indent = indent,
+            );
+            edit.insert(method.syntax().text_range().end(), new_method);
+            edit.replace(range, format!("self.{}({})", name, args.join(", ")));
+            edit.set_cursor(range.start() + TextUnit::of_str("self."));
+        },
+    )
+}
+
+/// The outermost field or index access `expr` is the base of.
+fn place_of(mut expr: ast::Expr) -> ast::Expr {
+    while let Some(parent) = expr.syntax().parent() {
+        let is_base = match ast::Expr::cast(parent.clone()) {
+            Some(ast::Expr::FieldExpr(it)) => it.expr().as_ref() == Some(&expr),
+            Some(ast::Expr::IndexExpr(it)) => it.base().as_ref() == Some(&expr),
+            _ => false,
+        };
+        if !is_base {
+            break;
+        }
+        expr = ast::Expr::cast(parent).unwrap();
+    }
+    expr
+}
+
+/// Whether `place` is assigned to, mutably borrowed or used as the receiver of
+/// a `&mut self` method.
+fn is_mutated(ctx: &AssistCtx, place: &ast::Expr) -> bool {
+    let parent = match place.syntax().parent().and_then(ast::Expr::cast) {
+        Some(it) => it,
+        None => return false,
+    };
+    match parent {
+        ast::Expr::BinExpr(it) => {
+            it.op_kind().map_or(false, |op| op.is_assignment()) && it.lhs().as_ref() == Some(place)
+        }
+        ast::Expr::RefExpr(it) => it.is_mut(),
+        ast::Expr::MethodCallExpr(it) => {
+            it.expr().as_ref() == Some(place)
+                && ctx
+                    .sema
+                    .resolve_method_call(&it)
+                    .and_then(|func| func.source(ctx.db).value.param_list()?.self_param())
+                    .map_or(false, |it| it.kind() == SelfParamKind::MutRef)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn extract_read_only_expression() {
+        check_assist(
+            extract_method,
+            "
+struct S { x: i32 }
+impl S {
+    fn f(&self) -> i32 {
+        <|>self.x * 2<|> + 1
+    }
+}",
+            "
+struct S { x: i32 }
+impl S {
+    fn f(&self) -> i32 {
+        self.<|>extracted() + 1
+    }
+
+    fn extracted(&self) -> i32 {
+        self.x * 2
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn extract_mutating_expression() {
+        check_assist(
+            extract_method,
+            "
+struct S { x: i32 }
+impl S {
+    fn bump(&mut self) {}
+    fn extracted(&self) {}
+    fn f(&mut self) {
+        let _ = <|>{ self.bump(); self.x }<|>;
+    }
+}",
+            "
+struct S { x: i32 }
+impl S {
+    fn bump(&mut self) {}
+    fn extracted(&self) {}
+    fn f(&mut self) {
+        let _ = self.<|>extracted1();
+    }
+
+    fn extracted1(&mut self) -> i32 {
+        { self.bump(); self.x }
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn extract_expression_referencing_local() {
+        check_assist(
+            extract_method,
+            "
+struct S { x: i32 }
+impl S {
+    fn f(&self, y: i32) -> i32 {
+        let z = y * 2;
+        <|>self.x + z + z<|>
+    }
+}",
+            "
+struct S { x: i32 }
+impl S {
+    fn f(&self, y: i32) -> i32 {
+        let z = y * 2;
+        self.<|>extracted(z)
+    }
+
+    fn extracted(&self, z: i32) -> i32 {
+        self.x + z + z
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn extract_method_not_applicable_in_free_fn() {
+        check_assist_not_applicable(
+            extract_method,
+            "
+fn f(x: i32) -> i32 {
+    <|>x * 2<|>
+}",
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use hir::{Adt, HasSource, Local, PathResolution};
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, ArgListOwner, AstNode, NameOwner},
+    SyntaxKind::{BREAK_EXPR, CONTINUE_EXPR, RETURN_EXPR, TRY_EXPR},
+    TextRange, TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: extract_option_helper
+//
+// Extracts a chain of `Option::unwrap` calls of a method into a new method
+// propagating the missing values with `?`. The outermost `unwrap` is kept at
+// the call site, or becomes a `?` if the method itself returns an `Option`.
+//
+// ```
+// # enum Option<T> { Some(T), None }
+// # impl<T> Option<T> { fn unwrap(self) -> T { loop {} } }
+// struct Section { value: Option<u32> }
+// struct Config { section: Option<Section> }
+// impl Config {
+//     fn value(self) -> u32 {
+//         <|>self.section.unwrap().value.unwrap()<|>
+//     }
+// }
+// ```
+// ->
+// ```
+// # enum Option<T> { Some(T), None }
+// # impl<T> Option<T> { fn unwrap(self) -> T { loop {} } }
+// struct Section { value: Option<u32> }
+// struct Config { section: Option<Section> }
+// impl Config {
+//     fn value(self) -> u32 {
+//         self.<|>extracted().unwrap()
+//     }
+//
+//     fn extracted(self) -> Option<u32> {
+//         self.section?.value
+//     }
+// }
+// ```
+pub(crate) fn extract_option_helper(ctx: AssistCtx) -> Option<Assist> {
+    if ctx.frange.range.is_empty() {
+        return None;
+    }
+    let expr = ctx.covering_element().ancestors().find_map(ast::MethodCallExpr::cast)?;
+    if expr.syntax().text_range() != ctx.frange.range || !is_option_unwrap(&ctx, &expr) {
+        return None;
+    }
+    let chain = expr.expr()?;
+    if chain.syntax().descendants().any(|it| match it.kind() {
+        RETURN_EXPR | BREAK_EXPR | CONTINUE_EXPR | TRY_EXPR => true,
+        _ => false,
+    }) {
+        return None;
+    }
+    let unwraps = chain
+        .syntax()
+        .descendants()
+        .filter_map(ast::MethodCallExpr::cast)
+        .filter(|it| it.name_ref().map_or(false, |it| it.text() == "unwrap"))
+        .collect::<Vec<_>>();
+    // Unwrapping a `Result` can't be propagated in a function returning `Option`.
+    if unwraps.is_empty() || !unwraps.iter().all(|it| is_option_unwrap(&ctx, it)) {
+        return None;
+    }
+    let method = expr.syntax().ancestors().find_map(ast::FnDef::cast)?;
+    let self_param = method.param_list()?.self_param()?;
+    let impl_def =
+        method.syntax().parent().and_then(|it| it.parent()).and_then(ast::ImplDef::cast)?;
+    if impl_def.target_trait().is_some() {
+        return None;
+    }
+
+    let db = ctx.db;
+    let chain_range = chain.syntax().text_range();
+    let mut params: Vec<Local> = Vec::new();
+    for path_expr in chain.syntax().descendants().filter_map(ast::PathExpr::cast) {
+        let local = match ctx.sema.resolve_path(&path_expr.path()?) {
+            Some(PathResolution::Local(it)) if !it.is_self(db) => it,
+            _ => continue,
+        };
+        let declared_inside = local
+            .source(db)
+            .value
+            .left()
+            .map_or(false, |pat| pat.syntax().text_range().is_subrange(&chain_range));
+        if !declared_inside && !params.contains(&local) {
+            params.push(local);
+        }
+    }
+    let mut param_list = vec![self_param.syntax().to_string()];
+    let mut args = Vec::new();
+    for param in &params {
+        let ty = param.ty(db);
+        if ty.contains_unknown() {
+            return None;
+        }
+        let name = param.name(db)?.to_string();
+        param_list.push(format!("{}: {}", name, ty.display(db)));
+        args.push(name);
+    }
+    let ret_ty = ctx.sema.type_of_expr(&chain)?;
+    if ret_ty.contains_unknown() {
+        return None;
+    }
+    let ret_ty = ret_ty.display(db).to_string();
+
+    // The `.unwrap()` parts of the calls, relative to the start of the chain.
+    let mut body = chain.syntax().to_string();
+    let mut unwrap_ranges = unwraps
+        .iter()
+        .filter_map(|it| {
+            let range = TextRange::from_to(
+                it.expr()?.syntax().text_range().end(),
+                it.syntax().text_range().end(),
+            );
+            range.checked_sub(chain_range.start())
+        })
+        .collect::<Vec<_>>();
+    unwrap_ranges.sort_by_key(|it| it.start());
+    for range in unwrap_ranges.into_iter().rev() {
+        body.replace_range(range.start().to_usize()..range.end().to_usize(), "?");
+    }
+
+    let names = impl_def
+        .item_list()?
+        .impl_items()
+        .filter_map(|it| match it {
+            ast::ImplItem::FnDef(it) => it.name(),
+            _ => None,
+        })
+        .map(|it| it.text().to_string())
+        .collect::<Vec<_>>();
+    let name = (0..)
+        .map(|idx| if idx == 0 { "extracted".to_string() } else { format!("extracted{}", idx) })
+        .find(|it| !names.contains(it))?;
+    let handling = if returns_option(&method) { "?" } else { ".unwrap()" };
+
+    let range = expr.syntax().text_range();
+    ctx.add_assist(
+        AssistId("extract_option_helper"),
+        AssistKind::RefactorExtract,
+        "Extract Option-propagating helper",
+        range,
+        |edit| {
+            let indent = leading_indent(method.syntax()).unwrap_or_default();
+            let new_method = format!(
+                "\n\n{indent}fn {}({}) -> {} {{\n{indent}    {}\n{indent}}}",
+                name,
+                param_list.join(", "),
+                ret_ty,
+                body,
+                indent = indent,
+            );
+            edit.insert(method.syntax().text_range().end(), new_method);
+            edit.replace(range, format!("self.{}({}){}", name, args.join(", "), handling));
+            edit.set_cursor(range.start() + TextUnit::of_str("self."));
+        },
+    )
+}
+
+/// Whether `call` is an argument-less `unwrap` of an `Option`.
+fn is_option_unwrap(ctx: &AssistCtx, call: &ast::MethodCallExpr) -> bool {
+    if !call.name_ref().map_or(false, |it| it.text() == "unwrap")
+        || call.arg_list().map_or(true, |it| it.args().next().is_some())
+    {
+        return false;
+    }
+    match call.expr().and_then(|it| ctx.sema.type_of_expr(&it)).and_then(|it| it.as_adt()) {
+        Some(Adt::Enum(it)) => it.name(ctx.db).to_string() == "Option",
+        _ => false,
+    }
+}
+
+fn returns_option(fn_def: &ast::FnDef) -> bool {
+    let path = match fn_def.ret_type().and_then(|it| it.type_ref()) {
+        Some(ast::TypeRef::PathType(it)) => it.path(),
+        _ => None,
+    };
+    path.and_then(|it| it.segment())
+        .and_then(|it| it.name_ref())
+        .map_or(false, |it| it.text() == "Option")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    const OPTION: &str = "
+enum Option<T> { Some(T), None }
+impl<T> Option<T> { fn unwrap(self) -> T { loop {} } }
+enum Result<T, E> { Ok(T), Err(E) }
+impl<T, E> Result<T, E> { fn unwrap(self) -> T { loop {} } }
+struct Section { value: Option<u32> }";
+
+    #[test]
+    fn extract_unwrap_chain_keeping_outer_unwrap() {
+        check_assist(
+            extract_option_helper,
+            &format!(
+                "{}
+struct Config {{ section: Option<Section> }}
+impl Config {{
+    fn value(self) -> u32 {{
+        <|>self.section.unwrap().value.unwrap()<|> + 1
+    }}
+}}",
+                OPTION
+            ),
+            &format!(
+                "{}
+struct Config {{ section: Option<Section> }}
+impl Config {{
+    fn value(self) -> u32 {{
+        self.<|>extracted().unwrap() + 1
+    }}
+
+    fn extracted(self) -> Option<u32> {{
+        self.section?.value
+    }}
+}}",
+                OPTION
+            ),
+        );
+    }
+
+    #[test]
+    fn extract_unwrap_chain_in_option_returning_method() {
+        check_assist(
+            extract_option_helper,
+            &format!(
+                "{}
+struct Config {{ fallback: u32 }}
+impl Config {{
+    fn value(&self, section: Option<Section>) -> Option<u32> {{
+        let value = <|>section.unwrap().value.unwrap()<|>;
+        Some(value + self.fallback)
+    }}
+}}",
+                OPTION
+            ),
+            &format!(
+                "{}
+struct Config {{ fallback: u32 }}
+impl Config {{
+    fn value(&self, section: Option<Section>) -> Option<u32> {{
+        let value = self.<|>extracted(section)?;
+        Some(value + self.fallback)
+    }}
+
+    fn extracted(&self, section: Option<Section>) -> Option<u32> {{
+        section?.value
+    }}
+}}",
+                OPTION
+            ),
+        );
+    }
+
+    #[test]
+    fn extract_not_applicable_with_result_unwrap() {
+        check_assist_not_applicable(
+            extract_option_helper,
+            &format!(
+                "{}
+struct Config {{ section: Result<Section, ()> }}
+impl Config {{
+    fn value(self) -> u32 {{
+        <|>self.section.unwrap().value.unwrap()<|>
+    }}
+}}",
+                OPTION
+            ),
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use hir::VariantDef;
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner, VisibilityOwner},
+    SyntaxKind::{ATTR, COMMENT, WHITESPACE},
+    SyntaxNode, TextRange, TextUnit,
+};
+
+use crate::{assist_ctx::ActionBuilder, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: extract_struct_from_enum_variant
+//
+// Extracts a struct from enum variant.
+//
+// ```
+// enum A { <|>One { foo: u32, bar: u32 } }
+// ```
+// ->
+// ```
+// struct One { foo: u32, bar: u32 }
+//
+// enum A { One(One) }
+// ```
+pub(crate) fn extract_struct_from_enum_variant(ctx: AssistCtx) -> Option<Assist> {
+    let variant = ctx.find_node_at_offset::<ast::EnumVariant>()?;
+    let field_list = match variant.kind() {
+        ast::StructKind::Record(field_list) => field_list,
+        _ => return None,
+    };
+    let variant_name = variant.name()?.text().to_string();
+    let variant_hir = ctx.sema.to_def(&variant)?;
+    let enum_ast = variant.parent_enum();
+    if struct_exists(&enum_ast, &variant_name) {
+        return None;
+    }
+    let sema = ctx.sema;
+
+    ctx.add_assist(
+        AssistId("extract_struct_from_enum_variant"),
+        AssistKind::RefactorExtract,
+        "Extract struct from enum variant",
+        variant.syntax().text_range(),
+        |edit| {
+            let vis = enum_ast.visibility().map(|v| format!("{} ", v.syntax()));
+            let vis = vis.as_deref().unwrap_or("");
+            let indent = leading_indent(enum_ast.syntax()).unwrap_or_default();
+            let struct_def = format!(
+                "{}struct {} {}\n\n{}",
+                vis,
+                variant_name,
+                field_list_text(&field_list, vis),
+                indent
+            );
+            edit.insert(enum_ast.syntax().text_range().start(), struct_def);
+
+            let name_end = variant.name().unwrap().syntax().text_range().end();
+            edit.replace(
+                TextRange::from_to(name_end, field_list.syntax().text_range().end()),
+                format!("({})", variant_name),
+            );
+
+            let variant_def = Some(VariantDef::EnumVariant(variant_hir));
+            let root = enum_ast.syntax().ancestors().last().unwrap();
+            for record_lit in root.descendants().filter_map(ast::RecordLit::cast) {
+                if sema.resolve_record_literal(&record_lit) != variant_def {
+                    continue;
+                }
+                if let (Some(path), Some(fields)) =
+                    (record_lit.path(), record_lit.record_field_list())
+                {
+                    wrap_fields(edit, path.syntax(), fields.syntax(), &variant_name);
+                }
+            }
+            for record_pat in root.descendants().filter_map(ast::RecordPat::cast) {
+                if sema.resolve_record_pattern(&record_pat) != variant_def {
+                    continue;
+                }
+                if let (Some(path), Some(fields)) =
+                    (record_pat.path(), record_pat.record_field_pat_list())
+                {
+                    wrap_fields(edit, path.syntax(), fields.syntax(), &variant_name);
+                }
+            }
+        },
+    )
+}
+
+/// Rewrites `Enum::Variant { .. }` into `Enum::Variant(Variant { .. })`, this
+/// works the same for record literals and record patterns.
+fn wrap_fields(
+    edit: &mut ActionBuilder,
+    path: &SyntaxNode,
+    fields: &SyntaxNode,
+    struct_name: &str,
+) {
+    let range = TextRange::from_to(path.text_range().end(), fields.text_range().end());
+    edit.replace(range, format!("({} {})", struct_name, fields));
+}
+
+fn struct_exists(enum_ast: &ast::EnumDef, name: &str) -> bool {
+    let scope = match enum_ast.syntax().parent() {
+        Some(it) => it,
+        None => return false,
+    };
+    scope
+        .children()
+        .filter_map(ast::NominalDef::cast)
+        .filter_map(|def| def.name())
+        .any(|def_name| def_name.text() == name)
+}
+
+// Fields of a variant share the visibility of the enum, so struct fields need
+// it spelled out explicitly.
+fn field_list_text(field_list: &ast::RecordFieldDefList, vis: &str) -> String {
+    let mut text = field_list.syntax().to_string();
+    if vis.is_empty() {
+        return text;
+    }
+    let list_start = field_list.syntax().text_range().start();
+    let fields = field_list.fields().filter(|it| it.visibility().is_none()).collect::<Vec<_>>();
+    for field in fields.into_iter().rev() {
+        let offset = field_start(field.syntax()) - list_start;
+        text.insert_str(offset.to_usize(), vis);
+    }
+    text
+}
+
+fn field_start(node: &SyntaxNode) -> TextUnit {
+    node.children_with_tokens()
+        .find(|it| match it.kind() {
+            WHITESPACE | COMMENT | ATTR => false,
+            _ => true,
+        })
+        .map(|it| it.text_range().start())
+        .unwrap_or_else(|| node.text_range().start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn extract_struct_updates_constructors_and_patterns() {
+        check_assist(
+            extract_struct_from_enum_variant,
+            r#"
+enum Event { <|>Click { x: i32, y: i32 }, Key(char) }
+
+fn handle(e: Event) {
+    let _ = Event::Click { x: 1, y: 2 };
+    match e {
+        Event::Click { x, y } => (),
+        Event::Key(_) => (),
+    }
+}"#,
+            r#"
+struct Click { x: i32, y: i32 }
+
+enum Event { <|>Click(Click), Key(char) }
+
+fn handle(e: Event) {
+    let _ = Event::Click(Click { x: 1, y: 2 });
+    match e {
+        Event::Click(Click { x, y }) => (),
+        Event::Key(_) => (),
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn extract_struct_single_field() {
+        check_assist(
+            extract_struct_from_enum_variant,
+            "pub enum A { <|>One { foo: u32 } }",
+            "pub struct One { pub foo: u32 }\n\npub enum A { <|>One(One) }",
+        );
+    }
+
+    #[test]
+    fn extract_struct_not_applicable_for_tuple_variant() {
+        check_assist_not_applicable(extract_struct_from_enum_variant, "enum A { <|>One(u32) }");
+    }
+
+    #[test]
+    fn extract_struct_not_applicable_if_struct_exists() {
+        check_assist_not_applicable(
+            extract_struct_from_enum_variant,
+            "struct One;\nenum A { <|>One { foo: u32 } }",
+        );
+    }
+}
@@ -4,8 +4,9 @@ use std::iter;
 
 use hir::{db::HirDatabase, Adt, HasSource, Semantics};
 use ra_syntax::ast::{self, edit::IndentLevel, make, AstNode, NameOwner};
+use test_utils::tested_by;
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 use ra_ide_db::RootDatabase;
 
 // Assist: fill_match_arms
@@ -42,6 +43,7 @@ pub(crate) fn fill_match_arms(ctx: AssistCtx) -> Option<Assist> {
     let mut existing_arms = match_arm_list.arms();
     if let Some(arm) = existing_arms.next() {
         if !is_trivial(&arm) || existing_arms.next().is_some() {
+            tested_by!(fill_match_arms_with_existing_arms);
             return None;
         }
     };
@@ -52,26 +54,32 @@ pub(crate) fn fill_match_arms(ctx: AssistCtx) -> Option<Assist> {
 
     let variants = enum_def.variants(ctx.db);
     if variants.is_empty() {
+        tested_by!(fill_match_arms_for_empty_enum);
         return None;
     }
 
     let db = ctx.db;
 
-    ctx.add_assist(AssistId("fill_match_arms"), "Fill match arms", |edit| {
-        let indent_level = IndentLevel::from_node(match_arm_list.syntax());
-
-        let new_arm_list = {
-            let arms = variants
-                .into_iter()
-                .filter_map(|variant| build_pat(db, module, variant))
-                .map(|pat| make::match_arm(iter::once(pat), make::expr_unit()));
-            indent_level.increase_indent(make::match_arm_list(arms))
-        };
-
-        edit.target(match_expr.syntax().text_range());
-        edit.set_cursor(expr.syntax().text_range().start());
-        edit.replace_ast(match_arm_list, new_arm_list);
-    })
+    ctx.add_assist(
+        AssistId("fill_match_arms"),
+        AssistKind::Refactor,
+        "Fill match arms",
+        match_expr.syntax().text_range(),
+        |edit| {
+            let indent_level = IndentLevel::from_node(match_arm_list.syntax());
+
+            let new_arm_list = {
+                let arms = variants
+                    .into_iter()
+                    .filter_map(|variant| build_pat(db, module, variant))
+                    .map(|pat| make::match_arm(iter::once(pat), make::expr_unit()));
+                indent_level.increase_indent(make::match_arm_list(arms))
+            };
+
+            edit.set_cursor(expr.syntax().text_range().start());
+            edit.replace_ast(match_arm_list, new_arm_list);
+        },
+    )
 }
 
 fn is_trivial(arm: &ast::MatchArm) -> bool {
@@ -82,10 +90,13 @@ fn is_trivial(arm: &ast::MatchArm) -> bool {
 }
 
 fn resolve_enum_def(sema: &Semantics<RootDatabase>, expr: &ast::Expr) -> Option<hir::Enum> {
-    sema.type_of_expr(&expr)?.autoderef(sema.db).find_map(|ty| match ty.as_adt() {
+    // The patterns match through references with the default binding modes,
+    // but other smart pointers, like `Box`, would need `box` patterns.
+    let ty = iter::successors(sema.type_of_expr(&expr), |it| it.remove_ref()).last()?;
+    match ty.as_adt() {
         Some(Adt::Enum(e)) => Some(e),
         _ => None,
-    })
+    }
 }
 
 fn build_pat(
@@ -114,7 +125,13 @@ fn build_pat(
 
 #[cfg(test)]
 mod tests {
-    use crate::helpers::{check_assist, check_assist_target};
+    use crate::{
+        helpers::{
+            check_assist, check_assist_not_applicable, check_assist_not_applicable_because,
+            check_assist_target,
+        },
+        marks,
+    };
 
     use super::fill_match_arms;
 
@@ -212,6 +229,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fill_match_arms_through_nested_refs() {
+        check_assist(
+            fill_match_arms,
+            r#"
+            enum A { As, Bs(u32) }
+
+            fn foo(a: &&A) {
+                match a<|> {
+                }
+            }
+            "#,
+            r#"
+            enum A { As, Bs(u32) }
+
+            fn foo(a: &&A) {
+                match <|>a {
+                    A::As => (),
+                    A::Bs(_) => (),
+                }
+            }
+            "#,
+        );
+    }
+
+    #[test]
+    fn fill_match_arms_not_applicable_through_box() {
+        check_assist_not_applicable(
+            fill_match_arms,
+            r#"
+            #[lang = "deref"]
+            trait Deref {
+                type Target;
+                fn deref(&self) -> &Self::Target;
+            }
+
+            struct Box<T>(T);
+
+            impl<T> Deref for Box<T> {
+                type Target = T;
+                fn deref(&self) -> &T { &self.0 }
+            }
+
+            enum A { As }
+
+            fn foo(a: Box<A>) {
+                match a<|> {
+                }
+            }
+            "#,
+        );
+    }
+
     #[test]
     fn fill_match_arms_target() {
         check_assist_target(
@@ -280,4 +350,38 @@ mod tests {
             "#,
         );
     }
+
+    #[test]
+    fn fill_match_arms_not_applicable_with_existing_arms() {
+        check_assist_not_applicable_because(
+            fill_match_arms,
+            r#"
+            enum E { X, Y }
+
+            fn main() {
+                match E::X {
+                    <|>E::X => {}
+                }
+            }
+            "#,
+            &marks::fill_match_arms_with_existing_arms,
+        );
+    }
+
+    #[test]
+    fn fill_match_arms_not_applicable_to_empty_enum() {
+        check_assist_not_applicable_because(
+            fill_match_arms,
+            r#"
+            enum E {}
+
+            fn foo(e: E) {
+                match e {
+                    <|>
+                }
+            }
+            "#,
+            &marks::fill_match_arms_for_empty_enum,
+        );
+    }
 }
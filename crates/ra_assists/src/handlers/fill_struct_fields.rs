@@ -0,0 +1,253 @@
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode},
+    Direction, SyntaxElement,
+    SyntaxKind::WHITESPACE,
+    TextRange, TextUnit, T,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+/// The value of a generated field.
+const PLACEHOLDER: &str = "todo!()";
+
+// Assist: fill_struct_fields
+//
+// Adds the missing fields to a record literal.
+//
+// ```
+// struct Point { x: i32, y: i32, z: i32 }
+//
+// fn main() {
+//     let p = Point { x: 1<|> };
+// }
+// ```
+// ->
+// ```
+// struct Point { x: i32, y: i32, z: i32 }
+//
+// fn main() {
+//     let p = Point { x: 1, y: todo!(), z: todo!() };
+// }
+// ```
+pub(crate) fn fill_struct_fields(ctx: AssistCtx) -> Option<Assist> {
+    let record_lit = ctx.find_node_at_offset::<ast::RecordLit>()?;
+    let field_list = record_lit.record_field_list()?;
+    let list_range = field_list.syntax().text_range();
+    let offset = ctx.frange.range.start();
+    if field_list.spread().is_some() || offset <= list_range.start() || list_range.end() <= offset {
+        return None;
+    }
+
+    let variant = ctx.sema.resolve_record_literal(&record_lit)?;
+    let names = variant.fields(ctx.db).into_iter().map(|it| it.name(ctx.db)).collect::<Vec<_>>();
+    if names.iter().any(|it| it.as_tuple_index().is_some()) {
+        return None;
+    }
+    let present = field_list
+        .fields()
+        .filter_map(|it| it.name_ref())
+        .map(|it| it.text().to_string())
+        .collect::<Vec<_>>();
+    let missing = names
+        .into_iter()
+        .map(|it| it.to_string())
+        .filter(|it| !present.contains(it))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return None;
+    }
+
+    let layout = Layout::new(&field_list)?;
+    let mut group = ctx.add_assist_group("Fill struct fields");
+    group.add_assist(
+        AssistId("fill_struct_fields"),
+        AssistKind::Refactor,
+        "Fill missing fields",
+        list_range,
+        |edit| {
+            let items = missing.iter().map(|it| format!("{}: {}", it, PLACEHOLDER)).collect();
+            let (text, range) = layout.insert(items, false);
+            // The cursor goes to the first of the values, which are tabstops.
+            for (idx, _) in text.match_indices(PLACEHOLDER) {
+                let start = range.start() + TextUnit::from_usize(idx);
+                edit.add_tabstop(TextRange::offset_len(start, TextUnit::of_str(PLACEHOLDER)));
+            }
+            edit.replace(range, text);
+        },
+    );
+    group.add_assist(
+        AssistId("fill_struct_fields"),
+        AssistKind::Refactor,
+        "Fill with `..Default::default()`",
+        list_range,
+        |edit| {
+            let (text, range) = layout.insert(vec!["..Default::default()".to_string()], true);
+            edit.set_cursor(range.start() + TextUnit::of_str(&text));
+            edit.replace(range, text);
+        },
+    );
+    group.finish()
+}
+
+/// Describes where and how new items are appended to a record field list, so
+/// that they follow the existing formatting.
+struct Layout {
+    /// Whether the last existing item is followed by a comma.
+    trailing_comma: bool,
+    /// Indentation of items of a multiline list.
+    indent: Option<String>,
+    /// End of the last existing item or comma, `None` for an empty list.
+    anchor: Option<TextUnit>,
+    /// Interior of the braces.
+    interior: TextRange,
+}
+
+impl Layout {
+    fn new(field_list: &ast::RecordFieldList) -> Option<Layout> {
+        let l_curly = field_list.syntax().first_token().filter(|it| it.kind() == T!['{'])?;
+        let r_curly = field_list.syntax().last_token().filter(|it| it.kind() == T!['}'])?;
+        let interior = TextRange::from_to(l_curly.text_range().end(), r_curly.text_range().start());
+        let last_field = match field_list.fields().last() {
+            Some(it) => it,
+            None => {
+                return Some(Layout { trailing_comma: false, indent: None, anchor: None, interior })
+            }
+        };
+        let comma = last_field
+            .syntax()
+            .siblings_with_tokens(Direction::Next)
+            .skip(1)
+            .filter_map(SyntaxElement::into_token)
+            .find(|it| it.kind() != WHITESPACE)
+            .filter(|it| it.kind() == T![,]);
+        let indent = if field_list.syntax().text().contains_char('\n') {
+            leading_indent(last_field.syntax()).map(|it| it.to_string())
+        } else {
+            None
+        };
+        let anchor = match &comma {
+            Some(comma) => comma.text_range().end(),
+            None => last_field.syntax().text_range().end(),
+        };
+        Some(Layout { trailing_comma: comma.is_some(), indent, anchor: Some(anchor), interior })
+    }
+
+    /// Returns the text inserting `items`, together with the range it replaces.
+    fn insert(&self, items: Vec<String>, is_spread: bool) -> (String, TextRange) {
+        let anchor = match self.anchor {
+            Some(it) => it,
+            None => return (format!(" {} ", items.join(", ")), self.interior),
+        };
+        let range = TextRange::offset_len(anchor, 0.into());
+        let mut buf = String::new();
+        if !self.trailing_comma {
+            buf.push(',');
+        }
+        match &self.indent {
+            Some(indent) => {
+                let separator = format!(",\n{}", indent);
+                buf.push_str(&format!("\n{}{}", indent, items.join(&separator)));
+                if self.trailing_comma && !is_spread {
+                    buf.push(',');
+                }
+            }
+            None => {
+                buf.push(' ');
+                buf.push_str(&items.join(", "));
+            }
+        }
+        (buf, range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_snippet};
+
+    #[test]
+    fn fill_empty_literal() {
+        check_assist(
+            fill_struct_fields,
+            r#"
+struct S { a: u32, b: bool }
+fn f() -> S { S {<|>} }"#,
+            r#"
+struct S { a: u32, b: bool }
+fn f() -> S { S { a: <|>todo!(), b: todo!() } }"#,
+        );
+    }
+
+    #[test]
+    fn fill_partially_filled_multiline_literal() {
+        check_assist(
+            fill_struct_fields,
+            r#"
+struct S { a: u32, b: bool, c: char }
+fn f(b: bool) -> S {
+    S {
+        b,<|>
+    }
+}"#,
+            r#"
+struct S { a: u32, b: bool, c: char }
+fn f(b: bool) -> S {
+    S {
+        b,
+        a: <|>todo!(),
+        c: todo!(),
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn fill_multiline_literal_without_trailing_comma() {
+        check_assist_snippet(
+            fill_struct_fields,
+            r#"
+struct S { a: u32, b: bool, c: char }
+fn f(b: bool) -> S {
+    S {
+        b<|>
+    }
+}"#,
+            r#"
+struct S { a: u32, b: bool, c: char }
+fn f(b: bool) -> S {
+    S {
+        b,
+        a: ${1:todo!()},
+        c: ${2:todo!()}
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn fill_enum_variant_literal() {
+        check_assist(
+            fill_struct_fields,
+            r#"
+enum E { V { x: i32, y: i32 } }
+fn f() -> E { E::V { x: 1<|> } }"#,
+            r#"
+enum E { V { x: i32, y: i32 } }
+fn f() -> E { E::V { x: 1, y: <|>todo!() } }"#,
+        );
+    }
+
+    #[test]
+    fn fill_struct_fields_not_applicable() {
+        check_assist_not_applicable(fill_struct_fields, "struct S(u32); fn f() -> S { S { <|> } }");
+        check_assist_not_applicable(
+            fill_struct_fields,
+            "struct S { a: u32 } fn f() -> S { S { a: 1<|> } }",
+        );
+        check_assist_not_applicable(
+            fill_struct_fields,
+            "struct S { a: u32 } fn f(s: S) -> S { S { <|>..s } }",
+        );
+    }
+}
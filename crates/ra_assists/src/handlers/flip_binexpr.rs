@@ -1,6 +1,6 @@
 use ra_syntax::ast::{AstNode, BinExpr, BinOp};
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: flip_binexpr
 //
@@ -33,14 +33,19 @@ pub(crate) fn flip_binexpr(ctx: AssistCtx) -> Option<Assist> {
         return None;
     }
 
-    ctx.add_assist(AssistId("flip_binexpr"), "Flip binary expression", |edit| {
-        edit.target(op_range);
-        if let FlipAction::FlipAndReplaceOp(new_op) = action {
-            edit.replace(op_range, new_op);
-        }
-        edit.replace(lhs.text_range(), rhs.text());
-        edit.replace(rhs.text_range(), lhs.text());
-    })
+    ctx.add_assist(
+        AssistId("flip_binexpr"),
+        AssistKind::RefactorRewrite,
+        "Flip binary expression",
+        op_range,
+        |edit| {
+            if let FlipAction::FlipAndReplaceOp(new_op) = action {
+                edit.replace(op_range, new_op);
+            }
+            edit.replace(lhs.text_range(), rhs.text());
+            edit.replace(rhs.text_range(), lhs.text());
+        },
+    )
 }
 
 enum FlipAction {
@@ -139,4 +144,13 @@ mod tests {
             "#,
         )
     }
+
+    #[test]
+    fn flip_binexpr_works_in_const_initializer() {
+        check_assist(
+            flip_binexpr,
+            "const SIZE: usize = 4 *<|> 1024;",
+            "const SIZE: usize = 1024 *<|> 4;",
+        )
+    }
 }
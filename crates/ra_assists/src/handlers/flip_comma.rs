@@ -1,6 +1,6 @@
 use ra_syntax::{algo::non_trivia_sibling, Direction, T};
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: flip_comma
 //
@@ -28,11 +28,16 @@ pub(crate) fn flip_comma(ctx: AssistCtx) -> Option<Assist> {
         return None;
     }
 
-    ctx.add_assist(AssistId("flip_comma"), "Flip comma", |edit| {
-        edit.target(comma.text_range());
-        edit.replace(prev.text_range(), next.to_string());
-        edit.replace(next.text_range(), prev.to_string());
-    })
+    ctx.add_assist(
+        AssistId("flip_comma"),
+        AssistKind::RefactorRewrite,
+        "Flip comma",
+        comma.text_range(),
+        |edit| {
+            edit.replace(prev.text_range(), next.to_string());
+            edit.replace(next.text_range(), prev.to_string());
+        },
+    )
 }
 
 #[cfg(test)]
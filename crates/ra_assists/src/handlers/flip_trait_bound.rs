@@ -4,7 +4,7 @@ use ra_syntax::{
     Direction, T,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: flip_trait_bound
 //
@@ -32,11 +32,16 @@ pub(crate) fn flip_trait_bound(ctx: AssistCtx) -> Option<Assist> {
         non_trivia_sibling(plus.clone().into(), Direction::Next)?,
     );
 
-    ctx.add_assist(AssistId("flip_trait_bound"), "Flip trait bounds", |edit| {
-        edit.target(plus.text_range());
-        edit.replace(before.text_range(), after.to_string());
-        edit.replace(after.text_range(), before.to_string());
-    })
+    ctx.add_assist(
+        AssistId("flip_trait_bound"),
+        AssistKind::RefactorRewrite,
+        "Flip trait bounds",
+        plus.text_range(),
+        |edit| {
+            edit.replace(before.text_range(), after.to_string());
+            edit.replace(after.text_range(), before.to_string());
+        },
+    )
 }
 
 #[cfg(test)]
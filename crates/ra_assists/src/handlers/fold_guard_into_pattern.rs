@@ -0,0 +1,199 @@
+use ra_ide_db::{defs::classify_name, search::SearchScope};
+use ra_syntax::{
+    ast::{self, AstNode, LiteralKind, NameOwner},
+    TextRange,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: fold_guard_into_pattern
+//
+// Replaces a match guard comparing the binding of the arm with a literal by
+// the literal pattern.
+//
+// ```
+// fn describe(n: u32) -> &'static str {
+//     match n {
+//         x <|>if x == 5 => "five",
+//         _ => "other",
+//     }
+// }
+// ```
+// ->
+// ```
+// fn describe(n: u32) -> &'static str {
+//     match n {
+//         5 => "five",
+//         _ => "other",
+//     }
+// }
+// ```
+pub(crate) fn fold_guard_into_pattern(ctx: AssistCtx) -> Option<Assist> {
+    let match_arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let bind_pat = match match_arm.pat()? {
+        ast::Pat::BindPat(it) if it.pat().is_none() && !it.is_ref() && !it.is_mutable() => it,
+        _ => return None,
+    };
+    let name = bind_pat.name()?;
+    let guard = match_arm.guard()?;
+    let guard_expr = guard.expr()?;
+    let conditions = conjuncts(guard_expr.clone());
+    let (idx, literal) = conditions
+        .iter()
+        .enumerate()
+        .find_map(|(idx, it)| Some((idx, compared_literal(it, name.text())?)))?;
+    let folded_range = conditions[idx].syntax().text_range();
+    let rest = conditions
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != idx)
+        .map(|(_, it)| it.syntax().to_string())
+        .collect::<Vec<_>>();
+
+    let def = classify_name(&ctx.sema, &name)?.definition();
+    let is_used = def
+        .find_usages(ctx.db, Some(SearchScope::single_file(ctx.frange.file_id)))
+        .iter()
+        .any(|it| !it.file_range.range.is_subrange(&folded_range));
+    let pat_text = if is_used {
+        format!("{} @ {}", name.text(), literal.syntax())
+    } else {
+        literal.syntax().to_string()
+    };
+
+    let pat_range = bind_pat.syntax().text_range();
+    ctx.add_assist(
+        AssistId("fold_guard_into_pattern"),
+        AssistKind::RefactorRewrite,
+        "Fold guard into pattern",
+        guard.syntax().text_range(),
+        |edit| {
+            edit.replace(pat_range, pat_text);
+            if rest.is_empty() {
+                edit.delete(TextRange::from_to(pat_range.end(), guard.syntax().text_range().end()));
+            } else {
+                edit.replace(guard_expr.syntax().text_range(), rest.join(" && "));
+            }
+            edit.set_cursor(pat_range.start());
+        },
+    )
+}
+
+/// The conditions of `expr` joined by `&&`.
+fn conjuncts(expr: ast::Expr) -> Vec<ast::Expr> {
+    if let ast::Expr::BinExpr(bin) = &expr {
+        if bin.op_kind() == Some(ast::BinOp::BooleanAnd) {
+            if let (Some(lhs), Some(rhs)) = (bin.lhs(), bin.rhs()) {
+                let mut res = conjuncts(lhs);
+                res.extend(conjuncts(rhs));
+                return res;
+            }
+        }
+    }
+    vec![expr]
+}
+
+/// The literal `expr` compares the binding `name` with, as in `name == 5`.
+fn compared_literal(expr: &ast::Expr, name: &str) -> Option<ast::Literal> {
+    let bin = match expr {
+        ast::Expr::BinExpr(it) if it.op_kind() == Some(ast::BinOp::EqualityTest) => it,
+        _ => return None,
+    };
+    let is_binding = |expr: &ast::Expr| match expr {
+        ast::Expr::PathExpr(it) => it.syntax().text() == name,
+        _ => false,
+    };
+    let literal = match (bin.lhs()?, bin.rhs()?) {
+        (lhs, ast::Expr::Literal(it)) if is_binding(&lhs) => it,
+        (ast::Expr::Literal(it), rhs) if is_binding(&rhs) => it,
+        _ => return None,
+    };
+    // Floats are not allowed in patterns.
+    match literal.kind() {
+        LiteralKind::FloatNumber { .. } => None,
+        _ => Some(literal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn fold_simple_guard() {
+        check_assist(
+            fold_guard_into_pattern,
+            r#"
+fn f(s: &str) -> u32 {
+    match s {
+        s if "foo" == <|>s => 1,
+        _ => 0,
+    }
+}"#,
+            r#"
+fn f(s: &str) -> u32 {
+    match s {
+        <|>"foo" => 1,
+        _ => 0,
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn fold_guard_keeps_used_binding() {
+        check_assist(
+            fold_guard_into_pattern,
+            "
+fn f(n: u32) -> u32 {
+    match n {
+        x <|>if x == 5 => x * 2,
+        _ => 0,
+    }
+}",
+            "
+fn f(n: u32) -> u32 {
+    match n {
+        <|>x @ 5 => x * 2,
+        _ => 0,
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn fold_part_of_compound_guard() {
+        check_assist(
+            fold_guard_into_pattern,
+            "
+fn f(n: u32, enabled: bool) -> u32 {
+    match n {
+        x <|>if enabled && x == 5 => 1,
+        _ => 0,
+    }
+}",
+            "
+fn f(n: u32, enabled: bool) -> u32 {
+    match n {
+        <|>5 if enabled => 1,
+        _ => 0,
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn fold_guard_not_applicable_to_non_literal_comparison() {
+        check_assist_not_applicable(
+            fold_guard_into_pattern,
+            "
+fn f(n: u32, m: u32) -> u32 {
+    match n {
+        x <|>if x == m => 1,
+        _ => 0,
+    }
+}",
+        );
+    }
+}
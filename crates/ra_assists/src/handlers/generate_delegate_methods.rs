@@ -0,0 +1,295 @@
+use hir::{Adt, Semantics};
+use ra_fmt::leading_indent;
+use ra_ide_db::RootDatabase;
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner, VisibilityOwner},
+    SyntaxKind::{ATTR, COMMENT, VISIBILITY, WHITESPACE},
+    SyntaxNode, TextUnit,
+};
+
+use crate::{
+    ast_transform::{self, QualifyPaths},
+    utils::impl_header,
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: generate_delegate_methods
+//
+// Generates a method forwarding to a method of a field.
+//
+// ```
+// struct Age(u8);
+// impl Age {
+//     pub fn age(&self) -> u8 { self.0 }
+// }
+//
+// struct Person {
+//     ag<|>e: Age,
+// }
+// ```
+// ->
+// ```
+// struct Age(u8);
+// impl Age {
+//     pub fn age(&self) -> u8 { self.0 }
+// }
+//
+// struct Person {
+//     age: Age,
+// }
+//
+// impl Person {
+//     <|>pub fn age(&self) -> u8 {
+//         self.age.age()
+//     }
+// }
+// ```
+pub(crate) fn generate_delegate_methods(ctx: AssistCtx) -> Option<Assist> {
+    let field = ctx.find_node_at_offset::<ast::RecordFieldDef>()?;
+    let field_name = field.name()?;
+    let strukt = field.syntax().ancestors().find_map(ast::StructDef::cast)?;
+    let strukt_name = strukt.name()?;
+    let sema = ctx.sema;
+    let db = ctx.db;
+    let inner = sema.to_def(&field)?.ty(db).as_adt()?;
+    let outer = Adt::from(sema.to_def(&strukt)?);
+
+    let file = strukt.syntax().ancestors().last()?;
+    let outer_impls = inherent_impls(sema, &file, outer);
+    let existing = outer_impls
+        .iter()
+        .flat_map(methods)
+        .filter_map(|it| it.name())
+        .map(|it| it.text().to_string())
+        .collect::<Vec<_>>();
+    let delegates = inherent_impls(sema, &file, inner)
+        .iter()
+        .flat_map(methods)
+        .filter(|it| it.visibility().map_or(false, |it| it.syntax().text() == "pub"))
+        .filter(|it| it.param_list().and_then(|it| it.self_param()).is_some())
+        .filter(|it| it.name().map_or(false, |it| !existing.contains(&it.text().to_string())))
+        .collect::<Vec<_>>();
+    if delegates.is_empty() {
+        return None;
+    }
+
+    let target_impl = outer_impls.into_iter().next();
+    let target_scope = sema.scope(strukt.syntax());
+    let mut group = ctx.add_assist_group("Generate delegate method");
+    for method in delegates {
+        let source_scope = sema.scope(method.syntax());
+        let method =
+            ast_transform::apply(&QualifyPaths::new(&target_scope, &source_scope, db), method);
+        let (signature, args) = match signature(&method) {
+            Some(it) => it,
+            None => continue,
+        };
+        let name = method.name()?;
+        let call = format!(
+            "self.{}.{}({}){}",
+            field_name.text(),
+            name.text(),
+            args.join(", "),
+            if method.is_async() { ".await" } else { "" }
+        );
+        let label = format!("Generate delegate method `{}`", name.text());
+        group.add_assist(
+            AssistId("generate_delegate_methods"),
+            AssistKind::Refactor,
+            label,
+            field.syntax().text_range(),
+            |edit| {
+                let items = target_impl.as_ref().and_then(|it| it.item_list());
+                if let Some(last_item) = items.as_ref().and_then(|it| it.impl_items().last()) {
+                    let indent = leading_indent(last_item.syntax()).unwrap_or_default();
+                    let prefix = format!("\n\n{}", indent);
+                    let offset = last_item.syntax().text_range().end();
+                    edit.insert(
+                        offset,
+                        format!("{}{}", prefix, delegate(&signature, &call, &indent)),
+                    );
+                    edit.set_cursor(offset + TextUnit::of_str(&prefix));
+                } else if let Some(items) = items {
+                    let indent =
+                        leading_indent(target_impl.as_ref().unwrap().syntax()).unwrap_or_default();
+                    let inner_indent = format!("{}    ", indent);
+                    let prefix = format!("{{\n{}", inner_indent);
+                    let range = items.syntax().text_range();
+                    edit.replace(
+                        range,
+                        format!(
+                            "{}{}\n{}}}",
+                            prefix,
+                            delegate(&signature, &call, &inner_indent),
+                            indent
+                        ),
+                    );
+                    edit.set_cursor(range.start() + TextUnit::of_str(&prefix));
+                } else {
+                    let nominal = ast::NominalDef::StructDef(strukt.clone());
+                    let prefix =
+                        format!("\n\n{} {{\n    ", impl_header(&nominal, &strukt_name, None));
+                    let offset = strukt.syntax().text_range().end();
+                    edit.insert(
+                        offset,
+                        format!("{}{}\n}}", prefix, delegate(&signature, &call, "    ")),
+                    );
+                    edit.set_cursor(offset + TextUnit::of_str(&prefix));
+                }
+            },
+        );
+    }
+    group.finish()
+}
+
+fn inherent_impls(
+    sema: &Semantics<RootDatabase>,
+    file: &SyntaxNode,
+    adt: Adt,
+) -> Vec<ast::ImplDef> {
+    file.descendants()
+        .filter_map(ast::ImplDef::cast)
+        .filter(|it| it.target_trait().is_none())
+        .filter(|it| sema.to_def(it).and_then(|it| it.target_ty(sema.db).as_adt()) == Some(adt))
+        .collect()
+}
+
+fn methods(impl_def: &ast::ImplDef) -> Vec<ast::FnDef> {
+    impl_def
+        .item_list()
+        .into_iter()
+        .flat_map(|it| it.impl_items())
+        .filter_map(|it| match it {
+            ast::ImplItem::FnDef(it) => Some(it),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The signature of `method` without its visibility, and the names of its
+/// parameters, unless some of them are patterns.
+fn signature(method: &ast::FnDef) -> Option<(String, Vec<String>)> {
+    let args = method
+        .param_list()?
+        .params()
+        .map(|it| match it.pat()? {
+            ast::Pat::BindPat(it) => Some(it.name()?.text().to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let start = method
+        .syntax()
+        .children_with_tokens()
+        .find(|it| match it.kind() {
+            ATTR | COMMENT | VISIBILITY | WHITESPACE => false,
+            _ => true,
+        })?
+        .text_range()
+        .start();
+    let end = method.body()?.syntax().text_range().start();
+    let offset = method.syntax().text_range().start();
+    let text = method.syntax().text().slice(start - offset..end - offset).to_string();
+    Some((text.trim_end().to_string(), args))
+}
+
+fn delegate(signature: &str, call: &str, indent: &str) -> String {
+    format!("pub {} {{\n{indent}    {}\n{indent}}}", signature, call, indent = indent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn delegate_ref_self_method() {
+        check_assist(
+            generate_delegate_methods,
+            "
+struct Inner;
+impl Inner {
+    pub fn get(&self, idx: usize) -> u32 { 0 }
+}
+struct Outer { in<|>ner: Inner }
+impl Outer {
+    fn new() -> Outer { Outer { inner: Inner } }
+}",
+            "
+struct Inner;
+impl Inner {
+    pub fn get(&self, idx: usize) -> u32 { 0 }
+}
+struct Outer { inner: Inner }
+impl Outer {
+    fn new() -> Outer { Outer { inner: Inner } }
+
+    <|>pub fn get(&self, idx: usize) -> u32 {
+        self.inner.get(idx)
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn delegate_mut_self_method() {
+        check_assist(
+            generate_delegate_methods,
+            "
+struct Inner;
+impl Inner {
+    pub fn set(&mut self, value: u32) {}
+}
+struct Outer { in<|>ner: Inner }",
+            "
+struct Inner;
+impl Inner {
+    pub fn set(&mut self, value: u32) {}
+}
+struct Outer { inner: Inner }
+
+impl Outer {
+    <|>pub fn set(&mut self, value: u32) {
+        self.inner.set(value)
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn delegate_skips_existing_methods() {
+        check_assist(
+            generate_delegate_methods,
+            "
+struct Inner;
+impl Inner {
+    pub fn get(&self) -> u32 { 0 }
+    pub fn len(&self) -> usize { 0 }
+}
+struct Outer { in<|>ner: Inner }
+impl Outer {}
+impl Outer {
+    pub fn get(&self) -> u32 { 1 }
+}",
+            "
+struct Inner;
+impl Inner {
+    pub fn get(&self) -> u32 { 0 }
+    pub fn len(&self) -> usize { 0 }
+}
+struct Outer { inner: Inner }
+impl Outer {
+    <|>pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+impl Outer {
+    pub fn get(&self) -> u32 { 1 }
+}",
+        );
+    }
+
+    #[test]
+    fn delegate_not_applicable_to_non_local_type() {
+        check_assist_not_applicable(generate_delegate_methods, "struct Outer { in<|>ner: u32 }");
+    }
+}
@@ -0,0 +1,185 @@
+use hir::{HasSource, PathResolution, Semantics};
+use ra_ide_db::{defs::Definition, RootDatabase};
+use ra_syntax::{
+    ast::{self, AstNode, AstToken, NameOwner},
+    TextRange, TextUnit,
+};
+
+use crate::{utils::needs_parens_when_inlined, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: inline_constant
+//
+// Replaces a use of a constant with its value.
+//
+// ```
+// const SIZE: usize = 4 + 4;
+// fn area() -> usize { SIZE<|> * 2 }
+// ```
+// ->
+// ```
+// const SIZE: usize = 4 + 4;
+// fn area() -> usize { (4 + 4) * 2 }
+// ```
+pub(crate) fn inline_constant(ctx: AssistCtx) -> Option<Assist> {
+    if let Some(const_def) = ctx.find_node_at_offset::<ast::ConstDef>() {
+        if const_def.name()?.syntax().text_range().contains_inclusive(ctx.frange.range.start()) {
+            return inline_into_all_usages(ctx, const_def);
+        }
+    }
+    let usage = ctx.find_node_at_offset::<ast::PathExpr>()?;
+    let konst = match ctx.sema.resolve_path(&usage.path()?)? {
+        PathResolution::Def(hir::ModuleDef::Const(it)) => it,
+        _ => return None,
+    };
+    let source = konst.source(ctx.db);
+    if source.file_id != ctx.frange.file_id.into() {
+        return None;
+    }
+    let const_def = ctx
+        .covering_node_for_range(source.value.syntax().text_range())
+        .ancestors()
+        .find_map(ast::ConstDef::cast)?;
+    let value = const_def.body()?;
+    let text = inlined_text(&ctx.sema, &value, &usage)?;
+
+    let range = usage.syntax().text_range();
+    ctx.add_assist(
+        AssistId("inline_constant"),
+        AssistKind::RefactorInline,
+        "Inline constant value",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+fn inline_into_all_usages(ctx: AssistCtx, const_def: ast::ConstDef) -> Option<Assist> {
+    let value = const_def.body()?;
+    let konst = ctx.sema.to_def(&const_def)?;
+    let refs = Definition::ModuleDef(konst.into()).find_usages(ctx.db, None);
+    if refs.is_empty() {
+        return None;
+    }
+    let mut replacements = Vec::new();
+    for reference in refs {
+        if reference.file_range.file_id != ctx.frange.file_id {
+            return None;
+        }
+        let usage = ctx
+            .covering_node_for_range(reference.file_range.range)
+            .ancestors()
+            .find_map(ast::PathExpr::cast)?;
+        let text = inlined_text(&ctx.sema, &value, &usage)?;
+        replacements.push((usage.syntax().text_range(), text));
+    }
+
+    let delete_range = match const_def
+        .syntax()
+        .next_sibling_or_token()
+        .and_then(|it| ast::Whitespace::cast(it.as_token()?.clone()))
+    {
+        Some(ws) => const_def.syntax().text_range().extend_to(&ws.syntax().text_range()),
+        None => const_def.syntax().text_range(),
+    };
+    let target = const_def.syntax().text_range();
+    let mut group = ctx.add_assist_group("Inline constant");
+    group.add_assist(
+        AssistId("inline_constant"),
+        AssistKind::RefactorInline,
+        "Inline into all usages and remove",
+        target,
+        |edit| {
+            let cursor = replacements
+                .iter()
+                .filter(|(range, _)| range.end() <= delete_range.start())
+                .fold(delete_range.start(), |acc, (range, text)| {
+                    acc + TextUnit::of_str(text) - range.len()
+                });
+            edit.delete(delete_range);
+            for (range, text) in replacements {
+                edit.replace(range, text);
+            }
+            edit.set_cursor(cursor);
+        },
+    );
+    group.finish()
+}
+
+/// The text replacing `usage` by `value`, unless some paths of `value` mean
+/// something else at `usage`.
+fn inlined_text(
+    sema: &Semantics<RootDatabase>,
+    value: &ast::Expr,
+    usage: &ast::PathExpr,
+) -> Option<String> {
+    let usage_scope = sema.scope(usage.syntax());
+    let paths_stay_valid = value
+        .syntax()
+        .descendants()
+        .filter_map(ast::Path::cast)
+        .filter(|it| it.syntax().parent().and_then(ast::Path::cast).is_none())
+        .all(|path| {
+            let here = sema.resolve_path(&path);
+            let there = hir::Path::from_ast(path).and_then(|it| usage_scope.resolve_hir_path(&it));
+            here.is_some() && here == there
+        });
+    if !paths_stay_valid {
+        return None;
+    }
+    let text = value.syntax().to_string();
+    Some(if needs_parens_when_inlined(value, usage) { format!("({})", text) } else { text })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn inline_single_use_with_parens() {
+        check_assist(
+            inline_constant,
+            "
+const A: u32 = 1 + 2;
+fn f() -> u32 { A<|> * 3 + A }",
+            "
+const A: u32 = 1 + 2;
+fn f() -> u32 { <|>(1 + 2) * 3 + A }",
+        );
+    }
+
+    #[test]
+    fn inline_into_all_usages() {
+        check_assist(
+            inline_constant,
+            "
+const B: u32 = 2;
+const A<|>: u32 = B * 2;
+fn f() -> u32 {
+    let x = A;
+    x + A * A
+}",
+            "
+const B: u32 = 2;
+<|>fn f() -> u32 {
+    let x = B * 2;
+    x + (B * 2) * (B * 2)
+}",
+        );
+    }
+
+    #[test]
+    fn inline_constant_not_applicable_when_paths_change_meaning() {
+        check_assist_not_applicable(
+            inline_constant,
+            "
+mod m {
+    const B: u32 = 2;
+    pub const A: u32 = B + 1;
+}
+fn f() -> u32 { m::A<|> }",
+        );
+    }
+}
@@ -5,7 +5,10 @@ use ra_syntax::{
 };
 use test_utils::tested_by;
 
-use crate::{assist_ctx::ActionBuilder, Assist, AssistCtx, AssistId};
+use crate::{
+    assist_ctx::ActionBuilder, utils::needs_parens_when_inlined, Assist, AssistCtx, AssistId,
+    AssistKind,
+};
 
 // Assist: inline_local_variable
 //
@@ -27,7 +30,10 @@ pub(crate) fn inline_local_variable(ctx: AssistCtx) -> Option<Assist> {
     let let_stmt = ctx.find_node_at_offset::<ast::LetStmt>()?;
     let bind_pat = match let_stmt.pat()? {
         ast::Pat::BindPat(pat) => pat,
-        _ => return None,
+        _ => {
+            tested_by!(not_applicable_to_destructuring_let);
+            return None;
+        }
     };
     if bind_pat.is_mutable() {
         tested_by!(test_not_inline_mut_variable);
@@ -60,46 +66,13 @@ pub(crate) fn inline_local_variable(ctx: AssistCtx) -> Option<Assist> {
         let_stmt.syntax().text_range()
     };
 
-    let mut wrap_in_parens = vec![true; refs.len()];
-
-    for (i, desc) in refs.iter().enumerate() {
+    let mut wrap_in_parens = Vec::with_capacity(refs.len());
+    for desc in refs.iter() {
         let usage_node = ctx
             .covering_node_for_range(desc.file_range.range)
             .ancestors()
             .find_map(ast::PathExpr::cast)?;
-        let usage_parent_option = usage_node.syntax().parent().and_then(ast::Expr::cast);
-        let usage_parent = match usage_parent_option {
-            Some(u) => u,
-            None => {
-                wrap_in_parens[i] = false;
-                continue;
-            }
-        };
-
-        wrap_in_parens[i] = match (&initializer_expr, usage_parent) {
-            (ast::Expr::CallExpr(_), _)
-            | (ast::Expr::IndexExpr(_), _)
-            | (ast::Expr::MethodCallExpr(_), _)
-            | (ast::Expr::FieldExpr(_), _)
-            | (ast::Expr::TryExpr(_), _)
-            | (ast::Expr::RefExpr(_), _)
-            | (ast::Expr::Literal(_), _)
-            | (ast::Expr::TupleExpr(_), _)
-            | (ast::Expr::ArrayExpr(_), _)
-            | (ast::Expr::ParenExpr(_), _)
-            | (ast::Expr::PathExpr(_), _)
-            | (ast::Expr::BlockExpr(_), _)
-            | (_, ast::Expr::CallExpr(_))
-            | (_, ast::Expr::TupleExpr(_))
-            | (_, ast::Expr::ArrayExpr(_))
-            | (_, ast::Expr::ParenExpr(_))
-            | (_, ast::Expr::ForExpr(_))
-            | (_, ast::Expr::WhileExpr(_))
-            | (_, ast::Expr::BreakExpr(_))
-            | (_, ast::Expr::ReturnExpr(_))
-            | (_, ast::Expr::MatchExpr(_)) => false,
-            _ => true,
-        };
+        wrap_in_parens.push(needs_parens_when_inlined(&initializer_expr, &usage_node));
     }
 
     let init_str = initializer_expr.syntax().text().to_string();
@@ -107,7 +80,9 @@ pub(crate) fn inline_local_variable(ctx: AssistCtx) -> Option<Assist> {
 
     ctx.add_assist(
         AssistId("inline_local_variable"),
+        AssistKind::RefactorInline,
         "Inline variable",
+        let_stmt.syntax().text_range(),
         move |edit: &mut ActionBuilder| {
             edit.delete(delete_range);
             for (desc, should_wrap) in refs.iter().zip(wrap_in_parens) {
@@ -122,9 +97,10 @@ pub(crate) fn inline_local_variable(ctx: AssistCtx) -> Option<Assist> {
 
 #[cfg(test)]
 mod tests {
-    use test_utils::covers;
-
-    use crate::helpers::{check_assist, check_assist_not_applicable};
+    use crate::{
+        helpers::{check_assist, check_assist_not_applicable_because},
+        marks,
+    };
 
     use super::*;
 
@@ -330,14 +306,14 @@ fn foo() {
 
     #[test]
     fn test_not_inline_mut_variable() {
-        covers!(test_not_inline_mut_variable);
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             inline_local_variable,
             r"
 fn foo() {
     let mut a<|> = 1 + 1;
     a + 1;
 }",
+            &marks::test_not_inline_mut_variable,
         );
     }
 
@@ -663,21 +639,20 @@ fn foo() {
 
     #[test]
     fn test_not_applicable_if_variable_unused() {
-        covers!(test_not_applicable_if_variable_unused);
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             inline_local_variable,
             r"
 fn foo() {
     let <|>a = 0;
 }
             ",
+            &marks::test_not_applicable_if_variable_unused,
         )
     }
 
     #[test]
     fn not_applicable_outside_of_bind_pat() {
-        covers!(not_applicable_outside_of_bind_pat);
-        check_assist_not_applicable(
+        check_assist_not_applicable_because(
             inline_local_variable,
             r"
 fn main() {
@@ -685,6 +660,21 @@ fn main() {
     x * 4;
 }
 ",
+            &marks::not_applicable_outside_of_bind_pat,
+        )
+    }
+
+    #[test]
+    fn not_applicable_to_destructuring_let() {
+        check_assist_not_applicable_because(
+            inline_local_variable,
+            r"
+fn main() {
+    let (x, y)<|> = (1, 2);
+    x + y;
+}
+",
+            &marks::not_applicable_to_destructuring_let,
         )
     }
 }
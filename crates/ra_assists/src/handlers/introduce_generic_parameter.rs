@@ -0,0 +1,205 @@
+use hir::PathResolution;
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner, TypeAscriptionOwner, TypeParamsOwner},
+    SyntaxElement,
+    SyntaxKind::{IDENT, WHITESPACE},
+    TextUnit, T,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: introduce_generic_parameter
+//
+// Replaces the concrete type of a function parameter with a generic parameter
+// bounded by `Into`.
+//
+// ```
+// fn greet(name: <|>String) {
+//     println!("Hello, {}!", name);
+// }
+// ```
+// ->
+// ```
+// fn greet<S: Into<String>>(name: S) {
+//     let name: String = name.into();
+//     println!("Hello, {}!", name);
+// }
+// ```
+pub(crate) fn introduce_generic_parameter(ctx: AssistCtx) -> Option<Assist> {
+    let path_type = ctx.find_node_at_offset::<ast::PathType>()?;
+    let param = path_type.syntax().parent().and_then(ast::Param::cast)?;
+    let fn_def = param.syntax().ancestors().find_map(ast::FnDef::cast)?;
+    let param_list = fn_def.param_list()?;
+    let path = path_type.path()?;
+    let type_name = path.segment()?.name_ref()?;
+    match ctx.sema.resolve_path(&path) {
+        Some(PathResolution::TypeParam(_)) | Some(PathResolution::SelfType(_)) => return None,
+        _ => (),
+    }
+
+    let ty = path_type.syntax().text().to_string();
+    let params = param_list
+        .params()
+        .filter(|it| match it.ascribed_type() {
+            Some(ast::TypeRef::PathType(it)) => it.syntax().text() == ty.as_str(),
+            _ => false,
+        })
+        .collect::<Vec<_>>();
+    let existing = fn_def
+        .type_param_list()
+        .into_iter()
+        .flat_map(|it| it.type_params())
+        .filter_map(|it| it.name())
+        .map(|it| it.text().to_string())
+        .collect::<Vec<_>>();
+    if existing.iter().any(|it| it == type_name.text().as_str()) {
+        return None;
+    }
+    let generic_name = generic_name(type_name.text(), &existing);
+    let generic_param = format!("{}: Into<{}>", generic_name, ty);
+
+    let body = fn_def.body().and_then(|it| it.block());
+    let conversions = params
+        .iter()
+        .filter_map(|param| match param.pat()? {
+            ast::Pat::BindPat(it) => it.name(),
+            _ => None,
+        })
+        .filter(|name| body.as_ref().map_or(false, |body| uses_name(body, name)))
+        .map(|name| format!("let {}: {} = {}.into();", name.text(), ty, name.text()))
+        .collect::<Vec<_>>();
+
+    ctx.add_assist(
+        AssistId("introduce_generic_parameter"),
+        AssistKind::RefactorRewrite,
+        "Introduce generic parameter",
+        path_type.syntax().text_range(),
+        |edit| {
+            let (offset, text) = match fn_def.type_param_list() {
+                Some(type_params) => {
+                    let r_angle = type_params.syntax().last_token().filter(|it| it.kind() == T![>]);
+                    let offset = r_angle.map_or(type_params.syntax().text_range().end(), |it| {
+                        it.text_range().start()
+                    });
+                    (offset, format!(", {}", generic_param))
+                }
+                None => {
+                    let offset = match fn_def.name() {
+                        Some(name) => name.syntax().text_range().end(),
+                        None => param_list.syntax().text_range().start(),
+                    };
+                    (offset, format!("<{}>", generic_param))
+                }
+            };
+            edit.set_cursor(offset + TextUnit::from_usize(text.find("Into").unwrap()));
+            edit.insert(offset, text);
+            for param in params.iter() {
+                if let Some(ty) = param.ascribed_type() {
+                    edit.replace(ty.syntax().text_range(), generic_name.clone());
+                }
+            }
+            if let Some((offset, text)) =
+                body.and_then(|it| conversions_insertion(&it, &conversions))
+            {
+                edit.insert(offset, text);
+            }
+        },
+    )
+}
+
+fn generic_name(type_name: &str, existing: &[String]) -> String {
+    let first = type_name.chars().next().map(|it| it.to_ascii_uppercase().to_string());
+    first
+        .into_iter()
+        .chain(Some("T".to_string()))
+        .chain((1..).map(|idx| format!("T{}", idx)))
+        .find(|it| !existing.contains(it))
+        .unwrap()
+}
+
+/// Checks textually, so that uses inside macro calls are found as well.
+fn uses_name(body: &ast::Block, name: &ast::Name) -> bool {
+    body.syntax()
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .any(|it| it.kind() == IDENT && it.text() == name.text())
+}
+
+/// Returns the offset and the text adding the `conversions` statements at the
+/// start of the `body`.
+fn conversions_insertion(body: &ast::Block, conversions: &[String]) -> Option<(TextUnit, String)> {
+    if conversions.is_empty() {
+        return None;
+    }
+    let l_curly = body
+        .syntax()
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|it| it.kind() == T!['{'])?;
+    let mut buf = String::new();
+    match l_curly.next_token().filter(|it| it.kind() == WHITESPACE && it.text().contains('\n')) {
+        Some(ws) => {
+            let indent = ws.text().rsplit('\n').next().unwrap_or_default();
+            for conversion in conversions {
+                buf.push_str(&format!("\n{}{}", indent, conversion));
+            }
+        }
+        None => {
+            for conversion in conversions {
+                buf.push_str(&format!(" {}", conversion));
+            }
+        }
+    }
+    Some((l_curly.text_range().end(), buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn introduce_generic_parameter_for_used_param() {
+        check_assist(
+            introduce_generic_parameter,
+            r#"
+struct Greeter;
+impl Greeter {
+    fn greet<'a>(&self, prefix: &'a str, name: <|>String) -> usize {
+        let s = format!("{}{}", prefix, name);
+        s.len()
+    }
+}"#,
+            r#"
+struct Greeter;
+impl Greeter {
+    fn greet<'a, S: <|>Into<String>>(&self, prefix: &'a str, name: S) -> usize {
+        let name: String = name.into();
+        let s = format!("{}{}", prefix, name);
+        s.len()
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn introduce_generic_parameter_replaces_every_occurrence() {
+        check_assist(
+            introduce_generic_parameter,
+            r#"
+struct Path;
+fn join(a: <|>Path, b: Path, _c: Path) -> Path { concat(a, b) }"#,
+            r#"
+struct Path;
+fn join<P: <|>Into<Path>>(a: P, b: P, _c: P) -> Path { let a: Path = a.into(); let b: Path = b.into(); concat(a, b) }"#,
+        );
+    }
+
+    #[test]
+    fn introduce_generic_parameter_not_applicable_to_generic_param() {
+        check_assist_not_applicable(
+            introduce_generic_parameter,
+            "fn f<T: Clone>(x: <|>T) -> T { x.clone() }",
+        );
+    }
+}
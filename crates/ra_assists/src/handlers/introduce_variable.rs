@@ -1,19 +1,20 @@
-use format_buf::format;
+use ra_fmt::{leading_indent, reindent};
 use ra_syntax::{
-    ast::{self, AstNode},
+    ast::{self, AstNode, AttrsOwner, TypeAscriptionOwner},
     SyntaxKind::{
-        BLOCK_EXPR, BREAK_EXPR, COMMENT, LAMBDA_EXPR, LOOP_EXPR, MATCH_ARM, PATH_EXPR, RETURN_EXPR,
-        WHITESPACE,
+        BLOCK, BLOCK_EXPR, BREAK_EXPR, COMMENT, FN_DEF, ITEM_LIST, LAMBDA_EXPR, LOOP_EXPR,
+        MATCH_ARM, MODULE, PATH_EXPR, RETURN_EXPR, SOURCE_FILE, TOKEN_TREE, WHITESPACE,
     },
     SyntaxNode, TextUnit,
 };
 use test_utils::tested_by;
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{utils::range_with_leading_comments, Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: introduce_variable
 //
-// Extracts subexpression into a variable.
+// Extracts subexpression into a variable, or into a constant where it is
+// evaluated at compile time.
 //
 // ```
 // fn main() {
@@ -29,6 +30,7 @@ use crate::{Assist, AssistCtx, AssistId};
 // ```
 pub(crate) fn introduce_variable(ctx: AssistCtx) -> Option<Assist> {
     if ctx.frange.range.is_empty() {
+        tested_by!(introduce_var_for_empty_selection_is_not_applicable);
         return None;
     }
     let node = ctx.covering_element();
@@ -36,58 +38,127 @@ pub(crate) fn introduce_variable(ctx: AssistCtx) -> Option<Assist> {
         tested_by!(introduce_var_in_comment_is_not_applicable);
         return None;
     }
-    let expr = node.ancestors().find_map(valid_target_expr)?;
-    let (anchor_stmt, wrap_in_block) = anchor_stmt(expr.clone())?;
+    let (expr, range, text) = if node.ancestors().any(|it| it.kind() == TOKEN_TREE) {
+        // Look for the expression in the expansion of the macro call, and
+        // anchor the variable on the call itself.
+        let range = ctx.frange.range;
+        let token = ctx.sema.descend_into_macros(ctx.token_at_offset().right_biased()?);
+        token
+            .parent()
+            .ancestors()
+            .filter_map(valid_target_expr)
+            .find(|it| ctx.original_range(it.syntax()) == Some(range))?;
+        let macro_call = node.ancestors().find_map(ast::MacroCall::cast)?;
+        (ast::Expr::from(macro_call), range, ctx.source_text(range))
+    } else {
+        let expr = node.ancestors().find_map(valid_target_expr)?;
+        let range = expr.syntax().text_range();
+        let text = expr.syntax().to_string();
+        (expr, range, text)
+    };
+    // A `let` can't be used where the expression is evaluated at compile time.
+    if let Some(ty) = const_context_type(&ctx, &expr) {
+        let anchor = expr.syntax().ancestors().find(is_item_position)?;
+        let separator = if anchor.parent().map(|it| it.kind()) == Some(BLOCK) {
+            format!("\n{}", leading_indent(&anchor).unwrap_or_default())
+        } else {
+            "\n\n".to_string()
+        };
+        let start = range_with_leading_comments(&anchor).start();
+        return ctx.add_assist(
+            AssistId("introduce_variable"),
+            AssistKind::RefactorExtract,
+            "Extract into constant",
+            range,
+            move |edit| {
+                edit.replace(range, "VAR_NAME");
+                edit.insert(start, format!("const VAR_NAME: {} = {};{}", ty, text, separator));
+                edit.set_cursor(start + TextUnit::of_str("const "));
+            },
+        );
+    }
+    let (anchor_stmt, wrap_in_block) = anchor_stmt(expr)?;
+    if wrap_in_block {
+        return ctx.add_assist(
+            AssistId("introduce_variable"),
+            AssistKind::RefactorExtract,
+            "Extract into variable",
+            range,
+            move |edit| {
+                // The body of a match arm or of a closure becomes a block, with
+                // the variable as its first statement.
+                let body_range = anchor_stmt.text_range();
+                let mut body = anchor_stmt.text().to_string();
+                body.replace_range(
+                    (range.start() - body_range.start()).to_usize()
+                        ..(range.end() - body_range.start()).to_usize(),
+                    "var_name",
+                );
+                let indent = leading_indent(&anchor_stmt).unwrap_or_default();
+                let inner_indent = format!("{}    ", indent);
+                let prefix = format!("{{\n{}", inner_indent);
+                edit.set_cursor(
+                    body_range.start() + TextUnit::of_str(&prefix) + TextUnit::of_str("let "),
+                );
+                edit.replace(
+                    body_range,
+                    format!(
+                        "{}let var_name = {};\n{}{}\n{}}}",
+                        prefix,
+                        text,
+                        inner_indent,
+                        reindent(&body, "    "),
+                        indent
+                    ),
+                );
+            },
+        );
+    }
     let indent = anchor_stmt.prev_sibling_or_token()?.as_token()?.clone();
     if indent.kind() != WHITESPACE {
+        tested_by!(introduce_var_without_indent_is_not_applicable);
         return None;
     }
-    ctx.add_assist(AssistId("introduce_variable"), "Extract into variable", move |edit| {
-        let mut buf = String::new();
-
-        let cursor_offset = if wrap_in_block {
-            buf.push_str("{ let var_name = ");
-            TextUnit::of_str("{ let ")
-        } else {
-            buf.push_str("let var_name = ");
-            TextUnit::of_str("let ")
-        };
-        format!(buf, "{}", expr.syntax());
-        let full_stmt = ast::ExprStmt::cast(anchor_stmt.clone());
-        let is_full_stmt = if let Some(expr_stmt) = &full_stmt {
-            Some(expr.syntax().clone()) == expr_stmt.expr().map(|e| e.syntax().clone())
-        } else {
-            false
-        };
-        if is_full_stmt {
-            tested_by!(test_introduce_var_expr_stmt);
-            if !full_stmt.unwrap().has_semi() {
-                buf.push_str(";");
-            }
-            edit.replace(expr.syntax().text_range(), buf);
-        } else {
-            buf.push_str(";");
-
-            // We want to maintain the indent level,
-            // but we do not want to duplicate possible
-            // extra newlines in the indent block
-            let text = indent.text();
-            if text.starts_with('\n') {
-                buf.push_str("\n");
-                buf.push_str(text.trim_start_matches('\n'));
+    ctx.add_assist(
+        AssistId("introduce_variable"),
+        AssistKind::RefactorExtract,
+        "Extract into variable",
+        range,
+        move |edit| {
+            let mut buf = String::from("let var_name = ");
+            buf.push_str(&text);
+            let full_stmt = ast::ExprStmt::cast(anchor_stmt.clone());
+            let is_full_stmt = if let Some(expr_stmt) = &full_stmt {
+                Some(range) == expr_stmt.expr().map(|e| e.syntax().text_range())
             } else {
-                buf.push_str(text);
-            }
+                false
+            };
+            if is_full_stmt {
+                tested_by!(test_introduce_var_expr_stmt);
+                if !full_stmt.unwrap().has_semi() {
+                    buf.push_str(";");
+                }
+                edit.replace(range, buf);
+            } else {
+                buf.push_str(";");
 
-            edit.target(expr.syntax().text_range());
-            edit.replace(expr.syntax().text_range(), "var_name".to_string());
-            edit.insert(anchor_stmt.text_range().start(), buf);
-            if wrap_in_block {
-                edit.insert(anchor_stmt.text_range().end(), " }");
+                // We want to maintain the indent level,
+                // but we do not want to duplicate possible
+                // extra newlines in the indent block
+                let text = indent.text();
+                if text.starts_with('\n') {
+                    buf.push_str("\n");
+                    buf.push_str(text.trim_start_matches('\n'));
+                } else {
+                    buf.push_str(text);
+                }
+
+                edit.replace(range, "var_name".to_string());
+                edit.insert(anchor_stmt.text_range().start(), buf);
             }
-        }
-        edit.set_cursor(anchor_stmt.text_range().start() + cursor_offset);
-    })
+            edit.set_cursor(anchor_stmt.text_range().start() + TextUnit::of_str("let "));
+        },
+    )
 }
 
 /// Check whether the node is a valid expression which can be extracted to a variable.
@@ -104,6 +175,77 @@ fn valid_target_expr(node: SyntaxNode) -> Option<ast::Expr> {
     }
 }
 
+/// The type of `expr` when it's evaluated at compile time, in the initializer
+/// of a `const` or a `static`, in an enum discriminant or in an array length.
+fn const_context_type(ctx: &AssistCtx, expr: &ast::Expr) -> Option<String> {
+    for node in expr.syntax().ancestors() {
+        if node.kind() == FN_DEF || node.kind() == LAMBDA_EXPR {
+            return None;
+        }
+        let parent = match node.parent() {
+            Some(it) => it,
+            None => return None,
+        };
+        let is_node = |it: Option<ast::Expr>| it.map_or(false, |it| it.syntax() == &node);
+        if let Some(array) = ast::ArrayType::cast(parent.clone()) {
+            if is_node(array.expr()) {
+                return Some("usize".to_string());
+            }
+        }
+        if let Some(array) = ast::ArrayExpr::cast(parent.clone()) {
+            if let ast::ArrayExprKind::Repeat { repeat, .. } = array.kind() {
+                if is_node(repeat) {
+                    return Some("usize".to_string());
+                }
+            }
+        }
+        if let Some(variant) = ast::EnumVariant::cast(parent.clone()) {
+            if is_node(variant.expr()) {
+                let enum_def = variant.syntax().ancestors().find_map(ast::EnumDef::cast)?;
+                return Some(repr_type(&enum_def).unwrap_or_else(|| "isize".to_string()));
+            }
+        }
+        let item = match (ast::ConstDef::cast(parent.clone()), ast::StaticDef::cast(parent)) {
+            (Some(it), _) if is_node(it.body()) => it.ascribed_type(),
+            (_, Some(it)) if is_node(it.body()) => it.ascribed_type(),
+            _ => continue,
+        };
+        if &node == expr.syntax() {
+            return Some(item?.syntax().to_string());
+        }
+        let ty = ctx.sema.type_of_expr(expr)?.display(ctx.db).to_string();
+        return if ty.contains("{unknown}") { None } else { Some(ty) };
+    }
+    None
+}
+
+/// The integer type of the `#[repr(..)]` attribute of `enum_def`.
+fn repr_type(enum_def: &ast::EnumDef) -> Option<String> {
+    let (_, args) =
+        enum_def.attrs().filter_map(|it| it.as_simple_call()).find(|(name, _)| name == "repr")?;
+    let text = args.syntax().text().to_string();
+    text.trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(|it| it.trim())
+        .find(|it| it.starts_with('i') || it.starts_with('u'))
+        .map(|it| it.to_string())
+}
+
+/// Whether `node` is an item or a statement, before which a constant can be
+/// declared for it. The items of impls and traits are not, as a constant
+/// there needs a path.
+fn is_item_position(node: &SyntaxNode) -> bool {
+    match node.parent() {
+        Some(parent) => match parent.kind() {
+            SOURCE_FILE | BLOCK => true,
+            ITEM_LIST => parent.parent().map_or(false, |it| it.kind() == MODULE),
+            _ => false,
+        },
+        None => false,
+    }
+}
+
 /// Returns the syntax node which will follow the freshly introduced var
 /// and a boolean indicating whether we have to wrap it within a { } block
 /// to produce correct code.
@@ -136,7 +278,13 @@ fn anchor_stmt(expr: ast::Expr) -> Option<(SyntaxNode, bool)> {
 mod tests {
     use test_utils::covers;
 
-    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+    use crate::{
+        helpers::{
+            check_assist, check_assist_not_applicable, check_assist_not_applicable_because,
+            check_assist_target,
+        },
+        marks,
+    };
 
     use super::*;
 
@@ -158,8 +306,11 @@ fn foo() {
 
     #[test]
     fn introduce_var_in_comment_is_not_applicable() {
-        covers!(introduce_var_in_comment_is_not_applicable);
-        check_assist_not_applicable(introduce_variable, "fn main() { 1 + /* <|>comment<|> */ 1; }");
+        check_assist_not_applicable_because(
+            introduce_variable,
+            "fn main() { 1 + /* <|>comment<|> */ 1; }",
+            &marks::introduce_var_in_comment_is_not_applicable,
+        );
     }
 
     #[test]
@@ -253,7 +404,10 @@ fn main() {
 fn main() {
     let x = true;
     let tuple = match x {
-        true => { let <|>var_name = 2 + 2; (var_name, true) }
+        true => {
+            let <|>var_name = 2 + 2;
+            (var_name, true)
+        }
         _ => (0, false)
     };
 }
@@ -261,6 +415,60 @@ fn main() {
         );
     }
 
+    #[test]
+    fn test_introduce_var_in_multiline_match_arm() {
+        check_assist(
+            introduce_variable,
+            "
+fn main() {
+    match x {
+        true=>foo(
+            <|>2 + 2<|>,
+        ),
+        _ => (),
+    }
+}
+",
+            "
+fn main() {
+    match x {
+        true=>{
+            let <|>var_name = 2 + 2;
+            foo(
+                var_name,
+            )
+        },
+        _ => (),
+    }
+}
+",
+        );
+    }
+
+    #[test]
+    fn test_introduce_var_in_if_condition() {
+        check_assist(
+            introduce_variable,
+            "
+fn main() {
+    if <|>1 + 1 == 2<|> {
+        foo();
+    }
+    bar();
+}
+",
+            "
+fn main() {
+    let <|>var_name = 1 + 1 == 2;
+    if var_name {
+        foo();
+    }
+    bar();
+}
+",
+        );
+    }
+
     #[test]
     fn test_introduce_var_in_match_arm_with_block() {
         check_assist(
@@ -304,7 +512,26 @@ fn main() {
 ",
             "
 fn main() {
-    let lambda = |x: u32| { let <|>var_name = x * 2; var_name };
+    let lambda = |x: u32| {
+        let <|>var_name = x * 2;
+        var_name
+    };
+}
+",
+        );
+        check_assist(
+            introduce_variable,
+            "
+fn main() {
+    let v = xs.iter().map(|x| x.pow(<|>2 * 2<|>)).collect();
+}
+",
+            "
+fn main() {
+    let v = xs.iter().map(|x| {
+        let <|>var_name = 2 * 2;
+        x.pow(var_name)
+    }).collect();
 }
 ",
         );
@@ -495,6 +722,54 @@ fn main() {
         check_assist_not_applicable(introduce_variable, "fn main() { loop { <|>break<|>; }; }");
     }
 
+    #[test]
+    fn test_introduce_var_in_macro_call() {
+        check_assist(
+            introduce_variable,
+            r#"
+macro_rules! assert_eq { ($a:expr, $b:expr) => { if $a != $b { panic(); } } }
+fn main() {
+    assert_eq!(<|>1 + 2<|>, 3);
+}"#,
+            r#"
+macro_rules! assert_eq { ($a:expr, $b:expr) => { if $a != $b { panic(); } } }
+fn main() {
+    let <|>var_name = 1 + 2;
+    assert_eq!(var_name, 3);
+}"#,
+        );
+    }
+
+    #[test]
+    fn test_introduce_var_in_macro_call_not_applicable_to_partial_expr() {
+        check_assist_not_applicable(
+            introduce_variable,
+            r#"
+macro_rules! assert_eq { ($a:expr, $b:expr) => { if $a != $b { panic(); } } }
+fn main() {
+    assert_eq!(1 + <|>2, 3<|>);
+}"#,
+        );
+    }
+
+    #[test]
+    fn introduce_var_for_empty_selection_is_not_applicable() {
+        check_assist_not_applicable_because(
+            introduce_variable,
+            "fn main() { foo(<|>1 + 1); }",
+            &marks::introduce_var_for_empty_selection_is_not_applicable,
+        );
+    }
+
+    #[test]
+    fn introduce_var_without_indent_is_not_applicable() {
+        check_assist_not_applicable_because(
+            introduce_variable,
+            "fn main() {foo(<|>1 + 1<|>);}",
+            &marks::introduce_var_without_indent_is_not_applicable,
+        );
+    }
+
     // FIXME: This is not quite correct, but good enough(tm) for the sorting heuristic
     #[test]
     fn introduce_var_target() {
@@ -514,4 +789,52 @@ fn main() {
             "2 + 2",
         );
     }
+
+    #[test]
+    fn introduce_constant_in_array_length() {
+        check_assist(
+            introduce_variable,
+            "
+/// A buffer.
+const BUF: [u8; <|>4 * 1024<|>] = [0; 4 * 1024];",
+            "
+const <|>VAR_NAME: usize = 4 * 1024;
+
+/// A buffer.
+const BUF: [u8; VAR_NAME] = [0; 4 * 1024];",
+        );
+        check_assist(
+            introduce_variable,
+            "
+fn main() {
+    let x = 1;
+    let buf = [0u8; <|>2 * 512<|>];
+}",
+            "
+fn main() {
+    let x = 1;
+    const <|>VAR_NAME: usize = 2 * 512;
+    let buf = [0u8; VAR_NAME];
+}",
+        );
+    }
+
+    #[test]
+    fn introduce_constant_in_enum_discriminant() {
+        check_assist(
+            introduce_variable,
+            "
+#[repr(u8)]
+enum Flag {
+    A = <|>1 << 4<|>,
+}",
+            "
+const <|>VAR_NAME: u8 = 1 << 4;
+
+#[repr(u8)]
+enum Flag {
+    A = VAR_NAME,
+}",
+        );
+    }
 }
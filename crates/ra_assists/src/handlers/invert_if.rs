@@ -1,14 +1,17 @@
 use ra_syntax::ast::{self, AstNode};
 use ra_syntax::T;
 
-use crate::{utils::invert_boolean_expression, Assist, AssistCtx, AssistId};
+use crate::{
+    utils::{invert_boolean_expression, invert_condition},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
 
 // Assist: invert_if
 //
 // Apply invert_if
 // This transforms if expressions of the form `if !x {A} else {B}` into `if x {B} else {A}`
 // This also works with `!=`. This assist can only be applied with the cursor
-// on `if`.
+// on `if`, which may be inside a macro call.
 //
 // ```
 // fn main() {
@@ -23,34 +26,92 @@ use crate::{utils::invert_boolean_expression, Assist, AssistCtx, AssistId};
 // ```
 
 pub(crate) fn invert_if(ctx: AssistCtx) -> Option<Assist> {
-    let if_keyword = ctx.find_token_at_offset(T![if])?;
-    let expr = ast::IfExpr::cast(if_keyword.parent())?;
-    let if_range = if_keyword.text_range();
+    let if_range = ctx.find_token_at_offset(T![if])?.text_range();
     let cursor_in_range = ctx.frange.range.is_subrange(&if_range);
     if !cursor_in_range {
         return None;
     }
+    let if_keyword = ctx.find_token_at_offset_with_macros(T![if])?;
+    let expr = ast::IfExpr::cast(if_keyword.parent())?;
 
     let cond = expr.condition()?.expr()?;
     let then_node = expr.then_branch()?.syntax().clone();
 
     if let ast::ElseBranch::Block(else_block) = expr.else_branch()? {
-        let cond_range = cond.syntax().text_range();
+        let cond_range = ctx.original_range(cond.syntax())?;
+        let then_range = ctx.original_range(&then_node)?;
+        let else_range = ctx.original_range(else_block.syntax())?;
         let flip_cond = invert_boolean_expression(cond);
-        let else_node = else_block.syntax();
-        let else_range = else_node.text_range();
-        let then_range = then_node.text_range();
-        return ctx.add_assist(AssistId("invert_if"), "Invert if", |edit| {
-            edit.target(if_range);
-            edit.replace(cond_range, flip_cond.syntax().text());
-            edit.replace(else_range, then_node.text());
-            edit.replace(then_range, else_node.text());
-        });
+        let then_text = ctx.source_text(then_range);
+        let else_text = ctx.source_text(else_range);
+        return ctx.add_assist(
+            AssistId("invert_if"),
+            AssistKind::RefactorRewrite,
+            "Invert if",
+            if_range,
+            |edit| {
+                edit.replace(cond_range, flip_cond.syntax().text());
+                edit.replace(else_range, then_text);
+                edit.replace(then_range, else_text);
+            },
+        );
     }
 
     None
 }
 
+// Assist: invert_if_condition
+//
+// Inverts the condition of an `if` with an `else` block and swaps its
+// branches, distributing the negation over `&&` and `||` and flipping
+// comparisons.
+//
+// ```
+// fn main() {
+//     if !x.is_empty() &&<|> y < 3 { A } else { B }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     if x.is_empty() || y >= 3 { B } else { A }
+// }
+// ```
+pub(crate) fn invert_if_condition(ctx: AssistCtx) -> Option<Assist> {
+    let expr = ctx.find_node_at_offset::<ast::IfExpr>()?;
+    let condition = expr.condition()?;
+    if condition.pat().is_some() {
+        return None;
+    }
+    let cond = condition.expr()?;
+    let if_token = expr.syntax().first_token()?;
+    let applicable_range = if_token.text_range().extend_to(&cond.syntax().text_range());
+    if !ctx.frange.range.is_subrange(&applicable_range) {
+        return None;
+    }
+    let else_block = match expr.else_branch()? {
+        ast::ElseBranch::Block(it) => it,
+        ast::ElseBranch::IfExpr(_) => return None,
+    };
+    let then_block = expr.then_branch()?;
+
+    let flip_cond = invert_condition(&cond);
+    let then_text = then_block.syntax().to_string();
+    let else_text = else_block.syntax().to_string();
+    ctx.add_assist(
+        AssistId("invert_if_condition"),
+        AssistKind::RefactorRewrite,
+        "Invert condition and swap branches",
+        cond.syntax().text_range(),
+        |edit| {
+            edit.replace(cond.syntax().text_range(), flip_cond);
+            edit.replace(then_block.syntax().text_range(), else_text);
+            edit.replace(else_block.syntax().text_range(), then_text);
+            edit.set_cursor(if_token.text_range().start());
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +149,64 @@ mod tests {
     fn invert_if_doesnt_apply_with_cursor_not_on_if() {
         check_assist_not_applicable(invert_if, "fn f() { if !<|>cond { 3 * 2 } else { 1 } }")
     }
+
+    #[test]
+    fn invert_if_inside_macro_call() {
+        check_assist(
+            invert_if,
+            r#"
+macro_rules! id { ($e:expr) => { $e } }
+fn f() { id!(i<|>f !cond { 3 * 2 } else { 1 }) }"#,
+            r#"
+macro_rules! id { ($e:expr) => { $e } }
+fn f() { id!(i<|>f cond { 1 } else { 3 * 2 }) }"#,
+        )
+    }
+
+    #[test]
+    fn invert_if_not_applicable_to_branches_from_macro_definition() {
+        check_assist_not_applicable(
+            invert_if,
+            r#"
+macro_rules! m { ($i:tt $c:expr) => { $i $c { 1 } else { 2 } } }
+fn f() { m!(i<|>f !cond) }"#,
+        )
+    }
+
+    #[test]
+    fn invert_condition_distributes_over_and() {
+        check_assist(
+            invert_if_condition,
+            "fn f() { if !a &&<|> !b { 1 } else { 2 } }",
+            "fn f() { <|>if a || b { 2 } else { 1 } }",
+        )
+    }
+
+    #[test]
+    fn invert_condition_flips_comparisons() {
+        check_assist(
+            invert_if_condition,
+            "fn f() { i<|>f x < 3 || y == 4 && !z { 1 } else { 2 } }",
+            "fn f() { <|>if x >= 3 && (y != 4 || z) { 2 } else { 1 } }",
+        )
+    }
+
+    #[test]
+    fn invert_condition_keeps_or_operands_grouped() {
+        check_assist(
+            invert_if_condition,
+            "fn f() { if <|>(a || b) && c.is_some() { 1 } else { 2 } }",
+            "fn f() { <|>if !a && !b || c.is_none() { 2 } else { 1 } }",
+        )
+    }
+
+    #[test]
+    fn invert_condition_not_applicable_without_else() {
+        check_assist_not_applicable(invert_if_condition, "fn f() { if a &&<|> b { 1 } }")
+    }
+
+    #[test]
+    fn invert_condition_not_applicable_in_branches() {
+        check_assist_not_applicable(invert_if_condition, "fn f() { if a && b { 1<|> } else { 2 } }")
+    }
 }
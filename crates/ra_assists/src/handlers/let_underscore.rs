@@ -0,0 +1,176 @@
+use hir::{Adt, HasSource, ModuleDef, PathResolution};
+use ra_syntax::{
+    ast::{self, AstNode, AttrsOwner, TypeAscriptionOwner},
+    TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: remove_let_underscore
+//
+// Turns `let _ = expr;` into `expr;`, unless the value must be used.
+//
+// ```
+// fn main() {
+//     let _ = <|>compute();
+// }
+// fn compute() -> u32 { 92 }
+// ```
+// ->
+// ```
+// fn main() {
+//     compute();
+// }
+// fn compute() -> u32 { 92 }
+// ```
+pub(crate) fn remove_let_underscore(ctx: AssistCtx) -> Option<Assist> {
+    let let_stmt = ctx.find_node_at_offset::<ast::LetStmt>()?;
+    match let_stmt.pat()? {
+        ast::Pat::PlaceholderPat(_) => (),
+        _ => return None,
+    }
+    if let_stmt.ascribed_type().is_some() {
+        return None;
+    }
+    let expr = let_stmt.initializer()?;
+    if must_be_used(&ctx, &expr) {
+        return None;
+    }
+
+    let range = let_stmt.syntax().text_range();
+    ctx.add_assist(
+        AssistId("remove_let_underscore"),
+        AssistKind::RefactorRewrite,
+        "Remove let binding",
+        range,
+        |edit| {
+            edit.replace(range, format!("{};", expr.syntax()));
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+// Assist: bind_to_underscore
+//
+// Turns an expression statement into `let _ = expr;`, to explicitly ignore its
+// value.
+//
+// ```
+// fn main() {
+//     <|>compute();
+// }
+// fn compute() -> u32 { 92 }
+// ```
+// ->
+// ```
+// fn main() {
+//     let _ = compute();
+// }
+// fn compute() -> u32 { 92 }
+// ```
+pub(crate) fn bind_to_underscore(ctx: AssistCtx) -> Option<Assist> {
+    let stmt = ctx.find_node_at_offset::<ast::ExprStmt>()?;
+    if !stmt.has_semi() {
+        return None;
+    }
+    let expr = stmt.expr()?;
+    let ty = ctx.sema.type_of_expr(&expr)?;
+    if ty.contains_unknown() || ty.display(ctx.db).to_string() == "()" {
+        return None;
+    }
+
+    let start = stmt.syntax().text_range().start();
+    ctx.add_assist(
+        AssistId("bind_to_underscore"),
+        AssistKind::RefactorRewrite,
+        "Bind to `_`",
+        stmt.syntax().text_range(),
+        |edit| {
+            edit.insert(start, "let _ = ");
+            edit.set_cursor(start + TextUnit::of_str("let _ = "));
+        },
+    )
+}
+
+/// Whether the value of `expr` is a `Result`, or is marked `#[must_use]` by
+/// its type or by the function computing it.
+fn must_be_used(ctx: &AssistCtx, expr: &ast::Expr) -> bool {
+    let db = ctx.db;
+    let func = match expr {
+        ast::Expr::CallExpr(call) => match call.expr() {
+            Some(ast::Expr::PathExpr(it)) => {
+                match it.path().and_then(|it| ctx.sema.resolve_path(&it)) {
+                    Some(PathResolution::Def(ModuleDef::Function(it))) => Some(it),
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        ast::Expr::MethodCallExpr(call) => ctx.sema.resolve_method_call(call),
+        _ => None,
+    };
+    if func.map_or(false, |it| has_must_use(it.source(db).value.attrs())) {
+        return true;
+    }
+    match ctx.sema.type_of_expr(expr).and_then(|it| it.as_adt()) {
+        Some(Adt::Enum(it)) => {
+            it.name(db).to_string() == "Result" || has_must_use(it.source(db).value.attrs())
+        }
+        Some(Adt::Struct(it)) => has_must_use(it.source(db).value.attrs()),
+        Some(Adt::Union(it)) => has_must_use(it.source(db).value.attrs()),
+        None => false,
+    }
+}
+
+fn has_must_use(mut attrs: impl Iterator<Item = ast::Attr>) -> bool {
+    attrs.any(|it| it.simple_name().map_or(false, |it| it == "must_use"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn remove_let_underscore_of_call() {
+        check_assist(
+            remove_let_underscore,
+            "fn f() -> u32 { 0 } fn main() { let _ = f()<|>; }",
+            "fn f() -> u32 { 0 } fn main() { <|>f(); }",
+        );
+    }
+
+    #[test]
+    fn bind_call_to_underscore() {
+        check_assist(
+            bind_to_underscore,
+            "fn f() -> u32 { 0 } fn main() { f()<|>; }",
+            "fn f() -> u32 { 0 } fn main() { let _ = <|>f(); }",
+        );
+    }
+
+    #[test]
+    fn remove_let_underscore_not_applicable_to_result() {
+        check_assist_not_applicable(
+            remove_let_underscore,
+            "enum Result<T, E> { Ok(T), Err(E) } fn f() -> Result<u32, ()> { Result::Ok(0) } fn main() { let _ = f()<|>; }",
+        );
+    }
+
+    #[test]
+    fn remove_let_underscore_not_applicable_to_must_use() {
+        check_assist_not_applicable(
+            remove_let_underscore,
+            "#[must_use] struct Guard; fn lock() -> Guard { Guard } fn main() { let _ = lock()<|>; }",
+        );
+        check_assist_not_applicable(
+            remove_let_underscore,
+            "#[must_use] fn f() -> u32 { 0 } fn main() { let _ = f()<|>; }",
+        );
+    }
+
+    #[test]
+    fn bind_to_underscore_not_applicable_to_unit() {
+        check_assist_not_applicable(bind_to_underscore, "fn f() {} fn main() { f()<|>; }");
+    }
+}
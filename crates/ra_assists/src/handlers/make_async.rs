@@ -0,0 +1,171 @@
+use hir::ModuleDef;
+use ra_ide_db::{defs::Definition, search::SearchScope};
+use ra_syntax::{
+    algo::find_node_at_offset,
+    ast::{self, AstNode},
+    SyntaxKind::{ABI, AWAIT_EXPR, DEFAULT_KW, FN_KW, UNSAFE_KW},
+    SyntaxNode,
+};
+
+use crate::{
+    utils::{call_of, is_trait_fn},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: make_async
+//
+// Makes a function `async`, adding `.await` to its calls from other async functions.
+//
+// ```
+// fn fetch<|>() -> u32 { 92 }
+//
+// async fn run() -> u32 {
+//     fetch()
+// }
+// ```
+// ->
+// ```
+// async fn fetch() -> u32 { 92 }
+//
+// async fn run() -> u32 {
+//     fetch().await
+// }
+// ```
+pub(crate) fn make_async(ctx: AssistCtx) -> Option<Assist> {
+    let fn_def = ctx.find_node_at_offset::<ast::FnDef>()?;
+    if let Some(body) = fn_def.body() {
+        let body_range = body.syntax().text_range();
+        let offset = ctx.frange.range.start();
+        if body_range.start() < offset && offset < body_range.end() {
+            return None;
+        }
+    }
+    if fn_def.is_async() || is_trait_fn(&fn_def) {
+        return None;
+    }
+    let insert_offset = fn_def
+        .syntax()
+        .children_with_tokens()
+        .find(|it| match it.kind() {
+            UNSAFE_KW | ABI | DEFAULT_KW | FN_KW => true,
+            _ => false,
+        })?
+        .text_range()
+        .start();
+
+    let function = ctx.sema.to_def(&fn_def)?;
+    let def = Definition::ModuleDef(ModuleDef::Function(function));
+    let usages = def.find_usages(ctx.db, Some(SearchScope::single_file(ctx.frange.file_id)));
+    let root = fn_def.syntax().ancestors().last()?;
+    let async_calls = usages
+        .into_iter()
+        .filter_map(|it| find_node_at_offset::<ast::NameRef>(&root, it.file_range.range.start()))
+        .filter_map(|name_ref| call_of(&name_ref))
+        .map(|call| call.syntax().clone())
+        .filter(|call| {
+            call.parent().map_or(true, |it| it.kind() != AWAIT_EXPR) && is_in_async_context(call)
+        })
+        .collect::<Vec<_>>();
+
+    ctx.add_assist(
+        AssistId("make_async"),
+        AssistKind::RefactorRewrite,
+        "Make async",
+        fn_def.syntax().text_range(),
+        |edit| {
+            edit.insert(insert_offset, "async ");
+            for call in async_calls {
+                edit.insert(call.text_range().end(), ".await");
+            }
+        },
+    )
+}
+
+fn is_in_async_context(node: &SyntaxNode) -> bool {
+    for ancestor in node.ancestors().skip(1) {
+        if let Some(fn_def) = ast::FnDef::cast(ancestor.clone()) {
+            return fn_def.is_async();
+        }
+        if let Some(lambda) = ast::LambdaExpr::cast(ancestor.clone()) {
+            return lambda.is_async();
+        }
+        if ast::BlockExpr::cast(ancestor).map_or(false, |it| it.is_async()) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn make_async_awaits_calls_from_async_fns() {
+        check_assist(
+            make_async,
+            r#"
+fn foo<|>() -> u32 { 1 }
+
+async fn bar() -> u32 {
+    foo() + 1
+}
+
+fn baz() -> u32 {
+    foo()
+}"#,
+            r#"
+async fn foo<|>() -> u32 { 1 }
+
+async fn bar() -> u32 {
+    foo().await + 1
+}
+
+fn baz() -> u32 {
+    foo()
+}"#,
+        );
+    }
+
+    #[test]
+    fn make_async_awaits_method_calls_in_async_blocks() {
+        check_assist(
+            make_async,
+            r#"
+struct S;
+impl S {
+    pub fn <|>get(&self) -> u32 { 1 }
+}
+
+fn f(s: S) {
+    let fut = async move { s.get() };
+}"#,
+            r#"
+struct S;
+impl S {
+    pub async fn <|>get(&self) -> u32 { 1 }
+}
+
+fn f(s: S) {
+    let fut = async move { s.get().await };
+}"#,
+        );
+    }
+
+    #[test]
+    fn make_async_target() {
+        check_assist_target(make_async, "fn <|>foo() {}", "fn foo() {}");
+    }
+
+    #[test]
+    fn make_async_not_applicable() {
+        check_assist_not_applicable(make_async, "async fn <|>foo() {}");
+        check_assist_not_applicable(make_async, "fn foo() { <|>bar(); }");
+        check_assist_not_applicable(make_async, "trait T { fn <|>foo(); }");
+        check_assist_not_applicable(
+            make_async,
+            "trait T { fn foo(); } struct S; impl T for S { fn <|>foo() {} }",
+        );
+    }
+}
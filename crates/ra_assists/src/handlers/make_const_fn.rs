@@ -0,0 +1,175 @@
+use hir::{Adt, HasSource, ModuleDef, PathResolution};
+use ra_syntax::{
+    ast::{self, AstNode, LiteralKind},
+    SyntaxKind::*,
+    SyntaxNode,
+};
+use test_utils::tested_by;
+
+use crate::{utils::is_trait_fn, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: make_const_fn
+//
+// Makes a function `const` when its body only uses constructs allowed in
+// constant evaluation.
+//
+// ```
+// fn square<|>(x: u32) -> u32 { x * x }
+// ```
+// ->
+// ```
+// const fn square(x: u32) -> u32 { x * x }
+// ```
+pub(crate) fn make_const_fn(ctx: AssistCtx) -> Option<Assist> {
+    let fn_def = ctx.find_node_at_offset::<ast::FnDef>()?;
+    let body = fn_def.body()?;
+    let body_range = body.syntax().text_range();
+    let offset = ctx.frange.range.start();
+    if body_range.start() < offset && offset < body_range.end() {
+        return None;
+    }
+    if fn_def.is_const() || fn_def.is_async() || is_trait_fn(&fn_def) {
+        return None;
+    }
+    let insert_offset = fn_def
+        .syntax()
+        .children_with_tokens()
+        .find(|it| match it.kind() {
+            UNSAFE_KW | ABI | FN_KW => true,
+            _ => false,
+        })?
+        .text_range()
+        .start();
+    for node in body.syntax().descendants() {
+        if !is_const_eligible(&ctx, &node) {
+            return None;
+        }
+    }
+
+    ctx.add_assist(
+        AssistId("make_const_fn"),
+        AssistKind::RefactorRewrite,
+        "Make function const",
+        fn_def.syntax().text_range(),
+        |edit| {
+            edit.insert(insert_offset, "const ");
+        },
+    )
+}
+
+/// Checks a node of the body against a conservative whitelist of what
+/// constant evaluation allows.
+fn is_const_eligible(ctx: &AssistCtx, node: &SyntaxNode) -> bool {
+    match node.kind() {
+        BLOCK_EXPR | BLOCK | LET_STMT | EXPR_STMT | BIN_EXPR | PREFIX_EXPR | PAREN_EXPR
+        | TUPLE_EXPR | IF_EXPR | CONDITION | MATCH_EXPR | MATCH_ARM_LIST | MATCH_ARM
+        | MATCH_GUARD | CALL_EXPR | ARG_LIST | RETURN_EXPR | CAST_EXPR | PATH | PATH_SEGMENT
+        | NAME_REF | NAME | BIND_PAT | PLACEHOLDER_PAT | LITERAL_PAT | TUPLE_PAT
+        | TUPLE_STRUCT_PAT | PATH_PAT | RANGE_PAT | OR_PAT | PATH_TYPE | TYPE_ARG_LIST
+        | TYPE_ARG => true,
+        LITERAL => match ast::Literal::cast(node.clone()).map(|it| it.kind()) {
+            Some(LiteralKind::FloatNumber { .. }) | None => {
+                tested_by!(make_const_fn_unsupported_construct);
+                false
+            }
+            Some(_) => true,
+        },
+        PATH_EXPR => {
+            let resolution = ast::PathExpr::cast(node.clone())
+                .and_then(|it| it.path())
+                .and_then(|it| ctx.sema.resolve_path(&it));
+            match resolution {
+                Some(PathResolution::Local(_))
+                | Some(PathResolution::Def(ModuleDef::Const(_)))
+                | Some(PathResolution::Def(ModuleDef::EnumVariant(_)))
+                | Some(PathResolution::Def(ModuleDef::Adt(Adt::Struct(_)))) => true,
+                Some(PathResolution::Def(ModuleDef::Function(function)))
+                    if function.source(ctx.db).value.is_const() =>
+                {
+                    true
+                }
+                Some(PathResolution::Def(ModuleDef::Function(_))) => {
+                    tested_by!(make_const_fn_calls_non_const_fn);
+                    false
+                }
+                _ => {
+                    tested_by!(make_const_fn_unsupported_construct);
+                    false
+                }
+            }
+        }
+        _ => {
+            tested_by!(make_const_fn_unsupported_construct);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        helpers::{check_assist, check_assist_not_applicable, check_assist_not_applicable_because},
+        marks,
+    };
+
+    #[test]
+    fn make_arithmetic_fn_const() {
+        check_assist(
+            make_const_fn,
+            "
+pub fn clamp<|>(x: i32, max: i32) -> i32 {
+    let y = if x > max { max } else { x };
+    match y { 0 => 1, _ => y * 2 }
+}",
+            "
+pub const fn clamp<|>(x: i32, max: i32) -> i32 {
+    let y = if x > max { max } else { x };
+    match y { 0 => 1, _ => y * 2 }
+}",
+        );
+    }
+
+    #[test]
+    fn make_fn_calling_const_fn_const() {
+        check_assist(
+            make_const_fn,
+            "
+const fn double(x: u32) -> u32 { x * 2 }
+fn quadruple<|>(x: u32) -> u32 { double(double(x)) }",
+            "
+const fn double(x: u32) -> u32 { x * 2 }
+const fn quadruple<|>(x: u32) -> u32 { double(double(x)) }",
+        );
+    }
+
+    #[test]
+    fn make_const_fn_not_applicable_calling_non_const_fn() {
+        check_assist_not_applicable_because(
+            make_const_fn,
+            "
+fn double(x: u32) -> u32 { x * 2 }
+fn quadruple<|>(x: u32) -> u32 { double(double(x)) }",
+            &marks::make_const_fn_calls_non_const_fn,
+        );
+    }
+
+    #[test]
+    fn make_const_fn_not_applicable_to_method_calls_and_floats() {
+        check_assist_not_applicable_because(
+            make_const_fn,
+            "fn len<|>(v: &Vec<u32>) -> usize { v.len() }",
+            &marks::make_const_fn_unsupported_construct,
+        );
+        check_assist_not_applicable_because(
+            make_const_fn,
+            "fn half<|>(x: f32) -> f32 { x * 0.5 }",
+            &marks::make_const_fn_unsupported_construct,
+        );
+    }
+
+    #[test]
+    fn make_const_fn_not_applicable_to_const_fn() {
+        check_assist_not_applicable(make_const_fn, "const fn zero<|>() -> u32 { 0 }");
+    }
+}
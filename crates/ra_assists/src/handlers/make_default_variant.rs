@@ -0,0 +1,219 @@
+use hir::Adt;
+use ra_fmt::{leading_indent, reindent};
+use ra_syntax::ast::{self, AstNode, AttrsOwner, NameOwner, StructKind};
+
+use crate::{
+    utils::{attribute_removal_range, derive_args, derives, insert_attribute, insert_derives},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: make_default_variant
+//
+// Makes an enum variant the one returned by `Default::default()`.
+//
+// ```
+// enum Mode {
+//     Fast,
+//     Sa<|>fe,
+// }
+// ```
+// ->
+// ```
+// #[derive(Default)]
+// enum Mode {
+//     Fast,
+//     #[default]
+//     Safe,
+// }
+// ```
+pub(crate) fn make_default_variant(ctx: AssistCtx) -> Option<Assist> {
+    let variant = ctx.find_node_at_offset::<ast::EnumVariant>()?;
+    let variant_name = variant.name()?;
+    if default_marker(&variant).is_some() {
+        return None;
+    }
+    let enum_def = variant.parent_enum();
+    let enum_name = enum_def.name()?;
+    if has_default_impl(&ctx, &enum_def)? {
+        return None;
+    }
+    let derive_args = derive_args(&enum_def);
+    let derives_default = derive_args.as_ref().map_or(false, |args| derives(args, "Default"));
+
+    if let StructKind::Unit = variant.kind() {
+        return ctx.add_assist(
+            AssistId("make_default_variant"),
+            AssistKind::Refactor,
+            "Make this the Default variant",
+            variant.syntax().text_range(),
+            |edit| {
+                for other in enum_def.variant_list().into_iter().flat_map(|it| it.variants()) {
+                    if let Some(marker) = default_marker(&other) {
+                        edit.delete(attribute_removal_range(&marker));
+                    }
+                }
+                let (offset, text) = insert_attribute(variant.syntax(), None, "#[default]");
+                edit.insert(offset, text);
+                if derives_default {
+                    return;
+                }
+                let (offset, text) =
+                    insert_derives(enum_def.syntax(), derive_args.as_ref(), "Default");
+                edit.insert(offset, text);
+            },
+        );
+    }
+
+    // `#[default]` only supports unit variants, and a manual impl can't be
+    // combined with a derived one.
+    if derives_default || enum_def.type_param_list().is_some() {
+        return None;
+    }
+    let value = match variant.kind() {
+        StructKind::Record(field_list) => {
+            let fields = field_list
+                .fields()
+                .filter_map(|it| it.name())
+                .map(|it| format!("{}: Default::default()", it.text()))
+                .collect::<Vec<_>>();
+            format!("Self::{} {{ {} }}", variant_name.text(), fields.join(", "))
+        }
+        StructKind::Tuple(field_list) => {
+            let fields = field_list.fields().map(|_| "Default::default()").collect::<Vec<_>>();
+            format!("Self::{}({})", variant_name.text(), fields.join(", "))
+        }
+        StructKind::Unit => unreachable!(),
+    };
+    ctx.add_assist(
+        AssistId("make_default_variant"),
+        AssistKind::Refactor,
+        "Make this the Default variant",
+        variant.syntax().text_range(),
+        |edit| {
+            let indent = leading_indent(enum_def.syntax()).unwrap_or_default();
+            let impl_text = format!(
+                "impl Default for {} {{\n    fn default() -> Self {{\n        {}\n    }}\n}}",
+                enum_name.text(),
+                value
+            );
+            edit.insert(
+                enum_def.syntax().text_range().end(),
+                format!("\n\n{}{}", indent, reindent(&impl_text, &indent)),
+            );
+        },
+    )
+}
+
+fn default_marker(variant: &ast::EnumVariant) -> Option<ast::Attr> {
+    variant.attrs().find(|it| it.as_simple_atom().map_or(false, |it| it == "default"))
+}
+
+fn has_default_impl(ctx: &AssistCtx, enum_def: &ast::EnumDef) -> Option<bool> {
+    let def = ctx.sema.to_def(enum_def)?;
+    let scope = enum_def.syntax().parent()?;
+    let res = scope.children().filter_map(ast::ImplDef::cast).any(|impl_def| {
+        let is_default = match impl_def.target_trait() {
+            Some(ast::TypeRef::PathType(it)) => it
+                .path()
+                .and_then(|it| it.segment())
+                .and_then(|it| it.name_ref())
+                .map_or(false, |it| it.text() == "Default"),
+            _ => false,
+        };
+        is_default
+            && ctx.sema.to_def(&impl_def).and_then(|it| it.target_ty(ctx.db).as_adt())
+                == Some(Adt::Enum(def))
+    });
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn make_default_variant_adds_derive() {
+        check_assist(
+            make_default_variant,
+            r#"
+/// Log level.
+#[derive(Debug, Clone)]
+pub enum Level {
+    Error,
+    /// Informational.
+    In<|>fo,
+}"#,
+            r#"
+/// Log level.
+#[derive(Debug, Clone, Default)]
+pub enum Level {
+    Error,
+    /// Informational.
+    #[default]
+    In<|>fo,
+}"#,
+        );
+        check_assist(
+            make_default_variant,
+            "enum E { A, B<|> }",
+            "#[derive(Default)]\nenum E { A, #[default] B<|> }",
+        );
+    }
+
+    #[test]
+    fn make_default_variant_moves_marker() {
+        check_assist(
+            make_default_variant,
+            r#"
+#[derive(Default)]
+enum Level {
+    #[default]
+    Error,
+    Wa<|>rn,
+}"#,
+            r#"
+#[derive(Default)]
+enum Level {
+    Error,
+    #[default]
+    Wa<|>rn,
+}"#,
+        );
+    }
+
+    #[test]
+    fn make_default_variant_with_fields_adds_impl() {
+        check_assist(
+            make_default_variant,
+            r#"
+enum Shape {
+    Circle { r<|>adius: f64 },
+    Rect(f64, f64),
+}"#,
+            r#"
+enum Shape {
+    Circle { r<|>adius: f64 },
+    Rect(f64, f64),
+}
+
+impl Default for Shape {
+    fn default() -> Self {
+        Self::Circle { radius: Default::default() }
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn make_default_variant_not_applicable() {
+        check_assist_not_applicable(
+            make_default_variant,
+            "#[derive(Default)] enum E { A, B { x<|>: u32 }, #[default] C }",
+        );
+        check_assist_not_applicable(
+            make_default_variant,
+            "enum E { A<|>, B } impl Default for E { fn default() -> E { E::B } }",
+        );
+    }
+}
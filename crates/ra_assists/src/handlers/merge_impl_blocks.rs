@@ -0,0 +1,246 @@
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, AttrsOwner, TypeParamsOwner},
+    SyntaxKind::WHITESPACE,
+    SyntaxNode, TextRange, T,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: merge_impl_blocks
+//
+// Moves the items of the inherent impl blocks of a type into the first one.
+//
+// ```
+// struct Counter { count: u32 }
+//
+// impl Counter {
+//     fn new() -> Counter { Counter { count: 0 } }
+// }
+//
+// impl<|> Counter {
+//     fn tick(&mut self) { self.count += 1 }
+// }
+// ```
+// ->
+// ```
+// struct Counter { count: u32 }
+//
+// impl Counter {
+//     fn new() -> Counter { Counter { count: 0 } }
+//
+//     fn tick(&mut self) { self.count += 1 }
+// }
+// ```
+pub(crate) fn merge_impl_blocks(ctx: AssistCtx) -> Option<Assist> {
+    let impl_def = ctx.find_node_at_offset::<ast::ImplDef>()?;
+    let item_list = impl_def.item_list()?;
+    if ctx.frange.range.start() >= item_list.syntax().text_range().start() {
+        return None;
+    }
+    let key = impl_key(&impl_def)?;
+    let blocks = impl_def
+        .syntax()
+        .parent()?
+        .children()
+        .filter_map(ast::ImplDef::cast)
+        .filter(|it| impl_key(it).as_ref() == Some(&key))
+        .collect::<Vec<_>>();
+    if blocks.len() < 2 {
+        return None;
+    }
+    // The attributes, like `#[cfg]`, apply to all the items of a block.
+    let attrs =
+        |it: &ast::ImplDef| it.attrs().map(|it| it.syntax().to_string()).collect::<Vec<_>>();
+    if blocks[1..].iter().any(|it| attrs(it) != attrs(&blocks[0])) {
+        return None;
+    }
+
+    let first_items = blocks[0].item_list()?;
+    let r_curly = first_items.syntax().children_with_tokens().find(|it| it.kind() == T!['}'])?;
+    let mut last = r_curly.prev_sibling_or_token()?;
+    if last.kind() == WHITESPACE {
+        last = last.prev_sibling_or_token()?;
+    }
+    let is_empty = last.kind() == T!['{'];
+    let indent = leading_indent(blocks[0].syntax()).unwrap_or_default();
+    let mut moved = Vec::new();
+    let mut deleted = Vec::new();
+    for block in &blocks[1..] {
+        let text = inner_text(&block.item_list()?)?;
+        if !text.is_empty() {
+            moved.push(text);
+        }
+        let mut range = block.syntax().text_range();
+        if let Some(ws) = block.syntax().prev_sibling_or_token() {
+            if ws.kind() == WHITESPACE {
+                range = range.extend_to(&ws.text_range());
+            }
+        }
+        deleted.push(range);
+    }
+
+    ctx.add_assist(
+        AssistId("merge_impl_blocks"),
+        AssistKind::RefactorRewrite,
+        "Merge impl blocks",
+        impl_def.syntax().text_range(),
+        |edit| {
+            if !moved.is_empty() {
+                let sep = format!("\n\n{}    ", indent);
+                let text = moved.join(&sep);
+                if is_empty {
+                    let range =
+                        TextRange::from_to(last.text_range().end(), r_curly.text_range().start());
+                    edit.replace(range, format!("\n{}    {}\n{}", indent, text, indent));
+                } else {
+                    edit.insert(last.text_range().end(), format!("{}{}", sep, text));
+                }
+            }
+            for range in deleted {
+                edit.delete(range);
+            }
+            edit.set_cursor(blocks[0].syntax().text_range().start());
+        },
+    )
+}
+
+/// What inherent impl blocks must share to be merged: the self type, the
+/// generic parameters and the where clause.
+fn impl_key(impl_def: &ast::ImplDef) -> Option<Vec<String>> {
+    if impl_def.target_trait().is_some() {
+        return None;
+    }
+    let text = |it: Option<SyntaxNode>| {
+        let text = it.map(|it| it.text().to_string()).unwrap_or_default();
+        text.split_whitespace().collect::<String>().trim_end_matches(',').to_string()
+    };
+    Some(vec![
+        text(Some(impl_def.target_type()?.syntax().clone())),
+        text(impl_def.type_param_list().map(|it| it.syntax().clone())),
+        text(impl_def.where_clause().map(|it| it.syntax().clone())),
+    ])
+}
+
+/// The text between the braces of `item_list`, without the surrounding white
+/// space.
+fn inner_text(item_list: &ast::ItemList) -> Option<String> {
+    let brace = |kind| item_list.syntax().children_with_tokens().find(|it| it.kind() == kind);
+    let range = TextRange::from_to(
+        brace(T!['{'])?.text_range().end(),
+        brace(T!['}'])?.text_range().start(),
+    );
+    let text = item_list.syntax().text().to_string();
+    let range = range.checked_sub(item_list.syntax().text_range().start())?;
+    Some(text[range.start().to_usize()..range.end().to_usize()].trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn merge_two_blocks() {
+        check_assist(
+            merge_impl_blocks,
+            "
+struct S;
+
+impl S {
+    fn a() {}
+}
+
+impl<|> S {
+    /// Does b.
+    #[inline]
+    fn b() {}
+
+    // Between.
+    const C: u32 = 0;
+}
+
+fn f() {}",
+            "
+struct S;
+
+<|>impl S {
+    fn a() {}
+
+    /// Does b.
+    #[inline]
+    fn b() {}
+
+    // Between.
+    const C: u32 = 0;
+}
+
+fn f() {}",
+        );
+    }
+
+    #[test]
+    fn merge_three_blocks_into_empty_first() {
+        check_assist(
+            merge_impl_blocks,
+            "
+struct S<T>(T);
+
+impl<T> S<T> where T: Clone {}
+
+impl<T> S<T> where T: Clone {
+    fn a() {}
+}
+
+impl<|><T> S<T>
+where
+    T: Clone,
+{
+    fn b() {}
+}",
+            "
+struct S<T>(T);
+
+<|>impl<T> S<T> where T: Clone {
+    fn a() {}
+
+    fn b() {}
+}",
+        );
+    }
+
+    #[test]
+    fn merge_not_applicable_with_different_cfg() {
+        check_assist_not_applicable(
+            merge_impl_blocks,
+            "
+struct S;
+
+#[cfg(test)]
+impl S {
+    fn a() {}
+}
+
+impl<|> S {
+    fn b() {}
+}",
+        );
+    }
+
+    #[test]
+    fn merge_not_applicable_with_different_generics() {
+        check_assist_not_applicable(
+            merge_impl_blocks,
+            "
+struct S<T>(T);
+
+impl S<u32> {
+    fn a() {}
+}
+
+impl<|><T> S<T> {
+    fn b() {}
+}",
+        );
+    }
+}
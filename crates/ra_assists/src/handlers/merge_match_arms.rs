@@ -5,7 +5,7 @@ use ra_syntax::{
     Direction, TextUnit,
 };
 
-use crate::{Assist, AssistCtx, AssistId, TextRange};
+use crate::{Assist, AssistCtx, AssistId, AssistKind, TextRange};
 
 // Assist: merge_match_arms
 //
@@ -69,30 +69,37 @@ pub(crate) fn merge_match_arms(ctx: AssistCtx) -> Option<Assist> {
         return None;
     }
 
-    ctx.add_assist(AssistId("merge_match_arms"), "Merge match arms", |edit| {
-        let pats = if arms_to_merge.iter().any(contains_placeholder) {
-            "_".into()
-        } else {
-            arms_to_merge
-                .iter()
-                .filter_map(ast::MatchArm::pat)
-                .map(|x| x.syntax().to_string())
-                .collect::<Vec<String>>()
-                .join(" | ")
-        };
+    ctx.add_assist(
+        AssistId("merge_match_arms"),
+        AssistKind::RefactorRewrite,
+        "Merge match arms",
+        current_text_range,
+        |edit| {
+            let pats = if arms_to_merge.iter().any(contains_placeholder) {
+                "_".into()
+            } else {
+                arms_to_merge
+                    .iter()
+                    .filter_map(ast::MatchArm::pat)
+                    .map(|x| x.syntax().to_string())
+                    .collect::<Vec<String>>()
+                    .join(" | ")
+            };
 
-        let arm = format!("{} => {}", pats, current_expr.syntax().text());
+            let arm = format!("{} => {}", pats, current_expr.syntax().text());
 
-        let start = arms_to_merge.first().unwrap().syntax().text_range().start();
-        let end = arms_to_merge.last().unwrap().syntax().text_range().end();
+            let start = arms_to_merge.first().unwrap().syntax().text_range().start();
+            let end = arms_to_merge.last().unwrap().syntax().text_range().end();
 
-        edit.target(current_text_range);
-        edit.set_cursor(match cursor_pos {
-            CursorPos::InExpr(back_offset) => start + TextUnit::from_usize(arm.len()) - back_offset,
-            CursorPos::InPat(offset) => offset,
-        });
-        edit.replace(TextRange::from_to(start, end), arm);
-    })
+            edit.set_cursor(match cursor_pos {
+                CursorPos::InExpr(back_offset) => {
+                    start + TextUnit::from_usize(arm.len()) - back_offset
+                }
+                CursorPos::InPat(offset) => offset,
+            });
+            edit.replace(TextRange::from_to(start, end), arm);
+        },
+    )
 }
 
 fn contains_placeholder(a: &ast::MatchArm) -> bool {
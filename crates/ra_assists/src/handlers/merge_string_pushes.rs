@@ -0,0 +1,354 @@
+use ra_syntax::{
+    ast::{self, ArgListOwner, AstNode, NameOwner, TypeAscriptionOwner},
+    SyntaxKind::{CHAR, IDENT, STRING},
+    TextRange,
+};
+
+use crate::{utils::mutates, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: merge_string_pushes
+//
+// Merges consecutive `push_str` and `push` calls on the same string into one,
+// or into its declaration when they follow it.
+//
+// ```
+// fn greet(name: &str) -> String {
+//     let mut s = String::new();
+//     s.push_str("Hello, ");
+//     s.push_str(name)<|>;
+//     s.push('!');
+//     s
+// }
+// ```
+// ->
+// ```
+// fn greet(name: &str) -> String {
+//     let s = format!("Hello, {}!", name);
+//     s
+// }
+// ```
+pub(crate) fn merge_string_pushes(ctx: AssistCtx) -> Option<Assist> {
+    let block = ctx.find_node_at_offset::<ast::Block>()?;
+    let statements = block.statements().collect::<Vec<_>>();
+    let offset = ctx.frange.range.start();
+    let idx =
+        statements.iter().position(|it| it.syntax().text_range().contains_inclusive(offset))?;
+    let cursor_push = Push::new(&statements[idx])?;
+    let continues_run = |stmt: &ast::Stmt| {
+        Push::new(stmt).filter(|it| it.receiver == cursor_push.receiver && !it.uses_receiver)
+    };
+    let mut first = idx;
+    while first > 0 && continues_run(&statements[first - 1]).is_some() {
+        first -= 1;
+    }
+    let mut last = idx;
+    while last + 1 < statements.len() && continues_run(&statements[last + 1]).is_some() {
+        last += 1;
+    }
+    let pushes = statements[first..=last].iter().filter_map(continues_run).collect::<Vec<_>>();
+    if pushes.len() != last - first + 1 {
+        return None;
+    }
+    let declaration = match first.checked_sub(1) {
+        Some(it) => string_declaration(&statements[it], &cursor_push.receiver),
+        None => None,
+    };
+    if pushes.len() < 2 && declaration.is_none() {
+        return None;
+    }
+
+    let mut format = String::new();
+    let mut text = String::new();
+    let mut args = Vec::new();
+    for push in pushes {
+        match push.piece {
+            Piece::Text(it) => {
+                format.push_str(&it.replace('{', "{{").replace('}', "}}"));
+                text.push_str(&it);
+            }
+            Piece::Arg(it) => {
+                format.push_str("{}");
+                args.push(it);
+            }
+        }
+    }
+    let value = |text_value: String| {
+        if args.is_empty() {
+            text_value
+        } else {
+            format!("format!(\"{}\", {})", format, args.join(", "))
+        }
+    };
+
+    let end = statements[last].syntax().text_range().end();
+    let (label, range, merged) = match declaration {
+        Some(declaration) => {
+            let later = block
+                .statements()
+                .skip(last + 1)
+                .map(|it| it.syntax().clone())
+                .chain(block.expr().map(|it| it.syntax().clone()))
+                .collect::<Vec<_>>();
+            let is_mutated = later.iter().any(|it| mutates(it, &cursor_push.receiver));
+            let start = declaration.syntax().text_range().start();
+            let ascription = declaration
+                .ascribed_type()
+                .map(|it| format!(": {}", it.syntax()))
+                .unwrap_or_default();
+            let merged = format!(
+                "let {}{}{} = {};",
+                if is_mutated { "mut " } else { "" },
+                cursor_push.receiver,
+                ascription,
+                value(format!("String::from(\"{}\")", text))
+            );
+            ("Merge pushes into the declaration", TextRange::from_to(start, end), merged)
+        }
+        None => {
+            let start = statements[first].syntax().text_range().start();
+            let merged = format!(
+                "{}.push_str({});",
+                cursor_push.receiver,
+                if args.is_empty() { format!("\"{}\"", text) } else { format!("&{}", value(text)) }
+            );
+            ("Merge pushes into one `push_str`", TextRange::from_to(start, end), merged)
+        }
+    };
+    ctx.add_assist(
+        AssistId("merge_string_pushes"),
+        AssistKind::RefactorRewrite,
+        label,
+        range,
+        |edit| {
+            edit.replace(range, merged);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// A `push_str` or `push` statement.
+struct Push {
+    receiver: String,
+    piece: Piece,
+    /// Whether the pushed value reads the string, which must then be pushed
+    /// to before.
+    uses_receiver: bool,
+}
+
+enum Piece {
+    /// The contents of a string or a char literal.
+    Text(String),
+    Arg(String),
+}
+
+impl Push {
+    fn new(stmt: &ast::Stmt) -> Option<Push> {
+        let call = match stmt {
+            ast::Stmt::ExprStmt(it) => match it.expr()? {
+                ast::Expr::MethodCallExpr(it) => it,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let receiver = match call.expr()? {
+            ast::Expr::PathExpr(it) => it.path()?,
+            _ => return None,
+        };
+        if receiver.qualifier().is_some() {
+            return None;
+        }
+        let receiver = receiver.segment()?.name_ref()?.text().to_string();
+        let mut args = call.arg_list()?.args();
+        let arg = args.next()?;
+        if args.next().is_some() {
+            return None;
+        }
+        let literal = match &arg {
+            ast::Expr::Literal(it) => Some(it.token()),
+            _ => None,
+        };
+        let piece = match call.name_ref()?.text().as_str() {
+            "push_str" => match literal {
+                Some(it) if it.kind() == STRING => {
+                    Piece::Text(it.text()[1..it.text().len() - 1].to_string())
+                }
+                _ => Piece::Arg(displayed_arg(arg.clone())?.syntax().to_string()),
+            },
+            "push" => match literal {
+                Some(it) if it.kind() == CHAR => {
+                    let text = &it.text()[1..it.text().len() - 1];
+                    Piece::Text(if text == "\"" { "\\\"".to_string() } else { text.to_string() })
+                }
+                _ => Piece::Arg(arg.syntax().to_string()),
+            },
+            _ => return None,
+        };
+        let uses_receiver = arg
+            .syntax()
+            .descendants_with_tokens()
+            .any(|it| it.kind() == IDENT && it.to_string() == receiver);
+        Some(Push { receiver, piece, uses_receiver })
+    }
+}
+
+/// The value displayed by `push_str`, without a borrow and `to_string()` call.
+fn displayed_arg(arg: ast::Expr) -> Option<ast::Expr> {
+    let arg = match arg {
+        ast::Expr::RefExpr(it) if !it.is_mut() => it.expr()?,
+        it => return Some(it),
+    };
+    match &arg {
+        ast::Expr::MethodCallExpr(call)
+            if call.name_ref()?.text() == "to_string"
+                && call.arg_list()?.args().next().is_none() =>
+        {
+            call.expr()
+        }
+        _ => Some(arg),
+    }
+}
+
+/// The `let mut name = String::new();` statement.
+fn string_declaration(stmt: &ast::Stmt, name: &str) -> Option<ast::LetStmt> {
+    let let_stmt = match stmt {
+        ast::Stmt::LetStmt(it) => it.clone(),
+        _ => return None,
+    };
+    let pat = match let_stmt.pat()? {
+        ast::Pat::BindPat(it) => it,
+        _ => return None,
+    };
+    if !pat.is_mutable() || pat.is_ref() || pat.name()?.text() != name {
+        return None;
+    }
+    let call = match let_stmt.initializer()? {
+        ast::Expr::CallExpr(it) => it,
+        _ => return None,
+    };
+    match call.expr()? {
+        ast::Expr::PathExpr(it) if it.syntax().text() == "String::new" => (),
+        _ => return None,
+    }
+    if call.arg_list()?.args().next().is_some() {
+        return None;
+    }
+    Some(let_stmt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn merge_pushes_in_the_middle_of_a_function() {
+        check_assist(
+            merge_string_pushes,
+            r#"
+fn f(s: &mut String, x: u32, name: &str) {
+    s.push('(');
+    s.push_str(&x.to_string())<|>;
+    s.push_str(", {");
+    s.push_str(name);
+    s.push('}');
+    s.push(')');
+}"#,
+            r#"
+fn f(s: &mut String, x: u32, name: &str) {
+    <|>s.push_str(&format!("({}, {{{}}})", x, name));
+}"#,
+        );
+    }
+
+    #[test]
+    fn merge_pushes_into_the_declaration() {
+        check_assist(
+            merge_string_pushes,
+            r#"
+fn f(x: u32) -> String {
+    let mut s = String::new();
+    s.push_str("x = ");
+    s.push_str(&x.to_string())<|>;
+    s.push('"');
+    s
+}"#,
+            r#"
+fn f(x: u32) -> String {
+    <|>let s = format!("x = {}\"", x);
+    s
+}"#,
+        );
+        check_assist(
+            merge_string_pushes,
+            r#"
+fn f() -> String {
+    let mut s = String::new();
+    s.push_str("a{}")<|>;
+    s
+}"#,
+            r#"
+fn f() -> String {
+    <|>let s = String::from("a{}");
+    s
+}"#,
+        );
+    }
+
+    #[test]
+    fn merge_stops_at_other_statements() {
+        check_assist(
+            merge_string_pushes,
+            r#"
+fn f(x: u32) -> String {
+    let mut s = String::new();
+    s.push_str("a");
+    let y = x + 1;
+    s.push_str("b")<|>;
+    s.push_str(&y.to_string());
+    s
+}"#,
+            r#"
+fn f(x: u32) -> String {
+    let mut s = String::new();
+    s.push_str("a");
+    let y = x + 1;
+    <|>s.push_str(&format!("b{}", y));
+    s
+}"#,
+        );
+        check_assist_not_applicable(
+            merge_string_pushes,
+            r#"
+fn f(s: &mut String, x: u32) {
+    s.push_str("a")<|>;
+    let y = x + 1;
+    s.push_str(&y.to_string());
+}"#,
+        );
+    }
+
+    #[test]
+    fn merge_into_declaration_keeps_mut_for_later_mutations() {
+        check_assist(
+            merge_string_pushes,
+            r#"
+fn f(items: &[&str]) -> String {
+    let mut s = String::new();
+    s.push('[');
+    s.push_str(" "<|>);
+    for item in items {
+        s.push_str(item);
+    }
+    s
+}"#,
+            r#"
+fn f(items: &[&str]) -> String {
+    <|>let mut s = String::from("[ ");
+    for item in items {
+        s.push_str(item);
+    }
+    s
+}"#,
+        );
+    }
+}
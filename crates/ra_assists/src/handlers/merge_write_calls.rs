@@ -0,0 +1,292 @@
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner},
+    SyntaxElement,
+    SyntaxKind::{COMMA, EQ, IDENT, L_PAREN, R_PAREN, STRING, WHITESPACE},
+    SyntaxNode, TextRange,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: merge_write_calls
+//
+// Merges consecutive `write!` calls to the same formatter into one.
+//
+// ```
+// impl fmt::Display for Point {
+//     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//         write!(f, "(")?;
+//         write!(f, "{}, {}", self.x, self.y)<|>?;
+//         write!(f, ")")
+//     }
+// }
+// ```
+// ->
+// ```
+// impl fmt::Display for Point {
+//     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//         write!(f, "({}, {})", self.x, self.y)
+//     }
+// }
+// ```
+pub(crate) fn merge_write_calls(ctx: AssistCtx) -> Option<Assist> {
+    let block = ctx.find_node_at_offset::<ast::Block>()?;
+    let fn_def = block.syntax().ancestors().find_map(ast::FnDef::cast)?;
+    if fn_def.name()?.text() != "fmt" {
+        return None;
+    }
+    let mut elements = block.statements().map(|it| it.syntax().clone()).collect::<Vec<_>>();
+    let has_tail = block.expr().is_some();
+    elements.extend(block.expr().map(|it| it.syntax().clone()));
+    let offset = ctx.frange.range.start();
+    let idx = elements.iter().position(|it| it.text_range().contains_inclusive(offset))?;
+    let is_tail = |idx: usize| has_tail && idx + 1 == elements.len();
+
+    let cursor_write = WriteCall::new(&elements[idx], is_tail(idx))?;
+    let continues_run =
+        |it: &WriteCall| it.formatter == cursor_write.formatter && it.suffix == "?;";
+    let mut first = idx;
+    while first > 0 {
+        match WriteCall::new(&elements[first - 1], false) {
+            Some(it) if continues_run(&it) => first -= 1,
+            _ => break,
+        }
+    }
+    let mut last = idx;
+    if continues_run(&cursor_write) {
+        while last + 1 < elements.len() {
+            match WriteCall::new(&elements[last + 1], is_tail(last + 1)) {
+                Some(it) if it.formatter == cursor_write.formatter => {
+                    last += 1;
+                    if it.suffix != "?;" {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+    if first == last {
+        return None;
+    }
+
+    let writes = elements[first..=last]
+        .iter()
+        .enumerate()
+        .map(|(i, it)| WriteCall::new(it, is_tail(first + i)))
+        .collect::<Option<Vec<_>>>()?;
+    if writes.iter().any(|it| it.format.contains(explicit_position)) {
+        return None;
+    }
+    let last_write = writes.last()?;
+    let mut format = String::new();
+    let mut args = Vec::new();
+    for (i, write) in writes.iter().enumerate() {
+        format.push_str(&write.format);
+        if write.is_writeln && i + 1 < writes.len() {
+            format.push_str("\\n");
+        }
+        args.extend(write.args.iter().cloned());
+    }
+    let mut merged = format!(
+        "{}!({}, \"{}\"",
+        if last_write.is_writeln { "writeln" } else { "write" },
+        cursor_write.formatter,
+        format
+    );
+    for arg in args {
+        merged.push_str(", ");
+        merged.push_str(&arg);
+    }
+    merged.push(')');
+    merged.push_str(&last_write.suffix);
+
+    let range =
+        TextRange::from_to(elements[first].text_range().start(), elements[last].text_range().end());
+    ctx.add_assist(
+        AssistId("merge_write_calls"),
+        AssistKind::RefactorRewrite,
+        "Merge `write!` calls",
+        range,
+        |edit| {
+            edit.replace(range, merged);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+struct WriteCall {
+    is_writeln: bool,
+    formatter: String,
+    /// The contents of the format string literal.
+    format: String,
+    args: Vec<String>,
+    /// `?;` for a statement, `?` or nothing for the tail expression.
+    suffix: String,
+}
+
+impl WriteCall {
+    fn new(node: &SyntaxNode, is_tail: bool) -> Option<WriteCall> {
+        let (expr, suffix) = if is_tail {
+            let expr = ast::Expr::cast(node.clone())?;
+            match expr {
+                ast::Expr::TryExpr(it) => (it.expr()?, "?"),
+                it => (it, ""),
+            }
+        } else {
+            match ast::ExprStmt::cast(node.clone())?.expr()? {
+                ast::Expr::TryExpr(it) => (it.expr()?, "?;"),
+                _ => return None,
+            }
+        };
+        let call = match expr {
+            ast::Expr::MacroCall(it) => it,
+            _ => return None,
+        };
+        let is_writeln = match call.path()?.segment()?.name_ref()?.text().as_str() {
+            "write" => false,
+            "writeln" => true,
+            _ => return None,
+        };
+        let mut args = macro_args(&call)?.into_iter();
+        let formatter = text_of(&args.next()?);
+        let format = match args.next() {
+            Some(format) => match significant(&format).as_slice() {
+                [it] if it.kind() == STRING => {
+                    let text = it.to_string();
+                    text[1..text.len() - 1].to_string()
+                }
+                _ => return None,
+            },
+            None if is_writeln => String::new(),
+            None => return None,
+        };
+        let args = args.collect::<Vec<_>>();
+        // Named arguments must come last, they can't be merged with others.
+        let is_named = |arg: &[SyntaxElement]| match significant(arg).as_slice() {
+            [name, eq, ..] => name.kind() == IDENT && eq.kind() == EQ,
+            _ => false,
+        };
+        if args.iter().any(|it| is_named(it)) {
+            return None;
+        }
+        let args = args.iter().map(|it| text_of(it)).collect();
+        Some(WriteCall { is_writeln, formatter, format, args, suffix: suffix.to_string() })
+    }
+}
+
+/// The elements of the comma-separated arguments of a macro call.
+fn macro_args(call: &ast::MacroCall) -> Option<Vec<Vec<SyntaxElement>>> {
+    let token_tree = call.token_tree()?;
+    let mut res = vec![Vec::new()];
+    for element in token_tree.syntax().children_with_tokens() {
+        match element.kind() {
+            L_PAREN | R_PAREN => (),
+            COMMA => res.push(Vec::new()),
+            _ => res.last_mut()?.push(element),
+        }
+    }
+    if res.last().map_or(false, |it| significant(it).is_empty()) {
+        res.pop();
+    }
+    Some(res)
+}
+
+fn significant(elements: &[SyntaxElement]) -> Vec<&SyntaxElement> {
+    elements.iter().filter(|it| it.kind() != WHITESPACE).collect()
+}
+
+fn text_of(elements: &[SyntaxElement]) -> String {
+    elements.iter().map(|it| it.to_string()).collect::<String>().trim().to_string()
+}
+
+/// Whether a format string contains a placeholder referring to an argument by
+/// its position, as in `{0}`.
+fn explicit_position(format: &str) -> bool {
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        match chars.peek() {
+            Some('{') => {
+                chars.next();
+            }
+            Some(it) if it.is_ascii_digit() => return true,
+            _ => (),
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn merge_three_writes() {
+        check_assist(
+            merge_write_calls,
+            r#"
+fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a")?;
+    write!(f, "{}", self.x)<|>?;
+    write!(f, "b{{}}")?;
+    Ok(())
+}"#,
+            r#"
+fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    <|>write!(f, "a{}b{{}}", self.x)?;
+    Ok(())
+}"#,
+        );
+    }
+
+    #[test]
+    fn merge_writeln_in_the_middle() {
+        check_assist(
+            merge_write_calls,
+            r#"
+fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write<|>!(f, "a")?;
+    writeln!(f)?;
+    writeln!(f, "{}", self.x)
+}"#,
+            r#"
+fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    <|>writeln!(f, "a\n{}", self.x)
+}"#,
+        );
+    }
+
+    #[test]
+    fn merge_stops_at_other_statements() {
+        check_assist(
+            merge_write_calls,
+            r#"
+fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a")?;
+    let x = self.x;
+    write!(f, "{}", x)<|>?;
+    write!(f, "b")?;
+    Ok(())
+}"#,
+            r#"
+fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a")?;
+    let x = self.x;
+    <|>write!(f, "{}b", x)?;
+    Ok(())
+}"#,
+        );
+        check_assist_not_applicable(
+            merge_write_calls,
+            r#"
+fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a")<|>?;
+    let x = self.x;
+    write!(f, "{}", x)
+}"#,
+        );
+    }
+}
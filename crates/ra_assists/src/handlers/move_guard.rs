@@ -4,7 +4,7 @@ use ra_syntax::{
     TextUnit,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: move_guard_to_arm_body
 //
@@ -40,27 +40,32 @@ pub(crate) fn move_guard_to_arm_body(ctx: AssistCtx) -> Option<Assist> {
     let arm_expr = match_arm.expr()?;
     let buf = format!("if {} {{ {} }}", guard_conditions.syntax().text(), arm_expr.syntax().text());
 
-    ctx.add_assist(AssistId("move_guard_to_arm_body"), "Move guard to arm body", |edit| {
-        edit.target(guard.syntax().text_range());
-        let offseting_amount = match space_before_guard.and_then(|it| it.into_token()) {
-            Some(tok) => {
-                if ast::Whitespace::cast(tok.clone()).is_some() {
-                    let ele = tok.text_range();
-                    edit.delete(ele);
-                    ele.len()
-                } else {
-                    TextUnit::from(0)
+    ctx.add_assist(
+        AssistId("move_guard_to_arm_body"),
+        AssistKind::RefactorRewrite,
+        "Move guard to arm body",
+        guard.syntax().text_range(),
+        |edit| {
+            let offseting_amount = match space_before_guard.and_then(|it| it.into_token()) {
+                Some(tok) => {
+                    if ast::Whitespace::cast(tok.clone()).is_some() {
+                        let ele = tok.text_range();
+                        edit.delete(ele);
+                        ele.len()
+                    } else {
+                        TextUnit::from(0)
+                    }
                 }
-            }
-            _ => TextUnit::from(0),
-        };
+                _ => TextUnit::from(0),
+            };
 
-        edit.delete(guard.syntax().text_range());
-        edit.replace_node_and_indent(arm_expr.syntax(), buf);
-        edit.set_cursor(
-            arm_expr.syntax().text_range().start() + TextUnit::from(3) - offseting_amount,
-        );
-    })
+            edit.delete(guard.syntax().text_range());
+            edit.replace_node_and_indent(arm_expr.syntax(), buf);
+            edit.set_cursor(
+                arm_expr.syntax().text_range().start() + TextUnit::from(3) - offseting_amount,
+            );
+        },
+    )
 }
 
 // Assist: move_arm_cond_to_match_guard
@@ -110,9 +115,10 @@ pub(crate) fn move_arm_cond_to_match_guard(ctx: AssistCtx) -> Option<Assist> {
 
     ctx.add_assist(
         AssistId("move_arm_cond_to_match_guard"),
+        AssistKind::RefactorRewrite,
         "Move condition to match guard",
+        if_expr.syntax().text_range(),
         |edit| {
-            edit.target(if_expr.syntax().text_range());
             let then_only_expr = then_block.block().and_then(|it| it.statements().next()).is_none();
 
             match &then_block.block().and_then(|it| it.expr()) {
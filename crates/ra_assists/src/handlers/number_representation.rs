@@ -0,0 +1,371 @@
+use ra_syntax::{
+    ast::{self, AstNode, LiteralKind},
+    SmolStr,
+};
+
+use crate::{utils::identify_number_literal, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: convert_byte_literal_to_number
+//
+// Converts a byte literal to the number it stands for.
+//
+// ```
+// const STAR: u8 = b'*'<|>;
+// ```
+// ->
+// ```
+// const STAR: u8 = 42u8;
+// ```
+pub(crate) fn convert_byte_literal_to_number(ctx: AssistCtx) -> Option<Assist> {
+    let literal = ctx.find_node_at_offset::<ast::Literal>()?;
+    let value = match literal.kind() {
+        LiteralKind::Byte => byte_value(literal.token().text())?,
+        _ => return None,
+    };
+
+    let range = literal.syntax().text_range();
+    let mut group = ctx.add_assist_group("Convert byte literal");
+    for (label, text) in &[
+        ("Convert to numeric literal", format!("{}u8", value)),
+        ("Convert to hexadecimal literal", format!("0x{:02X}u8", value)),
+    ] {
+        group.add_assist(
+            AssistId("convert_byte_literal_to_number"),
+            AssistKind::RefactorRewrite,
+            *label,
+            range,
+            |edit| {
+                edit.replace(range, text.clone());
+                edit.set_cursor(range.start());
+            },
+        );
+    }
+    group.finish()
+}
+
+// Assist: convert_number_to_byte_literal
+//
+// Converts a `u8` number standing for an ASCII character to a byte literal.
+//
+// ```
+// const STAR: u8 = 42<|>;
+// ```
+// ->
+// ```
+// const STAR: u8 = b'*';
+// ```
+pub(crate) fn convert_number_to_byte_literal(ctx: AssistCtx) -> Option<Assist> {
+    let literal = ctx.find_node_at_offset::<ast::Literal>()?;
+    let (value, suffix) = identify_number_literal(&literal)?;
+    let is_u8 = match suffix {
+        Some(suffix) => suffix == "u8",
+        None => ctx
+            .sema
+            .type_of_expr(&ast::Expr::Literal(literal.clone()))
+            .map_or(false, |it| it.display(ctx.db).to_string() == "u8"),
+    };
+    if !is_u8 {
+        return None;
+    }
+    let text = byte_literal(value)?;
+
+    let range = literal.syntax().text_range();
+    ctx.add_assist(
+        AssistId("convert_number_to_byte_literal"),
+        AssistKind::RefactorRewrite,
+        "Convert to byte literal",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// Expanded float literals with more digits than this are unreadable.
+const MAX_DECIMAL_DIGITS: usize = 27;
+
+// Assist: convert_float_to_scientific
+//
+// Converts a decimal float literal to the scientific notation.
+//
+// ```
+// const SPEED: f64 = 1_200_000.0<|>;
+// ```
+// ->
+// ```
+// const SPEED: f64 = 1.2e6;
+// ```
+pub(crate) fn convert_float_to_scientific(ctx: AssistCtx) -> Option<Assist> {
+    let literal = ctx.find_node_at_offset::<ast::Literal>()?;
+    let (float, suffix) = identify_float_literal(&literal)?;
+    if float.has_exponent {
+        return None;
+    }
+    let text = format!("{}{}", float.to_scientific(), suffix.as_ref().map_or("", |it| it.as_str()));
+
+    let range = literal.syntax().text_range();
+    ctx.add_assist(
+        AssistId("convert_float_to_scientific"),
+        AssistKind::RefactorRewrite,
+        "Convert to scientific notation",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+// Assist: convert_scientific_to_float
+//
+// Converts a float literal in scientific notation to the decimal notation.
+//
+// ```
+// const SPEED: f64 = 1.2e6<|>;
+// ```
+// ->
+// ```
+// const SPEED: f64 = 1200000.0;
+// ```
+pub(crate) fn convert_scientific_to_float(ctx: AssistCtx) -> Option<Assist> {
+    let literal = ctx.find_node_at_offset::<ast::Literal>()?;
+    let (float, suffix) = identify_float_literal(&literal)?;
+    if !float.has_exponent {
+        return None;
+    }
+    let decimal = float.to_decimal()?;
+    let text = format!("{}{}", decimal, suffix.as_ref().map_or("", |it| it.as_str()));
+
+    let range = literal.syntax().text_range();
+    ctx.add_assist(
+        AssistId("convert_scientific_to_float"),
+        AssistKind::RefactorRewrite,
+        "Convert to decimal notation",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// A non-zero float literal, as the significant digits and the position of the
+/// decimal point relative to the first of them.
+struct Float {
+    digits: String,
+    point: i64,
+    has_exponent: bool,
+}
+
+impl Float {
+    fn to_scientific(&self) -> String {
+        let (first, rest) = self.digits.split_at(1);
+        let fraction = if rest.is_empty() { String::new() } else { format!(".{}", rest) };
+        format!("{}{}e{}", first, fraction, self.point - 1)
+    }
+
+    fn to_decimal(&self) -> Option<String> {
+        let len = self.digits.len() as i64;
+        let res = if self.point <= 0 {
+            format!("0.{}{}", "0".repeat(-self.point as usize), self.digits)
+        } else if self.point >= len {
+            format!("{}{}.0", self.digits, "0".repeat((self.point - len) as usize))
+        } else {
+            let (int, fraction) = self.digits.split_at(self.point as usize);
+            format!("{}.{}", int, fraction)
+        };
+        if res.len() - 1 > MAX_DECIMAL_DIGITS {
+            return None;
+        }
+        Some(res)
+    }
+}
+
+/// The digits and the suffix of a float literal, done on the text to keep the
+/// digits exactly as written.
+fn identify_float_literal(literal: &ast::Literal) -> Option<(Float, Option<SmolStr>)> {
+    let suffix = match literal.kind() {
+        LiteralKind::FloatNumber { suffix } => suffix,
+        _ => return None,
+    };
+    let text = literal.token().text().replace('_', "");
+    let text = &text[..text.len() - suffix.as_ref().map_or(0, |it| it.len())];
+    let (mantissa, exponent) = match text.find(|c: char| c == 'e' || c == 'E') {
+        Some(idx) => (&text[..idx], Some(text[idx + 1..].parse::<i64>().ok()?)),
+        None => (text, None),
+    };
+    let (int, fraction) = match mantissa.find('.') {
+        Some(idx) => (&mantissa[..idx], &mantissa[idx + 1..]),
+        None => (mantissa, ""),
+    };
+    if !int.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let all_digits = format!("{}{}", int, fraction);
+    let digits = all_digits.trim_start_matches('0');
+    let point = int.len() as i64 + exponent.unwrap_or(0) - (all_digits.len() - digits.len()) as i64;
+    let digits = digits.trim_end_matches('0');
+    if digits.is_empty() {
+        return None;
+    }
+    Some((Float { digits: digits.to_string(), point, has_exponent: exponent.is_some() }, suffix))
+}
+
+/// The value of a byte literal, like `b'\n'`.
+fn byte_value(text: &str) -> Option<u8> {
+    if text.len() < 3 || !text.starts_with("b'") || !text.ends_with('\'') {
+        return None;
+    }
+    let inner = &text[2..text.len() - 1];
+    if inner.starts_with("\\x") {
+        return u8::from_str_radix(&inner[2..], 16).ok();
+    }
+    let value = match inner.as_bytes() {
+        [c] => *c,
+        [b'\\', b'n'] => b'\n',
+        [b'\\', b'r'] => b'\r',
+        [b'\\', b't'] => b'\t',
+        [b'\\', b'0'] => b'\0',
+        [b'\\', b'\\'] => b'\\',
+        [b'\\', b'\''] => b'\'',
+        [b'\\', b'"'] => b'"',
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// The byte literal for `value`, if it's a printable ASCII character or has a
+/// usual escape.
+fn byte_literal(value: u128) -> Option<String> {
+    let escaped = match value {
+        0x0A => "\\n".to_string(),
+        0x0D => "\\r".to_string(),
+        0x09 => "\\t".to_string(),
+        0x00 => "\\0".to_string(),
+        0x5C => "\\\\".to_string(),
+        0x27 => "\\'".to_string(),
+        0x20..=0x7E => (value as u8 as char).to_string(),
+        _ => return None,
+    };
+    Some(format!("b'{}'", escaped))
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use ra_syntax::TextRange;
+    use test_utils::extract_offset;
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    #[test]
+    fn convert_printable_byte_both_ways() {
+        check_assist(
+            convert_byte_literal_to_number,
+            "fn f() { let x = b'<|>*'; }",
+            "fn f() { let x = <|>42u8; }",
+        );
+        check_assist(
+            convert_number_to_byte_literal,
+            "fn f() { let x = 4<|>2u8; }",
+            "fn f() { let x = <|>b'*'; }",
+        );
+        check_assist(
+            convert_number_to_byte_literal,
+            "fn f() { let x: u8 = 4<|>2; }",
+            "fn f() { let x: u8 = <|>b'*'; }",
+        );
+    }
+
+    #[test]
+    fn convert_escaped_byte_both_ways() {
+        check_assist(
+            convert_byte_literal_to_number,
+            r"fn f() { let x = b'\<|>n'; }",
+            "fn f() { let x = <|>10u8; }",
+        );
+        check_assist(
+            convert_number_to_byte_literal,
+            "fn f() { let x = 0x0<|>Au8; }",
+            r"fn f() { let x = <|>b'\n'; }",
+        );
+    }
+
+    #[test]
+    fn convert_number_to_byte_literal_not_applicable() {
+        check_assist_not_applicable(convert_number_to_byte_literal, "fn f() { let x = 7<|>u8; }");
+        check_assist_not_applicable(convert_number_to_byte_literal, "fn f() { let x = 4<|>2u32; }");
+        check_assist_not_applicable(convert_number_to_byte_literal, "fn f() { let x = 4<|>2; }");
+    }
+
+    #[test]
+    fn convert_float_both_ways() {
+        check_assist(
+            convert_float_to_scientific,
+            "fn f() { let x = 1_200_<|>000.0; }",
+            "fn f() { let x = <|>1.2e6; }",
+        );
+        check_assist(
+            convert_scientific_to_float,
+            "fn f() { let x = 1.2<|>e6; }",
+            "fn f() { let x = <|>1200000.0; }",
+        );
+        check_assist(
+            convert_float_to_scientific,
+            "fn f() { let x = 0.1<|>; }",
+            "fn f() { let x = <|>1e-1; }",
+        );
+        check_assist(
+            convert_scientific_to_float,
+            "fn f() { let x = 1e-<|>1; }",
+            "fn f() { let x = <|>0.1; }",
+        );
+    }
+
+    #[test]
+    fn convert_float_with_negative_exponent_and_suffix() {
+        check_assist(
+            convert_float_to_scientific,
+            "fn f() { let x = 0.00<|>15f32; }",
+            "fn f() { let x = <|>1.5e-3f32; }",
+        );
+        check_assist(
+            convert_scientific_to_float,
+            "fn f() { let x = 1.5<|>e-3f32; }",
+            "fn f() { let x = <|>0.0015f32; }",
+        );
+        check_assist(
+            convert_scientific_to_float,
+            "fn f() { let x = 12.5<|>E+1; }",
+            "fn f() { let x = <|>125.0; }",
+        );
+    }
+
+    #[test]
+    fn convert_scientific_not_applicable_to_huge_exponent() {
+        check_assist_not_applicable(convert_scientific_to_float, "fn f() { let x = 1.5<|>e30; }");
+        check_assist_not_applicable(convert_float_to_scientific, "fn f() { let x = 1.5<|>e3; }");
+        check_assist_not_applicable(convert_float_to_scientific, "fn f() { let x = 0.<|>0; }");
+    }
+
+    #[test]
+    fn convert_byte_literal_to_hexadecimal() {
+        let (offset, before) = extract_offset("fn f() { let x = b'<|>*'; }");
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let entries = resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "convert_byte_literal_to_number")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries.iter().map(|it| it.label.label.as_str()).collect::<Vec<_>>(),
+            vec!["Convert to numeric literal", "Convert to hexadecimal literal"]
+        );
+        assert_eq!(entries[1].action.edit().apply(&before), "fn f() { let x = 0x2Au8; }");
+    }
+}
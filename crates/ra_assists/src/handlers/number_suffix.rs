@@ -0,0 +1,226 @@
+use ra_syntax::{
+    ast::{self, AstNode},
+    TextRange, TextUnit,
+};
+
+use crate::{utils::identify_number_literal, Assist, AssistCtx, AssistId, AssistKind};
+
+/// The suffixes offered for the literals of a selection, with the largest
+/// value they hold.
+const SUFFIXES: &[(&str, u128)] = &[
+    ("u8", u8::max_value() as u128),
+    ("u16", u16::max_value() as u128),
+    ("u32", u32::max_value() as u128),
+    ("i32", i32::max_value() as u128),
+    ("u64", u64::max_value() as u128),
+    ("usize", u64::max_value() as u128),
+];
+
+// Assist: add_number_suffixes
+//
+// Adds the same suffix to all the integer literals of the selection which
+// don't have one.
+//
+// ```
+// const MASKS: [u8; 3] = [<|>0x0F, 0xF0, 0xFF<|>];
+// ```
+// ->
+// ```
+// const MASKS: [u8; 3] = [0x0Fu8, 0xF0u8, 0xFFu8];
+// ```
+pub(crate) fn add_number_suffixes(ctx: AssistCtx) -> Option<Assist> {
+    let literals = selected_literals(&ctx)?
+        .into_iter()
+        .filter_map(|literal| {
+            let (value, suffix) = identify_number_literal(&literal)?;
+            if suffix.is_some() {
+                return None;
+            }
+            Some((literal.syntax().text_range(), value, is_negated(&literal)))
+        })
+        .collect::<Vec<_>>();
+    if literals.is_empty() {
+        return None;
+    }
+
+    let range = ctx.frange.range;
+    let mut group = ctx.add_assist_group("Add suffix to all literals in selection");
+    for &(suffix, max) in SUFFIXES {
+        let is_signed = suffix.starts_with('i');
+        // Negated literals only take the suffixes of signed types.
+        let fits = literals
+            .iter()
+            .all(|&(_, value, is_negated)| value <= max && (is_signed || !is_negated));
+        if !fits {
+            continue;
+        }
+        group.add_assist(
+            AssistId("add_number_suffixes"),
+            AssistKind::RefactorRewrite,
+            format!("Add `{}` suffix to all literals", suffix),
+            range,
+            |edit| {
+                for (range, _, _) in &literals {
+                    edit.insert(range.end(), suffix);
+                }
+            },
+        );
+    }
+    group.finish()
+}
+
+// Assist: remove_number_suffixes
+//
+// Removes the suffixes of all the integer literals of the selection.
+//
+// ```
+// const MASKS: [u8; 3] = [<|>0x0Fu8, 0xF0u8, 0xFF_u8<|>];
+// ```
+// ->
+// ```
+// const MASKS: [u8; 3] = [0x0F, 0xF0, 0xFF];
+// ```
+pub(crate) fn remove_number_suffixes(ctx: AssistCtx) -> Option<Assist> {
+    let suffixes = selected_literals(&ctx)?
+        .into_iter()
+        .filter_map(|literal| {
+            let suffix = identify_number_literal(&literal)?.1?;
+            let text = literal.token().text().as_str();
+            // A separated suffix, like in `1_u8`, goes with its separators.
+            let number = text[..text.len() - suffix.len()].trim_end_matches('_');
+            let range = literal.syntax().text_range();
+            Some(TextRange::from_to(range.start() + TextUnit::of_str(number), range.end()))
+        })
+        .collect::<Vec<_>>();
+    if suffixes.is_empty() {
+        return None;
+    }
+
+    ctx.add_assist(
+        AssistId("remove_number_suffixes"),
+        AssistKind::RefactorRewrite,
+        "Remove suffixes from all literals in selection",
+        ctx.frange.range,
+        |edit| {
+            for range in suffixes {
+                edit.delete(range);
+            }
+        },
+    )
+}
+
+/// The literals within the selection, which must not be empty.
+fn selected_literals(ctx: &AssistCtx) -> Option<Vec<ast::Literal>> {
+    let range = ctx.frange.range;
+    if range.is_empty() {
+        return None;
+    }
+    let node = ctx.covering_element().ancestors().next()?;
+    let literals = node
+        .descendants()
+        .filter_map(ast::Literal::cast)
+        .filter(|it| it.syntax().text_range().is_subrange(&range))
+        .collect();
+    Some(literals)
+}
+
+fn is_negated(literal: &ast::Literal) -> bool {
+    literal
+        .syntax()
+        .parent()
+        .and_then(ast::PrefixExpr::cast)
+        .map_or(false, |it| it.op_kind() == Some(ast::PrefixOp::Neg))
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use test_utils::extract_range;
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    /// The labels and results of the entries of the adding assist.
+    fn entries(text: &str) -> Vec<(String, String)> {
+        let (range, before) = extract_range(text);
+        let (db, file_id) = helpers::with_single_file(&before);
+        resolved_assists(&db, FileRange { file_id, range })
+            .into_iter()
+            .filter(|it| it.label.id.0 == "add_number_suffixes")
+            .map(|it| (it.label.label.clone(), it.action.edit().apply(&before)))
+            .collect()
+    }
+
+    #[test]
+    fn add_u8_suffix_to_array() {
+        check_assist(
+            add_number_suffixes,
+            "
+const TABLE: [u8; 5] = [
+    <|>1,
+    0x20,
+    0b11,
+    4_0,
+    255,<|>
+];",
+            "
+const TABLE: [u8; 5] = [
+    1u8,
+    0x20u8,
+    0b11u8,
+    4_0u8,
+    255u8,
+];",
+        );
+    }
+
+    #[test]
+    fn add_suffix_skips_suffixed_literals() {
+        check_assist(
+            add_number_suffixes,
+            "fn f() { let x = [<|>1, 2u16, 3<|>]; }",
+            "fn f() { let x = [1u8, 2u16, 3u8]; }",
+        );
+    }
+
+    #[test]
+    fn add_suffix_offers_types_of_the_values() {
+        let labels = |text: &str| entries(text).into_iter().map(|it| it.0).collect::<Vec<_>>();
+        assert_eq!(
+            labels("fn f() { let x = [<|>1, 300<|>]; }"),
+            vec![
+                "Add `u16` suffix to all literals",
+                "Add `u32` suffix to all literals",
+                "Add `i32` suffix to all literals",
+                "Add `u64` suffix to all literals",
+                "Add `usize` suffix to all literals",
+            ]
+        );
+        assert_eq!(
+            entries("fn f() { let x = [<|>1, -2<|>]; }"),
+            vec![(
+                "Add `i32` suffix to all literals".to_string(),
+                "fn f() { let x = [1i32, -2i32]; }".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn remove_suffixes_from_selection() {
+        check_assist(
+            remove_number_suffixes,
+            "fn f() { let x = [<|>1u8, 2, 3_u8<|>, 4u8]; }",
+            "fn f() { let x = [1, 2, 3, 4u8]; }",
+        );
+        check_assist_not_applicable(remove_number_suffixes, "fn f() { let x = [<|>1, 2<|>]; }");
+    }
+
+    #[test]
+    fn not_applicable_without_selection() {
+        check_assist_not_applicable(add_number_suffixes, "fn f() { let x = [1<|>, 2]; }");
+        check_assist_not_applicable(remove_number_suffixes, "fn f() { let x = [1u8<|>, 2u8]; }");
+    }
+}
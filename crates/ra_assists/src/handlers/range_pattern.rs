@@ -0,0 +1,223 @@
+use std::convert::TryFrom;
+
+use ra_syntax::{
+    ast::{self, AstNode, LiteralKind},
+    SmolStr,
+    SyntaxKind::{MATCH_ARM, OR_PAT},
+    T,
+};
+
+use crate::{utils::identify_number_literal, Assist, AssistCtx, AssistId, AssistKind};
+
+/// Ranges with more values than this are not worth spelling out.
+const MAX_EXPANDED_VALUES: i128 = 16;
+
+// Assist: expand_range_pattern
+//
+// Replaces a small range pattern by the or-pattern of its values.
+//
+// ```
+// fn is_small(n: u32) -> bool {
+//     match n {
+//         1..=3<|> => true,
+//         _ => false,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn is_small(n: u32) -> bool {
+//     match n {
+//         1 | 2 | 3 => true,
+//         _ => false,
+//     }
+// }
+// ```
+pub(crate) fn expand_range_pattern(ctx: AssistCtx) -> Option<Assist> {
+    let range_pat = ctx.find_node_at_offset::<ast::RangePat>()?;
+    // Nested or-patterns are not allowed everywhere yet.
+    match range_pat.syntax().parent()?.kind() {
+        MATCH_ARM | OR_PAT => (),
+        _ => return None,
+    }
+    let bounds = range_pat
+        .syntax()
+        .children()
+        .filter_map(ast::LiteralPat::cast)
+        .map(|it| bound_value(&it))
+        .collect::<Vec<_>>();
+    let inclusive = range_pat
+        .syntax()
+        .children_with_tokens()
+        .any(|it| it.kind() == T![..=] || it.kind() == T![...]);
+    let values = match (bounds.get(0)?.as_ref()?, bounds.get(1)?.as_ref()?) {
+        (Bound::Int(lo, suffix), Bound::Int(hi, _)) => {
+            let hi = if inclusive { *hi } else { hi - 1 };
+            if hi < *lo || hi - lo >= MAX_EXPANDED_VALUES {
+                return None;
+            }
+            let suffix = suffix.as_ref().map_or("", |it| it.as_str());
+            (*lo..=hi).map(|it| format!("{}{}", it, suffix)).collect::<Vec<_>>()
+        }
+        (Bound::Char(lo), Bound::Char(hi)) => {
+            let (lo, hi) = (*lo as i128, if inclusive { *hi as i128 } else { *hi as i128 - 1 });
+            if hi < lo || hi - lo >= MAX_EXPANDED_VALUES {
+                return None;
+            }
+            let mut values = Vec::new();
+            for value in lo..=hi {
+                let c = std::char::from_u32(u32::try_from(value).ok()?)?;
+                values.push(format!("'{}'", c.escape_default()));
+            }
+            values
+        }
+        _ => return None,
+    };
+
+    let range = range_pat.syntax().text_range();
+    ctx.add_assist(
+        AssistId("expand_range_pattern"),
+        AssistKind::RefactorRewrite,
+        "Expand range to or-pattern",
+        range,
+        |edit| {
+            edit.replace(range, values.join(" | "));
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+// Assist: collapse_to_range_pattern
+//
+// Replaces an or-pattern of consecutive integer or char literals by a range
+// pattern.
+//
+// ```
+// fn is_small(n: u32) -> bool {
+//     match n {
+//         3 | 1 <|>| 2 => true,
+//         _ => false,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn is_small(n: u32) -> bool {
+//     match n {
+//         1..=3 => true,
+//         _ => false,
+//     }
+// }
+// ```
+pub(crate) fn collapse_to_range_pattern(ctx: AssistCtx) -> Option<Assist> {
+    let or_pat = ctx.find_node_at_offset::<ast::OrPat>()?;
+    let mut literals = Vec::new();
+    for pat in or_pat.pats() {
+        let literal_pat = match pat {
+            ast::Pat::LiteralPat(it) => it,
+            _ => return None,
+        };
+        let (value, is_char) = match bound_value(&literal_pat)? {
+            Bound::Int(value, _) => (value, false),
+            Bound::Char(c) => (c as i128, true),
+        };
+        literals.push((value, is_char, literal_pat));
+    }
+    if literals.len() < 2 || literals.iter().any(|(_, is_char, _)| *is_char != literals[0].1) {
+        return None;
+    }
+    literals.sort_by_key(|(value, _, _)| *value);
+    if literals.windows(2).any(|pair| pair[1].0 - pair[0].0 != 1) {
+        return None;
+    }
+    let lo = &literals[0].2;
+    let hi = &literals[literals.len() - 1].2;
+    let text = format!("{}..={}", lo.syntax(), hi.syntax());
+
+    let range = or_pat.syntax().text_range();
+    ctx.add_assist(
+        AssistId("collapse_to_range_pattern"),
+        AssistKind::RefactorRewrite,
+        "Collapse consecutive literals to range",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+enum Bound {
+    Int(i128, Option<SmolStr>),
+    Char(char),
+}
+
+fn bound_value(pat: &ast::LiteralPat) -> Option<Bound> {
+    let literal = pat.literal()?;
+    let is_negative = pat.syntax().first_token()?.kind() == T![-];
+    match literal.kind() {
+        LiteralKind::IntNumber { .. } => {
+            let (value, suffix) = identify_number_literal(&literal)?;
+            let value = i128::try_from(value).ok()?;
+            Some(Bound::Int(if is_negative { -value } else { value }, suffix))
+        }
+        LiteralKind::Char => {
+            let text = literal.token().text().to_string();
+            let mut chars = text.trim_start_matches('\'').trim_end_matches('\'').chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '\\' => Some(Bound::Char(c)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    fn in_match(pat: &str) -> String {
+        format!("fn f(x: u32) {{ match x {{ {} => (), _ => () }} }}", pat)
+    }
+
+    #[test]
+    fn expand_and_collapse_inclusive_range() {
+        check_assist(expand_range_pattern, &in_match("1..<|>=4"), &in_match("<|>1 | 2 | 3 | 4"));
+        check_assist(
+            collapse_to_range_pattern,
+            &in_match("1 | 2 <|>| 3 | 4"),
+            &in_match("<|>1..=4"),
+        );
+    }
+
+    #[test]
+    fn expand_exclusive_range() {
+        check_assist(expand_range_pattern, &in_match("-1<|>..2"), &in_match("<|>-1 | 0 | 1"));
+    }
+
+    #[test]
+    fn expand_and_collapse_char_range() {
+        check_assist(
+            expand_range_pattern,
+            &in_match("'a'..=<|>'d'"),
+            &in_match("<|>'a' | 'b' | 'c' | 'd'"),
+        );
+        check_assist(
+            collapse_to_range_pattern,
+            &in_match("'c' | 'a' <|>| 'b'"),
+            &in_match("<|>'a'..='c'"),
+        );
+    }
+
+    #[test]
+    fn collapse_not_applicable_with_gap() {
+        check_assist_not_applicable(collapse_to_range_pattern, &in_match("1 | 2 <|>| 4"));
+    }
+
+    #[test]
+    fn expand_not_applicable_to_wide_range() {
+        check_assist_not_applicable(expand_range_pattern, &in_match("0..<|>=999"));
+    }
+}
@@ -0,0 +1,176 @@
+use hir::Semantics;
+use ra_ide_db::{
+    defs::{classify_name, classify_name_ref, Definition},
+    RootDatabase,
+};
+use ra_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind::{self, IDENT},
+    SyntaxToken, TextRange, TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+/// Keywords which are lexed as identifiers, as they are only reserved or
+/// belong to a newer edition.
+const RESERVED: &[&str] = &[
+    "abstract", "become", "do", "final", "gen", "override", "priv", "typeof", "unsized", "virtual",
+    "yield",
+];
+
+// Assist: make_raw_identifier
+//
+// Adds `r#` to an identifier which is a keyword in a newer edition, at the
+// definition and at all its uses in the file.
+//
+// ```
+// fn gen<|>() {}
+// fn main() { gen() }
+// ```
+// ->
+// ```
+// fn r#gen() {}
+// fn main() { r#gen() }
+// ```
+pub(crate) fn make_raw_identifier(ctx: AssistCtx) -> Option<Assist> {
+    let token = ctx.find_token_at_offset(IDENT)?;
+    if !RESERVED.contains(&token.text().as_str()) {
+        return None;
+    }
+    let name = token.text().to_string();
+    let ranges = occurrences(&ctx.sema, &token)?;
+    ctx.add_assist(
+        AssistId("make_raw_identifier"),
+        AssistKind::RefactorRewrite,
+        "Convert to raw identifier",
+        token.text_range(),
+        |edit| {
+            let new_name = format!("r#{}", name);
+            edit.set_cursor(shifted_start(&token, &ranges, &new_name));
+            for range in ranges {
+                edit.replace(range, new_name.clone());
+            }
+        },
+    )
+}
+
+// Assist: remove_raw_identifier_prefix
+//
+// Removes `r#` from an identifier which is not a keyword, at the definition
+// and at all its uses in the file.
+//
+// ```
+// fn r#foo<|>() {}
+// fn main() { r#foo() }
+// ```
+// ->
+// ```
+// fn foo() {}
+// fn main() { foo() }
+// ```
+pub(crate) fn remove_raw_identifier_prefix(ctx: AssistCtx) -> Option<Assist> {
+    let token = ctx.find_token_at_offset(IDENT)?;
+    let name = token.text().trim_start_matches("r#").to_string();
+    if name.len() == token.text().len()
+        || SyntaxKind::from_keyword(&name).is_some()
+        || RESERVED.contains(&name.as_str())
+    {
+        return None;
+    }
+    let ranges = occurrences(&ctx.sema, &token)?;
+    ctx.add_assist(
+        AssistId("remove_raw_identifier_prefix"),
+        AssistKind::RefactorRewrite,
+        "Remove r# prefix",
+        token.text_range(),
+        |edit| {
+            edit.set_cursor(shifted_start(&token, &ranges, &name));
+            for range in ranges {
+                edit.replace(range, name.clone());
+            }
+        },
+    )
+}
+
+/// The ranges of the identifiers in the file, raw or not, referring to the
+/// same definition as `token`.
+fn occurrences(sema: &Semantics<RootDatabase>, token: &SyntaxToken) -> Option<Vec<TextRange>> {
+    let def = definition(sema, token)?;
+    let name = token.text().trim_start_matches("r#");
+    let file = token.parent().ancestors().last()?;
+    let ranges = file
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| it.kind() == IDENT && it.text().trim_start_matches("r#") == name)
+        .filter(|it| definition(sema, it).as_ref() == Some(&def))
+        .map(|it| it.text_range())
+        .collect();
+    Some(ranges)
+}
+
+/// Where `token` starts once all the `ranges` are renamed to `new_name`.
+fn shifted_start(token: &SyntaxToken, ranges: &[TextRange], new_name: &str) -> TextUnit {
+    let start = token.text_range().start();
+    ranges
+        .iter()
+        .filter(|it| it.start() < start)
+        .fold(start, |acc, it| acc + TextUnit::of_str(new_name) - it.len())
+}
+
+fn definition(sema: &Semantics<RootDatabase>, token: &SyntaxToken) -> Option<Definition> {
+    let parent = token.parent();
+    if let Some(name) = ast::Name::cast(parent.clone()) {
+        classify_name(sema, &name).map(|it| it.definition())
+    } else {
+        let name_ref = ast::NameRef::cast(parent)?;
+        classify_name_ref(sema, &name_ref).map(|it| it.definition())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn make_raw_identifier_renames_uses() {
+        check_assist(
+            make_raw_identifier,
+            "
+fn ge<|>n() -> u32 { 0 }
+fn main() {
+    let x = gen();
+    let y = gen() + x;
+}",
+            "
+fn <|>r#gen() -> u32 { 0 }
+fn main() {
+    let x = r#gen();
+    let y = r#gen() + x;
+}",
+        );
+    }
+
+    #[test]
+    fn remove_raw_identifier_prefix_renames_uses() {
+        check_assist(
+            remove_raw_identifier_prefix,
+            "
+struct S { r#foo: u32 }
+fn f(s: S) -> u32 { s.r#fo<|>o + s.foo }",
+            "
+struct S { foo: u32 }
+fn f(s: S) -> u32 { s.<|>foo + s.foo }",
+        );
+    }
+
+    #[test]
+    fn remove_raw_identifier_prefix_not_applicable_to_keyword() {
+        check_assist_not_applicable(
+            remove_raw_identifier_prefix,
+            "
+fn r#match<|>() {}
+fn main() { r#match() }",
+        );
+    }
+}
@@ -1,15 +1,17 @@
 use ra_syntax::{
     ast::{self, HasStringValue},
     AstToken,
-    SyntaxKind::{RAW_STRING, STRING},
-    TextUnit,
+    SyntaxKind::{BYTE_STRING, RAW_BYTE_STRING, RAW_STRING, STRING},
+    TextRange, TextUnit,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: make_raw_string
 //
-// Adds `r#` to a plain string literal.
+// Adds `r#` to a plain string literal, which may be in an attribute or a
+// macro call. Strings with escaped white space or control characters, like
+// `\n`, are left alone.
 //
 // ```
 // fn main() {
@@ -24,16 +26,27 @@ use crate::{Assist, AssistCtx, AssistId};
 // ```
 pub(crate) fn make_raw_string(ctx: AssistCtx) -> Option<Assist> {
     let token = ctx.find_token_at_offset(STRING).and_then(ast::String::cast)?;
+    if escapes(token.text()).iter().any(|it| !is_written_out_in_raw_string(it)) {
+        return None;
+    }
     let value = token.value()?;
-    ctx.add_assist(AssistId("make_raw_string"), "Rewrite as raw string", |edit| {
-        edit.target(token.syntax().text_range());
-        let max_hash_streak = count_hashes(&value);
-        let mut hashes = String::with_capacity(max_hash_streak + 1);
-        for _ in 0..hashes.capacity() {
-            hashes.push('#');
-        }
-        edit.replace(token.syntax().text_range(), format!("r{}\"{}\"{}", hashes, value, hashes));
-    })
+    ctx.add_assist(
+        AssistId("make_raw_string"),
+        AssistKind::RefactorRewrite,
+        "Rewrite as raw string",
+        token.syntax().text_range(),
+        |edit| {
+            let max_hash_streak = count_hashes(&value);
+            let mut hashes = String::with_capacity(max_hash_streak + 1);
+            for _ in 0..hashes.capacity() {
+                hashes.push('#');
+            }
+            edit.replace(
+                token.syntax().text_range(),
+                format!("r{}\"{}\"{}", hashes, value, hashes),
+            );
+        },
+    )
 }
 
 // Assist: make_usual_string
@@ -54,12 +67,17 @@ pub(crate) fn make_raw_string(ctx: AssistCtx) -> Option<Assist> {
 pub(crate) fn make_usual_string(ctx: AssistCtx) -> Option<Assist> {
     let token = ctx.find_token_at_offset(RAW_STRING).and_then(ast::RawString::cast)?;
     let value = token.value()?;
-    ctx.add_assist(AssistId("make_usual_string"), "Rewrite as regular string", |edit| {
-        edit.target(token.syntax().text_range());
-        // parse inside string to escape `"`
-        let escaped = value.escape_default().to_string();
-        edit.replace(token.syntax().text_range(), format!("\"{}\"", escaped));
-    })
+    ctx.add_assist(
+        AssistId("make_usual_string"),
+        AssistKind::RefactorRewrite,
+        "Rewrite as regular string",
+        token.syntax().text_range(),
+        |edit| {
+            // parse inside string to escape `"`
+            let escaped = value.escape_default().to_string();
+            edit.replace(token.syntax().text_range(), format!("\"{}\"", escaped));
+        },
+    )
 }
 
 // Assist: add_hash
@@ -78,12 +96,20 @@ pub(crate) fn make_usual_string(ctx: AssistCtx) -> Option<Assist> {
 // }
 // ```
 pub(crate) fn add_hash(ctx: AssistCtx) -> Option<Assist> {
-    let token = ctx.find_token_at_offset(RAW_STRING)?;
-    ctx.add_assist(AssistId("add_hash"), "Add # to raw string", |edit| {
-        edit.target(token.text_range());
-        edit.insert(token.text_range().start() + TextUnit::of_char('r'), "#");
-        edit.insert(token.text_range().end(), "#");
-    })
+    let token = ctx
+        .find_token_at_offset(RAW_STRING)
+        .or_else(|| ctx.find_token_at_offset(RAW_BYTE_STRING))?;
+    let prefix_len = if token.kind() == RAW_BYTE_STRING { "br" } else { "r" };
+    ctx.add_assist(
+        AssistId("add_hash"),
+        AssistKind::RefactorRewrite,
+        "Add # to raw string",
+        token.text_range(),
+        |edit| {
+            edit.insert(token.text_range().start() + TextUnit::of_str(prefix_len), "#");
+            edit.insert(token.text_range().end(), "#");
+        },
+    )
 }
 
 // Assist: remove_hash
@@ -102,25 +128,275 @@ pub(crate) fn add_hash(ctx: AssistCtx) -> Option<Assist> {
 // }
 // ```
 pub(crate) fn remove_hash(ctx: AssistCtx) -> Option<Assist> {
-    let token = ctx.find_token_at_offset(RAW_STRING)?;
+    let token = ctx
+        .find_token_at_offset(RAW_STRING)
+        .or_else(|| ctx.find_token_at_offset(RAW_BYTE_STRING))?;
     let text = token.text().as_str();
-    if text.starts_with("r\"") {
+    let prefix = if token.kind() == RAW_BYTE_STRING { "br" } else { "r" };
+    let hashes = text[prefix.len()..].chars().take_while(|&c| c == '#').count();
+    if hashes == 0 || text.len() < prefix.len() + 2 * hashes + 2 {
         // no hash to remove
         return None;
     }
-    ctx.add_assist(AssistId("remove_hash"), "Remove hash from raw string", |edit| {
-        edit.target(token.text_range());
-        let result = &text[2..text.len() - 1];
-        let result = if result.starts_with('\"') {
-            // FIXME: this logic is wrong, not only the last has has to handled specially
-            // no more hash, escape
-            let internal_str = &result[1..result.len() - 1];
-            format!("\"{}\"", internal_str.escape_default().to_string())
+    // The content must not contain the shorter closing delimiter.
+    let content = &text[prefix.len() + hashes + 1..text.len() - hashes - 1];
+    let closing = format!("\"{}", "#".repeat(hashes - 1));
+    if content.contains(&closing) {
+        return None;
+    }
+    ctx.add_assist(
+        AssistId("remove_hash"),
+        AssistKind::RefactorRewrite,
+        "Remove hash from raw string",
+        token.text_range(),
+        |edit| {
+            let start = token.text_range().start() + TextUnit::of_str(prefix);
+            let end = token.text_range().end();
+            edit.delete(TextRange::offset_len(start, TextUnit::of_char('#')));
+            edit.delete(TextRange::from_to(end - TextUnit::of_char('#'), end));
+        },
+    )
+}
+
+// Assist: make_byte_string
+//
+// Turns an ASCII string literal into a byte string.
+//
+// ```
+// fn main() {
+//     "Hello,<|> World!";
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     b"Hello, World!";
+// }
+// ```
+pub(crate) fn make_byte_string(ctx: AssistCtx) -> Option<Assist> {
+    let token =
+        ctx.find_token_at_offset(STRING).or_else(|| ctx.find_token_at_offset(RAW_STRING))?;
+    let text = token.text().as_str();
+    if !text.is_ascii() {
+        return None;
+    }
+    // Unicode escapes are not allowed in byte strings.
+    if token.kind() == STRING && escapes(text).iter().any(|it| it.starts_with('u')) {
+        return None;
+    }
+    ctx.add_assist(
+        AssistId("make_byte_string"),
+        AssistKind::RefactorRewrite,
+        "Rewrite as byte string",
+        token.text_range(),
+        |edit| {
+            edit.insert(token.text_range().start(), "b");
+        },
+    )
+}
+
+// Assist: make_string_from_byte_string
+//
+// Turns a byte string literal into a string.
+//
+// ```
+// fn main() {
+//     b"Hello,<|> World!";
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     "Hello, World!";
+// }
+// ```
+pub(crate) fn make_string_from_byte_string(ctx: AssistCtx) -> Option<Assist> {
+    let token = ctx
+        .find_token_at_offset(BYTE_STRING)
+        .or_else(|| ctx.find_token_at_offset(RAW_BYTE_STRING))?;
+    // Bytes above 0x7F are not valid UTF-8 on their own.
+    let non_ascii_byte = |escape: &str| {
+        escape.starts_with('x')
+            && u8::from_str_radix(&escape[1..], 16).map_or(false, |it| it > 0x7F)
+    };
+    if token.kind() == BYTE_STRING && escapes(token.text()).iter().any(|it| non_ascii_byte(it)) {
+        return None;
+    }
+    ctx.add_assist(
+        AssistId("make_string_from_byte_string"),
+        AssistKind::RefactorRewrite,
+        "Rewrite as string",
+        token.text_range(),
+        |edit| {
+            let start = token.text_range().start();
+            edit.delete(TextRange::offset_len(start, TextUnit::of_char('b')));
+        },
+    )
+}
+
+// Assist: escape_non_ascii
+//
+// Replaces the non-ASCII characters of a string literal with unicode escapes.
+//
+// ```
+// fn main() {
+//     "caf<|>é";
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     "caf\u{00E9}";
+// }
+// ```
+pub(crate) fn escape_non_ascii(ctx: AssistCtx) -> Option<Assist> {
+    let token = ctx.find_token_at_offset(STRING)?;
+    if token.text().is_ascii() {
+        return None;
+    }
+    ctx.add_assist(
+        AssistId("escape_non_ascii"),
+        AssistKind::RefactorRewrite,
+        "Escape non-ASCII characters",
+        token.text_range(),
+        |edit| {
+            edit.replace(token.text_range(), escape_non_ascii_chars(token.text()));
+        },
+    )
+}
+
+// Assist: unescape_char_codes
+//
+// Replaces the `\u{..}` and `\x..` escapes of a string literal with the
+// characters they stand for.
+//
+// ```
+// fn main() {
+//     "caf<|>\u{E9}";
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     "café";
+// }
+// ```
+pub(crate) fn unescape_char_codes(ctx: AssistCtx) -> Option<Assist> {
+    let token =
+        ctx.find_token_at_offset(STRING).or_else(|| ctx.find_token_at_offset(BYTE_STRING))?;
+    let text = token.text();
+    if !escapes(text).iter().any(|it| it.starts_with('u') || it.starts_with('x')) {
+        return None;
+    }
+    let unescaped = unescape_char_code_escapes(text, token.kind() == BYTE_STRING)?;
+    ctx.add_assist(
+        AssistId("unescape_char_codes"),
+        AssistKind::RefactorRewrite,
+        "Unescape to literal characters",
+        token.text_range(),
+        |edit| {
+            edit.replace(token.text_range(), unescaped);
+        },
+    )
+}
+
+/// Returns the escape sequences of a literal, without the leading backslash.
+fn escapes(text: &str) -> Vec<&str> {
+    let mut res = Vec::new();
+    let mut chars = text.char_indices();
+    while let Some((_, c)) = chars.next() {
+        if c != '\\' {
+            continue;
+        }
+        let (start, c) = match chars.next() {
+            Some(it) => it,
+            None => break,
+        };
+        let len = match c {
+            'x' => 3,
+            'u' => text[start..].find('}').map_or(1, |it| it + 1),
+            _ => 1,
+        };
+        let end = (start + len).min(text.len());
+        res.push(&text[start..end]);
+        for _ in start + 1..end {
+            chars.next();
+        }
+    }
+    res
+}
+
+/// Whether the character of `escape` can be written as is in a raw string,
+/// unlike white space and control characters which would be silently turned
+/// into a different text.
+fn is_written_out_in_raw_string(escape: &str) -> bool {
+    match escape {
+        "\"" | "\\" | "'" | "\n" => true,
+        _ if escape.starts_with('x') || escape.starts_with("u{") => {
+            let digits = escape.trim_start_matches(|c| c == 'x' || c == 'u' || c == '{');
+            u32::from_str_radix(digits.trim_end_matches('}'), 16)
+                .ok()
+                .and_then(std::char::from_u32)
+                .map_or(false, |it| !it.is_control() && !it.is_whitespace())
+        }
+        _ => false,
+    }
+}
+
+/// Rewrites the non-ASCII characters of `text` as `\u{..}` escapes.
+fn escape_non_ascii_chars(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            res.push(c);
         } else {
-            result.to_owned()
+            res.push_str(&format!("\\u{{{:04X}}}", c as u32));
+        }
+    }
+    res
+}
+
+/// Rewrites the `\u{..}` and `\x..` escapes of `text` as the characters they
+/// stand for, keeping the other escapes.
+///
+/// Returns `None` if some escape stands for a control character, or for a
+/// non-ASCII one in a byte string.
+fn unescape_char_code_escapes(text: &str, is_byte_string: bool) -> Option<String> {
+    let mut res = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        let code = match chars.next()? {
+            'x' => {
+                let digits = chars.by_ref().take(2).collect::<String>();
+                u32::from_str_radix(&digits, 16).ok()?
+            }
+            'u' => {
+                let digits = chars.by_ref().take_while(|&it| it != '}').collect::<String>();
+                u32::from_str_radix(digits.trim_start_matches('{'), 16).ok()?
+            }
+            other => {
+                res.push('\\');
+                res.push(other);
+                continue;
+            }
         };
-        edit.replace(token.text_range(), format!("r{}", result));
-    })
+        let c = std::char::from_u32(code)?;
+        if c.is_control() || (is_byte_string && !c.is_ascii()) {
+            return None;
+        }
+        match c {
+            '"' | '\\' => {
+                res.push('\\');
+                res.push(c);
+            }
+            _ => res.push(c),
+        }
+    }
+    Some(res)
 }
 
 fn count_hashes(s: &str) -> usize {
@@ -146,10 +422,10 @@ mod test {
             make_raw_string,
             r#"
             fn f() {
-                let s = <|>"random\nstring";
+                let s = <|>"random\"string";
             }
             "#,
-            r#""random\nstring""#,
+            r#""random\"string""#,
         );
     }
 
@@ -159,13 +435,12 @@ mod test {
             make_raw_string,
             r#"
             fn f() {
-                let s = <|>"random\nstring";
+                let s = <|>"random\\string";
             }
             "#,
             r##"
             fn f() {
-                let s = <|>r#"random
-string"#;
+                let s = <|>r#"random\string"#;
             }
             "##,
         )
@@ -188,19 +463,82 @@ string"#;
         )
     }
 
+    #[test]
+    fn make_raw_string_works_in_attribute() {
+        check_assist(
+            make_raw_string,
+            r#"
+            #[doc = "Reads <|>\"quoted\" text"]
+            fn f() {}
+            "#,
+            r##"
+            #[doc = <|>r#"Reads "quoted" text"#]
+            fn f() {}
+            "##,
+        )
+    }
+
+    #[test]
+    fn make_raw_string_keeps_format_escapes() {
+        check_assist(
+            make_raw_string,
+            r#"
+            fn f() {
+                format!("{{<|>\"{}\"}}", 92)
+            }
+            "#,
+            r##"
+            fn f() {
+                format!(<|>r#"{{"{}"}}"#, 92)
+            }
+            "##,
+        )
+    }
+
+    #[test]
+    fn make_raw_string_works_in_nested_macro_call() {
+        check_assist(
+            make_raw_string,
+            r#"
+            fn f() {
+                println!("{}", concat!("a\\<|>b", "c"))
+            }
+            "#,
+            r##"
+            fn f() {
+                println!("{}", concat!(<|>r#"a\b"#, "c"))
+            }
+            "##,
+        )
+    }
+
+    #[test]
+    fn make_usual_string_works_in_attribute() {
+        check_assist(
+            make_usual_string,
+            r##"
+            #[doc = r#"Reads <|>"quoted" text"#]
+            fn f() {}
+            "##,
+            r#"
+            #[doc = <|>"Reads \"quoted\" text"]
+            fn f() {}
+            "#,
+        )
+    }
+
     #[test]
     fn make_raw_string_hashes_inside_works() {
         check_assist(
             make_raw_string,
             r###"
             fn f() {
-                let s = <|>"#random##\nstring";
+                let s = <|>"#random##\\string";
             }
             "###,
             r####"
             fn f() {
-                let s = <|>r#"#random##
-string"#;
+                let s = <|>r#"#random##\string"#;
             }
             "####,
         )
@@ -212,13 +550,12 @@ string"#;
             make_raw_string,
             r###"
             fn f() {
-                let s = <|>"#random\"##\nstring";
+                let s = <|>"#random\"##\\string";
             }
             "###,
             r####"
             fn f() {
-                let s = <|>r###"#random"##
-string"###;
+                let s = <|>r###"#random"##\string"###;
             }
             "####,
         )
@@ -241,6 +578,49 @@ string"###;
         )
     }
 
+    #[test]
+    fn raw_string_conversions_preserve_escaped_values() {
+        let cases = [
+            (r#""quote \" {{ }}""#, r##"r#"quote " {{ }}"#"##),
+            (r#""backslash \\{}""#, r##"r#"backslash \{}"#"##),
+            (r#""smile \u{1F600}\x21""#, r##"r#"smile 😀!"#"##),
+            (
+                r#""continued \
+                line""#,
+                r##"r#"continued line"#"##,
+            ),
+        ];
+        for (usual, raw) in cases.iter() {
+            check_assist(
+                make_raw_string,
+                &format!("fn f() {{ format!(<|>{}, 92) }}", usual),
+                &format!("fn f() {{ format!(<|>{}, 92) }}", raw),
+            );
+        }
+        let cases = [
+            (r##"r#"quote " {{ }}"#"##, r#""quote \" {{ }}""#),
+            (r##"r#"backslash \{}"#"##, r#""backslash \\{}""#),
+            (r##"r#"tab	"#"##, r#""tab\t""#),
+        ];
+        for (raw, usual) in cases.iter() {
+            check_assist(
+                make_usual_string,
+                &format!("fn f() {{ format!(<|>{}, 92) }}", raw),
+                &format!("fn f() {{ format!(<|>{}, 92) }}", usual),
+            );
+        }
+    }
+
+    #[test]
+    fn make_raw_string_not_works_with_white_space_escapes() {
+        for escape in [r"\n", r"\t", r"\r", r"\0", r"\x07", r"\u{a0}"].iter() {
+            check_assist_not_applicable(
+                make_raw_string,
+                &format!(r#"fn f() {{ format!(<|>"a{}b {{}}", 92) }}"#, escape),
+            );
+        }
+    }
+
     #[test]
     fn make_raw_string_not_works_on_partial_string() {
         check_assist_not_applicable(
@@ -355,20 +735,68 @@ string"###;
     }
 
     #[test]
-    fn remove_hash_with_quote_works() {
-        check_assist(
+    fn remove_hash_with_quote_not_works() {
+        check_assist_not_applicable(
             remove_hash,
             r##"
             fn f() {
                 let s = <|>r#"random"str"ing"#;
             }
             "##,
-            r#"
+        );
+        check_assist_not_applicable(
+            remove_hash,
+            r###"
+            fn f() {
+                let s = <|>br##"random"#string"##;
+            }
+            "###,
+        );
+    }
+
+    #[test]
+    fn add_and_remove_hash_on_raw_byte_string() {
+        check_assist(
+            add_hash,
+            r##"
             fn f() {
-                let s = <|>r"random\"str\"ing";
+                let s = <|>br#"random"string"#;
             }
+            "##,
+            r###"
+            fn f() {
+                let s = <|>br##"random"string"##;
+            }
+            "###,
+        );
+        check_assist(
+            remove_hash,
+            r###"
+            fn f() {
+                let s = <|>br##"random"string"##;
+            }
+            "###,
+            r##"
+            fn f() {
+                let s = <|>br#"random"string"#;
+            }
+            "##,
+        );
+    }
+
+    #[test]
+    fn add_hash_in_doc_attribute() {
+        check_assist(
+            add_hash,
+            r#"
+            #[doc = r"Reads <|>text"]
+            fn f() {}
             "#,
-        )
+            r##"
+            #[doc = r#"Reads <|>text"#]
+            fn f() {}
+            "##,
+        );
     }
 
     #[test]
@@ -488,6 +916,162 @@ string"###;
         );
     }
 
+    #[test]
+    fn make_byte_string_works() {
+        check_assist(
+            make_byte_string,
+            r#"
+            fn f() {
+                let s = <|>"random\nstring";
+            }
+            "#,
+            r#"
+            fn f() {
+                let s = <|>b"random\nstring";
+            }
+            "#,
+        )
+    }
+
+    #[test]
+    fn make_byte_string_from_raw_string() {
+        check_assist(
+            make_byte_string,
+            r##"
+            fn f() {
+                let s = <|>r#"random "string""#;
+            }
+            "##,
+            r##"
+            fn f() {
+                let s = <|>br#"random "string""#;
+            }
+            "##,
+        )
+    }
+
+    #[test]
+    fn make_byte_string_not_works() {
+        check_assist_not_applicable(
+            make_byte_string,
+            r#"
+            fn f() {
+                let s = <|>"smile \u{1F600}";
+            }
+            "#,
+        );
+        check_assist_not_applicable(
+            make_byte_string,
+            r#"
+            fn f() {
+                let s = <|>"héllo";
+            }
+            "#,
+        );
+    }
+
+    #[test]
+    fn make_string_from_byte_string_works() {
+        check_assist(
+            make_string_from_byte_string,
+            r#"
+            fn f() {
+                let s = b"random\x7F<|>string";
+            }
+            "#,
+            r#"
+            fn f() {
+                let s = "random\x7F<|>string";
+            }
+            "#,
+        )
+    }
+
+    #[test]
+    fn make_string_from_raw_byte_string() {
+        check_assist(
+            make_string_from_byte_string,
+            r##"
+            fn f() {
+                let s = br#"random<|> \xFF"#;
+            }
+            "##,
+            r##"
+            fn f() {
+                let s = r#"random<|> \xFF"#;
+            }
+            "##,
+        )
+    }
+
+    #[test]
+    fn make_string_from_byte_string_not_works() {
+        check_assist_not_applicable(
+            make_string_from_byte_string,
+            r#"
+            fn f() {
+                let s = b"random<|>\xFFstring";
+            }
+            "#,
+        );
+    }
+
+    #[test]
+    fn escapes_test() {
+        assert_eq!(escapes(r#""abc""#), Vec::<&str>::new());
+        assert_eq!(escapes(r#""a\nb\x7Fc\u{1F600}\\""#), vec!["n", "x7F", "u{1F600}", "\\"]);
+    }
+
+    #[test]
+    fn escape_non_ascii_works() {
+        check_assist(
+            escape_non_ascii,
+            r#"fn f() { let s = <|>"smile 😀"; }"#,
+            r#"fn f() { let s = <|>"smile \u{1F600}"; }"#,
+        );
+    }
+
+    #[test]
+    fn unescape_char_codes_works() {
+        check_assist(
+            unescape_char_codes,
+            r#"fn f() { let s = <|>"smile \u{1F600}\x21\n"; }"#,
+            r#"fn f() { let s = <|>"smile 😀!\n"; }"#,
+        );
+    }
+
+    #[test]
+    fn unescape_char_codes_not_works_for_control_chars() {
+        check_assist_not_applicable(unescape_char_codes, r#"fn f() { let s = <|>"bell \x07"; }"#);
+        check_assist_not_applicable(unescape_char_codes, r#"fn f() { let s = <|>b"\xFF"; }"#);
+    }
+
+    #[test]
+    fn escape_and_unescape_not_works_for_raw_strings() {
+        check_assist_not_applicable(escape_non_ascii, r##"fn f() { let s = <|>r#"😀"#; }"##);
+        check_assist_not_applicable(
+            unescape_char_codes,
+            r##"fn f() { let s = <|>r#"\u{1F600}"#; }"##,
+        );
+    }
+
+    #[test]
+    fn escape_non_ascii_chars_test() {
+        assert_eq!(escape_non_ascii_chars(r#""abc""#), r#""abc""#);
+        assert_eq!(escape_non_ascii_chars(r#""é😀\n""#), r#""\u{00E9}\u{1F600}\n""#);
+    }
+
+    #[test]
+    fn unescape_char_code_escapes_test() {
+        assert_eq!(
+            unescape_char_code_escapes(r#""\u{E9}\x41\x22\u{5C}\t""#, false),
+            Some(r#""éA\"\\\t""#.to_string())
+        );
+        assert_eq!(unescape_char_code_escapes(r#""\u{7F}""#, false), None);
+        assert_eq!(unescape_char_code_escapes(r#""\x7E""#, true), Some(r#""~""#.to_string()));
+        assert_eq!(unescape_char_code_escapes(r#""\u{E9}""#, true), None);
+    }
+
     #[test]
     fn count_hashes_test() {
         assert_eq!(0, count_hashes("abc"));
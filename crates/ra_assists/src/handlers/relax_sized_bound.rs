@@ -0,0 +1,180 @@
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner, TypeBoundsOwner, TypeParamsOwner},
+    SyntaxNode,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: relax_sized_bound
+//
+// Adds a `?Sized` bound to a type parameter which is only used behind a
+// pointer.
+//
+// ```
+// fn print<T<|>: std::fmt::Debug>(x: &T) {
+//     println!("{:?}", x);
+// }
+// ```
+// ->
+// ```
+// fn print<T: std::fmt::Debug + ?Sized>(x: &T) {
+//     println!("{:?}", x);
+// }
+// ```
+pub(crate) fn relax_sized_bound(ctx: AssistCtx) -> Option<Assist> {
+    let type_param = ctx.find_node_at_offset::<ast::TypeParam>()?;
+    let name = type_param.name()?;
+    let owner = type_param.syntax().parent()?.parent()?;
+    let (where_clause, signature): (_, Vec<SyntaxNode>) =
+        if let Some(fn_def) = ast::FnDef::cast(owner.clone()) {
+            let params = fn_def.param_list().map(|it| it.syntax().clone());
+            let ret_type = fn_def.ret_type().map(|it| it.syntax().clone());
+            (fn_def.where_clause(), params.into_iter().chain(ret_type).collect())
+        } else {
+            let strukt = ast::StructDef::cast(owner)?;
+            let fields = strukt
+                .syntax()
+                .children()
+                .filter(|it| {
+                    ast::RecordFieldDefList::can_cast(it.kind())
+                        || ast::TupleFieldDefList::can_cast(it.kind())
+                })
+                .collect();
+            (strukt.where_clause(), fields)
+        };
+
+    let where_pred = where_clause.as_ref().and_then(|it| {
+        it.predicates().find(|pred| {
+            pred.type_ref().map_or(false, |it| it.syntax().text() == name.text().as_str())
+        })
+    });
+    if is_maybe_sized(type_param.type_bound_list())
+        || is_maybe_sized(where_pred.as_ref().and_then(|it| it.type_bound_list()))
+    {
+        return None;
+    }
+
+    let uses = signature
+        .iter()
+        .flat_map(|it| it.descendants())
+        .filter_map(ast::PathType::cast)
+        .filter(|it| is_type_param(it, &name))
+        .collect::<Vec<_>>();
+    if uses.is_empty() || !uses.iter().all(is_behind_pointer) {
+        return None;
+    }
+
+    let (offset, text) = if let Some(bounds) = type_param.type_bound_list() {
+        (bounds.syntax().text_range().end(), " + ?Sized".to_string())
+    } else if let Some(bounds) = where_pred.and_then(|it| it.type_bound_list()) {
+        (bounds.syntax().text_range().end(), " + ?Sized".to_string())
+    } else if let Some(where_clause) = where_clause {
+        let last_pred = where_clause.predicates().last()?;
+        (last_pred.syntax().text_range().end(), format!(", {}: ?Sized", name.text()))
+    } else {
+        (name.syntax().text_range().end(), ": ?Sized".to_string())
+    };
+
+    ctx.add_assist(
+        AssistId("relax_sized_bound"),
+        AssistKind::RefactorRewrite,
+        format!("Relax {}: ?Sized", name.text()),
+        type_param.syntax().text_range(),
+        |edit| {
+            edit.insert(offset, text);
+        },
+    )
+}
+
+fn is_maybe_sized(bounds: Option<ast::TypeBoundList>) -> bool {
+    bounds.map_or(false, |it| it.bounds().any(|bound| bound.syntax().text() == "?Sized"))
+}
+
+fn is_type_param(ty: &ast::PathType, name: &ast::Name) -> bool {
+    let path = match ty.path() {
+        Some(it) => it,
+        None => return false,
+    };
+    path.qualifier().is_none()
+        && path.segment().map_or(false, |segment| {
+            segment.type_arg_list().is_none()
+                && segment.name_ref().map_or(false, |it| it.text() == name.text())
+        })
+}
+
+/// Checks syntactically that `ty` is pointed to by a reference, a raw pointer
+/// or one of the standard smart pointers.
+fn is_behind_pointer(ty: &ast::PathType) -> bool {
+    let parent = match ty.syntax().parent() {
+        Some(it) => it,
+        None => return false,
+    };
+    if ast::ReferenceType::can_cast(parent.kind()) || ast::PointerType::can_cast(parent.kind()) {
+        return true;
+    }
+    let pointer = ast::TypeArg::cast(parent)
+        .and_then(|it| it.syntax().parent())
+        .and_then(ast::TypeArgList::cast)
+        .and_then(|it| it.syntax().parent())
+        .and_then(ast::PathSegment::cast)
+        .and_then(|it| it.name_ref());
+    match pointer {
+        Some(name_ref) => ["Box", "Rc", "Arc"].contains(&name_ref.text().as_str()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn relax_param_used_by_reference() {
+        check_assist(
+            relax_sized_bound,
+            "fn f<<|>T>(x: &T, y: *const T) -> &mut T {}",
+            "fn f<<|>T: ?Sized>(x: &T, y: *const T) -> &mut T {}",
+        );
+    }
+
+    #[test]
+    fn relax_param_used_in_box() {
+        check_assist(
+            relax_sized_bound,
+            "struct S<<|>T: Debug> { x: Box<T>, y: std::rc::Rc<T> }",
+            "struct S<<|>T: Debug + ?Sized> { x: Box<T>, y: std::rc::Rc<T> }",
+        );
+    }
+
+    #[test]
+    fn relax_param_with_where_clause() {
+        check_assist(
+            relax_sized_bound,
+            "fn f<<|>T>(x: &T) where T: Debug {}",
+            "fn f<<|>T>(x: &T) where T: Debug + ?Sized {}",
+        );
+        check_assist(
+            relax_sized_bound,
+            "fn f<<|>T, U>(x: &T, y: U) where U: Debug {}",
+            "fn f<<|>T, U>(x: &T, y: U) where U: Debug, T: ?Sized {}",
+        );
+    }
+
+    #[test]
+    fn relax_not_applicable_to_param_used_by_value() {
+        check_assist_not_applicable(relax_sized_bound, "fn f<<|>T>(x: &T, y: T) {}");
+        check_assist_not_applicable(relax_sized_bound, "struct S<<|>T> { x: Vec<T> }");
+    }
+
+    #[test]
+    fn relax_not_applicable_to_maybe_sized_param() {
+        check_assist_not_applicable(relax_sized_bound, "fn f<<|>T: ?Sized>(x: &T) {}");
+        check_assist_not_applicable(relax_sized_bound, "fn f<<|>T>(x: &T) where T: ?Sized {}");
+    }
+
+    #[test]
+    fn relax_sized_bound_target() {
+        check_assist_target(relax_sized_bound, "fn f<<|>T: Copy>(x: &T) {}", "T: Copy");
+    }
+}
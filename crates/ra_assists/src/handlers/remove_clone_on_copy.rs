@@ -0,0 +1,137 @@
+use ra_syntax::{
+    ast::{self, ArgListOwner, AstNode},
+    TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: remove_clone_on_copy
+//
+// Removes a `clone()` call on a value of a `Copy` type.
+//
+// ```
+// #[lang = "copy"]
+// trait Copy {}
+// impl Copy for u32 {}
+//
+// fn double(x: u32) -> u32 {
+//     x.clo<|>ne() * 2
+// }
+// ```
+// ->
+// ```
+// #[lang = "copy"]
+// trait Copy {}
+// impl Copy for u32 {}
+//
+// fn double(x: u32) -> u32 {
+//     x * 2
+// }
+// ```
+pub(crate) fn remove_clone_on_copy(ctx: AssistCtx) -> Option<Assist> {
+    let call = ctx.find_node_at_offset::<ast::MethodCallExpr>()?;
+    let name_ref = call.name_ref()?;
+    if name_ref.text() != "clone"
+        || call.arg_list()?.args().next().is_some()
+        || ctx.frange.range.start() < name_ref.syntax().text_range().start()
+    {
+        return None;
+    }
+    let receiver = call.expr()?;
+    let ty = ctx.sema.type_of_expr(&receiver)?;
+    let (text, label) = match ty.remove_ref() {
+        None if ty.impls_copy(ctx.db) => {
+            (receiver.syntax().to_string(), "Remove `clone()` on a `Copy` value")
+        }
+        Some(pointee) if pointee.remove_ref().is_none() && pointee.impls_copy(ctx.db) => {
+            let deref = format!("*{}", receiver.syntax());
+            let text = if is_postfix_operand(&call) { format!("({})", deref) } else { deref };
+            (text, "Replace `clone()` with a dereference")
+        }
+        _ => return None,
+    };
+
+    let range = call.syntax().text_range();
+    ctx.add_assist(
+        AssistId("remove_clone_on_copy"),
+        AssistKind::RefactorRewrite,
+        label,
+        range,
+        |edit| {
+            edit.set_cursor(range.start() + TextUnit::of_str(&text));
+            edit.replace(range, text);
+        },
+    )
+}
+
+/// A dereference binds weaker than method calls, field accesses and the like.
+fn is_postfix_operand(expr: &ast::MethodCallExpr) -> bool {
+    let parent = match expr.syntax().parent() {
+        Some(it) => it,
+        None => return false,
+    };
+    let kind = parent.kind();
+    ast::MethodCallExpr::can_cast(kind)
+        || ast::FieldExpr::can_cast(kind)
+        || ast::TryExpr::can_cast(kind)
+        || ast::AwaitExpr::can_cast(kind)
+        || ast::IndexExpr::cast(parent.clone())
+            .and_then(|it| it.base())
+            .map_or(false, |it| it.syntax() == expr.syntax())
+        || ast::CallExpr::can_cast(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    const COPY: &str = r#"
+#[lang = "copy"]
+trait Copy {}
+impl Copy for u32 {}
+"#;
+
+    #[test]
+    fn remove_clone_on_owned_value() {
+        check_assist(
+            remove_clone_on_copy,
+            &format!("{}fn f(x: u32) -> u32 {{ let y = x.clone<|>(); y }}", COPY),
+            &format!("{}fn f(x: u32) -> u32 {{ let y = x<|>; y }}", COPY),
+        );
+    }
+
+    #[test]
+    fn replace_clone_on_reference_with_dereference() {
+        check_assist(
+            remove_clone_on_copy,
+            &format!("{}fn f(x: &u32) -> u32 {{ x.<|>clone() }}", COPY),
+            &format!("{}fn f(x: &u32) -> u32 {{ *x<|> }}", COPY),
+        );
+    }
+
+    #[test]
+    fn remove_clone_in_method_chain() {
+        check_assist(
+            remove_clone_on_copy,
+            &format!(
+                "{}struct P {{ x: u32 }} fn f(p: &P) -> u32 {{ p.x.clone<|>().pow(2) }}",
+                COPY
+            ),
+            &format!("{}struct P {{ x: u32 }} fn f(p: &P) -> u32 {{ p.x<|>.pow(2) }}", COPY),
+        );
+        check_assist(
+            remove_clone_on_copy,
+            &format!("{}fn f(p: (&u32, u8)) -> u32 {{ p.0.clone<|>().pow(2) }}", COPY),
+            &format!("{}fn f(p: (&u32, u8)) -> u32 {{ (*p.0)<|>.pow(2) }}", COPY),
+        );
+    }
+
+    #[test]
+    fn remove_clone_not_applicable_to_non_copy_type() {
+        check_assist_not_applicable(
+            remove_clone_on_copy,
+            &format!("{}struct S; fn f(s: &S) -> S {{ s.clone<|>() }}", COPY),
+        );
+    }
+}
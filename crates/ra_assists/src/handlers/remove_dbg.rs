@@ -1,13 +1,16 @@
 use ra_syntax::{
     ast::{self, AstNode},
-    TextUnit, T,
+    TextUnit,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{
+    utils::{is_valid_macrocall, macro_calls_in_fn},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
 
 // Assist: remove_dbg
 //
-// Removes `dbg!()` macro call.
+// Removes `dbg!()` macro call, or all the `dbg!()` calls of the function.
 //
 // ```
 // fn main() {
@@ -49,48 +52,68 @@ pub(crate) fn remove_dbg(ctx: AssistCtx) -> Option<Assist> {
         }
     };
 
-    let macro_content = {
-        let macro_args = macro_call.token_tree()?.syntax().clone();
-
-        let text = macro_args.text();
-        let without_parens = TextUnit::of_char('(')..text.len() - TextUnit::of_char(')');
-        text.slice(without_parens).to_string()
-    };
-
-    ctx.add_assist(AssistId("remove_dbg"), "Remove dbg!()", |edit| {
-        edit.target(macro_call.syntax().text_range());
-        edit.replace(macro_range, macro_content);
-        edit.set_cursor(cursor_pos);
-    })
-}
-
-/// Verifies that the given macro_call actually matches the given name
-/// and contains proper ending tokens
-fn is_valid_macrocall(macro_call: &ast::MacroCall, macro_name: &str) -> Option<bool> {
-    let path = macro_call.path()?;
-    let name_ref = path.segment()?.name_ref()?;
-
-    // Make sure it is actually a dbg-macro call, dbg followed by !
-    let excl = path.syntax().next_sibling_or_token()?;
-
-    if name_ref.text() != macro_name || excl.kind() != T![!] {
-        return None;
+    let macro_content = macro_content(&macro_call)?;
+    let others =
+        macro_calls_in_fn(macro_call.syntax(), |it| is_valid_macrocall(it, "dbg") == Some(true))
+            .into_iter()
+            .map(|it| Some((it.syntax().text_range(), macro_content(&it)?)))
+            .collect::<Option<Vec<_>>>()?;
+
+    let mut group = ctx.add_assist_group("Remove dbg!()");
+    group.add_assist(
+        AssistId("remove_dbg"),
+        AssistKind::RefactorRewrite,
+        "Remove dbg!()",
+        macro_range,
+        |edit| {
+            edit.replace(macro_range, macro_content);
+            edit.set_cursor(cursor_pos);
+        },
+    );
+    if others.len() > 1 {
+        group.add_assist(
+            AssistId("remove_dbg"),
+            AssistKind::RefactorRewrite,
+            "Remove all dbg!() in function",
+            macro_range,
+            |edit| {
+                // The cursor moves back by the `dbg!(` and `)` of the calls before it.
+                let removed_before = others
+                    .iter()
+                    .filter(|(range, _)| range.end() <= macro_range.start())
+                    .fold(TextUnit::from(0), |acc, (range, content)| {
+                        acc + range.len() - TextUnit::of_str(content)
+                    });
+                for (range, content) in others {
+                    edit.replace(range, content);
+                }
+                edit.set_cursor(cursor_pos - removed_before);
+            },
+        );
     }
+    group.finish()
+}
 
-    let node = macro_call.token_tree()?.syntax().clone();
-    let first_child = node.first_child_or_token()?;
-    let last_child = node.last_child_or_token()?;
+/// The arguments of `macro_call`, without its delimiters.
+fn macro_content(macro_call: &ast::MacroCall) -> Option<String> {
+    let macro_args = macro_call.token_tree()?.syntax().clone();
 
-    match (first_child.kind(), last_child.kind()) {
-        (T!['('], T![')']) | (T!['['], T![']']) | (T!['{'], T!['}']) => Some(true),
-        _ => Some(false),
-    }
+    let text = macro_args.text();
+    let without_parens = TextUnit::of_char('(')..text.len() - TextUnit::of_char(')');
+    Some(text.slice(without_parens).to_string())
 }
 
 #[cfg(test)]
 mod tests {
+    use ra_db::FileRange;
+    use ra_syntax::TextRange;
+    use test_utils::extract_offset;
+
     use super::*;
-    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable, check_assist_target},
+        resolved_assists,
+    };
 
     #[test]
     fn test_remove_dbg() {
@@ -147,4 +170,41 @@ fn foo(n: usize) {
             "dbg!(n.checked_sub(4))",
         );
     }
+
+    #[test]
+    fn remove_all_dbg_in_function() {
+        let (offset, before) = extract_offset(
+            "
+fn foo(n: usize) -> usize {
+    let m = dbg!(n + 1);
+    dbg!(m.<|>checked_sub(4));
+    dbg!(m)
+}
+
+fn bar() {
+    dbg!(1);
+}
+",
+        );
+        let (db, file_id) = helpers::with_single_file(&before);
+        let range = TextRange::offset_len(offset, 0.into());
+        let bulk = resolved_assists(&db, FileRange { file_id, range })
+            .into_iter()
+            .find(|it| it.label.label == "Remove all dbg!() in function")
+            .unwrap();
+        assert_eq!(
+            bulk.action.edit().apply(&before),
+            "
+fn foo(n: usize) -> usize {
+    let m = n + 1;
+    m.checked_sub(4);
+    m
+}
+
+fn bar() {
+    dbg!(1);
+}
+"
+        );
+    }
 }
@@ -0,0 +1,223 @@
+use ra_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind::{STRING, WHITESPACE},
+    TextRange, TextUnit, T,
+};
+
+use crate::{
+    utils::{is_valid_macrocall, macro_calls_in_fn},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+/// The starts of the messages of the prints which are left from debugging.
+const DEBUG_MARKERS: &[&str] = &["DEBUG", "[DEBUG]", "XXX"];
+
+// Assist: remove_debug_print
+//
+// Removes a `println!` or `eprintln!` statement whose message starts with a
+// debugging marker like `DEBUG`, or all such statements of the function.
+//
+// ```
+// fn main() {
+//     let x = 92;
+//     <|>eprintln!("DEBUG: x = {}", x);
+//     run(x);
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let x = 92;
+//     run(x);
+// }
+// ```
+pub(crate) fn remove_debug_print(ctx: AssistCtx) -> Option<Assist> {
+    let macro_call = ctx.find_node_at_offset::<ast::MacroCall>()?;
+    let removed = debug_print_removal(&macro_call)?;
+    let others = macro_calls_in_fn(macro_call.syntax(), |it| debug_print_removal(it).is_some())
+        .iter()
+        .filter_map(debug_print_removal)
+        .collect::<Vec<_>>();
+
+    let target = macro_call.syntax().text_range();
+    let mut group = ctx.add_assist_group("Remove debug print");
+    group.add_assist(
+        AssistId("remove_debug_print"),
+        AssistKind::RefactorRewrite,
+        "Remove debug print",
+        target,
+        |edit| {
+            edit.delete(removed);
+            edit.set_cursor(removed.start());
+        },
+    );
+    if others.len() > 1 {
+        group.add_assist(
+            AssistId("remove_debug_print"),
+            AssistKind::RefactorRewrite,
+            "Remove all debug prints in function",
+            target,
+            |edit| {
+                let removed_before = others
+                    .iter()
+                    .filter(|it| it.end() <= removed.start())
+                    .fold(TextUnit::from(0), |acc, it| acc + it.len());
+                for range in others {
+                    edit.delete(range);
+                }
+                edit.set_cursor(removed.start() - removed_before);
+            },
+        );
+    }
+    group.finish()
+}
+
+/// The range removing the debug print statement of `macro_call`, with the
+/// whitespace before it so that no blank line is left behind.
+fn debug_print_removal(macro_call: &ast::MacroCall) -> Option<TextRange> {
+    let is_print = is_valid_macrocall(macro_call, "println")
+        .or_else(|| is_valid_macrocall(macro_call, "eprintln"))?;
+    if !is_print {
+        return None;
+    }
+    let message = macro_call
+        .token_tree()?
+        .syntax()
+        .children_with_tokens()
+        .skip(1)
+        .find(|it| it.kind() != WHITESPACE)?
+        .into_token()?;
+    if message.kind() != STRING {
+        return None;
+    }
+    let text = &message.text()[1..];
+    if !DEBUG_MARKERS.iter().any(|it| text.starts_with(it)) {
+        return None;
+    }
+
+    // A print used as a value, like a match arm or the tail of a block, can't
+    // be removed.
+    let parent = macro_call.syntax().parent()?;
+    let stmt = if ast::ExprStmt::can_cast(parent.kind()) {
+        parent
+    } else if ast::Block::can_cast(parent.kind())
+        && macro_call.syntax().last_token().map(|it| it.kind()) == Some(T![;])
+    {
+        macro_call.syntax().clone()
+    } else {
+        return None;
+    };
+    let range = stmt.text_range();
+    match stmt.prev_sibling_or_token() {
+        Some(ws) if ws.kind() == WHITESPACE => Some(range.extend_to(&ws.text_range())),
+        _ => Some(range),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use test_utils::extract_offset;
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    #[test]
+    fn remove_single_debug_print() {
+        check_assist(
+            remove_debug_print,
+            r#"
+fn f(x: u32) -> u32 {
+    println!("DEBUG x = {}", x);
+    let y = x + 1;
+    eprintln!(<|>"[DEBUG] y = {}", y);
+    println!("y = {}", y);
+    y
+}"#,
+            r#"
+fn f(x: u32) -> u32 {
+    println!("DEBUG x = {}", x);
+    let y = x + 1;<|>
+    println!("y = {}", y);
+    y
+}"#,
+        );
+    }
+
+    #[test]
+    fn remove_all_debug_prints_in_function() {
+        let (offset, before) = extract_offset(
+            r#"
+fn f(x: u32) {
+    if x > 1 {
+        eprintln!("DEBUG big");
+        run(x);
+    } else {
+        eprintln!("DEBUG small");
+    }
+    <|>println!("DEBUG done: {}", x);
+    println!("done");
+}
+
+fn g() {
+    println!("DEBUG g");
+}"#,
+        );
+        let (db, file_id) = helpers::with_single_file(&before);
+        let range = TextRange::offset_len(offset, 0.into());
+        let bulk = resolved_assists(&db, FileRange { file_id, range })
+            .into_iter()
+            .find(|it| it.label.label == "Remove all debug prints in function")
+            .unwrap();
+        assert_eq!(
+            bulk.action.edit().apply(&before),
+            r#"
+fn f(x: u32) {
+    if x > 1 {
+        run(x);
+    } else {
+    }
+    println!("done");
+}
+
+fn g() {
+    println!("DEBUG g");
+}"#
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_print_in_expression_position() {
+        check_assist_not_applicable(
+            remove_debug_print,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 => <|>println!("DEBUG zero"),
+        _ => (),
+    }
+}"#,
+        );
+        check_assist_not_applicable(
+            remove_debug_print,
+            r#"
+fn f() {
+    let _ = <|>eprintln!("DEBUG");
+}"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_other_prints() {
+        check_assist_not_applicable(
+            remove_debug_print,
+            r#"
+fn f(x: u32) {
+    <|>println!("x = {}", x);
+}"#,
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner},
+    TextRange, TextUnit,
+};
+
+use crate::{
+    utils::{attribute_removal_range, derive_items, impl_header},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: remove_derive
+//
+// Removes a single trait from a `#[derive]` attribute.
+//
+// ```
+// #[derive(Clone, Deb<|>ug, PartialEq)]
+// struct S;
+// ```
+// ->
+// ```
+// #[derive(Clone, PartialEq)]
+// struct S;
+// ```
+pub(crate) fn remove_derive(ctx: AssistCtx) -> Option<Assist> {
+    let input = ctx.find_node_at_offset::<ast::TokenTree>()?;
+    let attr = input.syntax().parent().and_then(ast::Attr::cast)?;
+    let (attr_name, _) = attr.as_simple_call()?;
+    if attr_name.as_str() != "derive" {
+        return None;
+    }
+
+    let offset = ctx.frange.range.start();
+    let items = derive_items(&input);
+    let idx = items.iter().position(|(range, _)| range.contains_inclusive(offset))?;
+    let trait_name = items[idx].1.clone();
+
+    let removed = if items.len() == 1 {
+        attribute_removal_range(&attr)
+    } else if idx + 1 < items.len() {
+        TextRange::from_to(items[idx].0.start(), items[idx + 1].0.start())
+    } else {
+        TextRange::from_to(items[idx - 1].0.end(), items[idx].0.end())
+    };
+    let annotated = attr.syntax().parent().and_then(ast::NominalDef::cast).and_then(|it| {
+        let name = it.name()?;
+        Some((it, name))
+    });
+
+    let mut group = ctx.add_assist_group("Remove derive");
+    group.add_assist(
+        AssistId("remove_derive"),
+        AssistKind::RefactorRewrite,
+        format!("Remove derive `{}`", trait_name),
+        attr.syntax().text_range(),
+        |edit| {
+            edit.delete(removed);
+            edit.set_cursor(removed.start());
+        },
+    );
+    if let Some((annotated, name)) = annotated {
+        group.add_assist(
+            AssistId("remove_derive"),
+            AssistKind::RefactorRewrite,
+            format!("Convert `{}` to manual impl", trait_name),
+            attr.syntax().text_range(),
+            |edit| {
+                edit.delete(removed);
+
+                let start_offset = annotated.syntax().text_range().end();
+                let header = impl_header(&annotated, &name, Some(&trait_name));
+                let buf = format!("\n\n{} {{\n", header);
+                edit.set_cursor(start_offset - removed.len() + TextUnit::of_str(&buf));
+                edit.insert(start_offset, format!("{}\n}}", buf));
+            },
+        );
+    }
+    group.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{
+        check_assist, check_assist_multi_file_entry, check_assist_not_applicable,
+        check_assist_target,
+    };
+
+    #[test]
+    fn remove_first_derive() {
+        check_assist(
+            remove_derive,
+            "#[derive(Clo<|>ne, Debug, PartialEq)]\nstruct S;",
+            "#[derive(<|>Debug, PartialEq)]\nstruct S;",
+        );
+    }
+
+    #[test]
+    fn remove_middle_derive() {
+        check_assist(
+            remove_derive,
+            "#[derive(Clone, Deb<|>ug, PartialEq)]\nstruct S;",
+            "#[derive(Clone, <|>PartialEq)]\nstruct S;",
+        );
+    }
+
+    #[test]
+    fn remove_last_derive() {
+        check_assist(
+            remove_derive,
+            "#[derive(Clone, Debug, Partial<|>Eq)]\nstruct S;",
+            "#[derive(Clone, Debug<|>)]\nstruct S;",
+        );
+    }
+
+    #[test]
+    fn remove_only_derive_removes_attribute() {
+        check_assist(remove_derive, "#[derive(Deb<|>ug)]\nstruct S;", "<|>struct S;");
+    }
+
+    #[test]
+    fn remove_derive_multiline() {
+        check_assist(
+            remove_derive,
+            r#"
+#[derive(
+    Clone,
+    Deb<|>ug,
+)]
+struct S;"#,
+            r#"
+#[derive(
+    Clone<|>,
+)]
+struct S;"#,
+        );
+    }
+
+    #[test]
+    fn remove_derive_path() {
+        check_assist(
+            remove_derive,
+            "#[derive(Clone, serde::Ser<|>ialize)]\nstruct S;",
+            "#[derive(Clone<|>)]\nstruct S;",
+        );
+    }
+
+    #[test]
+    fn convert_derive_to_manual_impl() {
+        check_assist_multi_file_entry(
+            remove_derive,
+            "Convert `Debug` to manual impl",
+            r#"
+//- /main.rs
+#[derive(Clone, Deb<|>ug)]
+struct S;
+"#,
+            r#"
+//- /main.rs
+#[derive(Clone)]
+struct S;
+
+impl Debug for S {
+<|>
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_derive_to_manual_impl_for_generic_type() {
+        check_assist_multi_file_entry(
+            remove_derive,
+            "Convert `Debug` to manual impl",
+            r#"
+//- /main.rs
+#[derive(Deb<|>ug)]
+enum E<'a, T: Clone> {
+    V(&'a T),
+}
+"#,
+            r#"
+//- /main.rs
+enum E<'a, T: Clone> {
+    V(&'a T),
+}
+
+impl<'a, T: Clone> Debug for E<'a, T> {
+<|>
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_derive_target() {
+        check_assist_target(
+            remove_derive,
+            "#[derive(Clone, Deb<|>ug)]\nstruct S;",
+            "#[derive(Clone, Debug)]",
+        );
+    }
+
+    #[test]
+    fn remove_derive_not_applicable_for_other_attributes() {
+        check_assist_not_applicable(remove_derive, "#[cfg(fea<|>ture)]\nstruct S;");
+    }
+}
@@ -1,6 +1,6 @@
 use ra_syntax::{SyntaxKind, TextRange, T};
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: remove_mut
 //
@@ -25,8 +25,14 @@ pub(crate) fn remove_mut(ctx: AssistCtx) -> Option<Assist> {
         _ => mut_token.text_range().end(),
     };
 
-    ctx.add_assist(AssistId("remove_mut"), "Remove `mut` keyword", |edit| {
-        edit.set_cursor(delete_from);
-        edit.delete(TextRange::from_to(delete_from, delete_to));
-    })
+    ctx.add_assist(
+        AssistId("remove_mut"),
+        AssistKind::RefactorRewrite,
+        "Remove `mut` keyword",
+        mut_token.text_range(),
+        |edit| {
+            edit.set_cursor(delete_from);
+            edit.delete(TextRange::from_to(delete_from, delete_to));
+        },
+    )
 }
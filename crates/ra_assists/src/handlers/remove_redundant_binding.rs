@@ -0,0 +1,253 @@
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, TypeAscriptionOwner},
+    Direction,
+    SyntaxKind::COMMENT,
+    SyntaxNode, TextRange, TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: remove_redundant_binding
+//
+// Removes a binding which is returned right after it's declared.
+//
+// ```
+// fn sum(a: u32, b: u32) -> u32 {
+//     let s<|> = a + b;
+//     s
+// }
+// ```
+// ->
+// ```
+// fn sum(a: u32, b: u32) -> u32 {
+//     a + b
+// }
+// ```
+pub(crate) fn remove_redundant_binding(ctx: AssistCtx) -> Option<Assist> {
+    let offset = ctx.frange.range.start();
+    let binding = ctx.token_at_offset().find_map(|token| {
+        token
+            .parent()
+            .ancestors()
+            .filter_map(ast::Block::cast)
+            .filter_map(|it| returned_binding(&it))
+            .find(|it| it.is_at(offset))
+    })?;
+    let let_stmt = &binding.let_stmt;
+    let init = let_stmt.initializer()?;
+    if let_stmt.ascribed_type().is_some() {
+        // Without the ascription, the type of a tail is only known from the
+        // return type of the function.
+        let block_expr = binding.block.syntax().parent()?;
+        if !binding.is_return && !ast::FnDef::can_cast(block_expr.parent()?.kind()) {
+            return None;
+        }
+        // An ascription coercing the initializer can't be dropped.
+        let init_ty = ctx.sema.type_of_expr(&init)?;
+        let binding_ty = ctx.sema.type_of_pat(&let_stmt.pat()?)?;
+        if init_ty != binding_ty {
+            return None;
+        }
+    }
+
+    let indent = leading_indent(let_stmt.syntax()).unwrap_or_default();
+    let comments = let_stmt
+        .syntax()
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .take_while(|it| it.text_range().start() < binding.returned.text_range().start())
+        .filter(|it| it.kind() == COMMENT)
+        .map(|it| format!("{}\n{}", it, indent))
+        .collect::<String>();
+    let expr = if binding.is_return {
+        let semi = if ast::ExprStmt::can_cast(binding.returned.kind()) { ";" } else { "" };
+        format!("return {}{}", init.syntax(), semi)
+    } else {
+        init.syntax().to_string()
+    };
+    let range = TextRange::from_to(
+        let_stmt.syntax().text_range().start(),
+        binding.returned.text_range().end(),
+    );
+    let cursor = range.start() + TextUnit::of_str(&comments);
+
+    ctx.add_assist(
+        AssistId("remove_redundant_binding"),
+        AssistKind::RefactorInline,
+        "Remove redundant binding",
+        range,
+        |edit| {
+            edit.replace(range, format!("{}{}", comments, expr));
+            edit.set_cursor(cursor);
+        },
+    )
+}
+
+/// A `let x = ..;` statement followed by `x` as the tail or by `return x;`.
+struct ReturnedBinding {
+    block: ast::Block,
+    let_stmt: ast::LetStmt,
+    /// The tail or the statement returning the binding.
+    returned: SyntaxNode,
+    is_return: bool,
+}
+
+impl ReturnedBinding {
+    /// Whether `offset` is on the first line of the `let` statement or on the
+    /// returned binding.
+    fn is_at(&self, offset: TextUnit) -> bool {
+        let let_range = self.let_stmt.syntax().text_range();
+        let first_line_end = match self.let_stmt.syntax().text().find_char('\n') {
+            Some(it) => let_range.start() + it,
+            None => let_range.end(),
+        };
+        TextRange::from_to(let_range.start(), first_line_end).contains_inclusive(offset)
+            || self.returned.text_range().contains_inclusive(offset)
+    }
+}
+
+fn returned_binding(block: &ast::Block) -> Option<ReturnedBinding> {
+    let statements = block.statements().collect::<Vec<_>>();
+    let (returned, expr, stmts_before) = match block.expr() {
+        Some(tail) => (tail.syntax().clone(), tail, statements.len()),
+        None => match statements.last()? {
+            ast::Stmt::ExprStmt(it) => (it.syntax().clone(), it.expr()?, statements.len() - 1),
+            ast::Stmt::LetStmt(_) => return None,
+        },
+    };
+    let (path_expr, is_return) = match expr {
+        ast::Expr::PathExpr(it) if !ast::ExprStmt::can_cast(returned.kind()) => (it, false),
+        ast::Expr::ReturnExpr(it) => match it.expr()? {
+            ast::Expr::PathExpr(it) => (it, true),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let let_stmt = match statements[..stmts_before].last()? {
+        ast::Stmt::LetStmt(it) => it.clone(),
+        ast::Stmt::ExprStmt(_) => return None,
+    };
+
+    let path = path_expr.path()?;
+    if path.qualifier().is_some() {
+        return None;
+    }
+    let name_ref = path.segment()?.name_ref()?;
+    let is_binding = match let_stmt.pat()? {
+        ast::Pat::BindPat(it) => {
+            !it.is_ref() && it.pat().is_none() && it.name()?.text() == name_ref.text()
+        }
+        _ => false,
+    };
+    if !is_binding {
+        return None;
+    }
+    Some(ReturnedBinding { block: block.clone(), let_stmt, returned, is_return })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn remove_binding_returned_as_tail() {
+        check_assist(
+            remove_redundant_binding,
+            "
+fn double(base: u32) -> u32 {
+    let unused = 10;
+    let doubled: u32 = base * 2;
+    doub<|>led
+}",
+            "
+fn double(base: u32) -> u32 {
+    let unused = 10;
+    <|>base * 2
+}",
+        );
+    }
+
+    #[test]
+    fn remove_binding_returned_explicitly() {
+        check_assist(
+            remove_redundant_binding,
+            "
+fn f(flag: bool) -> i32 {
+    if flag {
+        return 0;
+    }
+    let <|>r = compute(
+        1,
+        2,
+    );
+    return r;
+}",
+            "
+fn f(flag: bool) -> i32 {
+    if flag {
+        return 0;
+    }
+    <|>return compute(
+        1,
+        2,
+    );
+}",
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_ascription_is_needed() {
+        check_assist_not_applicable(
+            remove_redundant_binding,
+            "
+fn f(y: &mut u32) -> &u32 {
+    let x<|>: &u32 = y;
+    x
+}",
+        );
+        check_assist_not_applicable(
+            remove_redundant_binding,
+            "
+fn f() {
+    let v = {
+        let x<|>: u64 = 1;
+        x
+    };
+}",
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_statement_in_between() {
+        check_assist_not_applicable(
+            remove_redundant_binding,
+            "
+fn f() -> u32 {
+    let x<|> = 1;
+    g(x);
+    x
+}",
+        );
+    }
+
+    #[test]
+    fn keep_comments_above_expression() {
+        check_assist(
+            remove_redundant_binding,
+            "
+fn f() -> u32 {
+    let x<|> = 1 + 2; // not 4
+    // the answer
+    x
+}",
+            "
+fn f() -> u32 {
+    // not 4
+    // the answer
+    <|>1 + 2
+}",
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use hir::VariantDef;
+use ra_ide_db::RootDatabase;
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner},
+    SyntaxKind::{BIND_PAT, RECORD_FIELD_PAT},
+};
+
+use crate::{utils::range_with_leading_comments, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: reorder_fields
+//
+// Reorders the fields of a record literal or pattern to match the declaration
+// of the struct.
+//
+// ```
+// struct Foo { foo: i32, bar: i32 }
+// const TEST: Foo = <|>Foo { bar: 0, foo: 1 };
+// ```
+// ->
+// ```
+// struct Foo { foo: i32, bar: i32 }
+// const TEST: Foo = <|>Foo { foo: 1, bar: 0 };
+// ```
+pub(crate) fn reorder_fields(ctx: AssistCtx) -> Option<Assist> {
+    let (node, variant, fields) = if let Some(lit) = ctx.find_node_at_offset::<ast::RecordLit>() {
+        let variant = ctx.sema.resolve_record_literal(&lit)?;
+        let fields = lit
+            .record_field_list()?
+            .fields()
+            .map(|it| Some((it.name_ref()?.text().to_string(), it.syntax().clone())))
+            .collect::<Option<Vec<_>>>()?;
+        (lit.syntax().clone(), variant, fields)
+    } else {
+        let pat = ctx.find_node_at_offset::<ast::RecordPat>()?;
+        let variant = ctx.sema.resolve_record_pattern(&pat)?;
+        let mut fields = Vec::new();
+        for child in pat.record_field_pat_list()?.syntax().children() {
+            let name = match child.kind() {
+                RECORD_FIELD_PAT => ast::RecordFieldPat::cast(child.clone())?.name()?,
+                BIND_PAT => ast::BindPat::cast(child.clone())?.name()?,
+                _ => return None,
+            };
+            fields.push((name.text().to_string(), child));
+        }
+        (pat.syntax().clone(), variant, fields)
+    };
+
+    let mut ordered = fields
+        .iter()
+        .map(|(name, node)| Some((declaration_index(ctx.db, variant, name)?, node)))
+        .collect::<Option<Vec<_>>>()?;
+    if ordered.windows(2).all(|it| it[0].0 < it[1].0) {
+        return None;
+    }
+    ordered.sort_by_key(|(idx, _)| *idx);
+    let replacements = fields
+        .iter()
+        .zip(ordered)
+        .filter(|((_, old), (_, new))| old != *new)
+        .map(|((_, old), (_, new))| {
+            (range_with_leading_comments(old), ctx.source_text(range_with_leading_comments(new)))
+        })
+        .collect::<Vec<_>>();
+
+    ctx.add_assist(
+        AssistId("reorder_fields"),
+        AssistKind::RefactorRewrite,
+        "Reorder record fields",
+        node.text_range(),
+        |edit| {
+            for (range, text) in replacements {
+                edit.replace(range, text);
+            }
+            edit.set_cursor(node.text_range().start());
+        },
+    )
+}
+
+fn declaration_index(db: &RootDatabase, variant: VariantDef, name: &str) -> Option<usize> {
+    variant.fields(db).iter().position(|it| it.name(db).to_string() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn reorder_literal_fields() {
+        check_assist(
+            reorder_fields,
+            "
+struct S { a: u32, b: u32, c: u32 }
+fn f() -> S {
+    S {
+        c: 3,
+        // the first one
+        a: <|>1,
+        b: 2,
+    }
+}",
+            "
+struct S { a: u32, b: u32, c: u32 }
+fn f() -> S {
+    <|>S {
+        // the first one
+        a: 1,
+        b: 2,
+        c: 3,
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn reorder_pattern_fields() {
+        check_assist(
+            reorder_fields,
+            "
+struct S { a: u32, b: u32 }
+fn f(s: S) -> u32 {
+    let S { b: x, a<|> } = s;
+    a + x
+}",
+            "
+struct S { a: u32, b: u32 }
+fn f(s: S) -> u32 {
+    let <|>S { a, b: x } = s;
+    a + x
+}",
+        );
+    }
+
+    #[test]
+    fn reorder_fields_keeps_spread_last() {
+        check_assist(
+            reorder_fields,
+            "
+struct S { a: u32, b: u32, c: u32 }
+fn f(base: S) -> S {
+    S { c: 3, <|>a: 1, ..base }
+}",
+            "
+struct S { a: u32, b: u32, c: u32 }
+fn f(base: S) -> S {
+    <|>S { a: 1, c: 3, ..base }
+}",
+        );
+    }
+
+    #[test]
+    fn reorder_fields_not_applicable_when_ordered() {
+        check_assist_not_applicable(
+            reorder_fields,
+            "
+struct S { a: u32, b: u32 }
+fn f() -> S { S { a: 1, <|>b: 2 } }",
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use ra_syntax::{
+    ast::{self, AstNode, LoopBodyOwner},
+    SyntaxKind::{
+        BREAK_EXPR, CONTINUE_EXPR, FOR_EXPR, LAMBDA_EXPR, LIFETIME, LOOP_EXPR, WHILE_EXPR,
+    },
+    SyntaxNode, TextRange,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: replace_for_with_if_let
+//
+// Replaces a `for` loop over an `Option` or a `Result` with an `if let`.
+//
+// ```
+// fn print(name: Option<&str>) {
+//     f<|>or name in name {
+//         println!("{}", name);
+//     }
+// }
+// ```
+// ->
+// ```
+// fn print(name: Option<&str>) {
+//     if let Some(name) = name {
+//         println!("{}", name);
+//     }
+// }
+// ```
+pub(crate) fn replace_for_with_if_let(ctx: AssistCtx) -> Option<Assist> {
+    let for_expr = ctx.find_node_at_offset::<ast::ForExpr>()?;
+    let pat = for_expr.pat()?;
+    let iterable = for_expr.iterable()?;
+    let body = for_expr.loop_body()?;
+    if ctx.frange.range.start() >= body.syntax().text_range().start() {
+        return None;
+    }
+    if for_expr.syntax().children_with_tokens().any(|it| it.kind() == LIFETIME) {
+        return None;
+    }
+    let variant = match ctx.sema.type_of_expr(&iterable)?.as_adt()? {
+        hir::Adt::Enum(it) => match it.name(ctx.db).to_string().as_str() {
+            "Option" => "Some",
+            "Result" => "Ok",
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if exits_loop(for_expr.syntax(), body.syntax()) {
+        return None;
+    }
+
+    let range = TextRange::from_to(
+        for_expr.syntax().text_range().start(),
+        body.syntax().text_range().start(),
+    );
+    ctx.add_assist(
+        AssistId("replace_for_with_if_let"),
+        AssistKind::RefactorRewrite,
+        "Replace `for` with `if let`",
+        range,
+        |edit| {
+            edit.replace(
+                range,
+                format!("if let {}({}) = {} ", variant, pat.syntax(), iterable.syntax()),
+            );
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// Whether some `break` or `continue` of `body` refers to `loop_expr`, or to
+/// a loop around it.
+fn exits_loop(loop_expr: &SyntaxNode, body: &SyntaxNode) -> bool {
+    body.descendants().filter(|it| it.kind() == BREAK_EXPR || it.kind() == CONTINUE_EXPR).any(
+        |exit| {
+            if exit.children_with_tokens().any(|it| it.kind() == LIFETIME) {
+                return true;
+            }
+            let target = exit.ancestors().find(|it| match it.kind() {
+                LOOP_EXPR | WHILE_EXPR | FOR_EXPR | LAMBDA_EXPR => true,
+                _ => false,
+            });
+            target.as_ref() == Some(loop_expr)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    const ENUMS: &str = "
+enum Option<T> { Some(T), None }
+enum Result<T, E> { Ok(T), Err(E) }
+";
+
+    #[test]
+    fn replace_for_over_option() {
+        check_assist(
+            replace_for_with_if_let,
+            &format!("{}fn f(o: Option<u32>) {{ <|>for x in o {{ g(x); }} }}", ENUMS),
+            &format!("{}fn f(o: Option<u32>) {{ <|>if let Some(x) = o {{ g(x); }} }}", ENUMS),
+        );
+    }
+
+    #[test]
+    fn replace_for_over_result() {
+        check_assist(
+            replace_for_with_if_let,
+            &format!("{}fn f(r: Result<u32, ()>) {{ for x in r<|> {{ g(x); }} }}", ENUMS),
+            &format!("{}fn f(r: Result<u32, ()>) {{ <|>if let Ok(x) = r {{ g(x); }} }}", ENUMS),
+        );
+    }
+
+    #[test]
+    fn replace_for_not_applicable_with_continue() {
+        check_assist_not_applicable(
+            replace_for_with_if_let,
+            &format!(
+                "{}fn f(o: Option<u32>) {{ for<|> x in o {{ if x == 0 {{ continue; }} g(x); }} }}",
+                ENUMS
+            ),
+        );
+    }
+
+    #[test]
+    fn replace_for_not_applicable_to_iterator() {
+        check_assist_not_applicable(
+            replace_for_with_if_let,
+            "struct Range; fn f(r: Range) { for<|> x in r { g(x); } }",
+        );
+    }
+}
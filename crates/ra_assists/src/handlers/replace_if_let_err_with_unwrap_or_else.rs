@@ -0,0 +1,271 @@
+use hir::{Adt, VariantDef};
+use ra_syntax::{
+    ast::{self, ArgListOwner, AstNode},
+    SyntaxKind::{
+        AWAIT_EXPR, BIND_PAT, BLOCK, BREAK_EXPR, CONTINUE_EXPR, FN_DEF, FOR_EXPR, LAMBDA_EXPR,
+        LIFETIME, LOOP_EXPR, PLACEHOLDER_PAT, RETURN_EXPR, TRY_EXPR, WHILE_EXPR,
+    },
+    SyntaxNode,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: replace_if_let_err_with_unwrap_or_else
+//
+// Replaces an `if let Err(..)` on a `Result` without a value with an
+// `unwrap_or_else` call.
+//
+// ```
+// enum Result<T, E> { Ok(T), Err(E) }
+//
+// fn main() {
+//     <|>if let Err(e) = run() {
+//         report(e);
+//     }
+// }
+//
+// fn run() -> Result<(), String> { Ok(()) }
+// fn report(e: String) {}
+// ```
+// ->
+// ```
+// enum Result<T, E> { Ok(T), Err(E) }
+//
+// fn main() {
+//     run().unwrap_or_else(|e| {
+//         report(e);
+//     });
+// }
+//
+// fn run() -> Result<(), String> { Ok(()) }
+// fn report(e: String) {}
+// ```
+pub(crate) fn replace_if_let_err_with_unwrap_or_else(ctx: AssistCtx) -> Option<Assist> {
+    let if_expr = ctx.find_node_at_offset::<ast::IfExpr>()?;
+    let body = if_expr.then_branch()?;
+    if ctx.frange.range.start() >= body.syntax().text_range().start()
+        || if_expr.else_branch().is_some()
+    {
+        return None;
+    }
+    let condition = if_expr.condition()?;
+    let pat = match condition.pat()? {
+        ast::Pat::TupleStructPat(it) => it,
+        _ => return None,
+    };
+    if pat.path()?.segment()?.name_ref()?.text() != "Err" {
+        return None;
+    }
+    let mut args = pat.args();
+    let err_pat = match (args.next()?, args.next()) {
+        (it, None) if it.syntax().kind() == BIND_PAT || it.syntax().kind() == PLACEHOLDER_PAT => it,
+        _ => return None,
+    };
+    let expr = condition.expr()?;
+    if !is_unit_result(&ctx, &expr) || escapes_closure(body.syntax()) {
+        return None;
+    }
+    let parent = if_expr.syntax().parent()?;
+    let semicolon = match ast::ExprStmt::cast(parent.clone()) {
+        Some(stmt) if stmt.has_semi() => "",
+        Some(_) => ";",
+        None if parent.kind() == BLOCK => ";",
+        None => return None,
+    };
+
+    let range = if_expr.syntax().text_range();
+    ctx.add_assist(
+        AssistId("replace_if_let_err_with_unwrap_or_else"),
+        AssistKind::RefactorRewrite,
+        "Replace `if let Err` with `unwrap_or_else`",
+        range,
+        |edit| {
+            edit.replace(
+                range,
+                format!(
+                    "{}.unwrap_or_else(|{}| {}){}",
+                    receiver_text(&expr),
+                    err_pat.syntax(),
+                    body.syntax(),
+                    semicolon
+                ),
+            );
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+// Assist: replace_unwrap_or_else_with_if_let_err
+//
+// Replaces an `unwrap_or_else` call on a `Result` without a value with an
+// `if let Err(..)`.
+//
+// ```
+// enum Result<T, E> { Ok(T), Err(E) }
+//
+// fn main() {
+//     run().unwrap_or_<|>else(|e| report(e));
+// }
+//
+// fn run() -> Result<(), String> { Ok(()) }
+// fn report(e: String) {}
+// ```
+// ->
+// ```
+// enum Result<T, E> { Ok(T), Err(E) }
+//
+// fn main() {
+//     if let Err(e) = run() { report(e) }
+// }
+//
+// fn run() -> Result<(), String> { Ok(()) }
+// fn report(e: String) {}
+// ```
+pub(crate) fn replace_unwrap_or_else_with_if_let_err(ctx: AssistCtx) -> Option<Assist> {
+    let call = ctx.find_node_at_offset::<ast::MethodCallExpr>()?;
+    let name_ref = call.name_ref()?;
+    if name_ref.text() != "unwrap_or_else"
+        || !name_ref.syntax().text_range().contains_inclusive(ctx.frange.range.start())
+    {
+        return None;
+    }
+    let mut args = call.arg_list()?.args();
+    let lambda = match (args.next()?, args.next()) {
+        (ast::Expr::LambdaExpr(it), None) => it,
+        _ => return None,
+    };
+    let mut params = lambda.param_list()?.params();
+    let err_pat = match (params.next()?.pat()?, params.next()) {
+        (it, None) => it,
+        _ => return None,
+    };
+    let body = lambda.body()?;
+    let receiver = call.expr()?;
+    if !is_unit_result(&ctx, &receiver) || escapes_closure(body.syntax()) {
+        return None;
+    }
+    let parent = call.syntax().parent()?;
+    let range = match ast::ExprStmt::cast(parent.clone()) {
+        Some(stmt) => stmt.syntax().text_range(),
+        None if parent.kind() == BLOCK => call.syntax().text_range(),
+        None => return None,
+    };
+    let body = match body {
+        ast::Expr::BlockExpr(it) => it.syntax().to_string(),
+        it => format!("{{ {} }}", it.syntax()),
+    };
+
+    ctx.add_assist(
+        AssistId("replace_unwrap_or_else_with_if_let_err"),
+        AssistKind::RefactorRewrite,
+        "Replace `unwrap_or_else` with `if let Err`",
+        call.syntax().text_range(),
+        |edit| {
+            edit.replace(
+                range,
+                format!("if let Err({}) = {} {}", err_pat.syntax(), receiver.syntax(), body),
+            );
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// Whether `expr` is a `Result` whose `Ok` value is `()`, so that dropping it
+/// loses nothing.
+fn is_unit_result(ctx: &AssistCtx, expr: &ast::Expr) -> bool {
+    let ty = match ctx.sema.type_of_expr(expr) {
+        Some(it) => it,
+        None => return false,
+    };
+    let result = match ty.as_adt() {
+        Some(Adt::Enum(it)) if it.name(ctx.db).to_string() == "Result" => it,
+        _ => return false,
+    };
+    let ok =
+        match result.variants(ctx.db).into_iter().find(|it| it.name(ctx.db).to_string() == "Ok") {
+            Some(it) => it,
+            None => return false,
+        };
+    match ty.variant_fields(ctx.db, VariantDef::EnumVariant(ok)).as_slice() {
+        [(_, ty)] => ty.is_unit(),
+        _ => false,
+    }
+}
+
+/// Whether `body` contains a `return`, a `?` or the like, or a `break` or
+/// `continue` leaving it, which can't be moved in or out of a closure.
+fn escapes_closure(body: &SyntaxNode) -> bool {
+    let nested_in = |node: &SyntaxNode, kinds: &[_]| {
+        node.ancestors().take_while(|it| it != body).any(|it| kinds.contains(&it.kind()))
+    };
+    body.descendants().any(|it| match it.kind() {
+        RETURN_EXPR | TRY_EXPR | AWAIT_EXPR => !nested_in(&it, &[LAMBDA_EXPR, FN_DEF]),
+        BREAK_EXPR | CONTINUE_EXPR => {
+            it.children_with_tokens().any(|it| it.kind() == LIFETIME)
+                || !nested_in(&it, &[LOOP_EXPR, WHILE_EXPR, FOR_EXPR, LAMBDA_EXPR, FN_DEF])
+        }
+        _ => false,
+    })
+}
+
+/// The text of `expr` as the receiver of a method call.
+fn receiver_text(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::PathExpr(_)
+        | ast::Expr::CallExpr(_)
+        | ast::Expr::MethodCallExpr(_)
+        | ast::Expr::FieldExpr(_)
+        | ast::Expr::IndexExpr(_)
+        | ast::Expr::ParenExpr(_)
+        | ast::Expr::MacroCall(_)
+        | ast::Expr::TryExpr(_) => expr.syntax().to_string(),
+        _ => format!("({})", expr.syntax()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    const PRELUDE: &str = "
+enum Result<T, E> { Ok(T), Err(E) }
+fn run() -> Result<(), String> { Result::Ok(()) }
+fn count() -> Result<u32, String> { Result::Ok(0) }
+fn report(e: String) {}
+";
+
+    #[test]
+    fn replace_if_let_err_on_unit_result() {
+        check_assist(
+            replace_if_let_err_with_unwrap_or_else,
+            &format!("{}fn f() {{ <|>if let Err(e) = run() {{ report(e); }} g(); }}", PRELUDE),
+            &format!("{}fn f() {{ <|>run().unwrap_or_else(|e| {{ report(e); }}); g(); }}", PRELUDE),
+        );
+    }
+
+    #[test]
+    fn replace_if_let_err_not_applicable_with_ok_value() {
+        check_assist_not_applicable(
+            replace_if_let_err_with_unwrap_or_else,
+            &format!("{}fn f() {{ <|>if let Err(e) = count() {{ report(e); }} }}", PRELUDE),
+        );
+    }
+
+    #[test]
+    fn replace_if_let_err_not_applicable_with_return() {
+        check_assist_not_applicable(
+            replace_if_let_err_with_unwrap_or_else,
+            &format!("{}fn f() {{ <|>if let Err(e) = run() {{ report(e); return; }} }}", PRELUDE),
+        );
+    }
+
+    #[test]
+    fn replace_unwrap_or_else_with_if_let_err_statement() {
+        check_assist(
+            replace_unwrap_or_else_with_if_let_err,
+            &format!("{}fn f() {{ run().unwrap_or_else<|>(|e| report(e)); g(); }}", PRELUDE),
+            &format!("{}fn f() {{ <|>if let Err(e) = run() {{ report(e) }} g(); }}", PRELUDE),
+        );
+    }
+}
@@ -4,12 +4,13 @@ use ra_syntax::{
     AstNode,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 use ast::edit::IndentLevel;
 
 // Assist: replace_if_let_with_match
 //
-// Replaces `if let` with an else branch with a `match` expression.
+// Replaces `if let` with an else branch, or a chain of `else if let` testing
+// the same expression, with a `match` expression.
 //
 // ```
 // enum Action { Move { distance: u32 }, Stop }
@@ -35,6 +36,9 @@ use ast::edit::IndentLevel;
 // ```
 pub(crate) fn replace_if_let_with_match(ctx: AssistCtx) -> Option<Assist> {
     let if_expr: ast::IfExpr = ctx.find_node_at_offset()?;
+    if let Some(chain) = IfLetChain::new(&if_expr) {
+        return replace_chain_with_match(ctx, chain);
+    }
     let cond = if_expr.condition()?;
     let pat = cond.pat()?;
     let expr = cond.expr()?;
@@ -44,31 +48,110 @@ pub(crate) fn replace_if_let_with_match(ctx: AssistCtx) -> Option<Assist> {
         ast::ElseBranch::IfExpr(_) => return None,
     };
 
-    ctx.add_assist(AssistId("replace_if_let_with_match"), "Replace with match", |edit| {
-        let match_expr = {
-            let then_arm = {
-                let then_expr = unwrap_trivial_block(then_block);
-                make::match_arm(vec![pat], then_expr)
-            };
-            let else_arm = {
-                let else_expr = unwrap_trivial_block(else_block);
-                make::match_arm(vec![make::placeholder_pat().into()], else_expr)
+    ctx.add_assist(
+        AssistId("replace_if_let_with_match"),
+        AssistKind::RefactorRewrite,
+        "Replace with match",
+        if_expr.syntax().text_range(),
+        |edit| {
+            let match_expr = {
+                let then_arm = {
+                    let then_expr = unwrap_trivial_block(then_block);
+                    make::match_arm(vec![pat], then_expr)
+                };
+                let else_arm = {
+                    let else_expr = unwrap_trivial_block(else_block);
+                    make::match_arm(vec![make::placeholder_pat().into()], else_expr)
+                };
+                make::expr_match(expr, make::match_arm_list(vec![then_arm, else_arm]))
             };
-            make::expr_match(expr, make::match_arm_list(vec![then_arm, else_arm]))
+
+            let match_expr = IndentLevel::from_node(if_expr.syntax()).increase_indent(match_expr);
+
+            edit.set_cursor(if_expr.syntax().text_range().start());
+            edit.replace_ast::<ast::Expr>(if_expr.into(), match_expr);
+        },
+    )
+}
+
+/// A chain of at least two `if let`, with their patterns and branches, all
+/// testing the same expression.
+struct IfLetChain {
+    if_expr: ast::IfExpr,
+    expr: ast::Expr,
+    arms: Vec<(ast::Pat, ast::BlockExpr)>,
+    else_block: Option<ast::BlockExpr>,
+}
+
+impl IfLetChain {
+    fn new(if_expr: &ast::IfExpr) -> Option<IfLetChain> {
+        let mut head = if_expr.clone();
+        while let Some(parent) = head.syntax().parent().and_then(ast::IfExpr::cast) {
+            head = parent;
+        }
+        let expr = head.condition()?.expr()?;
+        let text = |expr: &ast::Expr| {
+            expr.syntax().text().to_string().split_whitespace().collect::<String>()
         };
+        // Matching evaluates the expression once, instead of once per branch.
+        let has_call = expr.syntax().descendants().any(|it| {
+            ast::CallExpr::can_cast(it.kind())
+                || ast::MethodCallExpr::can_cast(it.kind())
+                || ast::MacroCall::can_cast(it.kind())
+        });
+        if has_call {
+            return None;
+        }
 
-        let match_expr = IndentLevel::from_node(if_expr.syntax()).increase_indent(match_expr);
+        let mut arms = Vec::new();
+        let mut current = head.clone();
+        let else_block = loop {
+            let cond = current.condition()?;
+            if text(&cond.expr()?) != text(&expr) {
+                return None;
+            }
+            arms.push((cond.pat()?, current.then_branch()?));
+            match current.else_branch() {
+                Some(ast::ElseBranch::IfExpr(it)) => current = it,
+                Some(ast::ElseBranch::Block(it)) => break Some(it),
+                None => break None,
+            }
+        };
+        if arms.len() < 2 {
+            return None;
+        }
+        Some(IfLetChain { if_expr: head, expr, arms, else_block })
+    }
+}
 
-        edit.target(if_expr.syntax().text_range());
-        edit.set_cursor(if_expr.syntax().text_range().start());
-        edit.replace_ast::<ast::Expr>(if_expr.into(), match_expr);
-    })
+fn replace_chain_with_match(ctx: AssistCtx, chain: IfLetChain) -> Option<Assist> {
+    let IfLetChain { if_expr, expr, arms, else_block } = chain;
+    ctx.add_assist(
+        AssistId("replace_if_let_with_match"),
+        AssistKind::RefactorRewrite,
+        "Replace with match",
+        if_expr.syntax().text_range(),
+        |edit| {
+            let mut match_arms = arms
+                .into_iter()
+                .map(|(pat, block)| make::match_arm(vec![pat], unwrap_trivial_block(block)))
+                .collect::<Vec<_>>();
+            // Without a final `else`, the chain is an expression of type `()`.
+            let else_expr = else_block.map_or_else(make::expr_unit, unwrap_trivial_block);
+            match_arms.push(make::match_arm(vec![make::placeholder_pat().into()], else_expr));
+            let match_expr = make::expr_match(expr, make::match_arm_list(match_arms));
+            let match_expr = IndentLevel::from_node(if_expr.syntax()).increase_indent(match_expr);
+
+            edit.set_cursor(if_expr.syntax().text_range().start());
+            edit.replace_ast::<ast::Expr>(if_expr.into(), match_expr);
+        },
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::helpers::{check_assist, check_assist_target};
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
 
     #[test]
     fn test_replace_if_let_with_match_unwraps_simple_expressions() {
@@ -145,4 +228,90 @@ impl VariantData {
         }",
         );
     }
+
+    #[test]
+    fn replace_if_let_chain_with_match() {
+        check_assist(
+            replace_if_let_with_match,
+            "
+fn foo(shape: Shape) -> u32 {
+    if let Shape::Circle(r) = shape {
+        r
+    } else if <|>let Shape::Square { side } = shape {
+        let area = side * side;
+        area
+    } else {
+        0
+    }
+}",
+            "
+fn foo(shape: Shape) -> u32 {
+    <|>match shape {
+        Shape::Circle(r) => r,
+        Shape::Square { side } => {
+            let area = side * side;
+            area
+        }
+        _ => 0,
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn replace_if_let_chain_without_else() {
+        check_assist(
+            replace_if_let_with_match,
+            "
+fn foo(event: &Event) {
+    if let Event::Key(key) = *event {
+        press(key);
+    } else if let Event::Click = <|>*event {
+        click();
+    }
+}",
+            "
+fn foo(event: &Event) {
+    <|>match *event {
+        Event::Key(key) => {
+            press(key);
+        }
+        Event::Click => {
+            click();
+        }
+        _ => (),
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn replace_if_let_chain_not_applicable() {
+        check_assist_not_applicable(
+            replace_if_let_with_match,
+            "
+fn foo(a: Option<u32>, b: Option<u32>) {
+    if <|>let Some(x) = a {
+        bar(x)
+    } else if let Some(y) = b {
+        bar(y)
+    } else {
+        baz()
+    }
+}",
+        );
+        check_assist_not_applicable(
+            replace_if_let_with_match,
+            "
+fn foo() {
+    if <|>let Some(x) = next() {
+        bar(x)
+    } else if let Some(y) = next() {
+        bar(y)
+    } else {
+        baz()
+    }
+}",
+        );
+    }
 }
@@ -0,0 +1,189 @@
+use ra_syntax::{
+    ast::{self, ArgListOwner, AstNode, NameOwner, TypeParamsOwner},
+    SyntaxKind::WHITESPACE,
+};
+
+use crate::{
+    utils::{derive_args, derives, insert_derives, self_type_name, trait_name},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+/// The traits whose manual impls are recognized as equivalent to the derived
+/// ones: the trait, its single method and the recognizer of the tail
+/// expression of the method, given the name of the type.
+const DERIVABLE: &[(&str, &str, fn(&ast::Expr, &str) -> bool)] =
+    &[("Default", "default", is_derived_default)];
+
+// Assist: replace_impl_with_derive
+//
+// Replaces a manual impl doing exactly what the derived one does by a
+// `#[derive]`.
+//
+// ```
+// struct Config { verbose: bool }
+//
+// impl Default for Config<|> {
+//     fn default() -> Self {
+//         Self { verbose: Default::default() }
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(Default)]
+// struct Config { verbose: bool }
+// ```
+pub(crate) fn replace_impl_with_derive(ctx: AssistCtx) -> Option<Assist> {
+    let impl_def = ctx.find_node_at_offset::<ast::ImplDef>()?;
+    if impl_def.type_param_list().is_some() {
+        return None;
+    }
+    let trait_ = trait_name(&impl_def)?;
+    let type_name = self_type_name(&impl_def)?;
+    let &(_, method, is_derived) = DERIVABLE.iter().find(|(it, _, _)| *it == trait_)?;
+
+    let mut items = impl_def.item_list()?.impl_items();
+    let fn_def = match (items.next()?, items.next()) {
+        (ast::ImplItem::FnDef(it), None) => it,
+        _ => return None,
+    };
+    if fn_def.name()?.text() != method {
+        return None;
+    }
+    let block = fn_def.body()?.block()?;
+    if block.statements().next().is_some() || !is_derived(&block.expr()?, &type_name) {
+        return None;
+    }
+    let struct_def = impl_def
+        .syntax()
+        .parent()?
+        .children()
+        .filter_map(ast::StructDef::cast)
+        .find(|it| it.name().map_or(false, |it| it.text() == type_name.as_str()))?;
+    let args = derive_args(&struct_def);
+    if args.as_ref().map_or(false, |it| derives(it, &trait_)) {
+        return None;
+    }
+
+    let (offset, text) = insert_derives(struct_def.syntax(), args.as_ref(), &trait_);
+    let mut impl_range = impl_def.syntax().text_range();
+    if let Some(ws) = impl_def.syntax().prev_sibling_or_token() {
+        if ws.kind() == WHITESPACE {
+            impl_range = impl_range.extend_to(&ws.text_range());
+        }
+    }
+    ctx.add_assist(
+        AssistId("replace_impl_with_derive"),
+        AssistKind::RefactorRewrite,
+        format!("Replace with #[derive({})]", trait_),
+        impl_def.syntax().text_range(),
+        |edit| {
+            edit.insert(offset, text);
+            edit.delete(impl_range);
+            edit.set_cursor(offset);
+        },
+    )
+}
+
+/// Whether `expr` builds the type with `Default::default()` for every field.
+fn is_derived_default(expr: &ast::Expr, type_name: &str) -> bool {
+    let is_default_call = |expr: &ast::Expr| match expr {
+        ast::Expr::CallExpr(call) => {
+            let is_default = match call.expr() {
+                Some(ast::Expr::PathExpr(it)) => it.syntax().text() == "Default::default",
+                _ => false,
+            };
+            is_default && call.arg_list().map_or(false, |it| it.args().next().is_none())
+        }
+        _ => false,
+    };
+    let is_self = |path: Option<ast::Path>| {
+        path.map_or(false, |it| it.syntax().text() == "Self" || it.syntax().text() == type_name)
+    };
+    match expr {
+        ast::Expr::RecordLit(lit) => {
+            let fields = match lit.record_field_list() {
+                Some(it) if it.spread().is_none() => it,
+                _ => return false,
+            };
+            is_self(lit.path())
+                && fields.fields().all(|it| it.expr().map_or(false, |it| is_default_call(&it)))
+        }
+        ast::Expr::CallExpr(call) => {
+            let callee = match call.expr() {
+                Some(ast::Expr::PathExpr(it)) => it.path(),
+                _ => None,
+            };
+            is_self(callee)
+                && call.arg_list().map_or(false, |it| it.args().all(|it| is_default_call(&it)))
+        }
+        ast::Expr::PathExpr(it) => is_self(it.path()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn replace_default_impl_of_record_struct() {
+        check_assist(
+            replace_impl_with_derive,
+            "
+#[derive(Debug)]
+struct Config { verbose: bool, name: String }
+
+impl Default<|> for Config {
+    fn default() -> Config {
+        Config { verbose: Default::default(), name: Default::default() }
+    }
+}",
+            "
+#[derive(Debug<|>, Default)]
+struct Config { verbose: bool, name: String }",
+        );
+    }
+
+    #[test]
+    fn replace_default_impl_of_tuple_and_unit_structs() {
+        check_assist(
+            replace_impl_with_derive,
+            "
+struct Pair(u32, u32);
+
+impl Default<|> for Pair {
+    fn default() -> Self { Self(Default::default(), Default::default()) }
+}",
+            "
+<|>#[derive(Default)]
+struct Pair(u32, u32);",
+        );
+        check_assist(
+            replace_impl_with_derive,
+            "
+struct Unit;
+
+impl Default<|> for Unit {
+    fn default() -> Self { Self }
+}",
+            "
+<|>#[derive(Default)]
+struct Unit;",
+        );
+    }
+
+    #[test]
+    fn replace_impl_not_applicable_with_other_values() {
+        check_assist_not_applicable(
+            replace_impl_with_derive,
+            "
+struct Counter { count: u32 }
+
+impl Default<|> for Counter {
+    fn default() -> Self { Self { count: 0 } }
+}",
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use hir::{HasSource, ModuleDef};
+use ra_ide_db::RootDatabase;
+use ra_syntax::ast::{self, AstNode};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: replace_literal_with_const
+//
+// Replaces a literal with an existing constant which has the same value.
+//
+// ```
+// const ANSWER: i32 = 42;
+//
+// fn main() {
+//     let x = 4<|>2;
+// }
+// ```
+// ->
+// ```
+// const ANSWER: i32 = 42;
+//
+// fn main() {
+//     let x = ANSWER;
+// }
+// ```
+pub(crate) fn replace_literal_with_const(ctx: AssistCtx) -> Option<Assist> {
+    let literal = ctx.find_node_at_offset::<ast::Literal>()?;
+    let key = literal_key(&literal)?;
+    let ty = ctx.sema.type_of_expr(&ast::Expr::Literal(literal.clone()))?;
+    let module = ctx.sema.scope(literal.syntax()).module()?;
+    let db = ctx.db;
+
+    // The initializer of a constant trivially matches the constant itself.
+    let enclosing_const = literal
+        .syntax()
+        .ancestors()
+        .find_map(ast::ConstDef::cast)
+        .and_then(|it| ctx.sema.to_def(&it));
+
+    let candidates = crate_consts(db, module.krate().root_module(db)?)
+        .into_iter()
+        .filter(|&it| Some(it) != enclosing_const)
+        .filter(|it| match it.source(db).value.body() {
+            Some(ast::Expr::Literal(init)) => literal_key(&init).as_ref() == Some(&key),
+            _ => false,
+        })
+        // Constants of another type than the literal's wouldn't type-check.
+        .filter(|it| it.ty(db) == ty)
+        .filter_map(|it| Some((it.name(db)?, module.find_use_path(db, it.into())?)))
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let mut group = ctx.add_assist_group("Replace with constant");
+    for (name, path) in candidates {
+        group.add_assist(
+            AssistId("replace_literal_with_const"),
+            AssistKind::RefactorRewrite,
+            format!("Replace with constant `{}`", name),
+            literal.syntax().text_range(),
+            |edit| {
+                edit.replace(literal.syntax().text_range(), path.to_string());
+            },
+        );
+    }
+    group.finish()
+}
+
+/// Literals are compared by their text, ignoring digit separators in numbers.
+fn literal_key(literal: &ast::Literal) -> Option<String> {
+    let text = literal.token().text().clone();
+    match literal.kind() {
+        ast::LiteralKind::IntNumber { .. } | ast::LiteralKind::FloatNumber { .. } => {
+            Some(text.replace('_', ""))
+        }
+        ast::LiteralKind::String
+        | ast::LiteralKind::ByteString
+        | ast::LiteralKind::Char
+        | ast::LiteralKind::Byte => Some(text.to_string()),
+        ast::LiteralKind::Bool => None,
+    }
+}
+
+fn crate_consts(db: &RootDatabase, root: hir::Module) -> Vec<hir::Const> {
+    let mut res = Vec::new();
+    let mut modules = vec![root];
+    while let Some(module) = modules.pop() {
+        modules.extend(module.children(db));
+        res.extend(module.declarations(db).into_iter().filter_map(|def| match def {
+            ModuleDef::Const(it) => Some(it),
+            _ => None,
+        }));
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn replace_number_with_const() {
+        check_assist(
+            replace_literal_with_const,
+            r#"
+const LIMIT: u32 = 1_000;
+
+fn f() -> u32 {
+    <|>1000
+}"#,
+            r#"
+const LIMIT: u32 = 1_000;
+
+fn f() -> u32 {
+    <|>LIMIT
+}"#,
+        );
+    }
+
+    #[test]
+    fn replace_string_with_const_from_other_module() {
+        check_assist(
+            replace_literal_with_const,
+            r#"
+mod consts {
+    pub const GREETING: &str = "hello";
+}
+
+fn f() -> &'static str {
+    <|>"hello"
+}"#,
+            r#"
+mod consts {
+    pub const GREETING: &str = "hello";
+}
+
+fn f() -> &'static str {
+    <|>consts::GREETING
+}"#,
+        );
+    }
+
+    #[test]
+    fn replace_literal_with_const_target() {
+        check_assist_target(
+            replace_literal_with_const,
+            "const A: i32 = 92; fn f() { let x = 9<|>2; }",
+            "92",
+        );
+    }
+
+    #[test]
+    fn replace_literal_with_const_no_match() {
+        check_assist_not_applicable(
+            replace_literal_with_const,
+            "const A: i32 = 1; fn f() { let x = <|>2; }",
+        );
+    }
+
+    #[test]
+    fn replace_literal_with_const_type_mismatch() {
+        check_assist_not_applicable(
+            replace_literal_with_const,
+            "const A: u8 = 1; fn f() { let x: i64 = <|>1; }",
+        );
+    }
+
+    #[test]
+    fn replace_literal_with_const_not_applicable_to_own_initializer() {
+        check_assist_not_applicable(replace_literal_with_const, "const A: i32 = <|>1;");
+    }
+}
@@ -0,0 +1,366 @@
+use hir::{ModuleDef, PathResolution};
+use ra_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind::{
+        IMPL_DEF, PATH, PATH_EXPR, PATH_PAT, RECORD_LIT, RECORD_PAT, TRAIT_DEF, TUPLE_STRUCT_PAT,
+    },
+    SyntaxNode, TextUnit, T,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: replace_self_with_type
+//
+// Replaces `Self` inside an impl block with the type the impl is for.
+//
+// ```
+// struct Foo<T>(T);
+//
+// impl<T> Foo<T> {
+//     fn new(t: T) -> <|>Self {
+//         Foo(t)
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Foo<T>(T);
+//
+// impl<T> Foo<T> {
+//     fn new(t: T) -> Foo<T> {
+//         Foo(t)
+//     }
+// }
+// ```
+pub(crate) fn replace_self_with_type(ctx: AssistCtx) -> Option<Assist> {
+    let name_ref = ctx.find_node_at_offset::<ast::NameRef>()?;
+    let segment = self_segment(&name_ref)?;
+    let impl_def = owning_impl(segment.syntax())?;
+    let target_type = impl_def.target_type()?;
+    let type_text = target_type.syntax().to_string();
+    replacement_text(&segment, &target_type)?;
+
+    let occurrences = impl_def
+        .item_list()?
+        .syntax()
+        .descendants()
+        .filter_map(ast::NameRef::cast)
+        .filter_map(|it| self_segment(&it))
+        .filter(|it| owning_impl(it.syntax()).as_ref() == Some(&impl_def))
+        .filter_map(|it| {
+            let text = replacement_text(&it, &target_type)?;
+            Some((it, text))
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = ctx.add_assist_group("Replace `Self` with type name");
+    group.add_assist(
+        AssistId("replace_self_with_type"),
+        AssistKind::RefactorRewrite,
+        format!("Replace `Self` with `{}`", type_text),
+        name_ref.syntax().text_range(),
+        |edit| {
+            edit.replace(
+                name_ref.syntax().text_range(),
+                replacement_text(&segment, &target_type).unwrap(),
+            );
+        },
+    );
+    if occurrences.len() > 1 {
+        group.add_assist(
+            AssistId("replace_self_with_type"),
+            AssistKind::RefactorRewrite,
+            format!("Replace all `Self` in impl with `{}`", type_text),
+            name_ref.syntax().text_range(),
+            |edit| {
+                let mut cursor = name_ref.syntax().text_range().start();
+                for (segment, text) in occurrences {
+                    let range = segment.syntax().text_range();
+                    if range.start() < name_ref.syntax().text_range().start() {
+                        cursor = cursor + TextUnit::of_str(&text) - range.len();
+                    }
+                    edit.replace(range, text);
+                }
+                edit.set_cursor(cursor);
+            },
+        );
+    }
+    group.finish()
+}
+
+// Assist: replace_type_with_self
+//
+// Replaces the spelled-out self type of an impl block with `Self`.
+//
+// ```
+// struct Foo;
+//
+// impl Foo {
+//     fn new() -> Foo {
+//         <|>Foo
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Foo;
+//
+// impl Foo {
+//     fn new() -> Foo {
+//         Self
+//     }
+// }
+// ```
+pub(crate) fn replace_type_with_self(ctx: AssistCtx) -> Option<Assist> {
+    let name_ref = ctx.find_node_at_offset::<ast::NameRef>()?;
+    if name_ref.text() == "Self" {
+        return None;
+    }
+    let segment = name_ref.syntax().parent().and_then(ast::PathSegment::cast)?;
+    let path = segment.parent_path();
+    let impl_def = owning_impl(path.syntax())?;
+    let item_list = impl_def.item_list()?;
+    if !path.syntax().text_range().is_subrange(&item_list.syntax().text_range()) {
+        return None;
+    }
+
+    let target_segment = match impl_def.target_type()? {
+        ast::TypeRef::PathType(it) => it.path()?.segment()?,
+        _ => return None,
+    };
+    if type_args_text(&segment) != type_args_text(&target_segment) {
+        return None;
+    }
+    let self_adt = ctx.sema.to_def(&impl_def)?.target_ty(ctx.db).as_adt()?;
+    match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(ModuleDef::Adt(adt)) if adt == self_adt => (),
+        _ => return None,
+    }
+
+    let range = path.syntax().text_range();
+    ctx.add_assist(
+        AssistId("replace_type_with_self"),
+        AssistKind::RefactorRewrite,
+        "Replace type name with `Self`",
+        range,
+        |edit| {
+            edit.replace(range, "Self");
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// Returns the segment of a path which consists of a lone `Self`, like in
+/// `Self`, `Self::new` or `Self { .. }`.
+fn self_segment(name_ref: &ast::NameRef) -> Option<ast::PathSegment> {
+    if name_ref.text() != "Self" {
+        return None;
+    }
+    let segment = name_ref.syntax().parent().and_then(ast::PathSegment::cast)?;
+    if segment.parent_path().qualifier().is_some() || segment.type_arg_list().is_some() {
+        return None;
+    }
+    Some(segment)
+}
+
+/// `Self` refers to the innermost impl or trait, so occurrences inside a
+/// nested impl are left alone.
+fn owning_impl(node: &SyntaxNode) -> Option<ast::ImplDef> {
+    node.ancestors()
+        .find(|it| it.kind() == IMPL_DEF || it.kind() == TRAIT_DEF)
+        .and_then(ast::ImplDef::cast)
+}
+
+/// Generic arguments need a turbofish in expression and pattern paths, so
+/// only path types can replace `Self` there.
+fn replacement_text(segment: &ast::PathSegment, target_type: &ast::TypeRef) -> Option<String> {
+    let text = target_type.syntax().to_string();
+    if !in_expression_path(segment) {
+        return Some(text);
+    }
+    let type_args = match target_type {
+        ast::TypeRef::PathType(it) => it.path()?.segment()?.type_arg_list(),
+        _ => return None,
+    };
+    let type_args = match type_args {
+        Some(it) if it.syntax().first_token()?.kind() != T![::] => it,
+        _ => return Some(text),
+    };
+    let offset =
+        type_args.syntax().text_range().start() - target_type.syntax().text_range().start();
+    let mut text = text;
+    text.insert_str(offset.to_usize(), "::");
+    Some(text)
+}
+
+fn in_expression_path(segment: &ast::PathSegment) -> bool {
+    let top_path = segment.syntax().ancestors().skip(1).take_while(|it| it.kind() == PATH).last();
+    match top_path.and_then(|it| it.parent()).map(|it| it.kind()) {
+        Some(PATH_EXPR)
+        | Some(PATH_PAT)
+        | Some(RECORD_LIT)
+        | Some(RECORD_PAT)
+        | Some(TUPLE_STRUCT_PAT) => true,
+        _ => false,
+    }
+}
+
+fn type_args_text(segment: &ast::PathSegment) -> String {
+    let text = match segment.type_arg_list() {
+        Some(it) => it.syntax().to_string(),
+        None => return String::new(),
+    };
+    let text = text.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    text.trim_start_matches("::").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn replace_self_in_type_position() {
+        check_assist(
+            replace_self_with_type,
+            r#"
+struct Foo;
+impl Foo {
+    fn new() -> <|>Self { Foo }
+}"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn new() -> <|>Foo { Foo }
+}"#,
+        );
+    }
+
+    #[test]
+    fn replace_self_in_expression_position_of_generic_impl() {
+        check_assist(
+            replace_self_with_type,
+            r#"
+struct Foo<T> { t: T }
+impl<T> Foo<T> {
+    fn new(t: T) -> Self { <|>Self { t } }
+}"#,
+            r#"
+struct Foo<T> { t: T }
+impl<T> Foo<T> {
+    fn new(t: T) -> Self { <|>Foo::<T> { t } }
+}"#,
+        );
+        check_assist(
+            replace_self_with_type,
+            r#"
+struct Foo<T> { t: T }
+impl<T: Default> Foo<T> {
+    fn new() -> Self { <|>Self::with(T::default()) }
+}"#,
+            r#"
+struct Foo<T> { t: T }
+impl<T: Default> Foo<T> {
+    fn new() -> Self { <|>Foo::<T>::with(T::default()) }
+}"#,
+        );
+    }
+
+    #[test]
+    fn replace_self_not_applicable_outside_of_impl() {
+        check_assist_not_applicable(replace_self_with_type, "trait T { fn f() -> <|>Self; }");
+        check_assist_not_applicable(
+            replace_self_with_type,
+            "struct Foo; impl Foo { fn f() -> Self::<|>Output {} }",
+        );
+    }
+
+    #[test]
+    fn replace_type_with_self_in_type_position() {
+        check_assist(
+            replace_type_with_self,
+            r#"
+struct Foo;
+impl Foo {
+    fn new() -> <|>Foo { Foo }
+}"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn new() -> <|>Self { Foo }
+}"#,
+        );
+    }
+
+    #[test]
+    fn replace_type_with_self_in_expression_position() {
+        check_assist(
+            replace_type_with_self,
+            r#"
+struct Foo { x: u32 }
+impl Foo {
+    fn new() -> Foo { <|>Foo { x: 0 } }
+    fn zero() -> Foo { Foo::new() }
+}"#,
+            r#"
+struct Foo { x: u32 }
+impl Foo {
+    fn new() -> Foo { <|>Self { x: 0 } }
+    fn zero() -> Foo { Foo::new() }
+}"#,
+        );
+        check_assist(
+            replace_type_with_self,
+            r#"
+struct Foo { x: u32 }
+impl Foo {
+    fn new() -> Foo { Foo { x: 0 } }
+    fn zero() -> Foo { <|>Foo::new() }
+}"#,
+            r#"
+struct Foo { x: u32 }
+impl Foo {
+    fn new() -> Foo { Foo { x: 0 } }
+    fn zero() -> Foo { <|>Self::new() }
+}"#,
+        );
+    }
+
+    #[test]
+    fn replace_type_with_self_in_generic_impl() {
+        check_assist(
+            replace_type_with_self,
+            r#"
+struct Foo<T>(T);
+impl<T> Foo<T> {
+    fn new(t: T) -> <|>Foo<T> { Foo(t) }
+}"#,
+            r#"
+struct Foo<T>(T);
+impl<T> Foo<T> {
+    fn new(t: T) -> <|>Self { Foo(t) }
+}"#,
+        );
+    }
+
+    #[test]
+    fn replace_type_with_self_not_applicable_for_other_arguments() {
+        check_assist_not_applicable(
+            replace_type_with_self,
+            r#"
+struct Foo<T>(T);
+impl<T> Foo<T> {
+    fn convert(self) -> <|>Foo<u32> { Foo(0) }
+}"#,
+        );
+        check_assist_not_applicable(
+            replace_type_with_self,
+            r#"
+struct Foo;
+struct Bar;
+impl Foo {
+    fn bar() -> <|>Bar { Bar }
+}"#,
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use ra_syntax::{
+    ast::{self, make, AstNode},
+    SyntaxKind::COMMENT,
+    SyntaxNode, T,
+};
+
+use crate::{utils::invert_boolean_expression, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: replace_with_bool_expr
+//
+// Replaces an `if` or a `match` producing `true` or `false` with the boolean
+// expression it branches on.
+//
+// ```
+// fn is_empty(len: usize) -> bool {
+//     <|>if len == 0 { true } else { false }
+// }
+// ```
+// ->
+// ```
+// fn is_empty(len: usize) -> bool {
+//     len == 0
+// }
+// ```
+pub(crate) fn replace_with_bool_expr(ctx: AssistCtx) -> Option<Assist> {
+    let (expr, cond, value_if_true, value_if_false) =
+        if let Some(if_expr) = ctx.find_node_at_offset::<ast::IfExpr>() {
+            let cond = if_expr.condition()?;
+            if cond.pat().is_some() {
+                return None;
+            }
+            let else_block = match if_expr.else_branch()? {
+                ast::ElseBranch::Block(it) => it,
+                ast::ElseBranch::IfExpr(_) => return None,
+            };
+            let then_value = bool_value(&if_expr.then_branch()?.into())?;
+            let else_value = bool_value(&else_block.into())?;
+            (if_expr.syntax().clone(), cond.expr()?, then_value, else_value)
+        } else {
+            let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+            let (value_if_true, value_if_false) = match_values(&match_expr)?;
+            (match_expr.syntax().clone(), match_expr.expr()?, value_if_true, value_if_false)
+        };
+
+    let replacement = match (value_if_true, value_if_false) {
+        (true, false) => cond,
+        (false, true) => negate(cond),
+        _ => return None,
+    };
+    ctx.add_assist(
+        AssistId("replace_with_bool_expr"),
+        AssistKind::RefactorRewrite,
+        "Replace with boolean expression",
+        expr.text_range(),
+        |edit| {
+            edit.set_cursor(expr.text_range().start());
+            edit.replace(expr.text_range(), replacement.syntax().to_string());
+        },
+    )
+}
+
+/// Returns the values a `match` on a `bool` produces for `true` and `false`.
+fn match_values(match_expr: &ast::MatchExpr) -> Option<(bool, bool)> {
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    if arms.len() != 2 || arms.iter().any(|it| it.guard().is_some()) {
+        return None;
+    }
+    let first_pat = pat_value(&arms[0].pat()?)?;
+    let second_pat = pat_value(&arms[1].pat()?);
+    let first = bool_value(&arms[0].expr()?)?;
+    let second = bool_value(&arms[1].expr()?)?;
+    match (first_pat, second_pat) {
+        (true, Some(false)) | (true, None) => Some((first, second)),
+        (false, Some(true)) | (false, None) => Some((second, first)),
+        _ => None,
+    }
+}
+
+/// `Some` for a `true` or `false` pattern, `None` for `_`.
+fn pat_value(pat: &ast::Pat) -> Option<Option<bool>> {
+    match pat {
+        ast::Pat::PlaceholderPat(_) => Some(None),
+        ast::Pat::LiteralPat(it) => literal_value(&it.literal()?).map(Some),
+        _ => None,
+    }
+}
+
+/// The value of a `true` or `false` literal, possibly the only content of a
+/// block.
+fn bool_value(expr: &ast::Expr) -> Option<bool> {
+    match expr {
+        ast::Expr::Literal(it) => literal_value(it),
+        ast::Expr::BlockExpr(it) => {
+            let block = it.block()?;
+            if block.statements().next().is_some() || has_comments(block.syntax()) {
+                return None;
+            }
+            bool_value(&block.expr()?)
+        }
+        _ => None,
+    }
+}
+
+fn literal_value(literal: &ast::Literal) -> Option<bool> {
+    match literal.token().kind() {
+        T![true] => Some(true),
+        T![false] => Some(false),
+        _ => None,
+    }
+}
+
+fn has_comments(node: &SyntaxNode) -> bool {
+    node.descendants_with_tokens().any(|it| it.kind() == COMMENT)
+}
+
+/// Binary and cast expressions need parentheses before being negated.
+fn negate(cond: ast::Expr) -> ast::Expr {
+    let inverted = invert_boolean_expression(cond.clone());
+    let needs_parens = match (&inverted, &cond) {
+        (ast::Expr::PrefixExpr(_), ast::Expr::BinExpr(_))
+        | (ast::Expr::PrefixExpr(_), ast::Expr::CastExpr(_))
+        | (ast::Expr::PrefixExpr(_), ast::Expr::RangeExpr(_)) => true,
+        _ => false,
+    };
+    if needs_parens {
+        let parenthesized = make::expr_from_text(&format!("({})", cond.syntax()));
+        make::expr_prefix(T![!], parenthesized)
+    } else {
+        inverted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn replace_if_true_else_false() {
+        check_assist(
+            replace_with_bool_expr,
+            "fn f(x: u32) -> bool { i<|>f x > 2 { true } else { false } }",
+            "fn f(x: u32) -> bool { <|>x > 2 }",
+        );
+    }
+
+    #[test]
+    fn replace_if_false_else_true() {
+        check_assist(
+            replace_with_bool_expr,
+            "fn f(x: Option<u32>) -> bool { i<|>f x.is_some() { false } else { true } }",
+            "fn f(x: Option<u32>) -> bool { <|>x.is_none() }",
+        );
+        check_assist(
+            replace_with_bool_expr,
+            "fn f(a: bool, b: bool) -> bool { i<|>f a && b { false } else { true } }",
+            "fn f(a: bool, b: bool) -> bool { <|>!(a && b) }",
+        );
+    }
+
+    #[test]
+    fn replace_match_true_true() {
+        check_assist(
+            replace_with_bool_expr,
+            "fn f(x: bool) -> bool { mat<|>ch x { true => true, false => false } }",
+            "fn f(x: bool) -> bool { <|>x }",
+        );
+    }
+
+    #[test]
+    fn replace_match_with_placeholder() {
+        check_assist(
+            replace_with_bool_expr,
+            "fn f(x: bool) -> bool { mat<|>ch x { false => true, _ => false } }",
+            "fn f(x: bool) -> bool { <|>!x }",
+        );
+    }
+
+    #[test]
+    fn replace_match_with_block_bodies() {
+        check_assist(
+            replace_with_bool_expr,
+            "
+fn f(x: bool) -> bool {
+    mat<|>ch x {
+        true => {
+            false
+        }
+        false => { true }
+    }
+}",
+            "
+fn f(x: bool) -> bool {
+    <|>!x
+}",
+        );
+    }
+
+    #[test]
+    fn replace_with_bool_expr_not_applicable() {
+        check_assist_not_applicable(
+            replace_with_bool_expr,
+            "fn f(x: bool) -> bool { i<|>f x { true } else { true } }",
+        );
+        check_assist_not_applicable(
+            replace_with_bool_expr,
+            "fn f(x: bool) -> bool { i<|>f x { g(); true } else { false } }",
+        );
+        check_assist_not_applicable(
+            replace_with_bool_expr,
+            "fn f(x: Option<bool>) -> bool { i<|>f let Some(_) = x { true } else { false } }",
+        );
+        check_assist_not_applicable(
+            replace_with_bool_expr,
+            "fn f(x: u8) -> bool { mat<|>ch x { 1 => true, _ => false } }",
+        );
+    }
+
+    #[test]
+    fn replace_with_bool_expr_target() {
+        check_assist_target(
+            replace_with_bool_expr,
+            "fn f(x: bool) -> bool { let y = i<|>f x { true } else { false }; y }",
+            "if x { true } else { false }",
+        );
+    }
+}
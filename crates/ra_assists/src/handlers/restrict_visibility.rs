@@ -0,0 +1,197 @@
+use hir::Module;
+use ra_ide_db::{defs::classify_name, RootDatabase};
+use ra_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind::WHITESPACE,
+    TextRange,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: restrict_visibility
+//
+// Narrows the visibility of an item to what its uses actually need.
+//
+// ```
+// mod frobnicator {
+//     <|>pub(crate) fn frobnicate() {}
+//
+//     pub(crate) fn run() { frobnicate() }
+// }
+// fn main() { frobnicator::run() }
+// ```
+// ->
+// ```
+// mod frobnicator {
+//     fn frobnicate() {}
+//
+//     pub(crate) fn run() { frobnicate() }
+// }
+// fn main() { frobnicator::run() }
+// ```
+pub(crate) fn restrict_visibility(ctx: AssistCtx) -> Option<Assist> {
+    let vis = ctx.find_node_at_offset::<ast::Visibility>()?;
+    let current = if vis.syntax().text() == "pub" {
+        Visibility::Public
+    } else if vis.syntax().text() == "pub(crate)" {
+        Visibility::Crate
+    } else {
+        return None;
+    };
+    let item = vis.syntax().parent()?;
+    let name = item.children().find_map(ast::Name::cast)?;
+    let def = classify_name(&ctx.sema, &name)?.definition();
+    let module = def.module(ctx.db)?;
+
+    let usages = def.find_usages(ctx.db, None);
+    // An item nobody uses might well be part of the public API.
+    if usages.is_empty() {
+        return None;
+    }
+    let mut used_in = Vec::new();
+    for usage in usages {
+        let root = ctx.sema.parse(usage.file_range.file_id);
+        let token = root.syntax().token_at_offset(usage.file_range.range.start()).right_biased()?;
+        let usage_module = ctx.sema.scope(&token.parent()).module()?;
+        if usage_module.krate() != module.krate() {
+            return None;
+        }
+        used_in.push(usage_module);
+    }
+
+    let needed = needed_visibility(ctx.db, module, &used_in);
+    if needed >= current {
+        return None;
+    }
+
+    let (range, text) = match needed {
+        Visibility::Private => {
+            let vis_range = vis.syntax().text_range();
+            let end = vis
+                .syntax()
+                .next_sibling_or_token()
+                .filter(|it| it.kind() == WHITESPACE)
+                .map_or(vis_range.end(), |it| it.text_range().end());
+            (TextRange::from_to(vis_range.start(), end), "")
+        }
+        Visibility::Super => (vis.syntax().text_range(), "pub(super)"),
+        Visibility::Crate => (vis.syntax().text_range(), "pub(crate)"),
+        Visibility::Public => return None,
+    };
+    ctx.add_assist(
+        AssistId("restrict_visibility"),
+        AssistKind::RefactorRewrite,
+        "Restrict visibility to minimum",
+        vis.syntax().text_range(),
+        |edit| {
+            edit.set_cursor(range.start());
+            edit.replace(range, text);
+        },
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Visibility {
+    Private,
+    Super,
+    Crate,
+    Public,
+}
+
+/// The narrowest visibility of an item of `module` which is visible in all of
+/// `used_in`. `pub(super)` in a top-level module amounts to `pub(crate)`.
+fn needed_visibility(db: &RootDatabase, module: Module, used_in: &[Module]) -> Visibility {
+    if used_in.iter().all(|&it| is_within(db, it, module)) {
+        return Visibility::Private;
+    }
+    match module.parent(db) {
+        Some(parent)
+            if parent.parent(db).is_some()
+                && used_in.iter().all(|&it| is_within(db, it, parent)) =>
+        {
+            Visibility::Super
+        }
+        _ => Visibility::Crate,
+    }
+}
+
+fn is_within(db: &RootDatabase, module: Module, ancestor: Module) -> bool {
+    let mut module = Some(module);
+    while let Some(it) = module {
+        if it == ancestor {
+            return true;
+        }
+        module = it.parent(db);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn restrict_to_private() {
+        check_assist(
+            restrict_visibility,
+            "
+mod m {
+    <|>pub fn f() {}
+    fn g() { f() }
+    mod inner { fn h() { super::f() } }
+}",
+            "
+mod m {
+    <|>fn f() {}
+    fn g() { f() }
+    mod inner { fn h() { super::f() } }
+}",
+        );
+    }
+
+    #[test]
+    fn restrict_to_pub_super() {
+        check_assist(
+            restrict_visibility,
+            "
+mod outer {
+    mod a { p<|>ub(crate) fn f() {} }
+    mod b { fn g() { super::a::f() } }
+}",
+            "
+mod outer {
+    mod a { <|>pub(super) fn f() {} }
+    mod b { fn g() { super::a::f() } }
+}",
+        );
+    }
+
+    #[test]
+    fn restrict_to_pub_crate() {
+        check_assist(
+            restrict_visibility,
+            "
+mod a { p<|>ub fn f() {} }
+mod b { fn g() { crate::a::f() } }",
+            "
+mod a { <|>pub(crate) fn f() {} }
+mod b { fn g() { crate::a::f() } }",
+        );
+    }
+
+    #[test]
+    fn restrict_visibility_not_applicable_when_minimal() {
+        check_assist_not_applicable(
+            restrict_visibility,
+            "
+mod a { p<|>ub(crate) fn f() {} }
+mod b { fn g() { crate::a::f() } }",
+        );
+    }
+
+    #[test]
+    fn restrict_visibility_not_applicable_to_unused_item() {
+        check_assist_not_applicable(restrict_visibility, "mod a { p<|>ub fn f() {} }");
+    }
+}
@@ -0,0 +1,268 @@
+use ra_syntax::{
+    ast::{self, LiteralKind},
+    AstNode,
+    SyntaxKind::{FLOAT_NUMBER, INT_NUMBER},
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: separate_number_literal
+//
+// Separates the digits of an integer literal with `_`, in groups of a width
+// depending on the radix.
+//
+// ```
+// const MAX: u32 = 4294967295<|>;
+// ```
+// ->
+// ```
+// const MAX: u32 = 4_294_967_295;
+// ```
+pub(crate) fn separate_number_literal(ctx: AssistCtx) -> Option<Assist> {
+    let token = ctx.find_token_at_offset_with_macros(INT_NUMBER)?;
+    let literal = ast::Literal::cast(token.parent())?;
+    let suffix = match literal.kind() {
+        LiteralKind::IntNumber { suffix } => suffix,
+        _ => return None,
+    };
+    let text = literal.token().text().as_str();
+    let number = &text[..text.len() - suffix.as_ref().map_or(0, |it| it.len())];
+    let details = separate_number_details(number);
+    let digits = number[details.prefix.len()..].replace('_', "");
+    let suffix = suffix.as_ref().map_or("", |it| it.as_str());
+
+    let entries = details
+        .widths
+        .iter()
+        .filter(|&&width| digits.len() > width)
+        .map(|&width| (width, format!("{}{}", details.prefix, group_digits(&digits, width))))
+        // Regrouping to the current grouping changes nothing.
+        .filter(|(_, it)| it != number)
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return None;
+    }
+
+    let range = ctx.original_range(literal.syntax())?;
+    let mut group = ctx.add_assist_group("Separate number literal");
+    for (width, separated) in entries {
+        group.add_assist(
+            AssistId("separate_number_literal"),
+            AssistKind::RefactorRewrite,
+            details.label.map_or_else(|| format!("Separate every {} digits", width), String::from),
+            range,
+            |edit| {
+                edit.replace(range, format!("{}{}", separated, suffix));
+                edit.set_cursor(range.start());
+            },
+        );
+    }
+    group.finish()
+}
+
+// Assist: remove_digit_separators
+//
+// Removes the `_` separating the digits of a number literal.
+//
+// ```
+// const MAX: u32 = 4_294_967_295<|>;
+// ```
+// ->
+// ```
+// const MAX: u32 = 4294967295;
+// ```
+pub(crate) fn remove_digit_separators(ctx: AssistCtx) -> Option<Assist> {
+    let token = ctx
+        .find_token_at_offset_with_macros(INT_NUMBER)
+        .or_else(|| ctx.find_token_at_offset_with_macros(FLOAT_NUMBER))?;
+    let literal = ast::Literal::cast(token.parent())?;
+    match literal.kind() {
+        LiteralKind::IntNumber { .. } | LiteralKind::FloatNumber { .. } => (),
+        _ => return None,
+    }
+    let text = literal.token().text();
+    if !text.contains('_') {
+        return None;
+    }
+    // A suffix may be separated too, as in `1_u8`.
+    let text = text.replace('_', "");
+
+    let range = ctx.original_range(literal.syntax())?;
+    ctx.add_assist(
+        AssistId("remove_digit_separators"),
+        AssistKind::RefactorRewrite,
+        "Remove digit separators",
+        range,
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// The prefix of a kind of integer literal, and the widths its digits are
+/// usually grouped by, the first being the most common.
+struct SeparateNumberDetails {
+    prefix: &'static str,
+    widths: &'static [usize],
+    /// The label of the entries, if not the one naming the width.
+    label: Option<&'static str>,
+}
+
+fn separate_number_details(number: &str) -> SeparateNumberDetails {
+    let (prefix, widths, label): (&str, &[usize], _) = match number.get(..2) {
+        Some("0x") => ("0x", &[4, 2], None),
+        Some("0b") => ("0b", &[8, 4], None),
+        Some("0o") => ("0o", &[3], Some("Separate octal digits")),
+        _ => ("", &[3], None),
+    };
+    SeparateNumberDetails { prefix, widths, label }
+}
+
+/// Joins the groups of `width` digits, from the right, with `_`.
+fn group_digits(digits: &str, width: usize) -> String {
+    let mut res = String::new();
+    for (idx, c) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx) % width == 0 {
+            res.push('_');
+        }
+        res.push(c);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use ra_syntax::TextRange;
+    use test_utils::extract_offset;
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    /// The labels and results of the entries of the assist.
+    fn entries(text: &str) -> Vec<(String, String)> {
+        let (offset, before) = extract_offset(text);
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "separate_number_literal")
+            .map(|it| (it.label.label.clone(), it.action.edit().apply(&before)))
+            .collect()
+    }
+
+    #[test]
+    fn separate_decimal_literal() {
+        check_assist(
+            separate_number_literal,
+            "fn f() { let x = 12345<|>67u64; }",
+            "fn f() { let x = <|>1_234_567u64; }",
+        );
+    }
+
+    #[test]
+    fn separate_hexadecimal_and_binary_by_each_width() {
+        assert_eq!(
+            entries("fn f() { let x = 0xFF<|>00FF; }"),
+            vec![
+                (
+                    "Separate every 4 digits".to_string(),
+                    "fn f() { let x = 0xFF_00FF; }".to_string()
+                ),
+                (
+                    "Separate every 2 digits".to_string(),
+                    "fn f() { let x = 0xFF_00_FF; }".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            entries("fn f() { let x = 0b1<|>010101010; }"),
+            vec![
+                (
+                    "Separate every 8 digits".to_string(),
+                    "fn f() { let x = 0b10_10101010; }".to_string()
+                ),
+                (
+                    "Separate every 4 digits".to_string(),
+                    "fn f() { let x = 0b10_1010_1010; }".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn separate_and_remove_octal_digits() {
+        assert_eq!(
+            entries("fn f() { let mode = 0o7<|>777777u32; }"),
+            vec![(
+                "Separate octal digits".to_string(),
+                "fn f() { let mode = 0o7_777_777u32; }".to_string()
+            )]
+        );
+        check_assist(
+            remove_digit_separators,
+            "fn f() { let mode = 0o7_<|>777_777u32; }",
+            "fn f() { let mode = <|>0o7777777u32; }",
+        );
+        check_assist_not_applicable(separate_number_literal, "fn f() { let mode = 0o7<|>55; }");
+        check_assist_not_applicable(separate_number_literal, "fn f() { let mode = 0o1_<|>755; }");
+    }
+
+    #[test]
+    fn regroup_skips_current_grouping() {
+        assert_eq!(
+            entries("fn f() { let x = 0xFF<|>FF_FFFF; }"),
+            vec![(
+                "Separate every 2 digits".to_string(),
+                "fn f() { let x = 0xFF_FF_FF_FF; }".to_string()
+            )]
+        );
+        check_assist_not_applicable(separate_number_literal, "fn f() { let x = 1_<|>000_000; }");
+        check_assist_not_applicable(separate_number_literal, "fn f() { let x = 1<|>00; }");
+    }
+
+    #[test]
+    fn separate_and_remove_in_macro_calls() {
+        check_assist(
+            remove_digit_separators,
+            r#"
+macro_rules! assert_eq { ($a:expr, $b:expr) => { if $a != $b { panic(); } } }
+fn f(x: u32) { assert_eq!(1_000<|>, x); }"#,
+            r#"
+macro_rules! assert_eq { ($a:expr, $b:expr) => { if $a != $b { panic(); } } }
+fn f(x: u32) { assert_eq!(<|>1000, x); }"#,
+        );
+        check_assist(
+            separate_number_literal,
+            r#"
+macro_rules! assert_eq { ($a:expr, $b:expr) => { if $a != $b { panic(); } } }
+fn f(x: u32) { assert_eq!(100<|>0, x); }"#,
+            r#"
+macro_rules! assert_eq { ($a:expr, $b:expr) => { if $a != $b { panic(); } } }
+fn f(x: u32) { assert_eq!(<|>1_000, x); }"#,
+        );
+    }
+
+    #[test]
+    fn separate_and_remove_in_const_contexts() {
+        check_assist(
+            remove_digit_separators,
+            "struct Page { bytes: [u8; 4_0<|>96] }",
+            "struct Page { bytes: [u8; <|>4096] }",
+        );
+        check_assist(
+            separate_number_literal,
+            "enum Size { Big = 1048<|>576 }",
+            "enum Size { Big = <|>1_048_576 }",
+        );
+        check_assist(
+            separate_number_literal,
+            "static LIMIT: u64 = 1 << 2000<|>0;",
+            "static LIMIT: u64 = 1 << <|>20_000;",
+        );
+    }
+}
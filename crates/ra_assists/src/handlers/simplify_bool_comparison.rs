@@ -0,0 +1,154 @@
+use ra_syntax::ast::{self, AstNode, LiteralKind};
+
+use crate::{utils::invert_boolean_expression, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: simplify_bool_comparison
+//
+// Removes the comparison of an expression with a boolean literal.
+//
+// ```
+// fn is_hidden(visible: bool) -> bool {
+//     visible =<|>= false
+// }
+// ```
+// ->
+// ```
+// fn is_hidden(visible: bool) -> bool {
+//     !visible
+// }
+// ```
+pub(crate) fn simplify_bool_comparison(ctx: AssistCtx) -> Option<Assist> {
+    let (bin, operand, negated) = ctx
+        .covering_element()
+        .ancestors()
+        .filter_map(ast::BinExpr::cast)
+        .find_map(|it| bool_comparison(&it).map(|(operand, negated)| (it, operand, negated)))?;
+
+    // A comparison has the same precedence as `bin`, so it can replace it
+    // without its parentheses.
+    let operand = match &operand {
+        ast::Expr::ParenExpr(paren) => match paren.expr() {
+            Some(inner @ ast::Expr::BinExpr(_)) if is_comparison(&inner) => inner,
+            _ => operand,
+        },
+        _ => operand,
+    };
+    // `!(x == false)` is simplified to `x` as a whole.
+    let outer_not = bin
+        .syntax()
+        .parent()
+        .and_then(ast::ParenExpr::cast)
+        .and_then(|it| it.syntax().parent())
+        .and_then(ast::PrefixExpr::cast)
+        .filter(|it| it.op_kind() == Some(ast::PrefixOp::Not));
+    let (range, negated) = match &outer_not {
+        Some(not) => (not.syntax().text_range(), !negated),
+        None => (bin.syntax().text_range(), negated),
+    };
+    let text = if negated {
+        invert_boolean_expression(operand).syntax().to_string()
+    } else {
+        operand.syntax().to_string()
+    };
+
+    ctx.add_assist(
+        AssistId("simplify_bool_comparison"),
+        AssistKind::RefactorRewrite,
+        "Simplify boolean comparison",
+        bin.syntax().text_range(),
+        |edit| {
+            edit.replace(range, text);
+            edit.set_cursor(range.start());
+        },
+    )
+}
+
+/// The operand of a comparison with `true` or `false`, and whether the
+/// comparison negates it.
+fn bool_comparison(bin: &ast::BinExpr) -> Option<(ast::Expr, bool)> {
+    let equal = match bin.op_kind()? {
+        ast::BinOp::EqualityTest => true,
+        ast::BinOp::NegatedEqualityTest => false,
+        _ => return None,
+    };
+    let (lhs, rhs) = (bin.lhs()?, bin.rhs()?);
+    let (operand, value) = match (bool_value(&lhs), bool_value(&rhs)) {
+        (_, Some(value)) => (lhs, value),
+        (Some(value), None) => (rhs, value),
+        (None, None) => return None,
+    };
+    Some((operand, value != equal))
+}
+
+fn bool_value(expr: &ast::Expr) -> Option<bool> {
+    match expr {
+        ast::Expr::Literal(lit) => match lit.kind() {
+            LiteralKind::Bool => Some(lit.token().text() == "true"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_comparison(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::BinExpr(bin) => match bin.op_kind() {
+            Some(ast::BinOp::EqualityTest) | Some(ast::BinOp::NegatedEqualityTest) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn simplify_comparisons_with_literals() {
+        let check = |before: &str, after: &str| {
+            check_assist(
+                simplify_bool_comparison,
+                &format!("fn f(x: bool) -> bool {{ {} }}", before),
+                &format!("fn f(x: bool) -> bool {{ {} }}", after),
+            )
+        };
+        check("x =<|>= true", "<|>x");
+        check("x =<|>= false", "<|>!x");
+        check("x !<|>= true", "<|>!x");
+        check("x !<|>= false", "<|>x");
+    }
+
+    #[test]
+    fn simplify_comparison_with_literal_on_the_left() {
+        check_assist(
+            simplify_bool_comparison,
+            "fn f(x: bool) -> bool { fal<|>se == x }",
+            "fn f(x: bool) -> bool { <|>!x }",
+        );
+    }
+
+    #[test]
+    fn simplify_comparison_inverts_inner_comparison() {
+        check_assist(
+            simplify_bool_comparison,
+            "fn f(a: u32, b: u32) -> bool { (a == b) =<|>= false }",
+            "fn f(a: u32, b: u32) -> bool { <|>a != b }",
+        );
+    }
+
+    #[test]
+    fn simplify_comparison_collapses_surrounding_negation() {
+        check_assist(
+            simplify_bool_comparison,
+            "fn f(x: bool) -> bool { !(x =<|>= false) }",
+            "fn f(x: bool) -> bool { <|>x }",
+        );
+    }
+
+    #[test]
+    fn simplify_comparison_not_applicable_to_other_operands() {
+        check_assist_not_applicable(simplify_bool_comparison, "fn f(x: u32) -> bool { x =<|>= 1 }");
+    }
+}
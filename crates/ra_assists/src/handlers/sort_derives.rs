@@ -0,0 +1,118 @@
+use ra_syntax::ast::{self, AstNode};
+
+use crate::{
+    utils::{attribute_removal_range, derive_items},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: sort_derives
+//
+// Sorts the traits of the `#[derive]` attributes of an item alphabetically,
+// removing duplicates and merging the attributes into the first one.
+//
+// ```
+// #[derive(PartialEq, Debug<|>)]
+// #[derive(Clone, Debug)]
+// struct S;
+// ```
+// ->
+// ```
+// #[derive(Clone, Debug, PartialEq)]
+// struct S;
+// ```
+pub(crate) fn sort_derives(ctx: AssistCtx) -> Option<Assist> {
+    let attr = ctx.find_node_at_offset::<ast::Attr>()?;
+    if !is_derive(&attr) {
+        return None;
+    }
+    let derives = attr
+        .syntax()
+        .parent()?
+        .children()
+        .filter_map(ast::Attr::cast)
+        .filter(is_derive)
+        .collect::<Vec<_>>();
+    let mut items = Vec::new();
+    for derive in &derives {
+        let (_, input) = derive.as_simple_call()?;
+        items.extend(derive_items(&input).into_iter().map(|(_, text)| text));
+    }
+    let mut sorted = items.clone();
+    // Paths like `serde::Serialize` sort by the name of the trait.
+    sorted.sort_by(|a, b| last_segment(a).cmp(last_segment(b)).then_with(|| a.cmp(b)));
+    sorted.dedup();
+    if derives.len() == 1 && sorted == items {
+        return None;
+    }
+
+    let (_, first_input) = derives[0].as_simple_call()?;
+    ctx.add_assist(
+        AssistId("sort_derives"),
+        AssistKind::RefactorRewrite,
+        "Sort derives",
+        attr.syntax().text_range(),
+        |edit| {
+            edit.replace(first_input.syntax().text_range(), format!("({})", sorted.join(", ")));
+            for derive in &derives[1..] {
+                edit.delete(attribute_removal_range(derive));
+            }
+            edit.set_cursor(derives[0].syntax().text_range().start());
+        },
+    )
+}
+
+fn is_derive(attr: &ast::Attr) -> bool {
+    attr.as_simple_call().map_or(false, |(name, _)| name == "derive")
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn sort_derives_removes_duplicates() {
+        check_assist(
+            sort_derives,
+            "#[derive(Debug, Clone, De<|>bug)]\nstruct S;",
+            "<|>#[derive(Clone, Debug)]\nstruct S;",
+        );
+    }
+
+    #[test]
+    fn sort_derives_merges_attributes() {
+        check_assist(
+            sort_derives,
+            r#"
+#[derive(PartialEq<|>)]
+#[serde(rename_all = "camelCase")]
+#[derive(Eq, Debug)]
+struct S;"#,
+            r#"
+<|>#[derive(Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct S;"#,
+        );
+    }
+
+    #[test]
+    fn sort_derives_by_last_segment() {
+        check_assist(
+            sort_derives,
+            "#[derive(serde::Serialize, Debug, serde::Deserialize, <|>Clone)]\nstruct S;",
+            "<|>#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]\nstruct S;",
+        );
+    }
+
+    #[test]
+    fn sort_derives_not_applicable_when_sorted() {
+        check_assist_not_applicable(
+            sort_derives,
+            "#[derive(Clone, <|>Debug, serde::Serialize)]\nstruct S;",
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use ra_fmt::leading_indent;
+use ra_syntax::{
+    ast::{self, AstNode, AttrsOwner},
+    SyntaxKind::{ATTR, COMMENT, WHITESPACE},
+    TextRange, TextUnit,
+};
+
+use crate::{utils::range_with_leading_comments, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: split_impl_block
+//
+// Moves the selected items of an inherent impl into a new impl block of the
+// same type, optionally gated by a `#[cfg]`.
+//
+// ```
+// struct Counter { count: u32 }
+//
+// impl Counter {
+//     fn tick(&mut self) { self.count += 1 }
+//
+//     fn dump<|>(&self) { }
+// }
+// ```
+// ->
+// ```
+// struct Counter { count: u32 }
+//
+// impl Counter {
+//     fn tick(&mut self) { self.count += 1 }
+// }
+//
+// impl Counter {
+//     fn dump(&self) { }
+// }
+// ```
+pub(crate) fn split_impl_block(ctx: AssistCtx) -> Option<Assist> {
+    let impl_def = ctx.find_node_at_offset::<ast::ImplDef>()?;
+    // All the items of a trait must stay in its impl.
+    if impl_def.target_trait().is_some() {
+        return None;
+    }
+    let item_list = impl_def.item_list()?;
+    let items = item_list.impl_items().collect::<Vec<_>>();
+    let range = ctx.frange.range;
+    let selected = items
+        .iter()
+        .enumerate()
+        .filter(|(_, it)| {
+            let item_range = it.syntax().text_range();
+            if range.is_empty() {
+                item_range.contains_inclusive(range.start())
+            } else {
+                item_range.start() < range.end() && range.start() < item_range.end()
+            }
+        })
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    let (first, last) = (*selected.first()?, *selected.last()?);
+    if selected.len() == items.len() {
+        return None;
+    }
+
+    let moved_range = TextRange::from_to(
+        range_with_leading_comments(items[first].syntax()).start(),
+        items[last].syntax().text_range().end(),
+    );
+    let moved_text = ctx.source_text(moved_range);
+    // Removes the white space before the items, or after them when they are
+    // the first ones, so that the block starts with an item.
+    let removed_range = if first == 0 {
+        let next = items[last + 1].syntax();
+        TextRange::from_to(moved_range.start(), range_with_leading_comments(next).start())
+    } else {
+        let prev = items[first - 1].syntax();
+        TextRange::from_to(prev.text_range().end(), moved_range.end())
+    };
+
+    let indent = leading_indent(impl_def.syntax()).unwrap_or_default();
+    let header_start = impl_def
+        .syntax()
+        .children_with_tokens()
+        .find(|it| match it.kind() {
+            ATTR | COMMENT | WHITESPACE => false,
+            _ => true,
+        })?
+        .text_range()
+        .start();
+    let header =
+        ctx.source_text(TextRange::from_to(header_start, item_list.syntax().text_range().start()));
+    let attrs =
+        impl_def.attrs().map(|it| format!("{}\n{}", it.syntax(), indent)).collect::<String>();
+    let new_block = move |cfg: &str| {
+        format!(
+            "\n\n{indent}{}{}{} {{\n{indent}    {}\n{indent}}}",
+            attrs,
+            cfg,
+            header.trim_end(),
+            moved_text,
+            indent = indent
+        )
+    };
+    let insert_offset = impl_def.syntax().text_range().end();
+    // The start of the new block, once the items are removed.
+    let new_block_start = insert_offset - removed_range.len() + TextUnit::of_str("\n\n");
+
+    let mut group = ctx.add_assist_group("Move to new impl block");
+    group.add_assist(
+        AssistId("split_impl_block"),
+        AssistKind::RefactorExtract,
+        "Move to new impl block",
+        moved_range,
+        |edit| {
+            edit.delete(removed_range);
+            edit.insert(insert_offset, new_block(""));
+            edit.set_cursor(new_block_start + TextUnit::of_str(&indent));
+        },
+    );
+    group.add_assist(
+        AssistId("split_impl_block"),
+        AssistKind::RefactorExtract,
+        "Move to new impl block with #[cfg(...)]",
+        moved_range,
+        |edit| {
+            let cfg = format!("#[cfg()]\n{}", indent);
+            let text = new_block(&cfg);
+            let cfg_offset = text.find("#[cfg(").unwrap() + "#[cfg(".len();
+            edit.delete(removed_range);
+            edit.insert(insert_offset, text);
+            edit.set_cursor(
+                new_block_start - TextUnit::of_str("\n\n") + TextUnit::from_usize(cfg_offset),
+            );
+        },
+    );
+    group.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use test_utils::extract_offset;
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    #[test]
+    fn move_one_method() {
+        check_assist(
+            split_impl_block,
+            "
+struct S;
+
+impl<T> S<T> where T: Clone {
+    fn a<|>() {}
+
+    /// Does b.
+    fn b() {}
+}",
+            "
+struct S;
+
+impl<T> S<T> where T: Clone {
+    /// Does b.
+    fn b() {}
+}
+
+<|>impl<T> S<T> where T: Clone {
+    fn a() {}
+}",
+        );
+    }
+
+    #[test]
+    fn move_selected_methods() {
+        check_assist(
+            split_impl_block,
+            "
+mod m {
+    struct S;
+
+    #[allow(dead_code)]
+    impl S {
+        fn a() {}
+
+        fn <|>b() {}
+
+        fn c<|>() {}
+    }
+}",
+            "
+mod m {
+    struct S;
+
+    #[allow(dead_code)]
+    impl S {
+        fn a() {}
+    }
+
+    <|>#[allow(dead_code)]
+    impl S {
+        fn b() {}
+
+        fn c() {}
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn move_to_cfg_gated_block() {
+        let (offset, before) = extract_offset(
+            "
+struct S;
+
+impl S {
+    fn a() {}
+
+    fn b<|>() {}
+}",
+        );
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let entries = resolved_assists(&db, frange)
+            .into_iter()
+            .filter(|it| it.label.id.0 == "split_impl_block")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries.iter().map(|it| it.label.label.as_str()).collect::<Vec<_>>(),
+            vec!["Move to new impl block", "Move to new impl block with #[cfg(...)]"]
+        );
+        let after = "
+struct S;
+
+impl S {
+    fn a() {}
+}
+
+#[cfg()]
+impl S {
+    fn b() {}
+}";
+        assert_eq!(entries[1].action.edit().apply(&before), after);
+        assert_eq!(
+            entries[1].action.cursor_position,
+            Some(TextUnit::from_usize(after.find("#[cfg(").unwrap() + "#[cfg(".len()))
+        );
+    }
+
+    #[test]
+    fn split_not_applicable_to_trait_impl() {
+        check_assist_not_applicable(
+            split_impl_block,
+            "
+struct S;
+
+impl Clone for S {
+    fn clone<|>(&self) -> S { S }
+    fn clone_from(&mut self, other: &S) {}
+}",
+        );
+    }
+}
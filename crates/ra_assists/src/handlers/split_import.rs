@@ -1,15 +1,16 @@
 use std::iter::{once, successors};
 
 use ra_syntax::{
-    ast::{self, make},
-    AstNode, T,
+    ast::{self, make, PathSegmentKind},
+    AstNode, TextUnit, T,
 };
 
-use crate::{Assist, AssistCtx, AssistId};
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
 
 // Assist: split_import
 //
-// Wraps the tail of import into braces.
+// Wraps the tail of import into braces, after the `::` under the cursor, or
+// after the last `::` from elsewhere on the import.
 //
 // ```
 // use std::<|>collections::HashMap;
@@ -19,20 +20,137 @@ use crate::{Assist, AssistCtx, AssistId};
 // use std::{collections::HashMap};
 // ```
 pub(crate) fn split_import(ctx: AssistCtx) -> Option<Assist> {
-    let colon_colon = ctx.find_token_at_offset(T![::])?;
-    let path = ast::Path::cast(colon_colon.parent())?.qualifier()?;
-    let top_path = successors(Some(path.clone()), |it| it.parent_path()).last()?;
-
-    let use_tree = top_path.syntax().ancestors().find_map(ast::UseTree::cast)?;
+    let (use_tree, path, target, cursor) = match ctx.find_token_at_offset(T![::]) {
+        Some(colon_colon) => {
+            let path = ast::Path::cast(colon_colon.parent())?.qualifier()?;
+            let top_path = successors(Some(path.clone()), |it| it.parent_path()).last()?;
+            let use_tree = top_path.syntax().ancestors().find_map(ast::UseTree::cast)?;
+            (use_tree, path, colon_colon.text_range(), ctx.frange.range.start())
+        }
+        None => {
+            let use_tree = whole_use_tree(&ctx)?;
+            let path = use_tree.path()?.qualifier()?;
+            let range = use_tree.syntax().text_range();
+            (use_tree, path, range, ctx.frange.range.start().min(range.start()))
+        }
+    };
 
     let new_tree = split_use_tree_prefix(&use_tree, &path)?;
-    let cursor = ctx.frange.range.start();
 
-    ctx.add_assist(AssistId("split_import"), "Split import", |edit| {
-        edit.target(colon_colon.text_range());
-        edit.replace_ast(use_tree, new_tree);
-        edit.set_cursor(cursor);
-    })
+    ctx.add_assist(
+        AssistId("split_import"),
+        AssistKind::RefactorRewrite,
+        "Split import",
+        target,
+        |edit| {
+            edit.replace_ast(use_tree, new_tree);
+            edit.set_cursor(cursor);
+        },
+    )
+}
+
+// Assist: remove_unnecessary_braces
+//
+// Removes the braces around the only item of an import, or of all the
+// nested trees from elsewhere on the import.
+//
+// ```
+// use std::{collections::{Hash<|>Map}};
+// ```
+// ->
+// ```
+// use std::collections::HashMap;
+// ```
+pub(crate) fn remove_unnecessary_braces(ctx: AssistCtx) -> Option<Assist> {
+    let outermost = match ctx.find_node_at_offset::<ast::UseTree>() {
+        Some(use_tree) => use_tree
+            .syntax()
+            .ancestors()
+            .filter_map(ast::UseTree::cast)
+            .filter(has_redundant_braces)
+            .last()?,
+        None => whole_use_tree(&ctx).filter(has_redundant_braces)?,
+    };
+    let text = flattened(&outermost);
+
+    let range = outermost.syntax().text_range();
+    ctx.add_assist(
+        AssistId("remove_unnecessary_braces"),
+        AssistKind::RefactorRewrite,
+        "Remove unnecessary braces",
+        range,
+        |edit| {
+            edit.set_cursor(range.start());
+            edit.replace(range, text);
+        },
+    )
+}
+
+// Assist: add_braces_to_use
+//
+// Wraps the last segment of an import into braces, to add more items to it.
+//
+// ```
+// use std::collections::Hash<|>Map;
+// ```
+// ->
+// ```
+// use std::collections::{HashMap};
+// ```
+pub(crate) fn add_braces_to_use(ctx: AssistCtx) -> Option<Assist> {
+    let use_tree = ctx.find_node_at_offset::<ast::UseTree>()?;
+    if use_tree.use_tree_list().is_some() || use_tree.has_star() {
+        return None;
+    }
+    let path = use_tree.path()?;
+    path.qualifier()?;
+    let segment = path.segment()?;
+
+    ctx.add_assist(
+        AssistId("add_braces_to_use"),
+        AssistKind::RefactorRewrite,
+        "Add braces",
+        use_tree.syntax().text_range(),
+        |edit| {
+            let end = use_tree.syntax().text_range().end();
+            edit.insert(segment.syntax().text_range().start(), "{");
+            edit.insert(end, "}");
+            edit.set_cursor(end + TextUnit::of_char('{'));
+        },
+    )
+}
+
+/// The tree of the `use` item under the cursor, for the assists which have an
+/// unambiguous meaning for the whole import, when the cursor isn't on a
+/// specific part of it, like on the `use` keyword or the semicolon.
+fn whole_use_tree(ctx: &AssistCtx) -> Option<ast::UseTree> {
+    ctx.find_node_at_offset::<ast::UseItem>()?.use_tree()
+}
+
+/// `foo::{Bar}`, the prefix is required so that `use ::{Bar}` stays intact.
+fn has_redundant_braces(use_tree: &ast::UseTree) -> bool {
+    use_tree.path().is_some()
+        && use_tree.use_tree_list().map_or(false, |it| it.use_trees().count() == 1)
+}
+
+fn flattened(use_tree: &ast::UseTree) -> String {
+    let (path, child) = match (use_tree.path(), use_tree.use_tree_list()) {
+        (Some(path), Some(list)) if has_redundant_braces(use_tree) => {
+            (path, list.use_trees().next().unwrap())
+        }
+        _ => return use_tree.syntax().to_string(),
+    };
+    let is_self = child.use_tree_list().is_none()
+        && child.path().map_or(false, |it| {
+            it.qualifier().is_none()
+                && it.segment().and_then(|it| it.kind()) == Some(PathSegmentKind::SelfKw)
+        });
+    if is_self {
+        let alias = child.alias().map(|it| format!(" {}", it.syntax())).unwrap_or_default();
+        format!("{}{}", path.syntax(), alias)
+    } else {
+        format!("{}::{}", path.syntax(), flattened(&child))
+    }
 }
 
 fn split_use_tree_prefix(use_tree: &ast::UseTree, prefix: &ast::Path) -> Option<ast::UseTree> {
@@ -54,7 +172,7 @@ fn split_path_prefix(prefix: &ast::Path) -> Option<ast::Path> {
 
 #[cfg(test)]
 mod tests {
-    use crate::helpers::{check_assist, check_assist_target};
+    use crate::helpers::{check_assist, check_assist_not_applicable, check_assist_target};
 
     use super::*;
 
@@ -80,4 +198,71 @@ mod tests {
     fn split_import_target() {
         check_assist_target(split_import, "use crate::<|>db::{RootDatabase, FileSymbol}", "::");
     }
+
+    #[test]
+    fn remove_braces_around_single_item() {
+        check_assist(
+            remove_unnecessary_braces,
+            "#[cfg(test)]\npub(crate) use crate::db::{Root<|>Database};",
+            "#[cfg(test)]\npub(crate) use <|>crate::db::RootDatabase;",
+        )
+    }
+
+    #[test]
+    fn remove_nested_unnecessary_braces() {
+        check_assist(
+            remove_unnecessary_braces,
+            "use std::{collections::{<|>HashMap as Map}};",
+            "use <|>std::collections::HashMap as Map;",
+        );
+        check_assist(
+            remove_unnecessary_braces,
+            "use std::{fmt, collections::{<|>HashMap}};",
+            "use std::{fmt, <|>collections::HashMap};",
+        );
+    }
+
+    #[test]
+    fn remove_braces_around_self() {
+        check_assist(remove_unnecessary_braces, "use std::{fmt::{se<|>lf}};", "use <|>std::fmt;")
+    }
+
+    #[test]
+    fn add_braces_to_use_cursor() {
+        check_assist(
+            add_braces_to_use,
+            "use std::collections::Hash<|>Map as Map;",
+            "use std::collections::{HashMap as Map<|>};",
+        );
+        check_assist_not_applicable(add_braces_to_use, "use Hash<|>Map;");
+    }
+
+    #[test]
+    fn split_import_from_elsewhere_splits_at_last_segment() {
+        check_assist(
+            split_import,
+            "pub <|>use crate::db::RootDatabase;",
+            "pub <|>use crate::db::{RootDatabase};",
+        );
+        check_assist(
+            split_import,
+            "use crate::db::{RootDatabase, FileSymbol}<|>;",
+            "use <|>crate::{db::{RootDatabase, FileSymbol}};",
+        );
+        check_assist(split_import, "use crate::a:<|>:b::C;", "use crate::a:<|>:{b::C};");
+        check_assist_not_applicable(split_import, "<|>use std;");
+    }
+
+    #[test]
+    fn remove_braces_from_use_keyword() {
+        check_assist(
+            remove_unnecessary_braces,
+            "<|>use std::{collections::{HashMap}};",
+            "use <|>std::collections::HashMap;",
+        );
+        check_assist_not_applicable(
+            remove_unnecessary_braces,
+            "<|>use std::{fmt, collections::{HashMap}};",
+        );
+    }
 }
@@ -0,0 +1,188 @@
+use hir::{EnumVariant, ModuleDef, PathResolution, Semantics, VariantDef};
+use ra_ide_db::RootDatabase;
+use ra_syntax::{
+    ast::{self, AstNode},
+    Direction, SyntaxKind, TextUnit, T,
+};
+
+use crate::{utils::range_with_leading_comments, Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: swap_match_arms
+//
+// Swaps a match arm with the next one, when their patterns don't overlap.
+//
+// ```
+// enum Action { Move, Stop }
+//
+// fn handle(action: Action) {
+//     match action {
+//         <|>Action::Move => {}
+//         Action::Stop => {}
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Action { Move, Stop }
+//
+// fn handle(action: Action) {
+//     match action {
+//         Action::Stop => {}
+//         Action::Move => {}
+//     }
+// }
+// ```
+pub(crate) fn swap_match_arms(ctx: AssistCtx) -> Option<Assist> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    if let Some(expr) = arm.expr() {
+        if expr.syntax().text_range().contains(ctx.frange.range.start()) {
+            return None;
+        }
+    }
+    let next = arm.syntax().next_sibling().and_then(ast::MatchArm::cast)?;
+    let heads = pattern_heads(&ctx.sema, &arm.pat()?)?;
+    let next_heads = pattern_heads(&ctx.sema, &next.pat()?)?;
+    if heads.iter().any(|it| next_heads.contains(it)) {
+        return None;
+    }
+
+    let first_range = range_with_leading_comments(arm.syntax());
+    let second_range = range_with_leading_comments(next.syntax());
+    let first_text = moved_text(ctx.source_text(second_range), &next, &arm);
+    let second_text = moved_text(ctx.source_text(first_range), &arm, &next);
+
+    ctx.add_assist(
+        AssistId("swap_match_arms"),
+        AssistKind::RefactorRewrite,
+        "Swap with the next arm",
+        arm.syntax().text_range(),
+        |edit| {
+            let cursor = second_range.start() + TextUnit::of_str(&first_text) - first_range.len();
+            edit.replace(first_range, first_text);
+            edit.replace(second_range, second_text);
+            edit.set_cursor(cursor);
+        },
+    )
+}
+
+/// What a pattern can match, when it matches only literals or variants.
+#[derive(PartialEq, Eq)]
+enum Head {
+    Literal(String),
+    Variant(EnumVariant),
+}
+
+fn pattern_heads(sema: &Semantics<RootDatabase>, pat: &ast::Pat) -> Option<Vec<Head>> {
+    let head = match pat {
+        ast::Pat::OrPat(it) => {
+            let mut res = Vec::new();
+            for pat in it.pats() {
+                res.extend(pattern_heads(sema, &pat)?);
+            }
+            return Some(res);
+        }
+        ast::Pat::ParenPat(it) => return pattern_heads(sema, &it.pat()?),
+        ast::Pat::LiteralPat(it) => Head::Literal(it.literal()?.syntax().to_string()),
+        ast::Pat::PathPat(it) => Head::Variant(variant(sema, &it.path()?)?),
+        ast::Pat::TupleStructPat(it) => Head::Variant(variant(sema, &it.path()?)?),
+        ast::Pat::RecordPat(it) => match sema.resolve_record_pattern(it)? {
+            VariantDef::EnumVariant(it) => Head::Variant(it),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(vec![head])
+}
+
+fn variant(sema: &Semantics<RootDatabase>, path: &ast::Path) -> Option<EnumVariant> {
+    match sema.resolve_path(path)? {
+        PathResolution::Def(ModuleDef::EnumVariant(it)) => Some(it),
+        _ => None,
+    }
+}
+
+fn has_comma(arm: &ast::MatchArm) -> bool {
+    arm.syntax()
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .find(|it| it.kind() != SyntaxKind::WHITESPACE && it.kind() != SyntaxKind::COMMENT)
+        .map_or(false, |it| it.kind() == T![,])
+}
+
+/// The text of `arm` taking the place of `other`, with a comma if it needs
+/// one there.
+fn moved_text(text: String, arm: &ast::MatchArm, other: &ast::MatchArm) -> String {
+    let is_block = match arm.expr() {
+        Some(ast::Expr::BlockExpr(_)) => true,
+        _ => false,
+    };
+    if is_block || has_comma(other) || other.syntax().next_sibling().is_none() {
+        text
+    } else {
+        format!("{},", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    const ENUM: &str = "enum E { A, B(u32), C { x: u32 } }\n";
+
+    #[test]
+    fn swap_variant_arms() {
+        check_assist(
+            swap_match_arms,
+            &format!(
+                "{}fn f(e: E) -> u32 {{ match e {{ <|>E::A => 0, E::B(x) => {{ x }} E::C {{ x }} => x }} }}",
+                ENUM
+            ),
+            &format!(
+                "{}fn f(e: E) -> u32 {{ match e {{ E::B(x) => {{ x }}, <|>E::A => 0, E::C {{ x }} => x }} }}",
+                ENUM
+            ),
+        );
+    }
+
+    #[test]
+    fn swap_arms_keeps_comments() {
+        check_assist(
+            swap_match_arms,
+            "
+fn f(n: u32) -> u32 {
+    match n {
+        // zero
+        0<|> | 1 => 1,
+        // two
+        2 => 4
+    }
+}",
+            "
+fn f(n: u32) -> u32 {
+    match n {
+        // two
+        2 => 4,
+        <|>// zero
+        0 | 1 => 1
+    }
+}",
+        );
+    }
+
+    #[test]
+    fn swap_arms_not_applicable_with_wildcard() {
+        check_assist_not_applicable(
+            swap_match_arms,
+            &format!("{}fn f(e: E) -> u32 {{ match e {{ <|>E::A => 0, _ => 1 }} }}", ENUM),
+        );
+    }
+
+    #[test]
+    fn swap_arms_not_applicable_with_binding() {
+        check_assist_not_applicable(
+            swap_match_arms,
+            "fn f(n: u32) -> u32 { match n { <|>0 => 0, m => m } }",
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use ra_syntax::{
+    ast::{self, AstNode, AttrsOwner},
+    TextUnit,
+};
+
+use crate::{
+    utils::{attribute_removal_range, insert_attribute},
+    Assist, AssistCtx, AssistId, AssistKind,
+};
+
+// Assist: toggle_ignore
+//
+// Adds or removes the `#[ignore]` attribute of a test function.
+//
+// ```
+// #[test]
+// fn <|>slow_test() {}
+// ```
+// ->
+// ```
+// #[test]
+// #[ignore]
+// fn slow_test() {}
+// ```
+pub(crate) fn toggle_ignore(ctx: AssistCtx) -> Option<Assist> {
+    let fn_def = ctx.find_node_at_offset::<ast::FnDef>()?;
+    if let Some(body) = fn_def.body() {
+        if body.syntax().text_range().start() < ctx.frange.range.start() {
+            return None;
+        }
+    }
+    let attrs = fn_def.attrs().collect::<Vec<_>>();
+    let test_attr = attrs.iter().find(|it| it.as_simple_atom().map_or(false, |it| it == "test"))?;
+    let named =
+        |name: &str| attrs.iter().find(|it| it.simple_name().map_or(false, |it| it == name));
+    let ignore_attr = named("ignore");
+    let has_should_panic = named("should_panic").is_some();
+
+    let mut group = ctx.add_assist_group("Test attributes");
+    match ignore_attr {
+        Some(ignore_attr) => group.add_assist(
+            AssistId("toggle_ignore"),
+            AssistKind::RefactorRewrite,
+            "Stop ignoring this test",
+            ignore_attr.syntax().text_range(),
+            |edit| {
+                edit.delete(attribute_removal_range(ignore_attr));
+            },
+        ),
+        None => group.add_assist(
+            AssistId("toggle_ignore"),
+            AssistKind::RefactorRewrite,
+            "Ignore this test",
+            test_attr.syntax().text_range(),
+            |edit| {
+                let (offset, text) =
+                    insert_attribute(fn_def.syntax(), Some(test_attr), "#[ignore]");
+                edit.insert(offset, text);
+            },
+        ),
+    }
+    if !has_should_panic {
+        group.add_assist(
+            AssistId("toggle_ignore"),
+            AssistKind::RefactorRewrite,
+            "Add `#[should_panic]`",
+            test_attr.syntax().text_range(),
+            |edit| {
+                let after = ignore_attr.unwrap_or(test_attr);
+                let (offset, text) = insert_attribute(
+                    fn_def.syntax(),
+                    Some(after),
+                    "#[should_panic(expected = \"\")]",
+                );
+                edit.set_cursor(offset + TextUnit::of_str(&text) - TextUnit::of_str("\")]"));
+                edit.insert(offset, text);
+            },
+        );
+    }
+    group.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_db::FileRange;
+    use ra_syntax::TextRange;
+    use test_utils::{add_cursor, assert_eq_text, extract_offset};
+
+    use super::*;
+    use crate::{
+        helpers::{self, check_assist, check_assist_not_applicable},
+        resolved_assists,
+    };
+
+    #[test]
+    fn add_ignore() {
+        check_assist(
+            toggle_ignore,
+            r#"
+mod tests {
+    #[test]
+    /// Takes a while.
+    fn <|>slow() {}
+}"#,
+            r#"
+mod tests {
+    #[test]
+    #[ignore]
+    /// Takes a while.
+    fn <|>slow() {}
+}"#,
+        );
+    }
+
+    #[test]
+    fn remove_ignore() {
+        check_assist(
+            toggle_ignore,
+            r#"
+#[test]
+#[ignore = "flaky"]
+#[cfg(unix)]
+fn <|>flaky() {}"#,
+            r#"
+#[test]
+#[cfg(unix)]
+fn <|>flaky() {}"#,
+        );
+    }
+
+    #[test]
+    fn add_should_panic() {
+        let (offset, before) = extract_offset(
+            r#"
+#[test]
+#[ignore]
+fn <|>panics() {}"#,
+        );
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let action = resolved_assists(&db, frange)
+            .into_iter()
+            .find(|it| it.label.label == "Add `#[should_panic]`")
+            .unwrap()
+            .action;
+        let actual = add_cursor(&action.edit().apply(&before), action.cursor_position.unwrap());
+        assert_eq_text!(
+            r#"
+#[test]
+#[ignore]
+#[should_panic(expected = "<|>")]
+fn panics() {}"#,
+            &actual
+        );
+    }
+
+    #[test]
+    fn toggle_ignore_not_applicable_to_non_test_fn() {
+        check_assist_not_applicable(toggle_ignore, "#[inline] fn <|>f() {}");
+        check_assist_not_applicable(toggle_ignore, "#[test] fn f() { <|>foo() }");
+    }
+}
@@ -0,0 +1,202 @@
+use hir::{PathResolution, Semantics};
+use ra_ide_db::RootDatabase;
+use ra_syntax::{
+    ast::{self, AstNode, NameOwner},
+    SyntaxNode, TextRange, TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: inline_use_alias
+//
+// Removes the alias of an import, renaming its uses in the file.
+//
+// ```
+// mod fmt { pub struct Formatter; }
+// use fmt::Formatter as <|>F;
+//
+// fn format(f: &F) {}
+// ```
+// ->
+// ```
+// mod fmt { pub struct Formatter; }
+// use fmt::Formatter<|>;
+//
+// fn format(f: &Formatter) {}
+// ```
+pub(crate) fn inline_use_alias(ctx: AssistCtx) -> Option<Assist> {
+    let tree = ctx.find_node_at_offset::<ast::UseTree>()?;
+    let alias = tree.alias()?;
+    let alias_name = alias.name()?;
+    let path = tree.path()?;
+    let name = imported_name(&path)?;
+    let target = ctx.sema.resolve_path(&path)?;
+    if is_in_scope(&ctx, tree.syntax(), &name) {
+        return None;
+    }
+
+    let uses = uses_of(&ctx.sema, tree.syntax(), &alias_name.text(), &target);
+    let alias_range =
+        TextRange::from_to(path.syntax().text_range().end(), alias.syntax().text_range().end());
+    ctx.add_assist(
+        AssistId("inline_use_alias"),
+        AssistKind::RefactorInline,
+        "Inline alias",
+        alias.syntax().text_range(),
+        |edit| {
+            let cursor = shifted(alias_range.start(), &uses, &alias_name.text(), &name);
+            edit.delete(alias_range);
+            for range in uses {
+                edit.replace(range, name.clone());
+            }
+            edit.set_cursor(cursor);
+        },
+    )
+}
+
+// Assist: add_use_alias
+//
+// Adds an alias to an import, renaming its uses in the file.
+//
+// ```
+// mod fmt { pub struct Formatter; }
+// use fmt::Format<|>ter;
+//
+// fn format(f: &Formatter) {}
+// ```
+// ->
+// ```
+// mod fmt { pub struct Formatter; }
+// use fmt::Formatter as <|>Formatter2;
+//
+// fn format(f: &Formatter2) {}
+// ```
+pub(crate) fn add_use_alias(ctx: AssistCtx) -> Option<Assist> {
+    let tree = ctx.find_node_at_offset::<ast::UseTree>()?;
+    if tree.alias().is_some() || tree.use_tree_list().is_some() {
+        return None;
+    }
+    let path = tree.path()?;
+    let name = imported_name(&path)?;
+    let target = ctx.sema.resolve_path(&path)?;
+    let alias = (2..)
+        .map(|idx| format!("{}{}", name, idx))
+        .find(|it| !is_in_scope(&ctx, tree.syntax(), it))?;
+
+    let uses = uses_of(&ctx.sema, tree.syntax(), &name, &target);
+    let offset = path.syntax().text_range().end();
+    ctx.add_assist(
+        AssistId("add_use_alias"),
+        AssistKind::RefactorRewrite,
+        "Add alias",
+        tree.syntax().text_range(),
+        |edit| {
+            let cursor = shifted(offset, &uses, &name, &alias) + TextUnit::of_str(" as ");
+            edit.insert(offset, format!(" as {}", alias));
+            for range in uses {
+                edit.replace(range, alias.clone());
+            }
+            edit.set_cursor(cursor);
+        },
+    )
+}
+
+/// The name a use tree imports, unless it is `self` or the like.
+fn imported_name(path: &ast::Path) -> Option<String> {
+    let name_ref = path.segment()?.name_ref()?;
+    Some(name_ref.text().to_string())
+}
+
+fn is_in_scope(ctx: &AssistCtx, node: &SyntaxNode, name: &str) -> bool {
+    let mut res = false;
+    ctx.sema.scope(node).process_all_names(&mut |it, _| res |= it.to_string() == name);
+    res
+}
+
+/// The ranges of the paths in the file which refer to `target` by `name`,
+/// outside of imports.
+fn uses_of(
+    sema: &Semantics<RootDatabase>,
+    node: &SyntaxNode,
+    name: &str,
+    target: &PathResolution,
+) -> Vec<TextRange> {
+    let file = node.ancestors().last().unwrap();
+    file.descendants()
+        .filter_map(ast::Path::cast)
+        .filter(|path| path.qualifier().is_none())
+        .filter(|path| path.syntax().ancestors().all(|it| !ast::UseItem::can_cast(it.kind())))
+        .filter_map(|path| {
+            let name_ref = path.segment()?.name_ref()?;
+            if name_ref.text() != name || sema.resolve_path(&path).as_ref() != Some(target) {
+                return None;
+            }
+            Some(name_ref.syntax().text_range())
+        })
+        .collect()
+}
+
+/// Where `offset` ends up once the `uses` of `old` are renamed to `new`.
+fn shifted(offset: TextUnit, uses: &[TextRange], old: &str, new: &str) -> TextUnit {
+    let before = uses.iter().filter(|it| it.end() <= offset).count();
+    offset + TextUnit::from_usize(before * new.len()) - TextUnit::from_usize(before * old.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn inline_alias_renames_uses() {
+        check_assist(
+            inline_use_alias,
+            "
+mod m { pub struct Bar; impl Bar { pub fn new() -> Bar { Bar } } }
+use m::Bar as B<|>az;
+
+fn f(x: Baz) -> Baz {
+    let _: Baz = Baz::new();
+    x
+}",
+            "
+mod m { pub struct Bar; impl Bar { pub fn new() -> Bar { Bar } } }
+use m::Bar<|>;
+
+fn f(x: Bar) -> Bar {
+    let _: Bar = Bar::new();
+    x
+}",
+        );
+    }
+
+    #[test]
+    fn inline_alias_not_applicable_when_name_is_taken() {
+        check_assist_not_applicable(
+            inline_use_alias,
+            "
+mod m { pub struct Bar; }
+use m::Bar as B<|>az;
+struct Bar;
+
+fn f(x: Baz) {}",
+        );
+    }
+
+    #[test]
+    fn add_alias_renames_uses() {
+        check_assist(
+            add_use_alias,
+            "
+mod m { pub struct Bar; pub struct Bar2; }
+use m::{B<|>ar, Bar2};
+
+fn f(x: Bar) -> Bar { x }",
+            "
+mod m { pub struct Bar; pub struct Bar2; }
+use m::{Bar as <|>Bar3, Bar2};
+
+fn f(x: Bar3) -> Bar3 { x }",
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use hir::VariantDef;
+use ra_ide_db::{
+    defs::{classify_name, Definition},
+    search::SearchScope,
+};
+use ra_syntax::{
+    algo::find_node_at_offset,
+    ast::{self, AstNode, NameOwner, TypeAscriptionOwner},
+    SyntaxNode, TextRange, TextUnit,
+};
+
+use crate::{Assist, AssistCtx, AssistId, AssistKind};
+
+// Assist: wrap_field_in_option
+//
+// Changes the type of a field to an `Option`, wrapping the values of the
+// field in `Some` in the struct literals of the file. Reads of the field
+// become `.unwrap()` calls, which compile but should usually be revisited.
+//
+// ```
+// struct Config { name<|>: String }
+//
+// fn make(name: String) -> Config {
+//     Config { name }
+// }
+//
+// fn name(config: Config) -> String {
+//     config.name
+// }
+// ```
+// ->
+// ```
+// struct Config { name: Option<String> }
+//
+// fn make(name: String) -> Config {
+//     Config { name: Some(name) }
+// }
+//
+// fn name(config: Config) -> String {
+//     config.name.unwrap()
+// }
+// ```
+pub(crate) fn wrap_field_in_option(ctx: AssistCtx) -> Option<Assist> {
+    let field_def = ctx.find_node_at_offset::<ast::RecordFieldDef>()?;
+    let name = field_def.name()?;
+    let type_ref = field_def.ascribed_type()?;
+    if ctx.frange.range.start() > type_ref.syntax().text_range().start() {
+        return None;
+    }
+    let def = classify_name(&ctx.sema, &name)?.definition();
+    let field = match &def {
+        Definition::StructField(it) => *it,
+        _ => return None,
+    };
+    let variant = field.parent_def(ctx.db);
+    let root = field_def.syntax().ancestors().last()?;
+    if is_destructured(&ctx, &root, variant, name.text()) {
+        return None;
+    }
+
+    // Shorthand fields of literals are references to the local, so the
+    // literals are found through the struct.
+    let mut edits = literal_edits(&ctx, &root, variant, name.text());
+    for usage in def.find_usages(ctx.db, Some(SearchScope::single_file(ctx.frange.file_id))) {
+        let name_ref = find_node_at_offset::<ast::NameRef>(&root, usage.file_range.range.start())?;
+        let parent = name_ref.syntax().parent()?;
+        if ast::RecordField::can_cast(parent.kind()) {
+            continue;
+        }
+        edits.push(read_edit(&ast::FieldExpr::cast(parent)?)?);
+    }
+
+    let type_range = type_ref.syntax().text_range();
+    ctx.add_assist(
+        AssistId("wrap_field_in_option"),
+        AssistKind::RefactorRewrite,
+        "Wrap field type in Option",
+        type_range,
+        |edit| {
+            edit.replace(type_range, format!("Option<{}>", type_ref.syntax()));
+            for (range, text) in edits {
+                edit.replace(range, text);
+            }
+            edit.set_cursor(type_range.start());
+        },
+    )
+}
+
+/// Whether a record pattern in `root` binds the field `name` of `variant`.
+fn is_destructured(ctx: &AssistCtx, root: &SyntaxNode, variant: VariantDef, name: &str) -> bool {
+    root.descendants().filter_map(ast::RecordPat::cast).any(|pat| {
+        if ctx.sema.resolve_record_pattern(&pat) != Some(variant) {
+            return false;
+        }
+        let field_pats = match pat.record_field_pat_list() {
+            Some(it) => it,
+            None => return false,
+        };
+        let named = |it: Option<ast::Name>| it.map_or(false, |it| it.text().as_str() == name);
+        field_pats.record_field_pats().any(|it| named(it.name()))
+            || field_pats.bind_pats().any(|it| named(it.name()))
+    })
+}
+
+/// Wraps the values given to the field `name` in the literals of `variant`
+/// in `Some`.
+fn literal_edits(
+    ctx: &AssistCtx,
+    root: &SyntaxNode,
+    variant: VariantDef,
+    name: &str,
+) -> Vec<(TextRange, String)> {
+    root.descendants()
+        .filter_map(ast::RecordLit::cast)
+        .filter(|it| ctx.sema.resolve_record_literal(it) == Some(variant))
+        .filter_map(|it| it.record_field_list())
+        .flat_map(|it| it.fields())
+        .filter_map(|record_field| {
+            let name_ref = record_field.name_ref()?;
+            if name_ref.text().as_str() != name {
+                return None;
+            }
+            Some(match record_field.expr() {
+                Some(expr) => (expr.syntax().text_range(), format!("Some({})", expr.syntax())),
+                // `S { name }` is shorthand for `S { name: name }`.
+                None => (record_field.syntax().text_range(), format!("{}: Some({})", name, name)),
+            })
+        })
+        .collect()
+}
+
+/// Unwraps a read of the field, or wraps the value assigned to it in `Some`.
+fn read_edit(field_expr: &ast::FieldExpr) -> Option<(TextRange, String)> {
+    let assignment = field_expr
+        .syntax()
+        .parent()
+        .and_then(ast::BinExpr::cast)
+        .filter(|it| it.op_kind() == Some(ast::BinOp::Assignment))
+        .filter(|it| it.lhs().map_or(false, |lhs| lhs.syntax() == field_expr.syntax()));
+    if let Some(assignment) = assignment {
+        let rhs = assignment.rhs()?;
+        return Some((rhs.syntax().text_range(), format!("Some({})", rhs.syntax())));
+    }
+    let end = field_expr.syntax().text_range().end();
+    Some((TextRange::offset_len(end, TextUnit::from(0)), ".unwrap()".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn wrap_field_updates_literals_and_reads() {
+        check_assist(
+            wrap_field_in_option,
+            "
+struct Config { verbose: bool, level<|>: u32 }
+
+fn make() -> Config {
+    Config { verbose: true, level: 1 + 2 }
+}
+
+fn update(config: &mut Config) {
+    let doubled = config.level * 2;
+    config.level = doubled;
+    if config.level > 3 {}
+}",
+            "
+struct Config { verbose: bool, level: <|>Option<u32> }
+
+fn make() -> Config {
+    Config { verbose: true, level: Some(1 + 2) }
+}
+
+fn update(config: &mut Config) {
+    let doubled = config.level.unwrap() * 2;
+    config.level = Some(doubled);
+    if config.level.unwrap() > 3 {}
+}",
+        );
+    }
+
+    #[test]
+    fn wrap_field_of_generic_struct() {
+        check_assist(
+            wrap_field_in_option,
+            "
+struct Wrapper<T> { value<|>: T }
+
+fn wrap<T>(value: T) -> Wrapper<T> {
+    Wrapper { value }
+}",
+            "
+struct Wrapper<T> { value: <|>Option<T> }
+
+fn wrap<T>(value: T) -> Wrapper<T> {
+    Wrapper { value: Some(value) }
+}",
+        );
+    }
+
+    #[test]
+    fn wrap_field_not_applicable_when_destructured() {
+        check_assist_not_applicable(
+            wrap_field_in_option,
+            "
+struct Config { level<|>: u32 }
+
+fn level(config: Config) -> u32 {
+    let Config { level } = config;
+    level
+}",
+        );
+    }
+}
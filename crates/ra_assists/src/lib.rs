@@ -5,24 +5,36 @@
 //! certain context. For example, if the cursor is over `,`, a "swap `,`" assist
 //! becomes available.
 
+mod assist_config;
 mod assist_ctx;
 mod marks;
+mod registry;
 #[cfg(test)]
 mod doc_tests;
+#[cfg(test)]
+mod corpus_tests;
+pub mod diff;
 pub mod utils;
 pub mod ast_transform;
 
-use ra_db::FileRange;
+use ra_db::{FileId, FilePosition, FileRange, RelativePathBuf, SourceRootId};
 use ra_ide_db::RootDatabase;
-use ra_syntax::{TextRange, TextUnit};
-use ra_text_edit::TextEdit;
+use ra_syntax::{AstNode, SyntaxElement, TextRange, TextUnit};
+use ra_text_edit::{AtomTextEdit, TextEdit, TextEditBuilder};
 
-pub(crate) use crate::assist_ctx::{Assist, AssistCtx, AssistHandler};
+pub use crate::{
+    assist_config::AssistConfig,
+    assist_ctx::{ActionBuilder, Assist, AssistCtx, AssistGroup, AssistHandler},
+    registry::AssistRegistry,
+};
 use hir::Semantics;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::assist_ctx::AssistInfo;
 
 /// Unique identifier of the assist, should not be shown to the user
 /// directly.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AssistId(pub &'static str);
 
 #[derive(Debug, Clone)]
@@ -30,25 +42,115 @@ pub struct AssistLabel {
     /// Short description of the assist, as shown in the UI.
     pub label: String,
     pub id: AssistId,
+    pub kind: AssistKind,
+    /// The range the assist applies to.
+    ///
+    /// Target ranges are used to sort assists: the smaller the target range,
+    /// the more specific assist is, and so it should be sorted first.
+    pub target: TextRange,
+    /// Another range affected by the assist, away from the target, like the
+    /// `use` item an import is added to.
+    pub secondary_target: Option<TextRange>,
 }
 
 #[derive(Clone, Debug)]
 pub struct GroupLabel(pub String);
 
 impl AssistLabel {
-    pub(crate) fn new(label: String, id: AssistId) -> AssistLabel {
+    pub(crate) fn new(
+        label: String,
+        id: AssistId,
+        kind: AssistKind,
+        target: TextRange,
+    ) -> AssistLabel {
         // FIXME: make fields private, so that this invariant can't be broken
         assert!(label.starts_with(|c: char| c.is_uppercase()));
-        AssistLabel { label, id }
+        AssistLabel { label, id, kind, target, secondary_target: None }
     }
 }
 
+/// The change made by an assist.
+///
+/// Most assists only edit the file they are applied to, see `single_file`.
 #[derive(Debug, Clone)]
 pub struct AssistAction {
-    pub edit: TextEdit,
+    /// The edits of the files, starting with the one of the file of the assist.
+    pub source_file_edits: Vec<(FileId, TextEdit)>,
+    /// A file to create, like the module file of an unresolved `mod foo;`.
+    pub file_creation: Option<FileCreation>,
+    /// The position of the cursor in the file of the assist after the edit.
+    /// Assists with tabstops put it at the start of the first one when they
+    /// don't set it.
     pub cursor_position: Option<TextUnit>,
-    // FIXME: This belongs to `AssistLabel`
-    pub target: Option<TextRange>,
+    pub snippet: Option<Snippet>,
+}
+
+impl AssistAction {
+    /// An action which only edits the file `file_id` of the assist.
+    pub fn single_file(
+        file_id: FileId,
+        edit: TextEdit,
+        cursor_position: Option<TextUnit>,
+    ) -> AssistAction {
+        AssistAction {
+            source_file_edits: vec![(file_id, edit)],
+            file_creation: None,
+            cursor_position,
+            snippet: None,
+        }
+    }
+
+    /// The edit of the file of the assist.
+    pub fn edit(&self) -> &TextEdit {
+        &self.source_file_edits[0].1
+    }
+
+    /// The edit of the file `file_id`, if the action changes it.
+    pub fn edit_of(&self, file_id: FileId) -> Option<&TextEdit> {
+        self.source_file_edits.iter().find(|(it, _)| *it == file_id).map(|(_, edit)| edit)
+    }
+}
+
+/// A new, empty file, at `path` in the `source_root`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCreation {
+    pub source_root: SourceRootId,
+    pub path: RelativePathBuf,
+}
+
+/// The ranges of the edited text the user can tab through after the assist is
+/// applied, like the placeholder bodies of generated methods. In LSP snippet
+/// syntax, they are `$1`, `$2`... and the final cursor is `$0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub tabstops: Vec<TextRange>,
+    pub final_cursor: Option<TextUnit>,
+}
+
+/// The kind of an assist, after the kinds of LSP code actions, by which clients
+/// group them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssistKind {
+    /// Fixes a diagnostic, see `assists_for_diagnostic`, or an error the
+    /// compiler would report, like a missing import.
+    QuickFix,
+    /// Any other refactoring, like generating an impl.
+    Refactor,
+    /// Moves code into a new item, like a variable or a function.
+    RefactorExtract,
+    /// Replaces an item by its definition where it's used.
+    RefactorInline,
+    /// Rewrites code in place.
+    RefactorRewrite,
+}
+
+impl AssistKind {
+    /// Whether the assists of this kind are requested with the `filter` kind,
+    /// which is either the same kind or a more general one: `Refactor` contains
+    /// `RefactorExtract`.
+    pub fn is_within(self, filter: AssistKind) -> bool {
+        self == filter || (filter == AssistKind::Refactor && self != AssistKind::QuickFix)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -58,45 +160,299 @@ pub struct ResolvedAssist {
     pub action: AssistAction,
 }
 
+impl ResolvedAssist {
+    /// Applies the edit of the assist to the `original` text of the file, and
+    /// returns the new text with the cursor position the assist sets, if any.
+    pub fn apply(&self, original: &str) -> (String, Option<TextUnit>) {
+        (self.action.edit().apply(original), self.action.cursor_position)
+    }
+
+    /// Like `apply`, but when the assist doesn't set the cursor position, the
+    /// `cursor` of the original text is mapped through the edit. There's no
+    /// position when the edit replaces the text around the cursor.
+    pub fn apply_with_cursor(
+        &self,
+        original: &str,
+        cursor: TextUnit,
+    ) -> (String, Option<TextUnit>) {
+        let (text, position) = self.apply(original);
+        (text, position.or_else(|| self.action.edit().apply_to_offset(cursor)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnresolvedAssist {
+    pub label: AssistLabel,
+    pub group_label: Option<GroupLabel>,
+}
+
 /// Return all the assists applicable at the given position.
 ///
 /// Assists are returned in the "unresolved" state, that is only labels are
-/// returned, without actual edits.
-pub fn unresolved_assists(db: &RootDatabase, range: FileRange) -> Vec<AssistLabel> {
+/// returned, without actual edits. Members of a group are contiguous and in
+/// the same order as in `resolved_assists`.
+pub fn unresolved_assists(db: &RootDatabase, range: FileRange) -> Vec<UnresolvedAssist> {
+    unresolved_assists_with(&AssistRegistry::builtin(), db, range)
+}
+
+/// Like `unresolved_assists`, but only with the assists enabled by `config`.
+pub fn unresolved_assists_with_config(
+    db: &RootDatabase,
+    range: FileRange,
+    config: &AssistConfig,
+) -> Vec<UnresolvedAssist> {
     let sema = Semantics::new(db);
-    let ctx = AssistCtx::new(&sema, range, false);
-    handlers::all()
-        .iter()
-        .filter_map(|f| f(ctx.clone()))
-        .flat_map(|it| it.0)
-        .map(|a| a.label)
+    let ctx = AssistCtx::new(&sema, range, false).with_config(config);
+    unresolved(&AssistRegistry::builtin(), ctx)
+}
+
+/// Like `unresolved_assists`, but uses the handlers of the `registry`.
+pub fn unresolved_assists_with(
+    registry: &AssistRegistry,
+    db: &RootDatabase,
+    range: FileRange,
+) -> Vec<UnresolvedAssist> {
+    let sema = Semantics::new(db);
+    unresolved(registry, AssistCtx::new(&sema, range, false))
+}
+
+fn unresolved(registry: &AssistRegistry, ctx: AssistCtx) -> Vec<UnresolvedAssist> {
+    collect_assists(registry, ctx)
+        .into_iter()
+        .flatten()
+        .map(|it| UnresolvedAssist { label: it.label, group_label: it.group_label })
         .collect()
 }
 
 /// Return all the assists applicable at the given position.
 ///
 /// Assists are returned in the "resolved" state, that is with edit fully
-/// computed.
+/// computed. Members of a group are kept together, groups are sorted by the
+/// target of their first member.
 pub fn resolved_assists(db: &RootDatabase, range: FileRange) -> Vec<ResolvedAssist> {
+    resolved_assists_with(&AssistRegistry::builtin(), db, range)
+}
+
+/// Like `resolved_assists`, but only with the assists enabled by `config`.
+pub fn resolved_assists_with_config(
+    db: &RootDatabase,
+    range: FileRange,
+    config: &AssistConfig,
+) -> Vec<ResolvedAssist> {
+    let sema = Semantics::new(db);
+    let ctx = AssistCtx::new(&sema, range, true).with_config(config);
+    resolved(&AssistRegistry::builtin(), ctx)
+}
+
+/// Like `resolved_assists`, but uses the handlers of the `registry`.
+pub fn resolved_assists_with(
+    registry: &AssistRegistry,
+    db: &RootDatabase,
+    range: FileRange,
+) -> Vec<ResolvedAssist> {
+    let sema = Semantics::new(db);
+    resolved(registry, AssistCtx::new(&sema, range, true))
+}
+
+fn resolved(registry: &AssistRegistry, ctx: AssistCtx) -> Vec<ResolvedAssist> {
+    let mut groups = collect_assists(registry, ctx)
+        .into_iter()
+        .map(|it| it.into_iter().map(|it| it.into_resolved().unwrap()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    groups.sort_by_key(|it| it[0].label.target.len());
+    groups.into_iter().flatten().collect()
+}
+
+/// Return the ids and the targets of all the assists applicable at the given
+/// position, in the same order as `resolved_assists`.
+///
+/// No edits are computed, so this is cheap enough to be called on every cursor
+/// move, for example to only show a lightbulb when the cursor is within the
+/// target of an assist.
+pub fn assist_targets(db: &RootDatabase, position: FilePosition) -> Vec<(AssistId, TextRange)> {
+    let sema = Semantics::new(db);
+    let range = FileRange {
+        file_id: position.file_id,
+        range: TextRange::offset_len(position.offset, 0.into()),
+    };
+    let ctx = AssistCtx::new(&sema, range, false);
+    let mut groups = collect_assists(&AssistRegistry::builtin(), ctx)
+        .into_iter()
+        .map(|it| it.into_iter().map(|it| (it.label.id, it.label.target)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    groups.sort_by_key(|it| it[0].1.len());
+    groups.into_iter().flatten().collect()
+}
+
+/// Runs the handlers of the `registry` enabled by the config of `ctx`, without
+/// duplicate assists.
+fn collect_assists(registry: &AssistRegistry, ctx: AssistCtx) -> Vec<Vec<AssistInfo>> {
+    let mut groups = registry
+        .handlers_with_ids()
+        .filter(|(id, _)| ctx.config().is_enabled(*id))
+        .filter_map(|(_, f)| f(ctx.clone()))
+        .map(|it| it.0)
+        .collect();
+    remove_duplicates(&mut groups);
+    groups
+}
+
+/// When a selection covers a node boundary, the same assist can be offered
+/// for an element and for its ancestor, with different targets. Of the assists
+/// with the same id and label, only the one with the smallest target is kept.
+///
+/// Returns the labels of the removed assists.
+fn remove_duplicates(groups: &mut Vec<Vec<AssistInfo>>) -> Vec<AssistLabel> {
+    let mut smallest_targets: FxHashMap<(AssistId, String), TextUnit> = FxHashMap::default();
+    for info in groups.iter().flatten() {
+        let len = info.label.target.len();
+        smallest_targets
+            .entry((info.label.id, info.label.label.clone()))
+            .and_modify(|it| *it = (*it).min(len))
+            .or_insert(len);
+    }
+
+    let mut kept = FxHashSet::default();
+    let mut removed = Vec::new();
+    for group in groups.iter_mut() {
+        group.retain(|info| {
+            let key = (info.label.id, info.label.label.clone());
+            if info.label.target.len() == smallest_targets[&key] && kept.insert(key) {
+                true
+            } else {
+                removed.push(info.label.clone());
+                false
+            }
+        });
+    }
+    groups.retain(|it| !it.is_empty());
+    removed
+}
+
+/// Returns the labels of the assists `resolved_assists` drops as duplicates.
+#[cfg(test)]
+pub(crate) fn duplicate_assists(db: &RootDatabase, range: FileRange) -> Vec<AssistLabel> {
+    let sema = Semantics::new(db);
+    let ctx = AssistCtx::new(&sema, range, false);
+    let mut groups = AssistRegistry::builtin()
+        .handlers()
+        .iter()
+        .filter_map(|f| f(ctx.clone()))
+        .map(|it| it.0)
+        .collect();
+    remove_duplicates(&mut groups)
+}
+
+/// Return the assists fixing the diagnostic with the given rustc error code,
+/// for example `E0425`, reported at `diagnostic_range`.
+///
+/// Only the handlers relevant to the diagnostic are run, and the assists are
+/// returned with the `QuickFix` kind. Unknown codes have no fixes.
+pub fn assists_for_diagnostic(
+    db: &RootDatabase,
+    file_id: FileId,
+    diagnostic_range: TextRange,
+    diagnostic_code: &str,
+) -> Vec<ResolvedAssist> {
+    let registry = AssistRegistry::builtin();
     let sema = Semantics::new(db);
-    let ctx = AssistCtx::new(&sema, range, true);
-    let mut a = handlers::all()
+    let ctx = AssistCtx::new(&sema, FileRange { file_id, range: diagnostic_range }, true);
+    fixing_assists(diagnostic_code)
         .iter()
+        .filter_map(|&id| registry.handler(id))
         .filter_map(|f| f(ctx.clone()))
         .flat_map(|it| it.0)
-        .map(|it| it.into_resolved().unwrap())
-        .collect::<Vec<_>>();
-    a.sort_by_key(|it| it.action.target.map_or(TextUnit::from(!0u32), |it| it.len()));
-    a
+        .filter_map(|it| it.into_resolved())
+        .map(|mut it| {
+            // The fixes of diagnostics are all quick fixes.
+            it.label.kind = AssistKind::QuickFix;
+            it
+        })
+        .collect()
+}
+
+fn fixing_assists(diagnostic_code: &str) -> &'static [AssistId] {
+    match diagnostic_code {
+        // Unresolved names, types and paths.
+        "E0412" | "E0422" | "E0425" | "E0433" => &[AssistId("auto_import")],
+        // Non-exhaustive patterns.
+        "E0004" => &[AssistId("fill_match_arms")],
+        // Missing trait items.
+        "E0046" => &[AssistId("add_impl_missing_members")],
+        // Missing struct fields.
+        "E0063" => &[AssistId("fill_struct_fields")],
+        _ => &[],
+    }
+}
+
+/// Applies the assist with the given `id` everywhere in the file where it is
+/// applicable, merging all the edits into one.
+///
+/// Each application is computed against the original text, applications whose
+/// edits overlap an earlier one are skipped.
+pub fn apply_assist_in_file(db: &RootDatabase, file_id: FileId, id: AssistId) -> Option<TextEdit> {
+    let registry = AssistRegistry::builtin();
+    // Assists with an id other than the one their handler is registered with
+    // need all the handlers to be tried.
+    let handlers = match registry.handler(id) {
+        Some(handler) => vec![handler],
+        None => registry.handlers().to_vec(),
+    };
+    let sema = Semantics::new(db);
+    let source_file = sema.parse(file_id);
+    let offsets = source_file
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .map(|it| it.text_range().start());
+
+    let mut atoms: Vec<AtomTextEdit> = Vec::new();
+    for offset in offsets {
+        let range = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+        let ctx = AssistCtx::new(&sema, range, true);
+        let action = handlers
+            .iter()
+            .filter_map(|f| f(ctx.clone()))
+            .flat_map(|it| it.0)
+            .find(|it| it.label.id == id)
+            .and_then(|it| it.into_resolved())
+            .map(|it| it.action);
+        // The edits of other files can't be merged into the one of this file.
+        let action = match action {
+            Some(it) if it.source_file_edits.len() == 1 && it.file_creation.is_none() => it,
+            _ => continue,
+        };
+        let overlaps = action
+            .edit()
+            .as_atoms()
+            .iter()
+            .any(|new| atoms.iter().any(|old| new.delete.intersection(&old.delete).is_some()));
+        if !overlaps {
+            atoms.extend(action.edit().as_atoms().iter().cloned());
+        }
+    }
+    if atoms.is_empty() {
+        return None;
+    }
+
+    atoms.sort_by_key(|it| it.delete.start());
+    let mut builder = TextEditBuilder::default();
+    for atom in atoms {
+        builder.replace(atom.delete, atom.insert);
+    }
+    Some(builder.finish())
 }
 
 mod handlers {
-    use crate::AssistHandler;
+    use crate::{AssistHandler, AssistId};
 
     mod add_derive;
+    mod add_else_branch;
     mod add_explicit_type;
     mod add_impl;
+    mod add_index_impl;
     mod add_custom_impl;
+    mod add_hash_impl;
     mod add_new;
     mod apply_demorgan;
     mod auto_import;
@@ -105,68 +461,298 @@ mod handlers {
     mod flip_binexpr;
     mod flip_trait_bound;
     mod change_visibility;
+    mod convert_doc_comment;
+    mod convert_loop_to_while;
+    mod convert_static_to_const;
+    mod convert_tuple_return_to_struct;
     mod fill_match_arms;
+    mod fill_struct_fields;
+    mod toggle_ignore;
+    mod make_async;
+    mod make_default_variant;
     mod merge_match_arms;
     mod introduce_variable;
+    mod introduce_generic_parameter;
     mod inline_local_variable;
     mod raw_string;
+    mod relax_sized_bound;
     mod remove_mut;
     mod replace_if_let_with_match;
+    mod replace_literal_with_const;
+    mod replace_with_bool_expr;
+    mod restrict_visibility;
+    mod use_alias;
+    mod extract_method;
+    mod reorder_fields;
+    mod generate_delegate_methods;
+    mod raw_identifier;
+    mod convert_return_type;
+    mod replace_for_with_if_let;
+    mod inline_constant;
+    mod swap_match_arms;
+    mod add_as_ref_impl;
+    mod merge_write_calls;
+    mod let_underscore;
+    mod add_serde_derive;
+    mod replace_if_let_err_with_unwrap_or_else;
+    mod number_representation;
+    mod absolute_path;
+    mod convert_bool_params_to_struct;
+    mod simplify_bool_comparison;
+    mod add_map_err;
+    mod convert_struct_literal;
+    mod make_const_fn;
+    mod add_non_exhaustive;
+    mod sort_derives;
+    mod wrap_field_in_option;
+    mod fold_guard_into_pattern;
+    mod add_error_impl;
+    mod range_pattern;
+    mod replace_impl_with_derive;
+    mod extract_option_helper;
+    mod merge_impl_blocks;
+    mod split_impl_block;
+    mod separate_number_literal;
+    mod add_missing_derive;
+    mod destructure_tuple_param;
+    mod number_suffix;
+    mod merge_string_pushes;
+    mod add_ref_trait_impl;
+    mod copy_doc_example_to_test;
+    mod remove_debug_print;
+    mod convert_out_param_to_return;
+    mod remove_redundant_binding;
+    mod create_module_file;
     mod split_import;
+    mod remove_clone_on_copy;
     mod remove_dbg;
+    mod remove_derive;
     pub(crate) mod replace_qualified_name_with_use;
+    mod replace_self_with_type;
     mod add_missing_impl_members;
+    mod add_move;
     mod move_guard;
     mod move_bounds;
+    mod duplicate_item;
     mod early_return;
+    mod extract_struct_from_enum_variant;
 
-    pub(crate) fn all() -> &'static [AssistHandler] {
+    pub(crate) fn all() -> &'static [(AssistId, AssistHandler)] {
         &[
-            add_derive::add_derive,
-            add_explicit_type::add_explicit_type,
-            add_impl::add_impl,
-            add_custom_impl::add_custom_impl,
-            add_new::add_new,
-            apply_demorgan::apply_demorgan,
-            invert_if::invert_if,
-            change_visibility::change_visibility,
-            fill_match_arms::fill_match_arms,
-            merge_match_arms::merge_match_arms,
-            flip_comma::flip_comma,
-            flip_binexpr::flip_binexpr,
-            flip_trait_bound::flip_trait_bound,
-            introduce_variable::introduce_variable,
-            replace_if_let_with_match::replace_if_let_with_match,
-            split_import::split_import,
-            remove_dbg::remove_dbg,
-            replace_qualified_name_with_use::replace_qualified_name_with_use,
-            add_missing_impl_members::add_missing_impl_members,
-            add_missing_impl_members::add_missing_default_members,
-            inline_local_variable::inline_local_variable,
-            move_guard::move_guard_to_arm_body,
-            move_guard::move_arm_cond_to_match_guard,
-            move_bounds::move_bounds_to_where_clause,
-            raw_string::add_hash,
-            raw_string::make_raw_string,
-            raw_string::make_usual_string,
-            raw_string::remove_hash,
-            remove_mut::remove_mut,
-            early_return::convert_to_guarded_return,
-            auto_import::auto_import,
+            (AssistId("add_derive"), add_derive::add_derive),
+            (AssistId("add_else_branch"), add_else_branch::add_else_branch),
+            (AssistId("add_explicit_type"), add_explicit_type::add_explicit_type),
+            (AssistId("add_impl"), add_impl::add_impl),
+            (AssistId("add_index_impl"), add_index_impl::add_index_impl),
+            (AssistId("add_custom_impl"), add_custom_impl::add_custom_impl),
+            (AssistId("add_hash_impl"), add_hash_impl::add_hash_impl),
+            (AssistId("add_new"), add_new::add_new),
+            (AssistId("apply_demorgan"), apply_demorgan::apply_demorgan),
+            (AssistId("invert_if"), invert_if::invert_if),
+            (AssistId("change_visibility"), change_visibility::change_visibility),
+            (
+                AssistId("convert_doc_comment_to_attr"),
+                convert_doc_comment::convert_doc_comment_to_attr,
+            ),
+            (
+                AssistId("convert_doc_attr_to_comment"),
+                convert_doc_comment::convert_doc_attr_to_comment,
+            ),
+            (AssistId("convert_loop_to_while"), convert_loop_to_while::convert_loop_to_while),
+            (AssistId("convert_const_to_static"), convert_static_to_const::convert_const_to_static),
+            (AssistId("convert_static_to_const"), convert_static_to_const::convert_static_to_const),
+            (
+                AssistId("convert_tuple_return_to_struct"),
+                convert_tuple_return_to_struct::convert_tuple_return_to_struct,
+            ),
+            (AssistId("fill_match_arms"), fill_match_arms::fill_match_arms),
+            (AssistId("fill_struct_fields"), fill_struct_fields::fill_struct_fields),
+            (AssistId("toggle_ignore"), toggle_ignore::toggle_ignore),
+            (AssistId("make_async"), make_async::make_async),
+            (AssistId("make_default_variant"), make_default_variant::make_default_variant),
+            (AssistId("merge_match_arms"), merge_match_arms::merge_match_arms),
+            (AssistId("flip_comma"), flip_comma::flip_comma),
+            (AssistId("flip_binexpr"), flip_binexpr::flip_binexpr),
+            (AssistId("flip_trait_bound"), flip_trait_bound::flip_trait_bound),
+            (AssistId("introduce_variable"), introduce_variable::introduce_variable),
+            (
+                AssistId("introduce_generic_parameter"),
+                introduce_generic_parameter::introduce_generic_parameter,
+            ),
+            (
+                AssistId("replace_if_let_with_match"),
+                replace_if_let_with_match::replace_if_let_with_match,
+            ),
+            (
+                AssistId("replace_literal_with_const"),
+                replace_literal_with_const::replace_literal_with_const,
+            ),
+            (AssistId("replace_with_bool_expr"), replace_with_bool_expr::replace_with_bool_expr),
+            (AssistId("restrict_visibility"), restrict_visibility::restrict_visibility),
+            (AssistId("inline_use_alias"), use_alias::inline_use_alias),
+            (AssistId("add_use_alias"), use_alias::add_use_alias),
+            (AssistId("extract_method"), extract_method::extract_method),
+            (AssistId("reorder_fields"), reorder_fields::reorder_fields),
+            (
+                AssistId("generate_delegate_methods"),
+                generate_delegate_methods::generate_delegate_methods,
+            ),
+            (AssistId("make_raw_identifier"), raw_identifier::make_raw_identifier),
+            (
+                AssistId("remove_raw_identifier_prefix"),
+                raw_identifier::remove_raw_identifier_prefix,
+            ),
+            (AssistId("convert_return_type"), convert_return_type::convert_return_type),
+            (AssistId("replace_for_with_if_let"), replace_for_with_if_let::replace_for_with_if_let),
+            (AssistId("inline_constant"), inline_constant::inline_constant),
+            (AssistId("swap_match_arms"), swap_match_arms::swap_match_arms),
+            (AssistId("add_as_ref_impl"), add_as_ref_impl::add_as_ref_impl),
+            (AssistId("merge_write_calls"), merge_write_calls::merge_write_calls),
+            (AssistId("remove_let_underscore"), let_underscore::remove_let_underscore),
+            (AssistId("bind_to_underscore"), let_underscore::bind_to_underscore),
+            (AssistId("add_serde_derive"), add_serde_derive::add_serde_derive),
+            (
+                AssistId("replace_if_let_err_with_unwrap_or_else"),
+                replace_if_let_err_with_unwrap_or_else::replace_if_let_err_with_unwrap_or_else,
+            ),
+            (
+                AssistId("replace_unwrap_or_else_with_if_let_err"),
+                replace_if_let_err_with_unwrap_or_else::replace_unwrap_or_else_with_if_let_err,
+            ),
+            (
+                AssistId("convert_byte_literal_to_number"),
+                number_representation::convert_byte_literal_to_number,
+            ),
+            (
+                AssistId("convert_number_to_byte_literal"),
+                number_representation::convert_number_to_byte_literal,
+            ),
+            (AssistId("make_path_absolute"), absolute_path::make_path_absolute),
+            (AssistId("make_path_relative"), absolute_path::make_path_relative),
+            (
+                AssistId("convert_bool_params_to_struct"),
+                convert_bool_params_to_struct::convert_bool_params_to_struct,
+            ),
+            (
+                AssistId("simplify_bool_comparison"),
+                simplify_bool_comparison::simplify_bool_comparison,
+            ),
+            (AssistId("add_map_err"), add_map_err::add_map_err),
+            (
+                AssistId("convert_call_to_struct_literal"),
+                convert_struct_literal::convert_call_to_struct_literal,
+            ),
+            (
+                AssistId("convert_struct_literal_to_call"),
+                convert_struct_literal::convert_struct_literal_to_call,
+            ),
+            (AssistId("make_const_fn"), make_const_fn::make_const_fn),
+            (AssistId("add_non_exhaustive"), add_non_exhaustive::add_non_exhaustive),
+            (AssistId("sort_derives"), sort_derives::sort_derives),
+            (AssistId("wrap_field_in_option"), wrap_field_in_option::wrap_field_in_option),
+            (AssistId("fold_guard_into_pattern"), fold_guard_into_pattern::fold_guard_into_pattern),
+            (AssistId("add_error_impl"), add_error_impl::add_error_impl),
+            (AssistId("expand_range_pattern"), range_pattern::expand_range_pattern),
+            (AssistId("collapse_to_range_pattern"), range_pattern::collapse_to_range_pattern),
+            (
+                AssistId("replace_impl_with_derive"),
+                replace_impl_with_derive::replace_impl_with_derive,
+            ),
+            (AssistId("invert_if_condition"), invert_if::invert_if_condition),
+            (AssistId("extract_option_helper"), extract_option_helper::extract_option_helper),
+            (AssistId("merge_impl_blocks"), merge_impl_blocks::merge_impl_blocks),
+            (AssistId("split_impl_block"), split_impl_block::split_impl_block),
+            (
+                AssistId("convert_float_to_scientific"),
+                number_representation::convert_float_to_scientific,
+            ),
+            (
+                AssistId("convert_scientific_to_float"),
+                number_representation::convert_scientific_to_float,
+            ),
+            (AssistId("separate_number_literal"), separate_number_literal::separate_number_literal),
+            (AssistId("remove_digit_separators"), separate_number_literal::remove_digit_separators),
+            (AssistId("add_missing_derive"), add_missing_derive::add_missing_derive),
+            (AssistId("destructure_tuple_param"), destructure_tuple_param::destructure_tuple_param),
+            (AssistId("add_number_suffixes"), number_suffix::add_number_suffixes),
+            (AssistId("remove_number_suffixes"), number_suffix::remove_number_suffixes),
+            (AssistId("merge_string_pushes"), merge_string_pushes::merge_string_pushes),
+            (AssistId("add_ref_trait_impl"), add_ref_trait_impl::add_ref_trait_impl),
+            (
+                AssistId("copy_doc_example_to_test"),
+                copy_doc_example_to_test::copy_doc_example_to_test,
+            ),
+            (AssistId("remove_debug_print"), remove_debug_print::remove_debug_print),
+            (
+                AssistId("convert_out_param_to_return"),
+                convert_out_param_to_return::convert_out_param_to_return,
+            ),
+            (
+                AssistId("remove_redundant_binding"),
+                remove_redundant_binding::remove_redundant_binding,
+            ),
+            (AssistId("create_module_file"), create_module_file::create_module_file),
+            (AssistId("split_import"), split_import::split_import),
+            (AssistId("remove_unnecessary_braces"), split_import::remove_unnecessary_braces),
+            (AssistId("add_braces_to_use"), split_import::add_braces_to_use),
+            (AssistId("remove_clone_on_copy"), remove_clone_on_copy::remove_clone_on_copy),
+            (AssistId("remove_dbg"), remove_dbg::remove_dbg),
+            (AssistId("remove_derive"), remove_derive::remove_derive),
+            (
+                AssistId("replace_qualified_name_with_use"),
+                replace_qualified_name_with_use::replace_qualified_name_with_use,
+            ),
+            (AssistId("replace_self_with_type"), replace_self_with_type::replace_self_with_type),
+            (AssistId("replace_type_with_self"), replace_self_with_type::replace_type_with_self),
+            (
+                AssistId("add_impl_missing_members"),
+                add_missing_impl_members::add_missing_impl_members,
+            ),
+            (AssistId("add_move"), add_move::add_move),
+            (AssistId("remove_move"), add_move::remove_move),
+            (
+                AssistId("add_impl_default_members"),
+                add_missing_impl_members::add_missing_default_members,
+            ),
+            (AssistId("inline_local_variable"), inline_local_variable::inline_local_variable),
+            (AssistId("move_guard_to_arm_body"), move_guard::move_guard_to_arm_body),
+            (AssistId("move_arm_cond_to_match_guard"), move_guard::move_arm_cond_to_match_guard),
+            (AssistId("move_bounds_to_where_clause"), move_bounds::move_bounds_to_where_clause),
+            (AssistId("add_hash"), raw_string::add_hash),
+            (AssistId("make_byte_string"), raw_string::make_byte_string),
+            (AssistId("make_raw_string"), raw_string::make_raw_string),
+            (AssistId("make_string_from_byte_string"), raw_string::make_string_from_byte_string),
+            (AssistId("make_usual_string"), raw_string::make_usual_string),
+            (AssistId("remove_hash"), raw_string::remove_hash),
+            (AssistId("escape_non_ascii"), raw_string::escape_non_ascii),
+            (AssistId("unescape_char_codes"), raw_string::unescape_char_codes),
+            (AssistId("relax_sized_bound"), relax_sized_bound::relax_sized_bound),
+            (AssistId("remove_mut"), remove_mut::remove_mut),
+            (AssistId("duplicate_item"), duplicate_item::duplicate_item),
+            (AssistId("convert_to_guarded_return"), early_return::convert_to_guarded_return),
+            (AssistId("auto_import"), auto_import::auto_import),
+            (
+                AssistId("extract_struct_from_enum_variant"),
+                extract_struct_from_enum_variant::extract_struct_from_enum_variant,
+            ),
         ]
     }
 }
 
 #[cfg(test)]
 mod helpers {
-    use std::sync::Arc;
+    use std::sync::{atomic::AtomicUsize, Arc};
 
-    use ra_db::{fixture::WithFixture, FileId, FileRange, SourceDatabaseExt};
+    use ra_db::{fixture::WithFixture, FileId, FileRange, RelativePathBuf, SourceDatabaseExt};
     use ra_ide_db::{symbol_index::SymbolsDatabase, RootDatabase};
-    use ra_syntax::TextRange;
-    use test_utils::{add_cursor, assert_eq_text, extract_range_or_offset, RangeOrOffset};
+    use ra_syntax::{TextRange, TextUnit};
+    use ra_text_edit::TextEdit;
+    use test_utils::{
+        add_cursor, assert_eq_text, extract_range_or_offset, marks::MarkChecker, parse_fixture,
+        RangeOrOffset,
+    };
 
-    use crate::{AssistCtx, AssistHandler};
+    use crate::{diff::unified_diff, AssistCtx, AssistHandler};
     use hir::Semantics;
 
     pub(crate) fn with_single_file(text: &str) -> (RootDatabase, FileId) {
@@ -197,6 +783,138 @@ mod helpers {
         check(assist, ra_fixture, ExpectedResult::NotApplicable);
     }
 
+    /// Like `check_assist_not_applicable`, but also checks that the assist was
+    /// refused for the reason tracked by the `mark`.
+    pub(crate) fn check_assist_not_applicable_because(
+        assist: AssistHandler,
+        ra_fixture: &str,
+        mark: &'static AtomicUsize,
+    ) {
+        let _checker = MarkChecker::new(mark);
+        check(assist, ra_fixture, ExpectedResult::NotApplicable);
+    }
+
+    /// Like `check_assist`, with the tabstops and the final cursor marked in
+    /// the LSP snippet syntax in `ra_fixture_after`, like `${1:todo}` and `$0`.
+    pub(crate) fn check_assist_snippet(
+        assist: AssistHandler,
+        ra_fixture_before: &str,
+        ra_fixture_after: &str,
+    ) {
+        let (range_or_offset, before) = extract_range_or_offset(ra_fixture_before);
+        let (db, file_id) = with_single_file(&before);
+        let frange = FileRange { file_id, range: range_or_offset.into() };
+        let sema = Semantics::new(&db);
+        let assist =
+            assist(AssistCtx::new(&sema, frange, true)).expect("code action is not applicable");
+        let action = assist.0[0].action.clone().unwrap();
+        let snippet = action.snippet.expect("the assist has no tabstops");
+
+        // The markers at the same offset are sorted as in `$0${1:}`, after
+        // the end of a previous tabstop.
+        let mut markers = Vec::new();
+        for (idx, range) in snippet.tabstops.iter().enumerate() {
+            markers.push((range.start(), 2, format!("${{{}:", idx + 1)));
+            markers.push((range.end(), 0, "}".to_string()));
+        }
+        if let Some(offset) = snippet.final_cursor {
+            markers.push((offset, 1, "$0".to_string()));
+        }
+        markers.sort_by_key(|(offset, order, _)| (*offset, *order));
+        let mut actual = action.edit().apply(&before);
+        for (offset, _, marker) in markers.into_iter().rev() {
+            actual.insert_str(offset.to_usize(), &marker);
+        }
+        if actual != ra_fixture_after {
+            panic!("{}", mismatch_diff(&actual, ra_fixture_after));
+        }
+    }
+
+    /// Like `check_assist`, for a fixture with several files, where the cursor
+    /// is in the file of the assist. Each file of `ra_fixture_after` is the
+    /// expected text of the file at its path, or of a created file, which is
+    /// empty.
+    pub(crate) fn check_assist_multi_file(
+        assist: AssistHandler,
+        ra_fixture_before: &str,
+        ra_fixture_after: &str,
+    ) {
+        check_multi_file(assist, None, ra_fixture_before, ra_fixture_after)
+    }
+
+    /// Like `check_assist_multi_file`, for the entry of a group with the given
+    /// `label`.
+    pub(crate) fn check_assist_multi_file_entry(
+        assist: AssistHandler,
+        label: &str,
+        ra_fixture_before: &str,
+        ra_fixture_after: &str,
+    ) {
+        check_multi_file(assist, Some(label), ra_fixture_before, ra_fixture_after)
+    }
+
+    /// Like `check_assist_not_applicable`, for a fixture with several files.
+    pub(crate) fn check_assist_multi_file_not_applicable(assist: AssistHandler, ra_fixture: &str) {
+        let (db, frange) = multi_file_frange(ra_fixture);
+        let sema = Semantics::new(&db);
+        assert!(assist(AssistCtx::new(&sema, frange, true)).is_none(), "code action is applicable");
+    }
+
+    /// The database of a fixture with several files, and the cursor in it.
+    fn multi_file_frange(ra_fixture: &str) -> (RootDatabase, FileRange) {
+        let (mut db, position) = RootDatabase::with_position(ra_fixture);
+        let source_root_id = db.file_source_root(position.file_id);
+        db.set_local_roots(Arc::new(vec![source_root_id]));
+        let frange = FileRange {
+            file_id: position.file_id,
+            range: TextRange::offset_len(position.offset, 0.into()),
+        };
+        (db, frange)
+    }
+
+    fn check_multi_file(
+        assist: AssistHandler,
+        label: Option<&str>,
+        ra_fixture_before: &str,
+        ra_fixture_after: &str,
+    ) {
+        let (db, frange) = multi_file_frange(ra_fixture_before);
+        let source_root_id = db.file_source_root(frange.file_id);
+        let sema = Semantics::new(&db);
+        let assist =
+            assist(AssistCtx::new(&sema, frange, true)).expect("code action is not applicable");
+        let info = match label {
+            Some(label) => assist.0.into_iter().find(|it| it.label.label == label).unwrap(),
+            None => assist.0.into_iter().next().unwrap(),
+        };
+        let action = info.action.unwrap();
+
+        let source_root = db.source_root(source_root_id);
+        for entry in parse_fixture(ra_fixture_after) {
+            let path = RelativePathBuf::from(entry.meta.split_whitespace().next().unwrap());
+            let actual = match source_root.file_by_relative_path(&path) {
+                Some(file_id) => {
+                    let text = db.file_text(file_id);
+                    let actual = action
+                        .edit_of(file_id)
+                        .map_or_else(|| text.to_string(), |it| it.apply(&text));
+                    match action.cursor_position {
+                        Some(offset) if file_id == frange.file_id => add_cursor(&actual, offset),
+                        _ => actual,
+                    }
+                }
+                None => {
+                    let created = action.file_creation.as_ref().map(|it| &it.path);
+                    assert_eq!(created, Some(&path), "the assist doesn't create {}", path);
+                    String::new()
+                }
+            };
+            if actual != entry.text {
+                panic!("{}\n{}", path, mismatch_diff(&actual, &entry.text));
+            }
+        }
+    }
+
     enum ExpectedResult<'a> {
         NotApplicable,
         After(&'a str),
@@ -214,27 +932,24 @@ mod helpers {
 
         match (assist(assist_ctx), expected) {
             (Some(assist), ExpectedResult::After(after)) => {
-                let action = assist.0[0].action.clone().unwrap();
-
-                let mut actual = action.edit.apply(&before);
-                match action.cursor_position {
-                    None => {
-                        if let RangeOrOffset::Offset(before_cursor_pos) = range_or_offset {
-                            let off = action
-                                .edit
-                                .apply_to_offset(before_cursor_pos)
-                                .expect("cursor position is affected by the edit");
-                            actual = add_cursor(&actual, off)
-                        }
+                let assist = assist.0[0].clone().into_resolved().unwrap();
+                let actual = match range_or_offset {
+                    RangeOrOffset::Offset(before_cursor_pos) => {
+                        let (actual, off) = assist.apply_with_cursor(&before, before_cursor_pos);
+                        add_cursor(&actual, off.expect("cursor position is affected by the edit"))
                     }
-                    Some(off) => actual = add_cursor(&actual, off),
+                    RangeOrOffset::Range(_) => match assist.apply(&before) {
+                        (actual, Some(off)) => add_cursor(&actual, off),
+                        (actual, None) => actual,
+                    },
                 };
 
-                assert_eq_text!(after, &actual);
+                if after != actual {
+                    panic!("{}", mismatch_diff(&actual, after));
+                }
             }
             (Some(assist), ExpectedResult::Target(target)) => {
-                let action = assist.0[0].action.clone().unwrap();
-                let range = action.target.expect("expected target on action");
+                let range = assist.0[0].label.target;
                 assert_eq_text!(&before[range.start().to_usize()..range.end().to_usize()], target);
             }
             (Some(_), ExpectedResult::NotApplicable) => panic!("assist should not be applicable!"),
@@ -244,15 +959,50 @@ mod helpers {
             (None, ExpectedResult::NotApplicable) => (),
         };
     }
+
+    /// Renders the difference as a single hunk, from the first to the last
+    /// differing character.
+    fn mismatch_diff(actual: &str, expected: &str) -> String {
+        let prefix: usize = actual
+            .chars()
+            .zip(expected.chars())
+            .take_while(|(a, e)| a == e)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        let suffix: usize = actual[prefix..]
+            .chars()
+            .rev()
+            .zip(expected[prefix..].chars().rev())
+            .take_while(|(a, e)| a == e)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        let range = TextRange::from_to(
+            TextUnit::from_usize(prefix),
+            TextUnit::from_usize(actual.len() - suffix),
+        );
+        let edit = TextEdit::replace(range, expected[prefix..expected.len() - suffix].to_string());
+        unified_diff("unexpected assist result (`-` actual, `+` expected)", actual, &edit)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use ra_db::FileRange;
-    use ra_syntax::TextRange;
-    use test_utils::{extract_offset, extract_range};
+    use ra_db::{FileId, FilePosition, FileRange, RelativePathBuf, SourceDatabaseExt};
+    use ra_syntax::{
+        ast::{self, NameOwner},
+        AstNode, TextRange, TextUnit,
+    };
+    use ra_text_edit::TextEdit;
+    use test_utils::{assert_eq_text, extract_offset, extract_range, extract_range_or_offset};
+
+    use hir::Semantics;
 
-    use crate::{helpers, resolved_assists};
+    use crate::{
+        apply_assist_in_file, assist_targets, assists_for_diagnostic, helpers, resolved,
+        resolved_assists, resolved_assists_with, resolved_assists_with_config, unresolved_assists,
+        unresolved_assists_with, unresolved_assists_with_config, Assist, AssistAction,
+        AssistConfig, AssistCtx, AssistId, AssistKind, AssistLabel, AssistRegistry, ResolvedAssist,
+    };
 
     #[test]
     fn assist_order_field_struct() {
@@ -271,6 +1021,27 @@ mod tests {
         assert_eq!(assists.next().expect("expected assist").label.label, "Add `#[derive]`");
     }
 
+    #[test]
+    fn assist_order_inside_struct_braces() {
+        let before = "
+struct Foo {
+    bar: u32,
+    <|>
+}";
+        let (before_cursor_pos, before) = extract_offset(before);
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange =
+            FileRange { file_id, range: TextRange::offset_len(before_cursor_pos, 0.into()) };
+        let assists = resolved_assists(&db, frange);
+        let labels = assists
+            .iter()
+            .filter(|it| ["add_derive", "add_impl", "add_new"].contains(&it.label.id.0))
+            .map(|it| it.label.label.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(labels, vec!["Add `#[derive]`", "Implement Foo", "Add default constructor"]);
+    }
+
     #[test]
     fn assist_order_if_expr() {
         let before = "
@@ -290,4 +1061,514 @@ mod tests {
         assert_eq!(assists.next().expect("expected assist").label.label, "Extract into variable");
         assert_eq!(assists.next().expect("expected assist").label.label, "Replace with match");
     }
+
+    #[test]
+    fn unresolved_assists_keep_groups() {
+        let before = r"
+            PubSt<|>ruct
+
+            pub mod PubMod1 {
+                pub struct PubStruct;
+            }
+            pub mod PubMod2 {
+                pub struct PubStruct;
+            }
+            pub mod PubMod3 {
+                pub struct PubStruct;
+            }
+            ";
+        let (before_cursor_pos, before) = extract_offset(before);
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange =
+            FileRange { file_id, range: TextRange::offset_len(before_cursor_pos, 0.into()) };
+
+        let unresolved = unresolved_assists(&db, frange)
+            .into_iter()
+            .filter_map(|it| Some((it.group_label?.0, it.label.label)))
+            .collect::<Vec<_>>();
+        let resolved = resolved_assists(&db, frange)
+            .into_iter()
+            .filter_map(|it| Some((it.group_label?.0, it.label.label)))
+            .collect::<Vec<_>>();
+        assert_eq!(unresolved, resolved);
+
+        let labels = unresolved
+            .iter()
+            .filter(|(group, _)| group == "Import PubStruct")
+            .map(|(_, label)| label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![
+                "Import `PubMod1::PubStruct` (new use at top)",
+                "Qualify as `PubMod1::PubStruct`",
+                "Import `PubMod2::PubStruct` (new use at top)",
+                "Qualify as `PubMod2::PubStruct`",
+                "Import `PubMod3::PubStruct` (new use at top)",
+                "Qualify as `PubMod3::PubStruct`",
+            ]
+        );
+        assert!(unresolved.iter().all(|(group, _)| group == "Import PubStruct"));
+    }
+
+    #[test]
+    fn apply_remove_dbg_in_file() {
+        let before = r#"
+fn f(a: u32) -> u32 {
+    let b = dbg!(a + 1);
+    dbg!(b);
+    dbg!(a * dbg!(b))
+}"#;
+        let (db, file_id) = helpers::with_single_file(before);
+        let edit = apply_assist_in_file(&db, file_id, AssistId("remove_dbg")).unwrap();
+        assert_eq_text!(
+            r#"
+fn f(a: u32) -> u32 {
+    let b = a + 1;
+    b;
+    a * dbg!(b)
+}"#,
+            &edit.apply(before)
+        );
+    }
+
+    #[test]
+    fn apply_remove_mut_in_file() {
+        let before = r#"
+fn f(mut a: u32, mut b: &mut u32) {
+    let mut c = a;
+}"#;
+        let (db, file_id) = helpers::with_single_file(before);
+        let edit = apply_assist_in_file(&db, file_id, AssistId("remove_mut")).unwrap();
+        assert_eq_text!(
+            r#"
+fn f(a: u32, b: &u32) {
+    let c = a;
+}"#,
+            &edit.apply(before)
+        );
+        assert!(apply_assist_in_file(&db, file_id, AssistId("remove_dbg")).is_none());
+    }
+
+    fn add_todo_comment(ctx: AssistCtx) -> Option<Assist> {
+        let offset = ctx.frange.range.start();
+        let target = TextRange::offset_len(offset, 0.into());
+        ctx.add_assist(
+            AssistId("add_todo_comment"),
+            AssistKind::RefactorRewrite,
+            "Add a comment",
+            target,
+            |edit| {
+                edit.insert(offset, "/* FIXME */");
+            },
+        )
+    }
+
+    #[test]
+    fn registered_assists_are_offered_with_builtin_ones() {
+        let (before_cursor_pos, before) = extract_offset("struct Foo { <|>bar: u32 }");
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange =
+            FileRange { file_id, range: TextRange::offset_len(before_cursor_pos, 0.into()) };
+
+        let mut registry = AssistRegistry::builtin();
+        registry.register(AssistId("add_todo_comment"), add_todo_comment);
+        let assists = resolved_assists_with(&registry, &db, frange);
+        let custom = assists.iter().find(|it| it.label.id == AssistId("add_todo_comment")).unwrap();
+        assert_eq_text!("struct Foo { /* FIXME */bar: u32 }", &custom.action.edit().apply(&before));
+        assert!(assists.iter().any(|it| it.label.label == "Add `#[derive]`"));
+
+        assert!(registry.handler(AssistId("add_todo_comment")).is_some());
+        assert!(resolved_assists(&db, frange)
+            .iter()
+            .all(|it| it.label.id != AssistId("add_todo_comment")));
+    }
+
+    fn add_todo_comment_before_stmt(ctx: AssistCtx) -> Option<Assist> {
+        let stmt = ctx.find_node_at_offset::<ast::LetStmt>()?;
+        let target = stmt.syntax().text_range();
+        ctx.add_assist(
+            AssistId("add_todo_comment"),
+            AssistKind::RefactorRewrite,
+            "Add a comment",
+            target,
+            |edit| {
+                edit.insert(target.start(), "/* FIXME */");
+            },
+        )
+    }
+
+    #[test]
+    fn duplicate_assists_keep_the_smallest_target() {
+        let (range, before) = extract_range("fn f() { <|>let x = 1;<|> }");
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range };
+
+        let mut registry = AssistRegistry::builtin();
+        registry.register(AssistId("add_todo_comment"), add_todo_comment);
+        registry.register(AssistId("add_todo_comment_before_stmt"), add_todo_comment_before_stmt);
+        let is_todo_comment = |id: AssistId| id == AssistId("add_todo_comment");
+
+        let resolved = resolved_assists_with(&registry, &db, frange)
+            .into_iter()
+            .filter(|it| is_todo_comment(it.label.id))
+            .map(|it| it.label.target)
+            .collect::<Vec<_>>();
+        assert_eq!(resolved, vec![TextRange::offset_len(range.start(), 0.into())]);
+        let unresolved = unresolved_assists_with(&registry, &db, frange)
+            .into_iter()
+            .filter(|it| is_todo_comment(it.label.id))
+            .count();
+        assert_eq!(unresolved, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "assist `remove_dbg` is registered twice")]
+    fn registering_an_id_twice_panics() {
+        let mut registry = AssistRegistry::builtin();
+        registry.register(AssistId("remove_dbg"), add_todo_comment);
+    }
+
+    #[test]
+    fn assists_for_unresolved_name() {
+        let (range, before) = extract_range(
+            r"
+            fn f() { <|>PubStruct<|>; }
+
+            pub mod PubMod {
+                pub struct PubStruct;
+            }
+            ",
+        );
+        let (db, file_id) = helpers::with_single_file(&before);
+        let assists = assists_for_diagnostic(&db, file_id, range, "E0425");
+        let labels = assists.iter().map(|it| it.label.label.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec!["Import `PubMod::PubStruct` (new use at top)", "Qualify as `PubMod::PubStruct`"]
+        );
+        assert!(assists.iter().all(|it| it.label.kind == AssistKind::QuickFix));
+    }
+
+    #[test]
+    fn assists_for_missing_impl_members() {
+        let (range, before) = extract_range(
+            r"
+            trait Foo { fn foo(&self); }
+            struct S;
+            <|>impl Foo for S<|> {}
+            ",
+        );
+        let (db, file_id) = helpers::with_single_file(&before);
+        let assists = assists_for_diagnostic(&db, file_id, range, "E0046");
+        let labels = assists.iter().map(|it| it.label.label.as_str()).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["Implement missing members"]);
+    }
+
+    #[test]
+    fn no_assists_for_unknown_diagnostic() {
+        let (range, before) =
+            extract_range("fn f() { <|>PubStruct<|>; } mod m { pub struct PubStruct; }");
+        let (db, file_id) = helpers::with_single_file(&before);
+        assert!(assists_for_diagnostic(&db, file_id, range, "E9999").is_empty());
+    }
+
+    #[test]
+    fn assist_targets_match_resolved_assists() {
+        for before in &[
+            "struct Foo { <|>bar: u32 }",
+            "pub mod m { pub struct PubStruct; } fn f() { PubSt<|>ruct; }",
+        ] {
+            let (offset, before) = extract_offset(before);
+            let (db, file_id) = helpers::with_single_file(&before);
+            let frange = FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) };
+
+            let targets = assist_targets(&db, FilePosition { file_id, offset });
+            let resolved = resolved_assists(&db, frange)
+                .into_iter()
+                .map(|it| (it.label.id, it.label.target))
+                .collect::<Vec<_>>();
+            assert!(!targets.is_empty());
+            assert_eq!(targets, resolved);
+        }
+    }
+
+    #[test]
+    fn unresolved_assists_have_the_targets_of_resolved_ones() {
+        let (db, frange) = field_frange("fn f() { let x<|> = 1 + 2; }");
+
+        // Unresolved assists aren't sorted by target.
+        let sorted = |mut targets: Vec<(AssistId, TextRange)>| {
+            targets.sort_by_key(|(id, target)| (id.0, target.start(), target.end()));
+            targets
+        };
+        let unresolved = sorted(
+            unresolved_assists(&db, frange)
+                .into_iter()
+                .map(|it| (it.label.id, it.label.target))
+                .collect(),
+        );
+        let resolved = sorted(
+            resolved_assists(&db, frange)
+                .into_iter()
+                .map(|it| (it.label.id, it.label.target))
+                .collect(),
+        );
+        assert!(!unresolved.is_empty());
+        assert_eq!(unresolved, resolved);
+    }
+
+    #[test]
+    fn assists_have_the_kind_of_their_handler() {
+        for (before, id, kind) in &[
+            ("fn f() { g(<|>1 + 2<|>); }", "introduce_variable", AssistKind::RefactorExtract),
+            ("fn f() { let x<|> = 1; g(x); }", "inline_local_variable", AssistKind::RefactorInline),
+            ("fn f(a: u32,<|> b: u32) {}", "flip_comma", AssistKind::RefactorRewrite),
+            ("struct Foo { <|>bar: u32 }", "add_derive", AssistKind::Refactor),
+            (
+                "pub mod m { pub struct PubStruct; } fn f() { PubSt<|>ruct; }",
+                "auto_import",
+                AssistKind::QuickFix,
+            ),
+        ] {
+            let (range, before) = extract_range_or_offset(before);
+            let (db, file_id) = helpers::with_single_file(&before);
+            let frange = FileRange { file_id, range: range.into() };
+            let assist = resolved_assists(&db, frange)
+                .into_iter()
+                .find(|it| it.label.id == AssistId(id))
+                .unwrap();
+            assert_eq!(assist.label.kind, *kind, "{}", id);
+        }
+    }
+
+    #[test]
+    fn assists_are_filtered_by_kind() {
+        let (range, before) = extract_range("fn f() { let x = 1; g(<|>x + 2<|>); }");
+        let (db, file_id) = helpers::with_single_file(&before);
+        let frange = FileRange { file_id, range };
+
+        let extract =
+            AssistConfig { kinds: Some(vec![AssistKind::RefactorExtract]), ..Default::default() };
+        let kinds = resolved_assists_with_config(&db, frange, &extract)
+            .into_iter()
+            .map(|it| (it.label.id, it.label.kind))
+            .collect::<Vec<_>>();
+        assert!(kinds.contains(&(AssistId("introduce_variable"), AssistKind::RefactorExtract)));
+        assert!(kinds.iter().all(|(_, kind)| *kind == AssistKind::RefactorExtract));
+        assert!(unresolved_assists_with_config(&db, frange, &extract)
+            .iter()
+            .all(|it| it.label.kind == AssistKind::RefactorExtract));
+
+        let (db, frange) =
+            field_frange("pub mod m { pub struct PubStruct; } fn f() { PubSt<|>ruct; }");
+        let ids = |config: &AssistConfig| {
+            unresolved_assists_with_config(&db, frange, config)
+                .into_iter()
+                .map(|it| it.label.id)
+                .collect::<Vec<_>>()
+        };
+        let refactor =
+            AssistConfig { kinds: Some(vec![AssistKind::Refactor]), ..Default::default() };
+        assert!(ids(&AssistConfig::default()).contains(&AssistId("auto_import")));
+        assert!(!ids(&refactor).contains(&AssistId("auto_import")));
+        assert!(AssistKind::RefactorInline.is_within(AssistKind::Refactor));
+        assert!(!AssistKind::Refactor.is_within(AssistKind::RefactorInline));
+    }
+
+    fn field_frange(text: &str) -> (ra_ide_db::RootDatabase, FileRange) {
+        let (offset, before) = extract_offset(text);
+        let (db, file_id) = helpers::with_single_file(&before);
+        (db, FileRange { file_id, range: TextRange::offset_len(offset, 0.into()) })
+    }
+
+    fn ids_with_config(config: &AssistConfig) -> Vec<&'static str> {
+        let (db, frange) = field_frange("struct Foo { <|>bar: u32 }");
+        resolved_assists_with_config(&db, frange, config).iter().map(|it| it.label.id.0).collect()
+    }
+
+    #[test]
+    fn default_config_offers_all_assists() {
+        let (db, frange) = field_frange("struct Foo { <|>bar: u32 }");
+        let config = AssistConfig::default();
+        let labels = |assists: Vec<crate::ResolvedAssist>| {
+            assists.into_iter().map(|it| it.label.label).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            labels(resolved_assists_with_config(&db, frange, &config)),
+            labels(resolved_assists(&db, frange))
+        );
+        assert_eq!(
+            unresolved_assists_with_config(&db, frange, &config).len(),
+            unresolved_assists(&db, frange).len()
+        );
+    }
+
+    #[test]
+    fn config_disables_single_assist() {
+        let all = ids_with_config(&AssistConfig::default());
+        assert!(all.contains(&"change_visibility") && all.contains(&"add_derive"));
+
+        let config = AssistConfig { denied: vec!["add_derive".to_string()], ..Default::default() };
+        let ids = ids_with_config(&config);
+        assert!(!ids.contains(&"add_derive"));
+        assert_eq!(ids, all.iter().cloned().filter(|&it| it != "add_derive").collect::<Vec<_>>());
+
+        let config = AssistConfig {
+            allowed: Some(vec!["change_visibility".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(ids_with_config(&config), vec!["change_visibility"]);
+    }
+
+    #[test]
+    fn config_disables_assists_by_prefix() {
+        let config = AssistConfig { denied: vec!["add_*".to_string()], ..Default::default() };
+        let ids = ids_with_config(&config);
+        assert!(ids.contains(&"change_visibility"));
+        assert!(ids.iter().all(|it| !it.starts_with("add_")));
+    }
+
+    fn panicking(_: AssistCtx) -> Option<Assist> {
+        panic!("disabled handlers are not run")
+    }
+
+    #[test]
+    fn disabled_handlers_are_not_run() {
+        let (db, frange) = field_frange("struct Foo { <|>bar: u32 }");
+        let mut registry = AssistRegistry::builtin();
+        registry.register(AssistId("panicking"), panicking);
+        let config = AssistConfig { denied: vec!["panicking".to_string()], ..Default::default() };
+        let sema = Semantics::new(&db);
+        let ctx = AssistCtx::new(&sema, frange, true).with_config(&config);
+        assert!(resolved(&registry, ctx).iter().any(|it| it.label.id == AssistId("add_derive")));
+    }
+
+    /// An assist replacing the value of `let x = 92;` with `1 + 2`.
+    fn replace_value(cursor_position: Option<TextUnit>) -> ResolvedAssist {
+        let range = TextRange::from_to(8.into(), 10.into());
+        ResolvedAssist {
+            label: AssistLabel::new(
+                "Replace value".to_string(),
+                AssistId("replace_value"),
+                AssistKind::RefactorRewrite,
+                range,
+            ),
+            group_label: None,
+            action: AssistAction::single_file(
+                FileId(0),
+                TextEdit::replace(range, "1 + 2".to_string()),
+                cursor_position,
+            ),
+        }
+    }
+
+    #[test]
+    fn apply_maps_cursor_before_and_after_edit() {
+        let assist = replace_value(None);
+        assert_eq!(
+            assist.apply_with_cursor("let x = 92;", 4.into()),
+            ("let x = 1 + 2;".to_string(), Some(4.into()))
+        );
+        assert_eq!(
+            assist.apply_with_cursor("let x = 92;", 10.into()),
+            ("let x = 1 + 2;".to_string(), Some(13.into()))
+        );
+        assert_eq!(assist.apply("let x = 92;"), ("let x = 1 + 2;".to_string(), None));
+    }
+
+    #[test]
+    fn apply_has_no_cursor_inside_replaced_range() {
+        assert_eq!(
+            replace_value(None).apply_with_cursor("let x = 92;", 9.into()),
+            ("let x = 1 + 2;".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn apply_prefers_cursor_position_of_assist() {
+        let assist = replace_value(Some(12.into()));
+        assert_eq!(assist.apply("let x = 92;"), ("let x = 1 + 2;".to_string(), Some(12.into())));
+        assert_eq!(
+            assist.apply_with_cursor("let x = 92;", 9.into()),
+            ("let x = 1 + 2;".to_string(), Some(12.into()))
+        );
+    }
+
+    /// Adds a comment to the `mod foo;` under the cursor and to the file of the
+    /// module, creating the file if needed.
+    fn comment_module_file(ctx: AssistCtx) -> Option<Assist> {
+        let module = ctx.find_node_at_offset::<ast::Module>()?;
+        let path = RelativePathBuf::from(format!("/{}.rs", module.name()?.text()));
+        let source_root_id = ctx.db.file_source_root(ctx.frange.file_id);
+        let file_id = ctx.db.source_root(source_root_id).file_by_relative_path(&path);
+        let target = module.syntax().text_range();
+        ctx.add_assist(
+            AssistId("comment_module_file"),
+            AssistKind::Refactor,
+            "Comment module file",
+            target,
+            |edit| {
+                edit.insert(target.start(), "// see foo.rs\n");
+                match file_id {
+                    Some(file_id) => {
+                        edit.edit_file(file_id).insert(0.into(), "// from main\n".into())
+                    }
+                    None => edit.create_file(source_root_id, path),
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn assist_edits_other_file() {
+        helpers::check_assist_multi_file(
+            comment_module_file,
+            r#"
+//- /main.rs
+mod <|>foo;
+fn main() {}
+//- /foo.rs
+fn f() {}
+"#,
+            r#"
+//- /main.rs
+// see foo.rs
+mod foo;
+fn main() {}
+//- /foo.rs
+// from main
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn assist_creates_file() {
+        helpers::check_assist_multi_file(
+            comment_module_file,
+            r#"
+//- /main.rs
+mod <|>foo;
+"#,
+            r#"
+//- /main.rs
+// see foo.rs
+mod foo;
+//- /foo.rs
+"#,
+        );
+        let (db, file_id) = helpers::with_single_file("mod foo;");
+        let frange = FileRange { file_id, range: TextRange::offset_len(0.into(), 0.into()) };
+        let mut registry = AssistRegistry::builtin();
+        registry.register(AssistId("comment_module_file"), comment_module_file);
+        let assist = resolved_assists_with(&registry, &db, frange)
+            .into_iter()
+            .find(|it| it.label.id == AssistId("comment_module_file"))
+            .unwrap();
+        assert_eq!(assist.action.source_file_edits.len(), 1);
+        assert_eq!(assist.action.source_file_edits[0].0, file_id);
+        let created = assist.action.file_creation.unwrap();
+        assert_eq!(created.path, RelativePathBuf::from("/foo.rs"));
+    }
 }
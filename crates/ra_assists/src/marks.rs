@@ -7,4 +7,21 @@ test_utils::marks![
     not_applicable_outside_of_bind_pat
     test_not_inline_mut_variable
     test_not_applicable_if_variable_unused
+    not_applicable_to_destructuring_let
+    introduce_var_for_empty_selection_is_not_applicable
+    introduce_var_without_indent_is_not_applicable
+    fill_match_arms_with_existing_arms
+    fill_match_arms_for_empty_enum
+    auto_import_in_use_item
+    auto_import_without_candidates
+    guarded_return_with_else_branch
+    guarded_return_for_unsupported_pattern
+    guarded_return_for_non_tail_if
+    guarded_return_for_existing_early_exit
+    guarded_return_outside_of_fn_or_loop
+    change_visibility_in_trait
+    change_visibility_in_trait_impl
+    change_visibility_in_enum_variant
+    make_const_fn_calls_non_const_fn
+    make_const_fn_unsupported_construct
 ];
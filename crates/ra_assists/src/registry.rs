@@ -0,0 +1,57 @@
+//! The set of handlers assists are computed with. Besides the built-in
+//! assists, clients can register handlers of their own.
+
+use rustc_hash::FxHashMap;
+
+use crate::{handlers, AssistHandler, AssistId};
+
+#[derive(Clone, Default)]
+pub struct AssistRegistry {
+    handlers: Vec<AssistHandler>,
+    ids: Vec<AssistId>,
+    by_id: FxHashMap<AssistId, usize>,
+}
+
+impl AssistRegistry {
+    /// A registry with no handlers at all.
+    pub fn empty() -> AssistRegistry {
+        AssistRegistry::default()
+    }
+
+    /// A registry with all the assists provided by this crate.
+    pub fn builtin() -> AssistRegistry {
+        let mut res = AssistRegistry::empty();
+        for &(id, handler) in handlers::all() {
+            res.register(id, handler);
+        }
+        res
+    }
+
+    /// Adds a `handler` producing assists with the given `id`.
+    ///
+    /// Panics if a handler with the same `id` is already registered.
+    pub fn register(&mut self, id: AssistId, handler: AssistHandler) {
+        if self.by_id.insert(id, self.handlers.len()).is_some() {
+            panic!("assist `{}` is registered twice", id.0);
+        }
+        self.handlers.push(handler);
+        self.ids.push(id);
+    }
+
+    /// Returns the handler registered with the given `id`.
+    ///
+    /// A handler can produce assists with different ids, only the one it was
+    /// registered with is known here.
+    pub fn handler(&self, id: AssistId) -> Option<AssistHandler> {
+        self.by_id.get(&id).map(|&idx| self.handlers[idx])
+    }
+
+    pub(crate) fn handlers(&self) -> &[AssistHandler] {
+        &self.handlers
+    }
+
+    /// The handlers with the ids they were registered with.
+    pub(crate) fn handlers_with_ids(&self) -> impl Iterator<Item = (AssistId, AssistHandler)> + '_ {
+        self.ids.iter().cloned().zip(self.handlers.iter().cloned())
+    }
+}
@@ -1,15 +1,26 @@
 //! Assorted functions shared by several assists.
 pub(crate) mod insert_use;
 
-use hir::Semantics;
+use hir::{HasSource, Semantics};
+use join_to_string::join;
+use ra_db::FileId;
+use ra_fmt::leading_indent;
 use ra_ide_db::RootDatabase;
 use ra_syntax::{
-    ast::{self, make, NameOwner},
-    AstNode, T,
+    ast::{self, edit, make, AttrsOwner, LiteralKind, NameOwner, TypeParamsOwner},
+    AstNode, SmolStr, SyntaxElement,
+    SyntaxKind::{ATTR, COMMENT, IDENT, IMPL_DEF, TRAIT_DEF, WHITESPACE},
+    SyntaxNode, TextRange, TextUnit, T,
 };
 use rustc_hash::FxHashSet;
 
-pub use insert_use::insert_use_statement;
+use crate::ast_transform::{self, AstTransform};
+
+pub use insert_use::{import_location, insert_use_statement, ImportLocation};
+
+/// The number of lines of the items whose assists are offered from anywhere
+/// inside them, see `AssistCtx::find_enclosing_item_if_small`.
+pub(crate) const SMALL_ITEM_LINES: usize = 20;
 
 pub fn get_missing_impl_items(
     sema: &Semantics<RootDatabase>,
@@ -79,6 +90,128 @@ pub(crate) fn resolve_target_trait(
     }
 }
 
+/// Finds the impl of `trait_` for `type_name` among the children of `scope`,
+/// by the last segments of the paths.
+pub(crate) fn find_trait_impl(
+    scope: &SyntaxNode,
+    type_name: &str,
+    trait_: &str,
+) -> Option<ast::ImplDef> {
+    scope.children().filter_map(ast::ImplDef::cast).find(|impl_def| {
+        trait_name(impl_def).as_deref() == Some(trait_)
+            && self_type_name(impl_def).as_deref() == Some(type_name)
+    })
+}
+
+pub(crate) fn trait_name(impl_def: &ast::ImplDef) -> Option<String> {
+    last_segment(impl_def.target_trait()?)
+}
+
+pub(crate) fn self_type_name(impl_def: &ast::ImplDef) -> Option<String> {
+    impl_def.target_trait()?;
+    last_segment(impl_def.target_type()?)
+}
+
+fn last_segment(type_ref: ast::TypeRef) -> Option<String> {
+    match type_ref {
+        ast::TypeRef::PathType(it) => Some(it.path()?.segment()?.name_ref()?.text().to_string()),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum AddMissingImplMembersMode {
+    DefaultMethodsOnly,
+    NoDefaultMethods,
+}
+
+/// Returns the syntax of those of `items` an impl added in `mode` would get.
+pub(crate) fn items_for_mode(
+    db: &RootDatabase,
+    items: Vec<hir::AssocItem>,
+    mode: AddMissingImplMembersMode,
+) -> Vec<ast::ImplItem> {
+    let def_name = |item: &ast::ImplItem| -> Option<SmolStr> {
+        match item {
+            ast::ImplItem::FnDef(def) => def.name(),
+            ast::ImplItem::TypeAliasDef(def) => def.name(),
+            ast::ImplItem::ConstDef(def) => def.name(),
+        }
+        .map(|it| it.text().clone())
+    };
+
+    items
+        .iter()
+        .map(|i| match i {
+            hir::AssocItem::Function(i) => ast::ImplItem::FnDef(i.source(db).value),
+            hir::AssocItem::TypeAlias(i) => ast::ImplItem::TypeAliasDef(i.source(db).value),
+            hir::AssocItem::Const(i) => ast::ImplItem::ConstDef(i.source(db).value),
+        })
+        .filter(|t| def_name(&t).is_some())
+        .filter(|t| match t {
+            ast::ImplItem::FnDef(def) => match mode {
+                AddMissingImplMembersMode::DefaultMethodsOnly => def.body().is_some(),
+                AddMissingImplMembersMode::NoDefaultMethods => def.body().is_none(),
+            },
+            _ => mode == AddMissingImplMembersMode::NoDefaultMethods,
+        })
+        .collect()
+}
+
+/// Turns trait items into impl items: paths are fixed up by `ast_transform`,
+/// missing bodies are filled with `unimplemented!()`.
+pub(crate) fn scaffold_items(
+    ast_transform: &dyn AstTransform,
+    items: Vec<ast::ImplItem>,
+) -> Vec<ast::ImplItem> {
+    items
+        .into_iter()
+        .map(|it| ast_transform::apply(ast_transform, it))
+        .map(|it| match it {
+            ast::ImplItem::FnDef(def) => ast::ImplItem::FnDef(add_body(def)),
+            _ => it,
+        })
+        .map(|it| edit::strip_attrs_and_docs(&it))
+        .collect()
+}
+
+fn add_body(fn_def: ast::FnDef) -> ast::FnDef {
+    if fn_def.body().is_none() {
+        fn_def.with_body(make::block_from_expr(make::expr_unimplemented()))
+    } else {
+        fn_def
+    }
+}
+
+/// `impl<T: Clone> Ctx<T>`, or `impl<T: Clone> Trait for Ctx<T>` with `trait_`.
+pub(crate) fn impl_header(
+    nominal: &ast::NominalDef,
+    name: &ast::Name,
+    trait_: Option<&str>,
+) -> String {
+    let type_params = nominal.type_param_list();
+    let mut buf = String::new();
+    buf.push_str("impl");
+    if let Some(type_params) = &type_params {
+        buf.push_str(&type_params.syntax().to_string());
+    }
+    buf.push_str(" ");
+    if let Some(trait_) = trait_ {
+        buf.push_str(&format!("{} for ", trait_));
+    }
+    buf.push_str(name.text().as_str());
+    if let Some(type_params) = type_params {
+        let lifetime_params = type_params
+            .lifetime_params()
+            .filter_map(|it| it.lifetime_token())
+            .map(|it| it.text().clone());
+        let type_params =
+            type_params.type_params().filter_map(|it| it.name()).map(|it| it.text().clone());
+        join(lifetime_params.chain(type_params)).surround_with("<", ">").to_buf(&mut buf);
+    }
+    buf
+}
+
 pub(crate) fn invert_boolean_expression(expr: ast::Expr) -> ast::Expr {
     if let Some(expr) = invert_special_case(&expr) {
         return expr;
@@ -94,8 +227,430 @@ fn invert_special_case(expr: &ast::Expr) -> Option<ast::Expr> {
             _ => None,
         },
         ast::Expr::PrefixExpr(pe) if pe.op_kind()? == ast::PrefixOp::Not => pe.expr(),
+        ast::Expr::MethodCallExpr(mce) => {
+            let method = mce.name_ref()?;
+            let inverse = match method.text().as_str() {
+                "is_some" => "is_none",
+                "is_none" => "is_some",
+                "is_ok" => "is_err",
+                "is_err" => "is_ok",
+                _ => return None,
+            };
+            let mut text = mce.syntax().to_string();
+            let method_range =
+                method.syntax().text_range().checked_sub(mce.syntax().text_range().start())?;
+            text.replace_range(
+                method_range.start().to_usize()..method_range.end().to_usize(),
+                inverse,
+            );
+            Some(make::expr_from_text(&text))
+        }
         // FIXME:
         // ast::Expr::Literal(true | false )
         _ => None,
     }
 }
+
+/// Returns the text of the inverse of the condition `expr`, going through
+/// `&&` and `||` chains with De Morgan's laws and flipping comparisons, as in
+/// `a < b || !c` to `a >= b && c`.
+pub(crate) fn invert_condition(expr: &ast::Expr) -> String {
+    invert_condition_with_precedence(expr).0
+}
+
+/// The loosest operator of an expression built by `invert_condition`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Or,
+    And,
+    Other,
+}
+
+fn invert_condition_with_precedence(expr: &ast::Expr) -> (String, Precedence) {
+    match expr {
+        ast::Expr::ParenExpr(it) => match it.expr() {
+            Some(inner) => return invert_condition_with_precedence(&inner),
+            None => (),
+        },
+        ast::Expr::BinExpr(bin) => {
+            let inverted_op = match bin.op_kind() {
+                Some(ast::BinOp::BooleanAnd) => Some(("||", Precedence::Or)),
+                Some(ast::BinOp::BooleanOr) => Some(("&&", Precedence::And)),
+                _ => None,
+            };
+            if let (Some((op, precedence)), Some(lhs), Some(rhs)) =
+                (inverted_op, bin.lhs(), bin.rhs())
+            {
+                let operand = |expr: &ast::Expr| match invert_condition_with_precedence(expr) {
+                    (text, it) if it < precedence => format!("({})", text),
+                    (text, _) => text,
+                };
+                return (format!("{} {} {}", operand(&lhs), op, operand(&rhs)), precedence);
+            }
+            let flipped = match bin.op_kind() {
+                Some(ast::BinOp::LesserTest) => Some(T![>=]),
+                Some(ast::BinOp::LesserEqualTest) => Some(T![>]),
+                Some(ast::BinOp::GreaterTest) => Some(T![<=]),
+                Some(ast::BinOp::GreaterEqualTest) => Some(T![<]),
+                Some(ast::BinOp::EqualityTest) => Some(T![!=]),
+                Some(ast::BinOp::NegatedEqualityTest) => Some(T![==]),
+                _ => None,
+            };
+            if let Some(it) = flipped.and_then(|it| bin.replace_op(it)) {
+                return (it.syntax().to_string(), Precedence::Other);
+            }
+        }
+        ast::Expr::PrefixExpr(it) if it.op_kind() == Some(ast::PrefixOp::Not) => {
+            if let Some(inner) = it.expr() {
+                let inner = match &inner {
+                    ast::Expr::ParenExpr(paren) => paren.expr().unwrap_or(inner),
+                    _ => inner,
+                };
+                let precedence = match &inner {
+                    ast::Expr::BinExpr(bin) => match bin.op_kind() {
+                        Some(ast::BinOp::BooleanOr) => Precedence::Or,
+                        Some(ast::BinOp::BooleanAnd) => Precedence::And,
+                        _ => Precedence::Other,
+                    },
+                    _ => Precedence::Other,
+                };
+                return (inner.syntax().to_string(), precedence);
+            }
+        }
+        ast::Expr::Literal(it) if it.kind() == ast::LiteralKind::Bool => {
+            let inverted = if it.token().text() == "true" { "false" } else { "true" };
+            return (inverted.to_string(), Precedence::Other);
+        }
+        _ => (),
+    }
+    let text = match expr {
+        ast::Expr::BinExpr(_) | ast::Expr::CastExpr(_) | ast::Expr::RangeExpr(_) => {
+            format!("!({})", expr.syntax())
+        }
+        _ => invert_boolean_expression(expr.clone()).syntax().to_string(),
+    };
+    (text, Precedence::Other)
+}
+
+/// Returns the offset and the text adding the outer attribute `attr` to `item`,
+/// either right after the `after` attribute or in front of all the other
+/// attributes. The attribute gets its own line, unless `item` is inline.
+pub(crate) fn insert_attribute(
+    item: &SyntaxNode,
+    after: Option<&ast::Attr>,
+    attr: &str,
+) -> (TextUnit, String) {
+    let separator = match leading_indent(item) {
+        Some(indent) => format!("\n{}", indent),
+        // An item sharing its line with other code gets its attributes inline.
+        None if item.first_token().and_then(|it| it.prev_token()).is_some() => " ".to_string(),
+        None => "\n".to_string(),
+    };
+    match after {
+        Some(after) => (after.syntax().text_range().end(), format!("{}{}", separator, attr)),
+        None => {
+            let offset = item
+                .children_with_tokens()
+                .find(|it| it.kind() != COMMENT && it.kind() != WHITESPACE)
+                .map_or(item.text_range().start(), |it| it.text_range().start());
+            (offset, format!("{}{}", attr, separator))
+        }
+    }
+}
+
+/// Returns the range removing `attr` together with the line break after it,
+/// so that no blank line is left behind.
+pub(crate) fn attribute_removal_range(attr: &ast::Attr) -> TextRange {
+    let range = attr.syntax().text_range();
+    match attr.syntax().next_sibling_or_token() {
+        Some(ws) if ws.kind() == WHITESPACE => range.extend_to(&ws.text_range()),
+        _ => range,
+    }
+}
+
+/// Whether `expr` needs parentheses when it replaces the path `usage`, as in
+/// `(1 + 2) * 4` when inlining `x` in `x * 4`.
+pub(crate) fn needs_parens_when_inlined(expr: &ast::Expr, usage: &ast::PathExpr) -> bool {
+    let parent = match usage.syntax().parent().and_then(ast::Expr::cast) {
+        Some(it) => it,
+        None => return false,
+    };
+    match (expr, parent) {
+        (ast::Expr::CallExpr(_), _)
+        | (ast::Expr::IndexExpr(_), _)
+        | (ast::Expr::MethodCallExpr(_), _)
+        | (ast::Expr::FieldExpr(_), _)
+        | (ast::Expr::TryExpr(_), _)
+        | (ast::Expr::RefExpr(_), _)
+        | (ast::Expr::Literal(_), _)
+        | (ast::Expr::TupleExpr(_), _)
+        | (ast::Expr::ArrayExpr(_), _)
+        | (ast::Expr::ParenExpr(_), _)
+        | (ast::Expr::PathExpr(_), _)
+        | (ast::Expr::BlockExpr(_), _)
+        | (_, ast::Expr::CallExpr(_))
+        | (_, ast::Expr::TupleExpr(_))
+        | (_, ast::Expr::ArrayExpr(_))
+        | (_, ast::Expr::ParenExpr(_))
+        | (_, ast::Expr::ForExpr(_))
+        | (_, ast::Expr::WhileExpr(_))
+        | (_, ast::Expr::BreakExpr(_))
+        | (_, ast::Expr::ReturnExpr(_))
+        | (_, ast::Expr::MatchExpr(_)) => false,
+        _ => true,
+    }
+}
+
+/// The range of `node`, including the attributes and comments before it.
+pub(crate) fn range_with_leading_comments(node: &SyntaxNode) -> TextRange {
+    let mut start = node.text_range().start();
+    let mut prev = node.prev_sibling_or_token();
+    while let Some(element) = prev {
+        match element.kind() {
+            WHITESPACE => (),
+            COMMENT | ATTR => start = element.text_range().start(),
+            _ => break,
+        }
+        prev = element.prev_sibling_or_token();
+    }
+    TextRange::from_to(start, node.text_range().end())
+}
+
+/// The arguments of the `#[derive(..)]` attribute of `item`.
+pub(crate) fn derive_args(item: &impl AttrsOwner) -> Option<ast::TokenTree> {
+    item.attrs()
+        .filter_map(|it| it.as_simple_call())
+        .find(|(name, _)| name == "derive")
+        .map(|(_, args)| args)
+}
+
+/// Whether the arguments of a `derive` attribute contain `trait_`, possibly
+/// qualified.
+pub(crate) fn derives(args: &ast::TokenTree, trait_: &str) -> bool {
+    args.syntax()
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .any(|it| it.kind() == IDENT && it.text() == trait_)
+}
+
+/// Splits the input of a derive attribute into comma-separated items,
+/// returning the range and the text of every item.
+pub(crate) fn derive_items(input: &ast::TokenTree) -> Vec<(TextRange, String)> {
+    let mut res = Vec::new();
+    let mut current: Option<(TextRange, String)> = None;
+    let tokens = input
+        .syntax()
+        .children_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| it.kind() != WHITESPACE && it.kind() != COMMENT);
+    for token in tokens {
+        match token.kind() {
+            T!['('] | T![')'] | T![,] => res.extend(current.take()),
+            _ => {
+                current = Some(match current.take() {
+                    None => (token.text_range(), token.text().to_string()),
+                    Some((range, text)) => {
+                        (range.extend_to(&token.text_range()), text + token.text().as_str())
+                    }
+                })
+            }
+        }
+    }
+    res.extend(current);
+    res
+}
+
+/// Returns the offset and the text adding `traits` to the derives of `item`,
+/// whose `derive` attribute has the arguments `args`, if it has one.
+pub(crate) fn insert_derives(
+    item: &SyntaxNode,
+    args: Option<&ast::TokenTree>,
+    traits: &str,
+) -> (TextUnit, String) {
+    match args {
+        Some(args) => {
+            let r_paren = args.syntax().text_range().end() - TextUnit::of_char(')');
+            let separator = if args.syntax().text() == "()" { "" } else { ", " };
+            (r_paren, format!("{}{}", separator, traits))
+        }
+        None => insert_attribute(item, None, &format!("#[derive({})]", traits)),
+    }
+}
+
+/// The path of `module` from the root of its crate, like `crate::foo::bar`.
+pub(crate) fn absolute_module_path(db: &RootDatabase, module: hir::Module) -> String {
+    let mut segments = vec!["crate".to_string()];
+    segments.extend(
+        module
+            .path_to_root(db)
+            .into_iter()
+            .rev()
+            .filter_map(|it| it.name(db))
+            .map(|it| it.to_string()),
+    );
+    segments.join("::")
+}
+
+/// The shortest path from the module `from` to the module `to` of the same
+/// crate, like `super::foo`, or `self::foo` when `to` is nested in `from`.
+pub(crate) fn relative_module_path(
+    db: &RootDatabase,
+    from: hir::Module,
+    to: hir::Module,
+) -> String {
+    let from = from.path_to_root(db).into_iter().rev().collect::<Vec<_>>();
+    let to = to.path_to_root(db).into_iter().rev().collect::<Vec<_>>();
+    let common = from.iter().zip(to.iter()).take_while(|(a, b)| a == b).count();
+    let mut segments = match from.len() - common {
+        0 => vec!["self".to_string()],
+        supers => vec!["super".to_string(); supers],
+    };
+    segments.extend(to[common..].iter().filter_map(|it| it.name(db)).map(|it| it.to_string()));
+    segments.join("::")
+}
+
+/// Whether the crate of `file_id` has a dependency called `name`.
+pub(crate) fn depends_on(sema: &Semantics<RootDatabase>, file_id: FileId, name: &str) -> bool {
+    sema.to_module_def(file_id).and_then(|it| it.krate(sema.db)).map_or(false, |krate| {
+        krate.dependencies(sema.db).iter().any(|dep| dep.name.to_string() == name)
+    })
+}
+
+/// Verifies that the given macro_call actually matches the given name
+/// and contains proper ending tokens
+pub(crate) fn is_valid_macrocall(macro_call: &ast::MacroCall, macro_name: &str) -> Option<bool> {
+    let path = macro_call.path()?;
+    let name_ref = path.segment()?.name_ref()?;
+
+    // Make sure it is actually a call of the macro, its name followed by !
+    let excl = path.syntax().next_sibling_or_token()?;
+
+    if name_ref.text() != macro_name || excl.kind() != T![!] {
+        return None;
+    }
+
+    let node = macro_call.token_tree()?.syntax().clone();
+    let first_child = node.first_child_or_token()?;
+    let last_child = node.last_child_or_token()?;
+
+    match (first_child.kind(), last_child.kind()) {
+        (T!['('], T![')']) | (T!['['], T![']']) | (T!['{'], T!['}']) => Some(true),
+        _ => Some(false),
+    }
+}
+
+/// The macro calls of the function containing `node` which `is_match`
+/// accepts, for the assists removing all of them at once.
+pub(crate) fn macro_calls_in_fn(
+    node: &SyntaxNode,
+    is_match: impl Fn(&ast::MacroCall) -> bool,
+) -> Vec<ast::MacroCall> {
+    match node.ancestors().find_map(ast::FnDef::cast) {
+        Some(fn_def) => fn_def
+            .syntax()
+            .descendants()
+            .filter_map(ast::MacroCall::cast)
+            .filter(|it| is_match(it))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Whether `node` may mutate the local `name`, by assigning it, borrowing it
+/// mutably or calling a method on it.
+pub(crate) fn mutates(node: &SyntaxNode, name: &str) -> bool {
+    node.descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| it.kind() == IDENT && it.text() == name)
+        .any(|token| {
+            let path_expr = token
+                .parent()
+                .ancestors()
+                .take_while(|it| !ast::Stmt::can_cast(it.kind()))
+                .find_map(ast::PathExpr::cast);
+            let path_expr = match path_expr {
+                Some(it) => it,
+                // The uses in macro calls are not known.
+                None => return true,
+            };
+            let range = path_expr.syntax().text_range();
+            match path_expr.syntax().parent().and_then(ast::Expr::cast) {
+                Some(ast::Expr::MethodCallExpr(it)) => {
+                    it.expr().map_or(false, |it| it.syntax().text_range() == range)
+                }
+                Some(ast::Expr::RefExpr(it)) => it.is_mut(),
+                Some(ast::Expr::BinExpr(it)) => {
+                    it.op_kind().map_or(false, |it| it.is_assignment())
+                        && it.lhs().map_or(false, |it| it.syntax().text_range() == range)
+                }
+                _ => false,
+            }
+        })
+}
+
+/// The call or method call of which `name_ref` is the callee, as in
+/// `foo(..)`, `Foo::new(..)` or `x.foo(..)`.
+pub(crate) fn call_of(name_ref: &ast::NameRef) -> Option<ast::Expr> {
+    let parent = name_ref.syntax().parent()?;
+    if let Some(method_call) = ast::MethodCallExpr::cast(parent) {
+        return Some(method_call.into());
+    }
+    let path = name_ref.syntax().ancestors().find_map(ast::Path::cast)?;
+    let path_expr = path.syntax().parent().and_then(ast::PathExpr::cast)?;
+    let call = path_expr.syntax().parent().and_then(ast::CallExpr::cast)?;
+    if call.expr()?.syntax() == path_expr.syntax() {
+        Some(call.into())
+    } else {
+        None
+    }
+}
+
+/// Whether `fn_def` is an item of an impl or of a trait.
+pub(crate) fn is_assoc_fn(fn_def: &ast::FnDef) -> bool {
+    fn_def.syntax().parent().and_then(ast::ItemList::cast).map_or(false, |it| {
+        it.syntax().parent().map_or(false, |it| {
+            ast::ImplDef::can_cast(it.kind()) || ast::TraitDef::can_cast(it.kind())
+        })
+    })
+}
+
+/// `snake_case` to `CamelCase`.
+pub(crate) fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|it| it.to_ascii_uppercase()).into_iter().chain(chars)
+        })
+        .collect()
+}
+
+/// Whether `fn_def` is an item of a trait or of an impl of a trait.
+pub(crate) fn is_trait_fn(fn_def: &ast::FnDef) -> bool {
+    let container = match fn_def.syntax().parent().and_then(|it| it.parent()) {
+        Some(it) => it,
+        None => return false,
+    };
+    match container.kind() {
+        TRAIT_DEF => true,
+        IMPL_DEF => ast::ImplDef::cast(container).and_then(|it| it.target_trait()).is_some(),
+        _ => false,
+    }
+}
+
+/// The value and the suffix of an integer literal.
+pub(crate) fn identify_number_literal(literal: &ast::Literal) -> Option<(u128, Option<SmolStr>)> {
+    let suffix = match literal.kind() {
+        LiteralKind::IntNumber { suffix } => suffix,
+        _ => return None,
+    };
+    let text = literal.token().text().replace('_', "");
+    let digits = &text[..text.len() - suffix.as_ref().map_or(0, |it| it.len())];
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x") => (16, &digits[2..]),
+        Some("0o") => (8, &digits[2..]),
+        Some("0b") => (2, &digits[2..]),
+        _ => (10, digits),
+    };
+    let value = u128::from_str_radix(digits, radix).ok()?;
+    Some((value, suffix))
+}
@@ -7,7 +7,7 @@ use ra_syntax::{
     ast::{self, NameOwner},
     AstNode, Direction, SmolStr,
     SyntaxKind::{PATH, PATH_SEGMENT},
-    SyntaxNode, T,
+    SyntaxNode, TextRange, T,
 };
 use ra_text_edit::TextEditBuilder;
 
@@ -20,18 +20,55 @@ pub fn insert_use_statement(
     path_to_import: &ModPath,
     edit: &mut TextEditBuilder,
 ) {
+    if let Some((action, target)) = best_action_at(position, path_to_import) {
+        make_assist(&action, &target, edit);
+    }
+}
+
+/// Where `insert_use_statement` imports a path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportLocation {
+    /// Merged into the existing use item of the range.
+    MergedUse(TextRange),
+    /// In a new use item, inserted at the (empty) range.
+    NewUse(TextRange),
+}
+
+/// Returns where `insert_use_statement` would import the path, without
+/// computing the edit, or `None` if the path is already imported.
+pub fn import_location(position: &SyntaxNode, path_to_import: &ModPath) -> Option<ImportLocation> {
+    let use_item_range = |node: &SyntaxNode| {
+        node.ancestors().find_map(ast::UseItem::cast).map(|it| it.syntax().text_range())
+    };
+    match best_action_at(position, path_to_import)?.0 {
+        ImportAction::Nothing => None,
+        ImportAction::AddNewUse { anchor, add_after_anchor } => {
+            let anchor = anchor?.text_range();
+            let offset = if add_after_anchor { anchor.end() } else { anchor.start() };
+            Some(ImportLocation::NewUse(TextRange::offset_len(offset, 0.into())))
+        }
+        ImportAction::AddNestedImport { path_to_split, .. } => {
+            use_item_range(path_to_split.syntax()).map(ImportLocation::MergedUse)
+        }
+        ImportAction::AddInTreeList { tree_list, .. } => {
+            use_item_range(tree_list.syntax()).map(ImportLocation::MergedUse)
+        }
+    }
+}
+
+fn best_action_at(
+    position: &SyntaxNode,
+    path_to_import: &ModPath,
+) -> Option<(ImportAction, Vec<SmolStr>)> {
     let target = path_to_import.to_string().split("::").map(SmolStr::new).collect::<Vec<_>>();
     let container = position.ancestors().find_map(|n| {
         if let Some(module) = ast::Module::cast(n.clone()) {
             return module.item_list().map(|it| it.syntax().clone());
         }
         ast::SourceFile::cast(n).map(|it| it.syntax().clone())
-    });
-
-    if let Some(container) = container {
-        let action = best_action_for_target(container, position.clone(), &target);
-        make_assist(&action, &target, edit);
-    }
+    })?;
+    let action = best_action_for_target(container, position.clone(), &target);
+    Some((action, target))
 }
 
 fn collect_path_segments_raw(
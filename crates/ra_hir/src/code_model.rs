@@ -507,6 +507,10 @@ impl VariantDef {
         }
     }
 
+    pub fn kind(self, db: &impl HirDatabase) -> StructKind {
+        self.variant_data(db).kind()
+    }
+
     pub(crate) fn variant_data(self, db: &impl DefDatabase) -> Arc<VariantData> {
         match self {
             VariantDef::Struct(it) => it.variant_data(db),
@@ -588,6 +592,15 @@ impl Const {
     pub fn name(self, db: &impl HirDatabase) -> Option<Name> {
         db.const_data(self.id).name.clone()
     }
+
+    pub fn ty(self, db: &impl HirDatabase) -> Type {
+        let data = db.const_data(self.id);
+        let resolver = self.id.resolver(db);
+        let ctx = hir_ty::TyLoweringContext::new(db, &resolver);
+        let environment = TraitEnvironment::lower(db, &resolver);
+        let ty = Ty::from_hir(&ctx, &data.type_ref);
+        Type { krate: self.module(db).id.krate, ty: InEnvironment { value: ty, environment } }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -967,6 +980,16 @@ impl Type {
         }
     }
 
+    pub fn is_unit(&self) -> bool {
+        match &self.ty.value {
+            Ty::Apply(a_ty) => match a_ty.ctor {
+                TypeCtor::Tuple { cardinality: 0 } => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
     pub fn is_mutable_reference(&self) -> bool {
         match &self.ty.value {
             Ty::Apply(a_ty) => match a_ty.ctor {
@@ -987,11 +1010,19 @@ impl Type {
     /// Checks that particular type `ty` implements `std::future::Future`.
     /// This function is used in `.await` syntax completion.
     pub fn impls_future(&self, db: &impl HirDatabase) -> bool {
+        self.impls_lang_trait(db, "future_trait")
+    }
+
+    /// Checks that particular type `ty` implements `Copy`.
+    pub fn impls_copy(&self, db: &impl HirDatabase) -> bool {
+        self.impls_lang_trait(db, "copy")
+    }
+
+    fn impls_lang_trait(&self, db: &impl HirDatabase, lang_item: &str) -> bool {
         let krate = self.krate;
 
-        let std_future_trait =
-            db.lang_item(krate, "future_trait".into()).and_then(|it| it.as_trait());
-        let std_future_trait = match std_future_trait {
+        let lang_trait = db.lang_item(krate, lang_item.into()).and_then(|it| it.as_trait());
+        let lang_trait = match lang_trait {
             Some(it) => it,
             None => return false,
         };
@@ -1002,10 +1033,16 @@ impl Type {
             db,
             self.ty.environment.clone(),
             krate,
-            std_future_trait,
+            lang_trait,
         )
     }
 
+    /// Returns the type behind a reference, `None` if this is not a reference.
+    pub fn remove_ref(&self) -> Option<Type> {
+        let (ty, _) = self.ty.value.as_reference()?;
+        Some(self.derived(ty.clone()))
+    }
+
     // FIXME: this method is broken, as it doesn't take closures into account.
     pub fn as_callable(&self) -> Option<CallableDef> {
         Some(self.ty.value.as_callable()?.0)
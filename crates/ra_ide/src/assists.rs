@@ -4,13 +4,14 @@ use ra_assists::{resolved_assists, AssistAction, AssistLabel};
 use ra_db::{FilePosition, FileRange};
 use ra_ide_db::RootDatabase;
 
-use crate::{FileId, SourceChange, SourceFileEdit};
+use crate::{FileId, FileSystemEdit, SourceChange, SourceFileEdit};
 
-pub use ra_assists::AssistId;
+pub use ra_assists::{AssistId, AssistKind};
 
 #[derive(Debug)]
 pub struct Assist {
     pub id: AssistId,
+    pub kind: AssistKind,
     pub label: String,
     pub group_label: Option<String>,
     pub source_change: SourceChange,
@@ -24,6 +25,7 @@ pub(crate) fn assists(db: &RootDatabase, frange: FileRange) -> Vec<Assist> {
             let assist_label = &assist.label;
             Assist {
                 id: assist_label.id,
+                kind: assist.label.kind,
                 label: assist_label.label.clone(),
                 group_label: assist.group_label.map(|it| it.0),
                 source_change: action_to_edit(assist.action, file_id, assist_label),
@@ -37,7 +39,16 @@ fn action_to_edit(
     file_id: FileId,
     assist_label: &AssistLabel,
 ) -> SourceChange {
-    let file_edit = SourceFileEdit { file_id, edit: action.edit };
-    SourceChange::source_file_edit(assist_label.label.clone(), file_edit)
+    let source_file_edits = action
+        .source_file_edits
+        .into_iter()
+        .map(|(file_id, edit)| SourceFileEdit { file_id, edit })
+        .collect();
+    let file_system_edits = action
+        .file_creation
+        .into_iter()
+        .map(|it| FileSystemEdit::CreateFile { source_root: it.source_root, path: it.path })
+        .collect();
+    SourceChange::from_edits(assist_label.label.clone(), source_file_edits, file_system_edits)
         .with_cursor_opt(action.cursor_position.map(|offset| FilePosition { offset, file_id }))
 }
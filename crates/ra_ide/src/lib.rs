@@ -60,7 +60,7 @@ use ra_syntax::{SourceFile, TextRange, TextUnit};
 use crate::display::ToNav;
 
 pub use crate::{
-    assists::{Assist, AssistId},
+    assists::{Assist, AssistId, AssistKind},
     call_hierarchy::CallItem,
     completion::{CompletionItem, CompletionItemKind, InsertTextFormat},
     diagnostics::Severity,
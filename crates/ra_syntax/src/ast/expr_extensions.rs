@@ -382,6 +382,16 @@ impl ast::BlockExpr {
             _ => true,
         }
     }
+
+    pub fn is_async(&self) -> bool {
+        self.syntax().children_with_tokens().any(|it| it.kind() == T![async])
+    }
+}
+
+impl ast::LambdaExpr {
+    pub fn is_async(&self) -> bool {
+        self.syntax().children_with_tokens().any(|it| it.kind() == T![async])
+    }
 }
 
 #[test]
@@ -245,6 +245,10 @@ impl ast::FnDef {
     pub fn is_async(&self) -> bool {
         self.syntax().children_with_tokens().any(|it| it.kind() == T![async])
     }
+
+    pub fn is_const(&self) -> bool {
+        self.syntax().children_with_tokens().any(|it| it.kind() == T![const])
+    }
 }
 
 impl ast::LetStmt {
@@ -16,13 +16,13 @@ pub fn path_segment(name_ref: ast::NameRef) -> ast::PathSegment {
     ast_from_text(&format!("use {};", name_ref.syntax()))
 }
 pub fn path_unqualified(segment: ast::PathSegment) -> ast::Path {
-    path_from_text(&format!("use {}", segment.syntax()))
+    path_from_text(&segment.syntax().to_string())
 }
 pub fn path_qualified(qual: ast::Path, segment: ast::PathSegment) -> ast::Path {
     path_from_text(&format!("{}::{}", qual.syntax(), segment.syntax()))
 }
-fn path_from_text(text: &str) -> ast::Path {
-    ast_from_text(text)
+pub fn path_from_text(text: &str) -> ast::Path {
+    ast_from_text(&format!("use {};", text))
 }
 
 pub fn use_tree(
@@ -187,6 +187,10 @@ pub fn match_arm_list(arms: impl IntoIterator<Item = ast::MatchArm>) -> ast::Mat
     }
 }
 
+pub fn impl_item_list() -> ast::ItemList {
+    ast_from_text("impl C for D {}")
+}
+
 pub fn where_pred(
     path: ast::Path,
     bounds: impl IntoIterator<Item = ast::TypeBound>,
@@ -20,8 +20,8 @@ use lsp_types::{
     TextEdit, WorkspaceEdit,
 };
 use ra_ide::{
-    Assist, AssistId, FileId, FilePosition, FileRange, Query, RangeInfo, Runnable, RunnableKind,
-    SearchScope,
+    Assist, AssistId, AssistKind, FileId, FilePosition, FileRange, Query, RangeInfo, Runnable,
+    RunnableKind, SearchScope,
 };
 use ra_prof::profile;
 use ra_syntax::{AstNode, SyntaxKind, TextRange, TextUnit};
@@ -664,7 +664,7 @@ fn create_single_code_action(assist: Assist, world: &WorldSnapshot) -> Result<Co
     let kind = match assist.id {
         AssistId("introduce_variable") => Some("refactor.extract.variable".to_string()),
         AssistId("add_custom_impl") => Some("refactor.rewrite.add_custom_impl".to_string()),
-        _ => None,
+        _ => Some(code_action_kind(assist.kind).to_string()),
     };
 
     Ok(CodeAction {
@@ -677,6 +677,16 @@ fn create_single_code_action(assist: Assist, world: &WorldSnapshot) -> Result<Co
     })
 }
 
+fn code_action_kind(kind: AssistKind) -> &'static str {
+    match kind {
+        AssistKind::QuickFix => "quickfix",
+        AssistKind::Refactor => "refactor",
+        AssistKind::RefactorExtract => "refactor.extract",
+        AssistKind::RefactorInline => "refactor.inline",
+        AssistKind::RefactorRewrite => "refactor.rewrite",
+    }
+}
+
 pub fn handle_code_action(
     world: WorldSnapshot,
     params: req::CodeActionParams,
@@ -723,10 +733,14 @@ pub fn handle_code_action(
         res.push(fix.action.clone());
     }
 
-    let mut grouped_assists: FxHashMap<String, Vec<Assist>> = FxHashMap::default();
+    // Members of a group are contiguous, keep the groups in the order of assists.
+    let mut grouped_assists: Vec<(String, Vec<Assist>)> = Vec::new();
     for assist in world.analysis().assists(FileRange { file_id, range })?.into_iter() {
         match &assist.group_label {
-            Some(label) => grouped_assists.entry(label.to_owned()).or_default().push(assist),
+            Some(label) => match grouped_assists.last_mut() {
+                Some((last_label, group)) if last_label == label => group.push(assist),
+                _ => grouped_assists.push((label.to_owned(), vec![assist])),
+            },
             None => res.push(create_single_code_action(assist, &world)?.into()),
         }
     }